@@ -0,0 +1,70 @@
+//! CLI `auth` command: inspects what the configured credentials may do.
+
+use clap::{Parser, Subcommand};
+use reqwest::StatusCode;
+use shared::api::{AccessReviewRequest, AccessReviewResponse};
+
+use crate::config::Config;
+
+/// CLI arguments for the `auth` command.
+#[derive(Parser, Debug)]
+pub struct AuthArgs {
+    #[command(subcommand)]
+    pub command: AuthCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AuthCommand {
+    /// Check whether an action is allowed, e.g. `can-i create replicasets`
+    CanI {
+        /// Verb such as get, list, watch, create, update, patch or delete
+        verb: String,
+        /// Resource such as pods, or a subresource such as pods/logs
+        resource: String,
+        /// Name of the object, for rules limited to some names
+        name: Option<String>,
+        /// Also print the binding that allows the action, or why it is denied
+        #[arg(short, long)]
+        verbose: bool,
+    },
+}
+
+/// Asks the apiserver to review the action and prints yes or no.
+pub async fn handle_auth(config: &Config, args: &AuthArgs) {
+    let AuthCommand::CanI {
+        verb,
+        resource,
+        name,
+        verbose,
+    } = &args.command;
+    let request = AccessReviewRequest {
+        verb: verb.clone(),
+        resource: resource.clone(),
+        name: name.clone(),
+    };
+    let url = format!("{}/accessreviews", config.url);
+    match shared::utils::client()
+        .post(&url)
+        .json(&request)
+        .send()
+        .await
+    {
+        Ok(resp) if resp.status() == StatusCode::OK => {
+            match resp.json::<AccessReviewResponse>().await {
+                Ok(review) => {
+                    println!("{}", if review.allowed { "yes" } else { "no" });
+                    if *verbose {
+                        println!("{}", review.reason);
+                    }
+                }
+                Err(e) => eprintln!("Invalid response: {}", e),
+            }
+        }
+        Ok(resp) => {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            eprintln!("Failed to review access ({}): {}", status, body);
+        }
+        Err(e) => eprintln!("Request error: {}", e),
+    }
+}
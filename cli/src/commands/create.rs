@@ -3,14 +3,27 @@
 
 use clap::Parser;
 use erased_serde::serialize_trait_object;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use shared::{
-    api::{PodContainers, PodManifest, ReplicaSetManifest},
+    api::{
+        ClusterRoleBindingManifest, ClusterRoleManifest, ConfigMapManifest, IngressManifest,
+        PersistentVolumeClaimManifest, PersistentVolumeManifest, PodContainers,
+        PodDisruptionBudgetManifest, PodGroupManifest, PodManifest, PriorityClassManifest,
+        ReplicaSetManifest, RoleBindingManifest, RoleManifest, SecretManifest, ServiceManifest,
+    },
     models::{
+        configmap::ConfigMapSpec,
+        ingress::IngressSpec,
         metadata::{LabelSelector, ObjectMetadata},
-        pod::ContainerSpec,
+        persistentvolume::PersistentVolumeSpec,
+        persistentvolumeclaim::PersistentVolumeClaimSpec,
+        poddisruptionbudget::PodDisruptionBudgetSpec,
+        podgroup::PodGroupSpec,
+        priorityclass::PriorityClassSpec,
+        rbac::{ClusterRoleBindingSpec, ClusterRoleSpec, RoleBindingSpec, RoleSpec},
         replicaset::ReplicaSetSpec,
+        secret::SecretSpec,
+        service::ServiceSpec,
     },
 };
 use tokio::fs;
@@ -27,38 +40,45 @@ pub struct CreateArgs {
 
 /// Reads a YAML file, parses objects, and posts them to the configured server.
 pub async fn handle_create(config: &Config, args: &CreateArgs) {
+    let Some(docs) = read_manifests(&args.file).await else {
+        return;
+    };
+
+    // send each manifest to the specified resource endpoint
+    let client = shared::utils::client();
+    for object in docs {
+        let url = format!("{}/{}?controller=false", config.url, object.spec.plural());
+        let manifest = object.spec.into_manifest(object.metadata);
+
+        match client.post(&url).json(&manifest).send().await {
+            Ok(_) => {}
+            Err(err) => eprintln!("Error: {:?}", err),
+        };
+    }
+}
+
+/// Reads a YAML file with one or more objects, printing errors.
+pub async fn read_manifests(file: &str) -> Option<Vec<GenericManifest>> {
     // read content of the file
-    let content = match fs::read_to_string(&args.file).await {
+    let content = match fs::read_to_string(file).await {
         Ok(c) => c,
         Err(e) => {
-            eprintln!("Failed to read file '{}': {}", args.file, e);
-            return;
+            eprintln!("Failed to read file '{}': {}", file, e);
+            return None;
         }
     };
 
     // parse each object into generic manifests
     // server will serialize into specific manifest depending on endpoint
-    let docs: Vec<GenericManifest> = match serde_yaml::Deserializer::from_str(&content)
+    match serde_yaml::Deserializer::from_str(&content)
         .map(|doc| serde_yaml::from_value(serde_yaml::Value::deserialize(doc).unwrap()))
         .collect::<Result<_, _>>()
     {
-        Ok(objs) => objs,
+        Ok(objs) => Some(objs),
         Err(e) => {
             eprintln!("Failed to parse YAML: {}", e);
-            return;
+            None
         }
-    };
-
-    // send each manifest to the specified resource endpoint
-    let client = Client::new();
-    for object in docs {
-        let url = format!("{}/{}s?controller=false", config.url, object.spec);
-        let manifest = object.spec.into_manifest(object.metadata);
-
-        match client.post(&url).json(&manifest).send().await {
-            Ok(_) => {}
-            Err(err) => eprintln!("Error: {:?}", err),
-        };
     }
 }
 
@@ -85,24 +105,40 @@ pub struct GenericManifest {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(tag = "kind", content = "spec", rename_all = "PascalCase")]
 pub enum Spec {
-    Pod {
-        containers: Vec<ContainerSpec>,
-    },
+    Pod(PodContainers),
     ReplicaSet {
         replicas: u16,
         selector: LabelSelector,
         template: PodManifest,
     },
+    PriorityClass(PriorityClassSpec),
+    PodGroup(PodGroupSpec),
+    PodDisruptionBudget(PodDisruptionBudgetSpec),
+    ConfigMap(ConfigMapSpec),
+    Secret(SecretSpec),
+    PersistentVolume(PersistentVolumeSpec),
+    PersistentVolumeClaim(PersistentVolumeClaimSpec),
+    Service(ServiceSpec),
+    Ingress(IngressSpec),
+    Role(RoleSpec),
+    ClusterRole(ClusterRoleSpec),
+    RoleBinding(RoleBindingSpec),
+    ClusterRoleBinding(ClusterRoleBindingSpec),
 }
 
 impl Spec {
+    /// Collection path the kind is created under.
+    pub fn plural(&self) -> String {
+        match self {
+            Spec::Ingress(_) => "ingresses".to_string(),
+            _ => format!("{}s", self),
+        }
+    }
+
     /// Converts the enum variant into a boxed `Manifest` implementation.
     pub fn into_manifest(self, metadata: ObjectMetadata) -> Box<dyn Manifest> {
         match self {
-            Spec::Pod { containers } => Box::new(PodManifest {
-                metadata,
-                spec: PodContainers { containers },
-            }),
+            Spec::Pod(spec) => Box::new(PodManifest { metadata, spec }),
             Spec::ReplicaSet {
                 replicas,
                 selector,
@@ -115,6 +151,25 @@ impl Spec {
                     template,
                 },
             }),
+            Spec::PriorityClass(spec) => Box::new(PriorityClassManifest { metadata, spec }),
+            Spec::PodGroup(spec) => Box::new(PodGroupManifest { metadata, spec }),
+            Spec::PodDisruptionBudget(spec) => {
+                Box::new(PodDisruptionBudgetManifest { metadata, spec })
+            }
+            Spec::ConfigMap(spec) => Box::new(ConfigMapManifest { metadata, spec }),
+            Spec::Secret(spec) => Box::new(SecretManifest { metadata, spec }),
+            Spec::PersistentVolume(spec) => Box::new(PersistentVolumeManifest { metadata, spec }),
+            Spec::PersistentVolumeClaim(spec) => {
+                Box::new(PersistentVolumeClaimManifest { metadata, spec })
+            }
+            Spec::Service(spec) => Box::new(ServiceManifest { metadata, spec }),
+            Spec::Ingress(spec) => Box::new(IngressManifest { metadata, spec }),
+            Spec::Role(spec) => Box::new(RoleManifest { metadata, spec }),
+            Spec::ClusterRole(spec) => Box::new(ClusterRoleManifest { metadata, spec }),
+            Spec::RoleBinding(spec) => Box::new(RoleBindingManifest { metadata, spec }),
+            Spec::ClusterRoleBinding(spec) => {
+                Box::new(ClusterRoleBindingManifest { metadata, spec })
+            }
         }
     }
 }
@@ -123,8 +178,21 @@ impl std::fmt::Display for Spec {
     /// Formats the spec type as a lowercase kind string.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Spec::Pod { .. } => write!(f, "pod"),
+            Spec::Pod(_) => write!(f, "pod"),
             Spec::ReplicaSet { .. } => write!(f, "replicaset"),
+            Spec::PriorityClass(_) => write!(f, "priorityclass"),
+            Spec::PodGroup(_) => write!(f, "podgroup"),
+            Spec::PodDisruptionBudget(_) => write!(f, "poddisruptionbudget"),
+            Spec::ConfigMap(_) => write!(f, "configmap"),
+            Spec::Secret(_) => write!(f, "secret"),
+            Spec::PersistentVolume(_) => write!(f, "persistentvolume"),
+            Spec::PersistentVolumeClaim(_) => write!(f, "persistentvolumeclaim"),
+            Spec::Service(_) => write!(f, "service"),
+            Spec::Ingress(_) => write!(f, "ingress"),
+            Spec::Role(_) => write!(f, "role"),
+            Spec::ClusterRole(_) => write!(f, "clusterrole"),
+            Spec::RoleBinding(_) => write!(f, "rolebinding"),
+            Spec::ClusterRoleBinding(_) => write!(f, "clusterrolebinding"),
         }
     }
 }
@@ -1,5 +1,5 @@
 //! CLI `delete` command to remove resources from the server by name.
-//! Currently supports deleting Pods via HTTP DELETE.
+//! Currently supports deleting Pods, persistent volumes, claims, services, ingresses and RBAC objects via HTTP DELETE.
 
 use clap::Parser;
 use reqwest::StatusCode;
@@ -15,16 +15,30 @@ pub struct DeleteArgs {
 
     /// Name or ID of the resource
     identifier: String,
+
+    /// Seconds the pod gets to shut down, overrides its own grace period
+    #[arg(long = "grace-period")]
+    grace_period: Option<u32>,
+
+    /// Remove the pod right away, same as --grace-period 0
+    #[arg(long)]
+    force: bool,
 }
 
 /// Constructs a DELETE request based on the resource type and sends it to the server.
 pub async fn handle_delete(config: &Config, args: &DeleteArgs) {
     match args.resource {
         ResourceKind::Pod => {
-            let url = format!("{}/{}s/{}", &config.url, args.resource, args.identifier);
-            match reqwest::Client::new().delete(&url).send().await {
+            let mut url = format!("{}/{}s/{}", &config.url, args.resource, args.identifier);
+            if let Some(grace) = args.force.then_some(0).or(args.grace_period) {
+                url.push_str(&format!("?gracePeriodSeconds={}", grace));
+            }
+            match shared::utils::client().delete(&url).send().await {
                 Ok(resp) => match resp.status() {
                     StatusCode::NO_CONTENT => {}
+                    StatusCode::ACCEPTED => {
+                        println!("{} {} terminating", args.resource, args.identifier)
+                    }
                     StatusCode::NOT_FOUND => {
                         eprintln!("{} {} not found", args.resource, args.identifier)
                     }
@@ -33,6 +47,34 @@ pub async fn handle_delete(config: &Config, args: &DeleteArgs) {
                 Err(_) => eprintln!("Error sending request"),
             }
         }
+        ResourceKind::Persistentvolume
+        | ResourceKind::Persistentvolumeclaim
+        | ResourceKind::Service
+        | ResourceKind::Ingress
+        | ResourceKind::Role
+        | ResourceKind::Clusterrole
+        | ResourceKind::Rolebinding
+        | ResourceKind::Clusterrolebinding => {
+            let url = format!(
+                "{}/{}/{}",
+                &config.url,
+                args.resource.plural(),
+                args.identifier
+            );
+            match shared::utils::client().delete(&url).send().await {
+                Ok(resp) => match resp.status() {
+                    StatusCode::NO_CONTENT => {}
+                    StatusCode::NOT_FOUND => {
+                        eprintln!("{} {} not found", args.resource, args.identifier)
+                    }
+                    StatusCode::CONFLICT => {
+                        eprintln!("{}", resp.text().await.unwrap_or_default())
+                    }
+                    _ => eprintln!("Error deleting resource"),
+                },
+                Err(_) => eprintln!("Error sending request"),
+            }
+        }
         ResourceKind::Deployment => eprintln!("not implemented"),
     }
 }
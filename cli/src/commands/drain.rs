@@ -0,0 +1,188 @@
+//! CLI `cordon`, `uncordon` and `drain` commands for node maintenance.
+//! Draining cordons the node and evicts its pods through the eviction
+//! subresource, so disruption budgets are respected.
+
+use std::{collections::HashSet, time::Duration};
+
+use clap::Parser;
+use reqwest::StatusCode;
+use shared::{
+    api::{NodePatch, NodeSpecPatch},
+    models::pod::Pod,
+};
+use tokio::time::{Instant, sleep};
+
+use crate::config::Config;
+
+/// Wait between eviction rounds while budgets block progress.
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+/// Wait between checks for evicted pods still on the node.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// CLI arguments for the `cordon` and `uncordon` commands.
+#[derive(Parser, Debug)]
+pub struct CordonArgs {
+    /// Name of the node
+    pub node: String,
+}
+
+/// CLI arguments for the `drain` command.
+#[derive(Parser, Debug)]
+pub struct DrainArgs {
+    /// Name of the node
+    pub node: String,
+    /// Seconds to wait for the pods to be evicted and terminate
+    #[arg(long, default_value_t = 60)]
+    pub timeout: u64,
+    /// Also evict pods not managed by a controller, they will not come back
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// Marks the node (un)schedulable.
+pub async fn handle_cordon(config: &Config, args: &CordonArgs, unschedulable: bool) {
+    if set_unschedulable(config, &args.node, unschedulable).await {
+        let action = if unschedulable {
+            "cordoned"
+        } else {
+            "uncordoned"
+        };
+        println!("node/{} {}", args.node, action);
+    }
+}
+
+/// Cordons the node and evicts its pods, then waits until they are gone or the timeout expires.
+pub async fn handle_drain(config: &Config, args: &DrainArgs) {
+    if let Err(err) = drain(config, args).await {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+async fn drain(config: &Config, args: &DrainArgs) -> Result<(), String> {
+    if !set_unschedulable(config, &args.node, true).await {
+        return Err(format!("Cannot drain node {}", args.node));
+    }
+    println!("node/{} cordoned", args.node);
+
+    let pods = node_pods(config, &args.node).await?;
+    let (mut pending, unmanaged): (Vec<Pod>, Vec<Pod>) = pods
+        .into_iter()
+        .partition(|p| p.metadata.owner_reference.is_some());
+    if !unmanaged.is_empty() {
+        if !args.force {
+            let names: Vec<String> = unmanaged.into_iter().map(|p| p.metadata.name).collect();
+            return Err(format!(
+                "Cannot drain, pods not managed by a controller (use --force):\n  {}",
+                names.join("\n  ")
+            ));
+        }
+        pending.extend(unmanaged);
+    }
+
+    let client = shared::utils::client();
+    let deadline = Instant::now() + Duration::from_secs(args.timeout);
+    let mut evicted = HashSet::new();
+    loop {
+        let mut blocked = Vec::new();
+        for pod in pending {
+            let url = format!("{}/pods/{}/eviction", config.url, pod.metadata.name);
+            match client.post(&url).send().await {
+                Ok(resp)
+                    if resp.status().is_success() || resp.status() == StatusCode::NOT_FOUND =>
+                {
+                    println!("pod/{} evicted", pod.metadata.name);
+                    evicted.insert(pod.metadata.id);
+                }
+                Ok(resp) if resp.status() == StatusCode::TOO_MANY_REQUESTS => {
+                    blocked.push(pod);
+                }
+                Ok(resp) => {
+                    return Err(format!(
+                        "Error evicting pod {}: {}",
+                        pod.metadata.name,
+                        resp.status()
+                    ));
+                }
+                Err(_) => return Err("Error sending request".to_string()),
+            }
+        }
+
+        if blocked.is_empty() {
+            break;
+        }
+        if Instant::now() + RETRY_INTERVAL > deadline {
+            let names: Vec<String> = blocked.into_iter().map(|p| p.metadata.name).collect();
+            return Err(format!(
+                "Timed out, eviction blocked by a disruption budget for:\n  {}",
+                names.join("\n  ")
+            ));
+        }
+        sleep(RETRY_INTERVAL).await;
+        pending = blocked;
+    }
+
+    // evictions are accepted right away, the pods still have to terminate
+    loop {
+        let remaining: Vec<String> = node_pods(config, &args.node)
+            .await?
+            .into_iter()
+            .filter(|p| evicted.contains(&p.metadata.id))
+            .map(|p| p.metadata.name)
+            .collect();
+        if remaining.is_empty() {
+            println!("node/{} drained", args.node);
+            return Ok(());
+        }
+        if Instant::now() + POLL_INTERVAL > deadline {
+            return Err(format!(
+                "Timed out waiting for pods to terminate:\n  {}",
+                remaining.join("\n  ")
+            ));
+        }
+        sleep(POLL_INTERVAL).await;
+    }
+}
+
+async fn set_unschedulable(config: &Config, node: &str, unschedulable: bool) -> bool {
+    let patch = NodePatch {
+        spec: NodeSpecPatch {
+            unschedulable: Some(unschedulable),
+            ..Default::default()
+        },
+    };
+    let url = format!("{}/nodes/{}", config.url, node);
+    match shared::utils::client()
+        .patch(&url)
+        .json(&patch)
+        .send()
+        .await
+    {
+        Ok(resp) => match resp.status() {
+            StatusCode::OK => true,
+            StatusCode::NOT_FOUND => {
+                eprintln!("node {} not found", node);
+                false
+            }
+            _ => {
+                eprintln!("Error updating node");
+                false
+            }
+        },
+        Err(_) => {
+            eprintln!("Error sending request");
+            false
+        }
+    }
+}
+
+async fn node_pods(config: &Config, node: &str) -> Result<Vec<Pod>, String> {
+    let url = format!("{}/pods?nodeName={}", config.url, node);
+    match shared::utils::client().get(&url).send().await {
+        Ok(resp) if resp.status().is_success() => resp
+            .json::<Vec<Pod>>()
+            .await
+            .map_err(|e| format!("Failed to parse pods: {}", e)),
+        _ => Err(format!("Error listing pods on node {}", node)),
+    }
+}
@@ -2,7 +2,22 @@
 //! Fetches a list and displays it as a formatted table.
 
 use clap::Parser;
-use shared::models::{node::Node, pod::Pod, replicaset::ReplicaSet};
+use shared::models::{
+    configmap::ConfigMap,
+    endpoints::Endpoints,
+    ingress::Ingress,
+    node::Node,
+    persistentvolume::PersistentVolume,
+    persistentvolumeclaim::PersistentVolumeClaim,
+    pod::Pod,
+    poddisruptionbudget::PodDisruptionBudget,
+    podgroup::PodGroup,
+    priorityclass::PriorityClass,
+    rbac::{ClusterRole, ClusterRoleBinding, Role, RoleBinding},
+    replicaset::ReplicaSet,
+    secret::Secret,
+    service::Service,
+};
 use tabled::{Table, settings::Style};
 
 use super::ResourceType;
@@ -19,7 +34,7 @@ pub struct GetArgs {
 /// Sends a GET request for the specified resource type and prints a table view.
 pub async fn handle_get(config: &Config, args: &GetArgs) {
     let url = format!("{}/{}", &config.url, args.resource);
-    let response = reqwest::get(&url).await;
+    let response = shared::utils::client().get(&url).send().await;
 
     // Parse response and show in tabled
     match response {
@@ -48,6 +63,123 @@ pub async fn handle_get(config: &Config, args: &GetArgs) {
                 }
                 Err(e) => eprintln!("Failed to parse replicasets: {}", e),
             },
+            ResourceType::Priorityclasses => match resp.json::<Vec<PriorityClass>>().await {
+                Ok(data) => {
+                    let mut table = Table::new(data);
+                    table.with(Style::blank());
+                    println!("{}", table);
+                }
+                Err(e) => eprintln!("Failed to parse priority classes: {}", e),
+            },
+            ResourceType::Podgroups => match resp.json::<Vec<PodGroup>>().await {
+                Ok(data) => {
+                    let mut table = Table::new(data);
+                    table.with(Style::blank());
+                    println!("{}", table);
+                }
+                Err(e) => eprintln!("Failed to parse pod groups: {}", e),
+            },
+            ResourceType::Poddisruptionbudgets => {
+                match resp.json::<Vec<PodDisruptionBudget>>().await {
+                    Ok(data) => {
+                        let mut table = Table::new(data);
+                        table.with(Style::blank());
+                        println!("{}", table);
+                    }
+                    Err(e) => eprintln!("Failed to parse disruption budgets: {}", e),
+                }
+            }
+            ResourceType::Configmaps => match resp.json::<Vec<ConfigMap>>().await {
+                Ok(data) => {
+                    let mut table = Table::new(data);
+                    table.with(Style::blank());
+                    println!("{}", table);
+                }
+                Err(e) => eprintln!("Failed to parse config maps: {}", e),
+            },
+            ResourceType::Secrets => match resp.json::<Vec<Secret>>().await {
+                Ok(data) => {
+                    let mut table = Table::new(data);
+                    table.with(Style::blank());
+                    println!("{}", table);
+                }
+                Err(e) => eprintln!("Failed to parse secrets: {}", e),
+            },
+            ResourceType::Persistentvolumes => match resp.json::<Vec<PersistentVolume>>().await {
+                Ok(data) => {
+                    let mut table = Table::new(data);
+                    table.with(Style::blank());
+                    println!("{}", table);
+                }
+                Err(e) => eprintln!("Failed to parse persistent volumes: {}", e),
+            },
+            ResourceType::Persistentvolumeclaims => {
+                match resp.json::<Vec<PersistentVolumeClaim>>().await {
+                    Ok(data) => {
+                        let mut table = Table::new(data);
+                        table.with(Style::blank());
+                        println!("{}", table);
+                    }
+                    Err(e) => eprintln!("Failed to parse persistent volume claims: {}", e),
+                }
+            }
+            ResourceType::Services => match resp.json::<Vec<Service>>().await {
+                Ok(data) => {
+                    let mut table = Table::new(data);
+                    table.with(Style::blank());
+                    println!("{}", table);
+                }
+                Err(e) => eprintln!("Failed to parse services: {}", e),
+            },
+            ResourceType::Endpoints => match resp.json::<Vec<Endpoints>>().await {
+                Ok(data) => {
+                    let mut table = Table::new(data);
+                    table.with(Style::blank());
+                    println!("{}", table);
+                }
+                Err(e) => eprintln!("Failed to parse endpoints: {}", e),
+            },
+            ResourceType::Ingresses => match resp.json::<Vec<Ingress>>().await {
+                Ok(data) => {
+                    let mut table = Table::new(data);
+                    table.with(Style::blank());
+                    println!("{}", table);
+                }
+                Err(e) => eprintln!("Failed to parse ingresses: {}", e),
+            },
+            ResourceType::Roles => match resp.json::<Vec<Role>>().await {
+                Ok(data) => {
+                    let mut table = Table::new(data);
+                    table.with(Style::blank());
+                    println!("{}", table);
+                }
+                Err(e) => eprintln!("Failed to parse roles: {}", e),
+            },
+            ResourceType::Clusterroles => match resp.json::<Vec<ClusterRole>>().await {
+                Ok(data) => {
+                    let mut table = Table::new(data);
+                    table.with(Style::blank());
+                    println!("{}", table);
+                }
+                Err(e) => eprintln!("Failed to parse cluster roles: {}", e),
+            },
+            ResourceType::Rolebindings => match resp.json::<Vec<RoleBinding>>().await {
+                Ok(data) => {
+                    let mut table = Table::new(data);
+                    table.with(Style::blank());
+                    println!("{}", table);
+                }
+                Err(e) => eprintln!("Failed to parse role bindings: {}", e),
+            },
+            ResourceType::Clusterrolebindings => match resp.json::<Vec<ClusterRoleBinding>>().await
+            {
+                Ok(data) => {
+                    let mut table = Table::new(data);
+                    table.with(Style::blank());
+                    println!("{}", table);
+                }
+                Err(e) => eprintln!("Failed to parse cluster role bindings: {}", e),
+            },
         },
         Ok(_) => {}
         Err(_) => {}
@@ -36,7 +36,7 @@ pub async fn handle_logs(config: &Config, args: &LogArgs) {
         url = format!("{}?{}", url, query.join("&"));
     }
 
-    match reqwest::Client::new().get(&url).send().await {
+    match shared::utils::client().get(&url).send().await {
         Ok(resp) => match resp.status() {
             StatusCode::OK => {
                 if args.follow {
@@ -1,7 +1,11 @@
+pub mod auth;
 pub mod create;
 pub mod delete;
+pub mod drain;
 pub mod get;
 pub mod logs;
+pub mod schedule;
+pub mod token;
 
 use clap::ValueEnum;
 use std::fmt;
@@ -11,12 +15,34 @@ pub enum ResourceType {
     Nodes,
     Pods,
     Replicasets,
+    Priorityclasses,
+    Podgroups,
+    Poddisruptionbudgets,
+    Configmaps,
+    Secrets,
+    Persistentvolumes,
+    Persistentvolumeclaims,
+    Services,
+    Endpoints,
+    Ingresses,
+    Roles,
+    Clusterroles,
+    Rolebindings,
+    Clusterrolebindings,
 }
 
 #[derive(ValueEnum, Debug, Clone, PartialEq)]
 pub enum ResourceKind {
     Pod,
     Deployment,
+    Persistentvolume,
+    Persistentvolumeclaim,
+    Service,
+    Ingress,
+    Role,
+    Clusterrole,
+    Rolebinding,
+    Clusterrolebinding,
 }
 
 impl fmt::Display for ResourceType {
@@ -25,16 +51,48 @@ impl fmt::Display for ResourceType {
             ResourceType::Nodes => "nodes",
             ResourceType::Pods => "pods",
             ResourceType::Replicasets => "replicasets",
+            ResourceType::Priorityclasses => "priorityclasses",
+            ResourceType::Podgroups => "podgroups",
+            ResourceType::Poddisruptionbudgets => "poddisruptionbudgets",
+            ResourceType::Configmaps => "configmaps",
+            ResourceType::Secrets => "secrets",
+            ResourceType::Persistentvolumes => "persistentvolumes",
+            ResourceType::Persistentvolumeclaims => "persistentvolumeclaims",
+            ResourceType::Services => "services",
+            ResourceType::Endpoints => "endpoints",
+            ResourceType::Ingresses => "ingresses",
+            ResourceType::Roles => "roles",
+            ResourceType::Clusterroles => "clusterroles",
+            ResourceType::Rolebindings => "rolebindings",
+            ResourceType::Clusterrolebindings => "clusterrolebindings",
         };
         write!(f, "{}", s)
     }
 }
 
+impl ResourceKind {
+    /// Collection path the kind lives under.
+    pub fn plural(&self) -> String {
+        match self {
+            ResourceKind::Ingress => "ingresses".to_string(),
+            _ => format!("{}s", self),
+        }
+    }
+}
+
 impl fmt::Display for ResourceKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
             ResourceKind::Pod => "pod",
             ResourceKind::Deployment => "deployment",
+            ResourceKind::Persistentvolume => "persistentvolume",
+            ResourceKind::Persistentvolumeclaim => "persistentvolumeclaim",
+            ResourceKind::Service => "service",
+            ResourceKind::Ingress => "ingress",
+            ResourceKind::Role => "role",
+            ResourceKind::Clusterrole => "clusterrole",
+            ResourceKind::Rolebinding => "rolebinding",
+            ResourceKind::Clusterrolebinding => "clusterrolebinding",
         };
         write!(f, "{}", s)
     }
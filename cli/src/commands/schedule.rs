@@ -0,0 +1,89 @@
+//! CLI `schedule` command: asks the scheduler where the pods in a manifest would land.
+//! ReplicaSets are expanded into their replicas, other kinds are ignored.
+
+use clap::Parser;
+use reqwest::StatusCode;
+use shared::api::{PodManifest, SimulationRequest, SimulationResult};
+use tabled::{Table, settings::Style};
+
+use crate::{
+    commands::create::{Spec, read_manifests},
+    config::Config,
+};
+
+/// CLI arguments for the `schedule` command.
+#[derive(Parser, Debug)]
+pub struct ScheduleArgs {
+    /// Path to the YAML file containing the pods to place
+    #[clap(short = 'f', long = "file")]
+    pub file: String,
+    /// Only simulate placement, nothing is created or bound
+    #[arg(long = "dry-run")]
+    pub dry_run: bool,
+}
+
+/// Sends the pods in the file to the simulation endpoint and prints a table per pod.
+pub async fn handle_schedule(config: &Config, args: &ScheduleArgs) {
+    if !args.dry_run {
+        eprintln!("Only --dry-run is supported, use `create` to apply the manifest");
+        return;
+    }
+    let Some(docs) = read_manifests(&args.file).await else {
+        return;
+    };
+
+    let mut pods = Vec::new();
+    for object in docs {
+        match object.spec {
+            Spec::Pod(spec) => pods.push(PodManifest {
+                metadata: object.metadata,
+                spec,
+            }),
+            Spec::ReplicaSet {
+                replicas, template, ..
+            } => {
+                for i in 0..replicas {
+                    let mut pod = template.clone();
+                    pod.metadata.name = format!("{}-{}", object.metadata.name, i);
+                    pods.push(pod);
+                }
+            }
+            _ => {}
+        }
+    }
+    if pods.is_empty() {
+        eprintln!("No pods found in '{}'", args.file);
+        return;
+    }
+
+    let url = format!("{}/scheduler/simulate", config.url);
+    let request = SimulationRequest { pods };
+    match shared::utils::client()
+        .post(&url)
+        .json(&request)
+        .send()
+        .await
+    {
+        Ok(resp) if resp.status() == StatusCode::OK => {
+            match resp.json::<SimulationResult>().await {
+                Ok(result) => {
+                    for pod in result.pods {
+                        match &pod.node_name {
+                            Some(node) => println!("{} -> {}", pod.pod_name, node),
+                            None => println!("{} -> does not fit", pod.pod_name),
+                        }
+                        let mut table = Table::new(pod.nodes);
+                        table.with(Style::blank());
+                        println!("{}\n", table);
+                    }
+                }
+                Err(e) => eprintln!("Failed to parse simulation: {}", e),
+            }
+        }
+        Ok(resp) => eprintln!(
+            "Simulation failed: {}",
+            resp.text().await.unwrap_or_default()
+        ),
+        Err(_) => eprintln!("Error sending request"),
+    }
+}
@@ -0,0 +1,58 @@
+//! CLI `token` command: issues signed tokens for service accounts.
+
+use clap::{Parser, Subcommand};
+use reqwest::StatusCode;
+use shared::api::{TokenRequest, TokenResponse};
+
+use crate::config::Config;
+
+/// CLI arguments for the `token` command.
+#[derive(Parser, Debug)]
+pub struct TokenArgs {
+    #[command(subcommand)]
+    pub command: TokenCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TokenCommand {
+    /// Issue a token for a service account and print it
+    Create {
+        /// Name of the service account
+        name: String,
+        /// Extra group of the account, can be repeated
+        #[arg(long = "group")]
+        groups: Vec<String>,
+        /// Lifetime of the token in seconds, it never expires without one
+        #[arg(long)]
+        ttl: Option<u64>,
+    },
+}
+
+pub async fn handle_token(config: &Config, args: &TokenArgs) {
+    let TokenCommand::Create { name, groups, ttl } = &args.command;
+    let request = TokenRequest {
+        service_account: name.clone(),
+        groups: groups.clone(),
+        expiration_seconds: *ttl,
+    };
+    let url = format!("{}/tokens", config.url);
+    match shared::utils::client()
+        .post(&url)
+        .json(&request)
+        .send()
+        .await
+    {
+        Ok(resp) if resp.status() == StatusCode::CREATED => {
+            match resp.json::<TokenResponse>().await {
+                Ok(body) => println!("{}", body.token),
+                Err(e) => eprintln!("Invalid response: {}", e),
+            }
+        }
+        Ok(resp) => {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            eprintln!("Failed to issue token ({}): {}", status, body);
+        }
+        Err(e) => eprintln!("Request error: {}", e),
+    }
+}
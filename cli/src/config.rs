@@ -6,6 +6,8 @@ const CR8S_SERVER_PORT: u16 = 7620;
 #[derive(Debug)]
 pub struct Config {
     pub url: String,
+    /// Bearer token sent with every request
+    pub token: Option<String>,
 }
 
 impl Config {
@@ -17,8 +19,16 @@ impl Config {
             .and_then(|s| s.parse::<u16>().ok())
             .unwrap_or(CR8S_SERVER_PORT);
 
+        // a cluster CA means the apiserver serves TLS
+        let scheme = if env::var("CR8S_CA_CERT").is_ok() {
+            "https"
+        } else {
+            "http"
+        };
+
         Self {
-            url: format!("http://{}:{}", address, port),
+            url: format!("{}://{}:{}", scheme, address, port),
+            token: env::var("CR8S_TOKEN").ok(),
         }
     }
 }
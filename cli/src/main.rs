@@ -2,10 +2,14 @@ use clap::{Parser, Subcommand};
 
 use crate::{
     commands::{
+        auth::{AuthArgs, handle_auth},
         create::{CreateArgs, handle_create},
         delete::{DeleteArgs, handle_delete},
+        drain::{CordonArgs, DrainArgs, handle_cordon, handle_drain},
         get::{GetArgs, handle_get},
         logs::{LogArgs, handle_logs},
+        schedule::{ScheduleArgs, handle_schedule},
+        token::{TokenArgs, handle_token},
     },
     config::Config,
 };
@@ -31,16 +35,35 @@ enum Commands {
     Delete(DeleteArgs),
     /// Display the logs for a resource
     Logs(LogArgs),
+    /// Preview where pods would be scheduled
+    Schedule(ScheduleArgs),
+    /// Mark a node unschedulable
+    Cordon(CordonArgs),
+    /// Mark a node schedulable again
+    Uncordon(CordonArgs),
+    /// Cordon a node and evict its pods
+    Drain(DrainArgs),
+    /// Issue service account tokens
+    Token(TokenArgs),
+    /// Inspect authorization
+    Auth(AuthArgs),
 }
 
 #[tokio::main]
 async fn main() {
     let cli = Cr8sCtl::parse();
     let config = Config::from_env();
+    shared::utils::set_bearer_token(config.token.clone());
     match cli.command {
         Commands::Get(args) => handle_get(&config, &args).await,
         Commands::Create(args) => handle_create(&config, &args).await,
         Commands::Delete(args) => handle_delete(&config, &args).await,
         Commands::Logs(args) => handle_logs(&config, &args).await,
+        Commands::Schedule(args) => handle_schedule(&config, &args).await,
+        Commands::Cordon(args) => handle_cordon(&config, &args, true).await,
+        Commands::Uncordon(args) => handle_cordon(&config, &args, false).await,
+        Commands::Drain(args) => handle_drain(&config, &args).await,
+        Commands::Token(args) => handle_token(&config, &args).await,
+        Commands::Auth(args) => handle_auth(&config, &args).await,
     };
 }
@@ -6,26 +6,77 @@ use actix_web::{App, HttpResponse, HttpServer, Responder, web};
 use bytes::Bytes;
 use futures_util::StreamExt;
 use shared::api::LogsQueryParams;
+use std::sync::Arc;
+use tokio_rustls::rustls::{
+    ServerConfig,
+    crypto::ring,
+    pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+};
 use uuid::Uuid;
 
 /// Routes:
 /// - `GET /pods/{pod_id}/logs`: Retrieves logs for a specific pod container.
+///
+/// Served over TLS with the configured certificate or the one issued at registration.
 pub async fn run(state: State) -> Result<(), String> {
     let port = state.config.port;
     let node_api_workers = state.config.node_api_workers;
+    let tls = tls_config(&state)?;
 
-    HttpServer::new(move || {
+    let app_state = state.clone();
+    let server = HttpServer::new(move || {
         App::new()
-            .app_data(state.clone())
+            .app_data(app_state.clone())
             .route("/pods/{pod_id}/logs", web::get().to(pod_logs))
             .route("/", web::get().to(root))
-    })
-    .bind(("0.0.0.0", port))
-    .map_err(|e| e.to_string())?
-    .workers(node_api_workers)
-    .run()
-    .await
-    .map_err(|e| e.to_string())
+    });
+    let server = match tls {
+        Some(config) => server.bind_rustls_0_23(("0.0.0.0", port), config),
+        None => {
+            tracing::warn!("No serving certificate, node API served over plain HTTP");
+            server.bind(("0.0.0.0", port))
+        }
+    };
+
+    server
+        .map_err(|e| e.to_string())?
+        .workers(node_api_workers)
+        // signals are handled by core::shutdown so logs stay available while draining
+        .disable_signals()
+        .run()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// TLS settings from `NODE_TLS_CERT` and `NODE_TLS_KEY`, or the certificate issued at registration.
+fn tls_config(state: &State) -> Result<Option<ServerConfig>, String> {
+    let (cert, key) = match (&state.config.tls_cert, &state.config.tls_key) {
+        (Some(cert), Some(key)) => (
+            std::fs::read_to_string(cert).map_err(|e| format!("{}: {}", cert, e))?,
+            std::fs::read_to_string(key).map_err(|e| format!("{}: {}", key, e))?,
+        ),
+        (None, None) => match state.serving_cert() {
+            Some(pair) => pair.clone(),
+            None => return Ok(None),
+        },
+        _ => return Err("NODE_TLS_CERT and NODE_TLS_KEY must be set together".to_string()),
+    };
+    server_config(&cert, &key).map(Some)
+}
+
+/// Builds the server TLS settings from a PEM certificate chain and private key.
+fn server_config(cert: &str, key: &str) -> Result<ServerConfig, String> {
+    let chain = CertificateDer::pem_slice_iter(cert.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid serving certificate: {}", e))?;
+    let key = PrivateKeyDer::from_pem_slice(key.as_bytes())
+        .map_err(|e| format!("Invalid serving key: {}", e))?;
+    ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| e.to_string())?
+        .with_no_client_auth()
+        .with_single_cert(chain, key)
+        .map_err(|e| e.to_string())
 }
 
 /// Root endpoint handler.
@@ -117,3 +168,26 @@ async fn pod_logs(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    //! - test_server_config
+    //!   certificate and key issued at registration accepted
+    //! - test_server_config_invalid
+
+    use super::*;
+
+    #[test]
+    fn test_server_config() {
+        let cert = rcgen::generate_simple_self_signed(vec!["n1".to_string()]).unwrap();
+        let config = server_config(&cert.cert.pem(), &cert.key_pair.serialize_pem());
+        assert!(config.is_ok());
+    }
+
+    #[test]
+    fn test_server_config_invalid() {
+        let cert = rcgen::generate_simple_self_signed(vec!["n1".to_string()]).unwrap();
+        assert!(server_config(&cert.cert.pem(), "not a key").is_err());
+        assert!(server_config("", &cert.key_pair.serialize_pem()).is_err());
+    }
+}
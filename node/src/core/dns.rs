@@ -0,0 +1,616 @@
+//! # Cluster DNS
+//!
+//! Answers the cluster zone from the watched services, endpoints and pods:
+//! - `<svc>.default.svc.<domain>`: A records of the ready endpoints, SRV records of every port
+//! - `_<port>._<proto>.<svc>.default.svc.<domain>`: SRV records of a named port
+//! - `<pod>.default.pod.<domain>`: A record of the pod
+//!
+//! There are no namespaces yet, every object lives in `default`. Queries
+//! outside the zone are forwarded to the upstream resolver.
+
+use std::{
+    collections::BTreeSet,
+    net::{Ipv4Addr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use dashmap::{DashMap, DashSet};
+use hickory_proto::{
+    op::{Message, MessageType, OpCode, ResponseCode},
+    rr::{
+        Name, RData, Record, RecordType,
+        rdata::{A, SRV},
+    },
+};
+use shared::{
+    api::{EndpointsEvent, EventType, PodEvent, ServiceEvent},
+    models::endpoints::{EndpointPort, Endpoints},
+    utils::watch_stream,
+};
+use tokio::{net::UdpSocket, time::timeout};
+
+use crate::state::State;
+
+/// Cluster records change often, resolvers should not keep them long.
+const TTL: u32 = 5;
+
+const NAMESPACE: &str = "default";
+
+/// Time the upstream resolver gets to answer a forwarded query.
+const UPSTREAM_TIMEOUT: Duration = Duration::from_secs(2);
+
+const MAX_DATAGRAM: usize = 4096;
+
+/// Watches the cluster objects and serves DNS queries.
+///
+/// A node that can't bind the address, taken by a local resolver or needing
+/// privileges, keeps running without cluster DNS.
+pub async fn run(state: State) -> Result<(), String> {
+    let Some(address) = state.config.dns_bind_address() else {
+        return Ok(());
+    };
+    let records = Arc::new(Records::new(&state.config.cluster_domain)?);
+    let upstream = match &state.config.dns_upstream {
+        Some(addr) => addr
+            .parse()
+            .map_err(|e| format!("Invalid DNS upstream: {}", e))?,
+        None => system_resolver(),
+    };
+    let socket = match UdpSocket::bind(&address).await {
+        Ok(socket) => socket,
+        Err(err) => {
+            tracing::error!(error=%err, %address, "Failed to bind DNS address, serving no cluster DNS");
+            return Ok(());
+        }
+    };
+    tracing::info!(addr=%address, domain=%state.config.cluster_domain, %upstream, "Serving cluster DNS");
+
+    let server = &state.config.server_url;
+    let services_url = format!("{}/services?watch=true", server);
+    let endpoints_url = format!("{}/endpoints?watch=true", server);
+    let pods_url = format!("{}/pods?watch=true", server);
+    tokio::join!(
+        {
+            let records = records.clone();
+            watch_stream(&services_url, move |event| {
+                records.handle_service_event(event)
+            })
+        },
+        {
+            let records = records.clone();
+            watch_stream(&endpoints_url, move |event| {
+                records.handle_endpoints_event(event)
+            })
+        },
+        {
+            let records = records.clone();
+            watch_stream(&pods_url, move |event| records.handle_pod_event(event))
+        },
+        serve(socket, records.clone(), upstream),
+    );
+    Ok(())
+}
+
+/// First nameserver of resolv.conf, the one the agent itself uses.
+fn system_resolver() -> SocketAddr {
+    std::fs::read_to_string("/etc/resolv.conf")
+        .ok()
+        .and_then(|conf| {
+            conf.lines()
+                .filter_map(|line| line.trim().strip_prefix("nameserver"))
+                .find_map(|ip| ip.trim().parse().ok())
+        })
+        .map(|ip| SocketAddr::new(ip, 53))
+        .unwrap_or_else(|| SocketAddr::from(([8, 8, 8, 8], 53)))
+}
+
+/// Answers queries until the socket fails.
+pub async fn serve(socket: UdpSocket, records: Arc<Records>, upstream: SocketAddr) {
+    let socket = Arc::new(socket);
+    let mut buf = vec![0u8; MAX_DATAGRAM];
+    loop {
+        let (len, peer) = match socket.recv_from(&mut buf).await {
+            Ok(received) => received,
+            Err(err) => {
+                tracing::warn!(error=%err, "Failed to receive DNS query");
+                continue;
+            }
+        };
+        let query = buf[..len].to_vec();
+        let socket = socket.clone();
+        let records = records.clone();
+        tokio::spawn(async move {
+            if let Some(reply) = answer(&records, &query, upstream).await
+                && let Err(err) = socket.send_to(&reply, peer).await
+            {
+                tracing::debug!(error=%err, "Failed to send DNS reply");
+            }
+        });
+    }
+}
+
+/// Reply to a raw query, resolved locally or by the upstream resolver.
+async fn answer(records: &Records, query: &[u8], upstream: SocketAddr) -> Option<Vec<u8>> {
+    let request = Message::from_vec(query).ok()?;
+    let Some(question) = request.queries().first() else {
+        return Message::error_msg(request.id(), request.op_code(), ResponseCode::FormErr)
+            .to_vec()
+            .ok();
+    };
+
+    let (code, answers, additionals) = match records.resolve(question.name(), question.query_type())
+    {
+        Some(Ok((answers, additionals))) => (ResponseCode::NoError, answers, additionals),
+        Some(Err(code)) => (code, Vec::new(), Vec::new()),
+        None => {
+            return match forward(query, upstream).await {
+                Ok(reply) => Some(reply),
+                Err(err) => {
+                    tracing::debug!(error=%err, name=%question.name(), "Upstream resolver failed");
+                    Message::error_msg(request.id(), request.op_code(), ResponseCode::ServFail)
+                        .to_vec()
+                        .ok()
+                }
+            };
+        }
+    };
+
+    let mut response = Message::new();
+    response
+        .set_id(request.id())
+        .set_message_type(MessageType::Response)
+        .set_op_code(OpCode::Query)
+        .set_authoritative(true)
+        .set_recursion_desired(request.recursion_desired())
+        .set_recursion_available(true)
+        .set_response_code(code)
+        .add_query(question.clone())
+        .add_answers(answers)
+        .add_additionals(additionals);
+    response.to_vec().ok()
+}
+
+async fn forward(query: &[u8], upstream: SocketAddr) -> std::io::Result<Vec<u8>> {
+    let local = if upstream.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    };
+    let socket = UdpSocket::bind(local).await?;
+    socket.connect(upstream).await?;
+    socket.send(query).await?;
+    let mut buf = vec![0u8; MAX_DATAGRAM];
+    let len = timeout(UPSTREAM_TIMEOUT, socket.recv(&mut buf))
+        .await
+        .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+    buf.truncate(len);
+    Ok(buf)
+}
+
+/// Answer and additional records of a query.
+type Answer = (Vec<Record>, Vec<Record>);
+
+/// Cluster objects the zone is built from.
+pub struct Records {
+    domain: Name,
+    services: DashSet<String>,
+    endpoints: DashMap<String, Endpoints>,
+    pods: DashMap<String, Ipv4Addr>,
+}
+
+impl Records {
+    pub fn new(domain: &str) -> Result<Self, String> {
+        let mut domain = Name::from_ascii(domain)
+            .map_err(|e| format!("Invalid cluster domain: {}", e))?
+            .to_lowercase();
+        domain.set_fqdn(true);
+        Ok(Self {
+            domain,
+            services: DashSet::new(),
+            endpoints: DashMap::new(),
+            pods: DashMap::new(),
+        })
+    }
+
+    pub fn handle_service_event(&self, event: ServiceEvent) {
+        let name = event.service.metadata.name;
+        match event.event_type {
+            EventType::Deleted => {
+                self.services.remove(&name);
+            }
+            EventType::Added | EventType::Modified => {
+                self.services.insert(name);
+            }
+        }
+    }
+
+    pub fn handle_endpoints_event(&self, event: EndpointsEvent) {
+        let name = event.endpoints.metadata.name.clone();
+        match event.event_type {
+            EventType::Deleted => {
+                self.endpoints.remove(&name);
+            }
+            EventType::Added | EventType::Modified => {
+                self.endpoints.insert(name, event.endpoints);
+            }
+        }
+    }
+
+    pub fn handle_pod_event(&self, event: PodEvent) {
+        let name = event.pod.metadata.name;
+        let ip = event.pod.status.pod_ip.and_then(|ip| ip.parse().ok());
+        match (event.event_type, ip) {
+            (EventType::Deleted, _) | (_, None) => {
+                self.pods.remove(&name);
+            }
+            (_, Some(ip)) => {
+                self.pods.insert(name, ip);
+            }
+        }
+    }
+
+    /// Records for a name of the zone, `None` if the name is outside of it.
+    pub fn resolve(
+        &self,
+        name: &Name,
+        record_type: RecordType,
+    ) -> Option<Result<Answer, ResponseCode>> {
+        let name = name.to_lowercase();
+        if !self.domain.zone_of(&name) {
+            return None;
+        }
+        let labels: Vec<String> = name
+            .iter()
+            .take((name.num_labels() - self.domain.num_labels()).into())
+            .map(|l| String::from_utf8_lossy(l).into_owned())
+            .collect();
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+
+        Some(match labels[..] {
+            [service, NAMESPACE, "svc"] => self.service(&name, service, None, record_type),
+            [port, proto, service, NAMESPACE, "svc"] => {
+                let port = port.strip_prefix('_').zip(proto.strip_prefix('_'));
+                match port {
+                    Some(port) => self.service(&name, service, Some(port), record_type),
+                    None => Err(ResponseCode::NXDomain),
+                }
+            }
+            [pod, NAMESPACE, "pod"] => match self.pods.get(pod) {
+                Some(ip) if record_type == RecordType::A => Ok((
+                    vec![Record::from_rdata(name.clone(), TTL, RData::A(A(*ip)))],
+                    Vec::new(),
+                )),
+                Some(_) => Ok((Vec::new(), Vec::new())),
+                None => Err(ResponseCode::NXDomain),
+            },
+            _ => Err(ResponseCode::NXDomain),
+        })
+    }
+
+    /// A records of the ready backends or SRV records of the selected ports.
+    fn service(
+        &self,
+        name: &Name,
+        service: &str,
+        port: Option<(&str, &str)>,
+        record_type: RecordType,
+    ) -> Result<Answer, ResponseCode> {
+        if !self.services.contains(service) {
+            return Err(ResponseCode::NXDomain);
+        }
+        let endpoints = self.endpoints.get(service);
+        let ports: Vec<&EndpointPort> = endpoints
+            .iter()
+            .flat_map(|ep| ep.ports.iter())
+            .filter(|p| {
+                port.is_none_or(|(port_name, proto)| {
+                    p.name.as_deref() == Some(port_name) && p.protocol.as_str() == proto
+                })
+            })
+            .collect();
+        if port.is_some() && ports.is_empty() {
+            return Err(ResponseCode::NXDomain);
+        }
+
+        match record_type {
+            RecordType::A if port.is_none() => {
+                let ips: BTreeSet<Ipv4Addr> = ports
+                    .iter()
+                    .flat_map(|p| p.addresses.iter())
+                    .filter_map(|a| a.ip.parse().ok())
+                    .collect();
+                let answers = ips
+                    .into_iter()
+                    .map(|ip| Record::from_rdata(name.clone(), TTL, RData::A(A(ip))))
+                    .collect();
+                Ok((answers, Vec::new()))
+            }
+            RecordType::SRV => {
+                let mut answers = Vec::new();
+                let mut additionals = Vec::new();
+                for address in ports.iter().flat_map(|p| p.addresses.iter()) {
+                    let Ok(target) = self.pod_name(&address.pod_name) else {
+                        continue;
+                    };
+                    let srv = SRV::new(0, 100, address.port, target.clone());
+                    answers.push(Record::from_rdata(name.clone(), TTL, RData::SRV(srv)));
+                    if let Ok(ip) = address.ip.parse() {
+                        additionals.push(Record::from_rdata(target, TTL, RData::A(A(ip))));
+                    }
+                }
+                Ok((answers, additionals))
+            }
+            _ => Ok((Vec::new(), Vec::new())),
+        }
+    }
+
+    fn pod_name(&self, pod: &str) -> Result<Name, ResponseCode> {
+        Name::from_ascii(format!("{}.{}.pod", pod, NAMESPACE))
+            .and_then(|n| n.append_domain(&self.domain))
+            .map_err(|_| ResponseCode::ServFail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! - test_resolve_service
+    //!   A records of the ready endpoints, NXDOMAIN for unknown names
+    //! - test_resolve_srv
+    //! - test_resolve_pod
+    //! - test_serve
+    //!   answers the zone and forwards the rest upstream
+    //! - test_run_optional
+    //!   returns without an address or when the address is taken
+
+    use super::*;
+    use crate::{docker::test::TestDocker, models::Config, state::NodeState};
+    use hickory_proto::op::Query;
+    use shared::models::{
+        endpoints::EndpointAddress,
+        metadata::Metadata,
+        pod::{Pod, Protocol},
+        service::{Service, ServiceSpec},
+    };
+
+    fn records() -> Records {
+        let records = Records::new("cr8s.local").unwrap();
+        records.handle_service_event(ServiceEvent {
+            event_type: EventType::Added,
+            service: Service {
+                metadata: Metadata {
+                    name: "web".to_string(),
+                    ..Default::default()
+                },
+                spec: ServiceSpec {
+                    selector: None,
+                    ports: Vec::new(),
+                },
+            },
+        });
+        let address = |ip: &str, port, pod: &str| EndpointAddress {
+            ip: ip.to_string(),
+            port,
+            pod_name: pod.to_string(),
+            node_name: "node-a".to_string(),
+        };
+        records.handle_endpoints_event(EndpointsEvent {
+            event_type: EventType::Added,
+            endpoints: Endpoints {
+                metadata: Metadata {
+                    name: "web".to_string(),
+                    ..Default::default()
+                },
+                ports: vec![
+                    EndpointPort {
+                        name: Some("http".to_string()),
+                        port: 80,
+                        protocol: Protocol::Tcp,
+                        addresses: vec![
+                            address("10.0.0.1", 8080, "web-a"),
+                            address("10.0.0.2", 8080, "web-b"),
+                        ],
+                    },
+                    EndpointPort {
+                        name: Some("dns".to_string()),
+                        port: 53,
+                        protocol: Protocol::Udp,
+                        addresses: vec![address("10.0.0.1", 5353, "web-a")],
+                    },
+                ],
+            },
+        });
+        records
+    }
+
+    fn name(name: &str) -> Name {
+        Name::from_ascii(name).unwrap()
+    }
+
+    fn ips(records: &[Record]) -> Vec<String> {
+        records
+            .iter()
+            .filter_map(|r| match r.data() {
+                Some(RData::A(a)) => Some(a.0.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_service() {
+        let records = records();
+
+        let (answers, _) = records
+            .resolve(&name("Web.default.svc.cr8s.local."), RecordType::A)
+            .unwrap()
+            .unwrap();
+        assert_eq!(ips(&answers), ["10.0.0.1", "10.0.0.2"]);
+
+        let (answers, _) = records
+            .resolve(&name("web.default.svc.cr8s.local."), RecordType::AAAA)
+            .unwrap()
+            .unwrap();
+        assert!(answers.is_empty());
+
+        for unknown in [
+            "api.default.svc.cr8s.local.",
+            "web.other.svc.cr8s.local.",
+            "cr8s.local.",
+        ] {
+            assert_eq!(
+                records
+                    .resolve(&name(unknown), RecordType::A)
+                    .unwrap()
+                    .err(),
+                Some(ResponseCode::NXDomain)
+            );
+        }
+        assert!(
+            records
+                .resolve(&name("example.com."), RecordType::A)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_resolve_srv() {
+        let records = records();
+
+        let (answers, additionals) = records
+            .resolve(
+                &name("_http._tcp.web.default.svc.cr8s.local."),
+                RecordType::SRV,
+            )
+            .unwrap()
+            .unwrap();
+        let targets: Vec<(String, u16)> = answers
+            .iter()
+            .filter_map(|r| match r.data() {
+                Some(RData::SRV(srv)) => Some((srv.target().to_string(), srv.port())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            targets,
+            [
+                ("web-a.default.pod.cr8s.local.".to_string(), 8080),
+                ("web-b.default.pod.cr8s.local.".to_string(), 8080),
+            ]
+        );
+        assert_eq!(ips(&additionals), ["10.0.0.1", "10.0.0.2"]);
+
+        let (answers, _) = records
+            .resolve(&name("web.default.svc.cr8s.local."), RecordType::SRV)
+            .unwrap()
+            .unwrap();
+        assert_eq!(answers.len(), 3);
+
+        assert_eq!(
+            records
+                .resolve(
+                    &name("_http._udp.web.default.svc.cr8s.local."),
+                    RecordType::SRV
+                )
+                .unwrap()
+                .err(),
+            Some(ResponseCode::NXDomain)
+        );
+    }
+
+    #[test]
+    fn test_resolve_pod() {
+        let records = records();
+        let mut pod = Pod::default();
+        pod.metadata.name = "web-a".to_string();
+        pod.status.pod_ip = Some("10.0.0.1".to_string());
+        records.handle_pod_event(PodEvent {
+            event_type: EventType::Added,
+            pod: pod.clone(),
+        });
+
+        let pod_name = name("web-a.default.pod.cr8s.local.");
+        let (answers, _) = records.resolve(&pod_name, RecordType::A).unwrap().unwrap();
+        assert_eq!(ips(&answers), ["10.0.0.1"]);
+
+        records.handle_pod_event(PodEvent {
+            event_type: EventType::Deleted,
+            pod,
+        });
+        assert_eq!(
+            records.resolve(&pod_name, RecordType::A).unwrap().err(),
+            Some(ResponseCode::NXDomain)
+        );
+    }
+
+    async fn query(socket: &UdpSocket, name: &str) -> Message {
+        let mut request = Message::new();
+        request
+            .set_id(7)
+            .set_recursion_desired(true)
+            .add_query(Query::query(self::name(name), RecordType::A));
+        socket.send(&request.to_vec().unwrap()).await.unwrap();
+        let mut buf = vec![0u8; MAX_DATAGRAM];
+        let len = timeout(Duration::from_secs(5), socket.recv(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        Message::from_vec(&buf[..len]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_serve() {
+        // Upstream answering every query with an empty NOERROR marked by its id
+        let upstream = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let upstream_addr = upstream.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0u8; MAX_DATAGRAM];
+            while let Ok((len, peer)) = upstream.recv_from(&mut buf).await {
+                let mut reply = Message::from_vec(&buf[..len]).unwrap();
+                reply.set_message_type(MessageType::Response);
+                upstream
+                    .send_to(&reply.to_vec().unwrap(), peer)
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        tokio::spawn(serve(server, Arc::new(records()), upstream_addr));
+
+        let client = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        client.connect(server_addr).await.unwrap();
+
+        let reply = query(&client, "web.default.svc.cr8s.local.").await;
+        assert_eq!(reply.id(), 7);
+        assert_eq!(reply.response_code(), ResponseCode::NoError);
+        assert!(reply.authoritative());
+        assert_eq!(ips(reply.answers()), ["10.0.0.1", "10.0.0.2"]);
+
+        let reply = query(&client, "nope.default.svc.cr8s.local.").await;
+        assert_eq!(reply.response_code(), ResponseCode::NXDomain);
+
+        let reply = query(&client, "example.com.").await;
+        assert_eq!(reply.response_code(), ResponseCode::NoError);
+        assert!(!reply.authoritative());
+        assert_eq!(reply.queries()[0].name(), &name("example.com."));
+    }
+
+    #[tokio::test]
+    async fn test_run_optional() {
+        let docker = Box::new(TestDocker::new());
+        let state = NodeState::new_with(Some(Config::default()), Some(docker));
+        assert_eq!(state.config.dns_bind_address(), None);
+        assert!(run(state).await.is_ok());
+
+        let taken = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let config = Config {
+            dns_address: Some(taken.local_addr().unwrap().to_string()),
+            ..Default::default()
+        };
+        let state = NodeState::new_with(Some(config), Some(Box::new(TestDocker::new())));
+        let res = timeout(Duration::from_secs(1), run(state)).await;
+        assert!(matches!(res, Ok(Ok(()))));
+    }
+}
@@ -0,0 +1,254 @@
+//! HTTP reverse proxy of the gateway.
+//!
+//! Every proxied request gets its own backend connection. A backend that
+//! cannot be reached is skipped for the next one, requests without a body are
+//! also sent again when the backend drops them before answering.
+
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use http_body_util::{BodyExt, Empty, Full, combinators::BoxBody};
+use hyper::{
+    HeaderMap, Request, Response, StatusCode,
+    body::{Body as _, Incoming},
+    client::conn::http1::SendRequest,
+    header::{self, HeaderValue},
+    http::request::Parts,
+    server::conn::http1,
+    service::service_fn,
+};
+use hyper_util::rt::TokioIo;
+use tokio::{
+    net::{TcpListener, TcpStream},
+    time::timeout,
+};
+use tokio_rustls::TlsAcceptor;
+
+use super::{Gateway, routes::Route};
+
+type Body = BoxBody<Bytes, hyper::Error>;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Headers of a single connection, not forwarded.
+const HOP_BY_HOP: [&str; 8] = [
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "proxy-connection",
+    "te",
+    "trailer",
+    "upgrade",
+];
+
+/// Accepts connections, over TLS when an acceptor is given.
+pub async fn serve(listener: TcpListener, gateway: Arc<Gateway>, tls: Option<TlsAcceptor>) {
+    let scheme = if tls.is_some() { "https" } else { "http" };
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(err) => {
+                tracing::warn!(error=%err, "Failed to accept ingress connection");
+                continue;
+            }
+        };
+        let gateway = gateway.clone();
+        let tls = tls.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |req| {
+                let gateway = gateway.clone();
+                async move { Ok::<_, Infallible>(proxy(&gateway, req, peer, scheme).await) }
+            });
+            let result = match tls {
+                Some(acceptor) => match acceptor.accept(stream).await {
+                    Ok(stream) => {
+                        http1::Builder::new()
+                            .serve_connection(TokioIo::new(stream), service)
+                            .await
+                    }
+                    Err(err) => {
+                        tracing::debug!(%peer, error=%err, "TLS handshake failed");
+                        return;
+                    }
+                },
+                None => {
+                    http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await
+                }
+            };
+            if let Err(err) = result {
+                tracing::debug!(%peer, error=%err, "Ingress connection closed");
+            }
+        });
+    }
+}
+
+/// Routes a request to a backend and logs it.
+async fn proxy(
+    gateway: &Gateway,
+    req: Request<Incoming>,
+    peer: SocketAddr,
+    scheme: &'static str,
+) -> Response<Body> {
+    let started = Instant::now();
+    let method = req.method().clone();
+    let host = request_host(&req);
+    let path = req.uri().path().to_string();
+
+    let routes = gateway.routes();
+    let (response, backend) = match routes.find(&host, &path) {
+        Some(route) => forward(gateway, route, req, peer, scheme).await,
+        None => (status(StatusCode::NOT_FOUND), None),
+    };
+
+    tracing::info!(
+        %peer,
+        %method,
+        %host,
+        %path,
+        status = response.status().as_u16(),
+        backend = backend.map(|b| b.to_string()).unwrap_or_else(|| "-".to_string()),
+        duration_ms = started.elapsed().as_millis() as u64,
+        "Ingress request"
+    );
+    response
+}
+
+/// Sends the request to the backends of the route until one answers.
+///
+/// Unreachable backends are always skipped. A request that reached a backend is only
+/// sent again when it has no body and an idempotent method, the backend may have
+/// acted on any other before the connection broke.
+async fn forward(
+    gateway: &Gateway,
+    route: &Route,
+    req: Request<Incoming>,
+    peer: SocketAddr,
+    scheme: &'static str,
+) -> (Response<Body>, Option<SocketAddr>) {
+    let (mut parts, body) = req.into_parts();
+    forwarded_headers(&mut parts, peer, scheme);
+    let replayable = body.is_end_stream() && parts.method.is_idempotent();
+    let mut body = Some(body.boxed());
+
+    let mut code = StatusCode::SERVICE_UNAVAILABLE;
+    for backend in route.backends().take(usize::from(gateway.retries) + 1) {
+        let mut sender = match connect(backend).await {
+            Ok(sender) => sender,
+            Err(err) => {
+                tracing::debug!(%backend, error=%err, "Skipping unreachable backend");
+                code = StatusCode::BAD_GATEWAY;
+                continue;
+            }
+        };
+        let body = match replayable {
+            true => Empty::new().map_err(|never| match never {}).boxed(),
+            false => match body.take() {
+                Some(body) => body,
+                None => break,
+            },
+        };
+        match timeout(gateway.timeout, sender.send_request(request(&parts, body))).await {
+            Ok(Ok(resp)) => {
+                let (mut parts, body) = resp.into_parts();
+                strip_hop_by_hop(&mut parts.headers);
+                return (Response::from_parts(parts, body.boxed()), Some(backend));
+            }
+            Ok(Err(err)) => {
+                tracing::debug!(%backend, error=%err, "Backend request failed");
+                if !replayable {
+                    return (status(StatusCode::BAD_GATEWAY), Some(backend));
+                }
+                code = StatusCode::BAD_GATEWAY;
+            }
+            Err(_) => return (status(StatusCode::GATEWAY_TIMEOUT), Some(backend)),
+        }
+    }
+    (status(code), None)
+}
+
+async fn connect(backend: SocketAddr) -> Result<SendRequest<Body>, String> {
+    let stream = timeout(CONNECT_TIMEOUT, TcpStream::connect(backend))
+        .await
+        .map_err(|_| "connect timed out".to_string())?
+        .map_err(|e| e.to_string())?;
+    let (sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+        .await
+        .map_err(|e| e.to_string())?;
+    tokio::spawn(async move {
+        if let Err(err) = conn.await {
+            tracing::debug!(%backend, error=%err, "Backend connection closed");
+        }
+    });
+    Ok(sender)
+}
+
+fn request(parts: &Parts, body: Body) -> Request<Body> {
+    let mut req = Request::new(body);
+    *req.method_mut() = parts.method.clone();
+    *req.uri_mut() = parts.uri.clone();
+    *req.headers_mut() = parts.headers.clone();
+    req
+}
+
+/// Drops connection headers and tells the backend who the client is.
+fn forwarded_headers(parts: &mut Parts, peer: SocketAddr, scheme: &'static str) {
+    let headers = &mut parts.headers;
+    strip_hop_by_hop(headers);
+    let forwarded_for = match headers.get("x-forwarded-for").and_then(|v| v.to_str().ok()) {
+        Some(chain) => format!("{}, {}", chain, peer.ip()),
+        None => peer.ip().to_string(),
+    };
+    if let Ok(value) = HeaderValue::from_str(&forwarded_for) {
+        headers.insert("x-forwarded-for", value);
+    }
+    headers.insert("x-forwarded-proto", HeaderValue::from_static(scheme));
+    if let Some(host) = headers.get(header::HOST).cloned() {
+        headers.insert("x-forwarded-host", host);
+    }
+    // Backends expect the origin form
+    if let Some(origin) = parts
+        .uri
+        .path_and_query()
+        .and_then(|pq| pq.as_str().parse().ok())
+    {
+        parts.uri = origin;
+    }
+}
+
+fn strip_hop_by_hop(headers: &mut HeaderMap) {
+    for name in HOP_BY_HOP {
+        headers.remove(name);
+    }
+}
+
+/// Lowercase host the request is for, without the port.
+fn request_host<B>(req: &Request<B>) -> String {
+    let host = req
+        .headers()
+        .get(header::HOST)
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| req.uri().host())
+        .unwrap_or_default();
+    host.rsplit_once(':')
+        .filter(|(_, port)| port.parse::<u16>().is_ok())
+        .map_or(host, |(host, _)| host)
+        .to_ascii_lowercase()
+}
+
+fn status(code: StatusCode) -> Response<Body> {
+    let mut resp = Response::new(
+        Full::new(Bytes::from(code.to_string()))
+            .map_err(|never| match never {})
+            .boxed(),
+    );
+    *resp.status_mut() = code;
+    resp
+}
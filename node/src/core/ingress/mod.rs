@@ -0,0 +1,546 @@
+//! # Ingress Gateway
+//!
+//! Optional HTTP(S) entry point of the cluster, run by the nodes configured
+//! with an ingress address. Requests are routed by host and path prefix to the
+//! ready endpoints of the backend services, retried on the next endpoint when
+//! one cannot be reached and logged once answered. Route changes swap the
+//! routing table, open client connections are kept.
+
+mod http;
+mod routes;
+mod tls;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use dashmap::DashMap;
+use shared::{
+    api::{EndpointsEvent, EventType, IngressEvent},
+    models::{
+        endpoints::Endpoints,
+        ingress::{Ingress, TLS_CERT_KEY, TLS_PRIVATE_KEY},
+        secret::Secret,
+    },
+    utils::watch_stream,
+};
+use tokio::{net::TcpListener, sync::Notify};
+
+use crate::{core::sources::fetch_one, state::State};
+use routes::RouteTable;
+use tls::CertResolver;
+
+/// Secrets are not watched, their certificates are fetched again this often.
+const CERT_SYNC_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Watches ingresses and endpoints and serves the routes they make up.
+pub async fn run(state: State) -> Result<(), String> {
+    let Some(address) = &state.config.ingress_address else {
+        return Ok(());
+    };
+    let gateway = Arc::new(Gateway::new(
+        state.config.server_url.clone(),
+        Duration::from_secs(state.config.ingress_timeout.into()),
+        state.config.ingress_retries,
+    ));
+
+    let listener = TcpListener::bind(address)
+        .await
+        .map_err(|e| format!("Failed to bind ingress address: {}", e))?;
+    let tls = match &state.config.ingress_tls_address {
+        Some(address) => {
+            let listener = TcpListener::bind(address)
+                .await
+                .map_err(|e| format!("Failed to bind ingress TLS address: {}", e))?;
+            Some((listener, tls::acceptor(gateway.certs.clone())?))
+        }
+        None => None,
+    };
+    tracing::info!(%address, tls=?state.config.ingress_tls_address, "Serving ingress gateway");
+
+    let ingresses_url = format!("{}/ingresses?watch=true", state.config.server_url);
+    let endpoints_url = format!("{}/endpoints?watch=true", state.config.server_url);
+    tokio::join!(
+        {
+            let gateway = gateway.clone();
+            watch_stream(&ingresses_url, move |event| {
+                gateway.handle_ingress_event(event)
+            })
+        },
+        {
+            let gateway = gateway.clone();
+            watch_stream(&endpoints_url, move |event| {
+                gateway.handle_endpoints_event(event)
+            })
+        },
+        gateway.sync_certs(),
+        http::serve(listener, gateway.clone(), None),
+        async {
+            if let Some((listener, acceptor)) = tls {
+                http::serve(listener, gateway.clone(), Some(acceptor)).await;
+            }
+        },
+    );
+    Ok(())
+}
+
+pub struct Gateway {
+    server_url: String,
+    ingresses: DashMap<String, Ingress>,
+    endpoints: DashMap<String, Endpoints>,
+    routes: RwLock<Arc<RouteTable>>,
+    certs: Arc<CertResolver>,
+    /// Woken when the TLS secrets of the ingresses may have changed
+    ingresses_changed: Notify,
+    timeout: Duration,
+    retries: u8,
+}
+
+impl Gateway {
+    pub fn new(server_url: String, timeout: Duration, retries: u8) -> Self {
+        Self {
+            server_url,
+            ingresses: DashMap::new(),
+            endpoints: DashMap::new(),
+            routes: RwLock::new(Arc::default()),
+            certs: Arc::default(),
+            ingresses_changed: Notify::new(),
+            timeout,
+            retries,
+        }
+    }
+
+    pub fn handle_ingress_event(&self, event: IngressEvent) {
+        let name = event.ingress.metadata.name.clone();
+        match event.event_type {
+            EventType::Deleted => {
+                self.ingresses.remove(&name);
+            }
+            EventType::Added | EventType::Modified => {
+                self.ingresses.insert(name, event.ingress);
+            }
+        }
+        self.rebuild();
+        self.ingresses_changed.notify_one();
+    }
+
+    pub fn handle_endpoints_event(&self, event: EndpointsEvent) {
+        let name = event.endpoints.metadata.name.clone();
+        match event.event_type {
+            EventType::Deleted => {
+                self.endpoints.remove(&name);
+            }
+            EventType::Added | EventType::Modified => {
+                self.endpoints.insert(name, event.endpoints);
+            }
+        }
+        self.rebuild();
+    }
+
+    /// Current routing table.
+    fn routes(&self) -> Arc<RouteTable> {
+        self.routes.read().unwrap().clone()
+    }
+
+    fn rebuild(&self) {
+        let ingresses: Vec<Ingress> = self.ingresses.iter().map(|i| i.value().clone()).collect();
+        let table = RouteTable::build(ingresses.iter(), &self.endpoints);
+        *self.routes.write().unwrap() = Arc::new(table);
+    }
+
+    /// Loads the certificates of the ingress TLS secrets on every change and interval.
+    async fn sync_certs(&self) {
+        let client = shared::utils::client();
+        loop {
+            let mut certs = HashMap::new();
+            let tls: Vec<_> = self
+                .ingresses
+                .iter()
+                .flat_map(|i| i.spec.tls.clone())
+                .collect();
+            for tls in tls {
+                let url = format!("{}/secrets/{}", self.server_url, tls.secret_name);
+                let key = match fetch_one::<Secret>(&client, &url).await {
+                    Ok(Some(secret)) => {
+                        let data = &secret.spec.data;
+                        match (data.get(TLS_CERT_KEY), data.get(TLS_PRIVATE_KEY)) {
+                            (Some(cert), Some(key)) => tls::certified_key(cert, key),
+                            _ => Err(format!("needs {} and {}", TLS_CERT_KEY, TLS_PRIVATE_KEY)),
+                        }
+                    }
+                    Ok(None) => Err("not found".to_string()),
+                    Err(err) => Err(err),
+                };
+                match key {
+                    Ok(key) => {
+                        let key = Arc::new(key);
+                        for host in tls.hosts {
+                            certs.entry(host).or_insert_with(|| key.clone());
+                        }
+                    }
+                    Err(err) => {
+                        tracing::warn!(secret=%tls.secret_name, error=%err, "Invalid ingress TLS secret")
+                    }
+                }
+            }
+            self.certs.replace(certs);
+
+            tokio::select! {
+                _ = self.ingresses_changed.notified() => {}
+                _ = tokio::time::sleep(CERT_SYNC_INTERVAL) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! - test_routing
+    //!   host rules win over catch-all ones, longest prefix on segment boundaries
+    //! - test_round_robin_with_retry
+    //!   unreachable endpoints are skipped
+    //! - test_retry_only_idempotent
+    //!   a POST whose backend closes mid-response is not sent again
+    //! - test_unavailable_and_timeout
+    //! - test_reload_keeps_connection
+    //!   an open client connection follows the new routes
+    //! - test_tls
+    //!   certificate picked from the secret by SNI
+
+    use std::{
+        net::SocketAddr,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::*;
+    use bytes::Bytes;
+    use http_body_util::{BodyExt, Empty};
+    use hyper::{Request, StatusCode, client::conn::http1::SendRequest, service::service_fn};
+    use hyper_util::rt::TokioIo;
+    use shared::models::{
+        endpoints::{EndpointAddress, EndpointPort},
+        ingress::{IngressBackend, IngressPath, IngressRule, IngressSpec, IngressTls},
+        metadata::Metadata,
+        pod::Protocol,
+        secret::SecretSpec,
+    };
+    use tokio::{
+        io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+        net::TcpStream,
+    };
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers::path};
+
+    /// Backend answering with its id and the request path, after a delay.
+    async fn backend(id: &'static str, delay: Duration) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let service = service_fn(move |req: Request<hyper::body::Incoming>| async move {
+                    tokio::time::sleep(delay).await;
+                    let body = format!("{} {}", id, req.uri().path());
+                    Ok::<_, std::convert::Infallible>(hyper::Response::new(body))
+                });
+                tokio::spawn(
+                    hyper::server::conn::http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service),
+                );
+            }
+        });
+        addr
+    }
+
+    /// Backend reading each request, then closing in the middle of the status line.
+    async fn dropping_backend(requests: Arc<AtomicUsize>) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = vec![0u8; 4096];
+                if matches!(stream.read(&mut buf).await, Ok(n) if n > 0) {
+                    requests.fetch_add(1, Ordering::SeqCst);
+                    let _ = stream.write_all(b"HTTP/1.1 20").await;
+                }
+            }
+        });
+        addr
+    }
+
+    /// Port nothing listens on.
+    async fn closed_port() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        listener.local_addr().unwrap()
+    }
+
+    fn ingress(rules: Vec<(Option<&str>, &str, &str)>) -> IngressEvent {
+        IngressEvent {
+            event_type: EventType::Added,
+            ingress: Ingress {
+                metadata: Metadata {
+                    name: "web".to_string(),
+                    ..Default::default()
+                },
+                spec: IngressSpec {
+                    rules: rules
+                        .into_iter()
+                        .map(|(host, path, service)| IngressRule {
+                            host: host.map(str::to_string),
+                            paths: vec![IngressPath {
+                                path: path.to_string(),
+                                backend: IngressBackend {
+                                    service: service.to_string(),
+                                    port: 80,
+                                },
+                            }],
+                        })
+                        .collect(),
+                    tls: vec![],
+                },
+            },
+        }
+    }
+
+    fn endpoints(service: &str, backends: &[SocketAddr]) -> EndpointsEvent {
+        EndpointsEvent {
+            event_type: EventType::Modified,
+            endpoints: Endpoints {
+                metadata: Metadata {
+                    name: service.to_string(),
+                    ..Default::default()
+                },
+                ports: vec![EndpointPort {
+                    name: None,
+                    port: 80,
+                    protocol: Protocol::Tcp,
+                    addresses: backends
+                        .iter()
+                        .map(|addr| EndpointAddress {
+                            ip: addr.ip().to_string(),
+                            port: addr.port(),
+                            pod_name: format!("{}-{}", service, addr.port()),
+                            node_name: "node-a".to_string(),
+                        })
+                        .collect(),
+                }],
+            },
+        }
+    }
+
+    async fn gateway(timeout: Duration) -> (Arc<Gateway>, SocketAddr) {
+        let gateway = Arc::new(Gateway::new(String::new(), timeout, 2));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(http::serve(listener, gateway.clone(), None));
+        (gateway, addr)
+    }
+
+    async fn client<S>(stream: S) -> SendRequest<Empty<Bytes>>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let (sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .unwrap();
+        tokio::spawn(conn);
+        sender
+    }
+
+    async fn get(
+        sender: &mut SendRequest<Empty<Bytes>>,
+        host: &str,
+        path: &str,
+    ) -> (StatusCode, String) {
+        let req = Request::get(path)
+            .header("host", host)
+            .body(Empty::new())
+            .unwrap();
+        let resp = sender.send_request(req).await.unwrap();
+        let status = resp.status();
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8_lossy(&body).into_owned())
+    }
+
+    #[tokio::test]
+    async fn test_routing() {
+        let (gateway, addr) = gateway(Duration::from_secs(5)).await;
+        for (service, id) in [("shop", "shop"), ("api", "api"), ("site", "site")] {
+            let backend = backend(id, Duration::ZERO).await;
+            gateway.handle_endpoints_event(endpoints(service, &[backend]));
+        }
+        gateway.handle_ingress_event(ingress(vec![
+            (None, "/", "site"),
+            (Some("shop.example.com"), "/", "shop"),
+            (Some("shop.example.com"), "/api", "api"),
+        ]));
+
+        let mut sender = client(TcpStream::connect(addr).await.unwrap()).await;
+        for (host, path, expected) in [
+            ("shop.example.com", "/cart", "shop /cart"),
+            ("Shop.Example.com:8080", "/api/items", "api /api/items"),
+            ("shop.example.com", "/apis", "shop /apis"),
+            ("other.example.com", "/api", "site /api"),
+        ] {
+            let (status, body) = get(&mut sender, host, path).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body, expected, "{}{}", host, path);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_with_retry() {
+        let (gateway, addr) = gateway(Duration::from_secs(5)).await;
+        let backends = [
+            backend("a", Duration::ZERO).await,
+            closed_port().await,
+            backend("b", Duration::ZERO).await,
+        ];
+        gateway.handle_endpoints_event(endpoints("web", &backends));
+        gateway.handle_ingress_event(ingress(vec![(None, "/", "web")]));
+
+        let mut sender = client(TcpStream::connect(addr).await.unwrap()).await;
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            let (status, body) = get(&mut sender, "example.com", "/").await;
+            assert_eq!(status, StatusCode::OK);
+            seen.push(body);
+        }
+        assert_eq!(seen, ["a /", "b /", "b /", "a /"]);
+    }
+
+    #[tokio::test]
+    async fn test_retry_only_idempotent() {
+        let (gateway, addr) = gateway(Duration::from_secs(5)).await;
+        let requests = Arc::new(AtomicUsize::new(0));
+        let backends = [
+            dropping_backend(requests.clone()).await,
+            backend("b", Duration::ZERO).await,
+        ];
+        gateway.handle_endpoints_event(endpoints("web", &backends));
+        gateway.handle_ingress_event(ingress(vec![(None, "/", "web")]));
+
+        // round robin starts on the dropping backend, again for the third request
+        let mut sender = client(TcpStream::connect(addr).await.unwrap()).await;
+        assert_eq!(
+            get(&mut sender, "example.com", "/").await,
+            (StatusCode::OK, "b /".to_string())
+        );
+        assert_eq!(get(&mut sender, "example.com", "/").await.0, StatusCode::OK);
+        let req = Request::post("/orders")
+            .header("host", "example.com")
+            .body(Empty::new())
+            .unwrap();
+        let resp = sender.send_request(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_and_timeout() {
+        let (gateway, addr) = gateway(Duration::from_millis(200)).await;
+        let slow = backend("slow", Duration::from_secs(2)).await;
+        gateway.handle_endpoints_event(endpoints("slow", &[slow]));
+        gateway.handle_ingress_event(ingress(vec![
+            (None, "/empty", "missing"),
+            (None, "/slow", "slow"),
+        ]));
+
+        let mut sender = client(TcpStream::connect(addr).await.unwrap()).await;
+        for (path, expected) in [
+            ("/", StatusCode::NOT_FOUND),
+            ("/empty", StatusCode::SERVICE_UNAVAILABLE),
+            ("/slow", StatusCode::GATEWAY_TIMEOUT),
+        ] {
+            assert_eq!(get(&mut sender, "example.com", path).await.0, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reload_keeps_connection() {
+        let (gateway, addr) = gateway(Duration::from_secs(5)).await;
+        let v1 = backend("v1", Duration::ZERO).await;
+        let v2 = backend("v2", Duration::ZERO).await;
+        gateway.handle_endpoints_event(endpoints("web-v1", &[v1]));
+        gateway.handle_endpoints_event(endpoints("web-v2", &[v2]));
+        gateway.handle_ingress_event(ingress(vec![(None, "/", "web-v1")]));
+
+        let mut sender = client(TcpStream::connect(addr).await.unwrap()).await;
+        assert_eq!(get(&mut sender, "example.com", "/").await.1, "v1 /");
+
+        let mut event = ingress(vec![(None, "/", "web-v2")]);
+        event.event_type = EventType::Modified;
+        gateway.handle_ingress_event(event);
+        assert_eq!(get(&mut sender, "example.com", "/").await.1, "v2 /");
+    }
+
+    #[tokio::test]
+    async fn test_tls() {
+        let cert =
+            rcgen::generate_simple_self_signed(vec!["shop.example.com".to_string()]).unwrap();
+        let server = MockServer::start().await;
+        let secret = Secret {
+            metadata: Metadata {
+                name: "shop-tls".to_string(),
+                ..Default::default()
+            },
+            spec: SecretSpec {
+                data: HashMap::from([
+                    (TLS_CERT_KEY.to_string(), cert.cert.pem()),
+                    (TLS_PRIVATE_KEY.to_string(), cert.key_pair.serialize_pem()),
+                ]),
+            },
+        };
+        Mock::given(path("/secrets/shop-tls"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&secret))
+            .mount(&server)
+            .await;
+
+        let gateway = Arc::new(Gateway::new(server.uri(), Duration::from_secs(5), 2));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let acceptor = tls::acceptor(gateway.certs.clone()).unwrap();
+        tokio::spawn(http::serve(listener, gateway.clone(), Some(acceptor)));
+        {
+            let gateway = gateway.clone();
+            tokio::spawn(async move { gateway.sync_certs().await });
+        }
+
+        let shop = backend("shop", Duration::ZERO).await;
+        gateway.handle_endpoints_event(endpoints("shop", &[shop]));
+        let mut event = ingress(vec![(Some("shop.example.com"), "/", "shop")]);
+        event.ingress.spec.tls = vec![IngressTls {
+            hosts: vec!["shop.example.com".to_string()],
+            secret_name: "shop-tls".to_string(),
+        }];
+        gateway.handle_ingress_event(event);
+
+        let mut roots = tokio_rustls::rustls::RootCertStore::empty();
+        roots.add(cert.cert.der().clone()).unwrap();
+        let config = tokio_rustls::rustls::ClientConfig::builder_with_provider(Arc::new(
+            tokio_rustls::rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+
+        // The secret is fetched in the background
+        let mut stream = None;
+        for _ in 0..50 {
+            let tcp = TcpStream::connect(addr).await.unwrap();
+            let name = "shop.example.com".try_into().unwrap();
+            if let Ok(tls) = connector.connect(name, tcp).await {
+                stream = Some(tls);
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        let mut sender = client(stream.expect("TLS handshake")).await;
+        assert_eq!(
+            get(&mut sender, "shop.example.com", "/").await,
+            (StatusCode::OK, "shop /".to_string())
+        );
+    }
+}
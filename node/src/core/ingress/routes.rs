@@ -0,0 +1,94 @@
+//! Routing table built from the ingresses and the endpoints of their backends.
+
+use std::{
+    net::SocketAddr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use dashmap::DashMap;
+use shared::models::{
+    endpoints::Endpoints,
+    ingress::{Ingress, IngressBackend, IngressPath},
+    pod::Protocol,
+};
+
+/// Snapshot of the routes, requests keep the one they started with.
+#[derive(Default)]
+pub struct RouteTable {
+    /// Host rules first, then the longest prefixes
+    routes: Vec<Route>,
+}
+
+pub struct Route {
+    pub ingress: String,
+    pub host: Option<String>,
+    pub path: IngressPath,
+    /// Ready pods behind the backend service port
+    pub addrs: Vec<SocketAddr>,
+    next: AtomicUsize,
+}
+
+impl RouteTable {
+    pub fn build<'a>(
+        ingresses: impl Iterator<Item = &'a Ingress>,
+        endpoints: &DashMap<String, Endpoints>,
+    ) -> Self {
+        let mut routes: Vec<Route> = ingresses
+            .flat_map(|ingress| {
+                ingress.spec.rules.iter().flat_map(move |rule| {
+                    rule.paths.iter().map(move |path| Route {
+                        ingress: ingress.metadata.name.clone(),
+                        host: rule.host.clone(),
+                        path: path.clone(),
+                        addrs: backend_addrs(&path.backend, endpoints),
+                        next: AtomicUsize::new(0),
+                    })
+                })
+            })
+            .collect();
+        // Ties between ingresses go to the first name
+        routes.sort_by(|a, b| {
+            b.host
+                .is_some()
+                .cmp(&a.host.is_some())
+                .then_with(|| b.path.path.len().cmp(&a.path.path.len()))
+                .then_with(|| a.ingress.cmp(&b.ingress))
+        });
+        Self { routes }
+    }
+
+    /// Route of a request, rules naming the host win over catch-all ones.
+    pub fn find(&self, host: &str, path: &str) -> Option<&Route> {
+        self.routes
+            .iter()
+            .find(|route| route.host.as_ref().is_none_or(|h| h == host) && route.path.matches(path))
+    }
+}
+
+impl Route {
+    /// Backends in round-robin order, each request starts one further.
+    pub fn backends(&self) -> impl Iterator<Item = SocketAddr> + '_ {
+        let start = match self.addrs.len() {
+            0 => 0,
+            len => self.next.fetch_add(1, Ordering::Relaxed) % len,
+        };
+        self.addrs[start..]
+            .iter()
+            .chain(&self.addrs[..start])
+            .copied()
+    }
+}
+
+fn backend_addrs(
+    backend: &IngressBackend,
+    endpoints: &DashMap<String, Endpoints>,
+) -> Vec<SocketAddr> {
+    let Some(endpoints) = endpoints.get(&backend.service) else {
+        return Vec::new();
+    };
+    endpoints
+        .addresses(backend.port, Protocol::Tcp)
+        .iter()
+        .filter_map(|a| a.ip.parse().ok().map(|ip| SocketAddr::new(ip, a.port)))
+        .collect()
+}
@@ -0,0 +1,61 @@
+//! Certificates of the ingress TLS secrets, picked by the SNI of each handshake.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use tokio_rustls::{
+    TlsAcceptor,
+    rustls::{
+        ServerConfig,
+        crypto::ring,
+        pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+        server::{ClientHello, ResolvesServerCert},
+        sign::CertifiedKey,
+    },
+};
+
+/// Host certificates, replaced as a whole when the secrets are synced.
+#[derive(Debug, Default)]
+pub struct CertResolver {
+    certs: RwLock<HashMap<String, Arc<CertifiedKey>>>,
+}
+
+impl CertResolver {
+    pub fn replace(&self, certs: HashMap<String, Arc<CertifiedKey>>) {
+        *self.certs.write().unwrap() = certs;
+    }
+}
+
+impl ResolvesServerCert for CertResolver {
+    fn resolve(&self, client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        let host = client_hello.server_name()?.to_ascii_lowercase();
+        self.certs.read().unwrap().get(&host).cloned()
+    }
+}
+
+/// Acceptor serving the certificates of the resolver, handshakes for unknown hosts fail.
+pub fn acceptor(resolver: Arc<CertResolver>) -> Result<TlsAcceptor, String> {
+    let mut config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| e.to_string())?
+        .with_no_client_auth()
+        .with_cert_resolver(resolver);
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// Parses a PEM certificate chain and private key.
+pub fn certified_key(cert: &str, key: &str) -> Result<CertifiedKey, String> {
+    let chain = CertificateDer::pem_slice_iter(cert.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid certificate: {}", e))?;
+    if chain.is_empty() {
+        return Err("No certificate found".to_string());
+    }
+    let key = PrivateKeyDer::from_pem_slice(key.as_bytes())
+        .map_err(|e| format!("Invalid private key: {}", e))?;
+    let key = ring::sign::any_supported_type(&key).map_err(|e| e.to_string())?;
+    Ok(CertifiedKey::new(chain, key))
+}
@@ -1,3 +1,11 @@
+pub mod dns;
+pub mod ingress;
+pub mod provisioner;
+pub mod proxy;
+pub mod recovery;
+pub mod shutdown;
+pub mod sources;
 pub mod sync;
+pub mod volumes;
 pub mod watcher;
 pub mod worker;
@@ -0,0 +1,215 @@
+//! # Local-Path Provisioner
+//!
+//! Keeps the persistent volumes bound to this node on disk. Volumes are
+//! provisioned under the persistent root as soon as a claim is bound to them,
+//! and once released under the `Delete` policy their directory is removed
+//! along with the volume itself. Volumes an admin gave a path are left alone.
+
+use std::{
+    path::{Component, Path},
+    time::Duration,
+};
+
+use reqwest::Client;
+use shared::models::persistentvolume::{PersistentVolume, ReclaimPolicy, VolumePhase};
+use tokio::time;
+
+use crate::state::State;
+
+/// Starts the periodic provisioning loop.
+pub async fn run(state: State) -> Result<(), String> {
+    let mut interval = time::interval(Duration::from_secs(state.config.sync_loop.into()));
+    loop {
+        interval.tick().await;
+        run_iteration(&state).await;
+    }
+}
+
+pub async fn run_iteration(state: &State) {
+    let client = shared::utils::client();
+    let url = format!(
+        "{}/persistentvolumes?nodeName={}",
+        state.config.server_url, state.config.name
+    );
+    let volumes: Vec<PersistentVolume> = match client.get(&url).send().await {
+        Ok(resp) => match resp.json().await {
+            Ok(volumes) => volumes,
+            Err(err) => {
+                tracing::warn!(error=%err, "Failed to parse persistent volumes");
+                return;
+            }
+        },
+        Err(err) => {
+            tracing::warn!(error=%err, "Failed to list persistent volumes");
+            return;
+        }
+    };
+
+    let paths = state.config.volume_paths();
+    for pv in volumes.iter().filter(|pv| pv.is_provisioned()) {
+        let dir = paths.persistent_dir(pv);
+        let res = match (pv.status.phase, pv.spec.reclaim_policy) {
+            (VolumePhase::Released, ReclaimPolicy::Delete) => {
+                reclaim(&client, state, pv, &dir).await
+            }
+            (VolumePhase::Released, ReclaimPolicy::Retain) => Ok(()),
+            _ if dir.is_dir() => Ok(()),
+            _ => std::fs::create_dir_all(&dir)
+                .map(|_| tracing::info!(volume=%pv.metadata.name, "Provisioned volume"))
+                .map_err(|e| e.to_string()),
+        };
+        if let Err(err) = res {
+            tracing::warn!(error=%err, volume=%pv.metadata.name, "Failed to provision volume");
+        }
+    }
+}
+
+/// Removes the data of a released volume, then the volume from the apiserver.
+/// Only a directory right inside the persistent root is ever removed.
+async fn reclaim(
+    client: &Client,
+    state: &State,
+    pv: &PersistentVolume,
+    dir: &Path,
+) -> Result<(), String> {
+    let root = state.config.volume_paths().persistent_root;
+    if dir.parent() != Some(root.as_path())
+        || !matches!(dir.components().next_back(), Some(Component::Normal(_)))
+    {
+        return Err(format!(
+            "Refusing to reclaim {}, not a volume directory of {}",
+            dir.display(),
+            root.display()
+        ));
+    }
+    match std::fs::remove_dir_all(dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.to_string()),
+    }
+    let url = format!(
+        "{}/persistentvolumes/{}",
+        state.config.server_url, pv.metadata.name
+    );
+    let resp = client
+        .delete(&url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("Failed to delete volume: {}", resp.status()));
+    }
+    tracing::info!(volume=%pv.metadata.name, "Deleted released volume");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    //! - test_provision_and_reclaim
+    //!   bound volumes get a directory, released ones follow their reclaim policy
+    //! - test_reclaim_outside_root
+    //!   names resolving to the root or its parent are never removed
+
+    use super::*;
+    use crate::{docker::test::TestDocker, models::Config, state::NodeState};
+    use shared::models::{
+        metadata::Metadata,
+        persistentvolume::{PersistentVolumeSpec, PersistentVolumeStatus},
+    };
+    use uuid::Uuid;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    fn volume(name: &str, phase: VolumePhase, reclaim_policy: ReclaimPolicy) -> PersistentVolume {
+        PersistentVolume {
+            metadata: Metadata {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            spec: PersistentVolumeSpec {
+                capacity: "1Gi".to_string(),
+                node_name: Config::default().name,
+                path: None,
+                reclaim_policy,
+                claim_ref: Some("db".to_string()),
+            },
+            status: PersistentVolumeStatus { phase },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_provision_and_reclaim() {
+        let server = MockServer::start().await;
+        let volumes = vec![
+            volume("bound", VolumePhase::Bound, ReclaimPolicy::Delete),
+            volume("deleted", VolumePhase::Released, ReclaimPolicy::Delete),
+            volume("retained", VolumePhase::Released, ReclaimPolicy::Retain),
+        ];
+        Mock::given(method("GET"))
+            .and(path("/persistentvolumes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&volumes))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/persistentvolumes/deleted"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let root = std::env::temp_dir().join(format!("cr8s-provisioner-{}", Uuid::new_v4()));
+        let config = Config {
+            server_url: server.uri(),
+            persistent_volume_root: root.display().to_string(),
+            ..Default::default()
+        };
+        let state = NodeState::new_with(Some(config), Some(Box::new(TestDocker::new())));
+        for name in ["deleted", "retained"] {
+            std::fs::create_dir_all(root.join(name).join("data")).unwrap();
+        }
+
+        run_iteration(&state).await;
+
+        assert!(root.join("bound").is_dir());
+        assert!(!root.join("deleted").exists());
+        assert!(root.join("retained/data").is_dir());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_reclaim_outside_root() {
+        let server = MockServer::start().await;
+        let volumes = vec![
+            volume("", VolumePhase::Released, ReclaimPolicy::Delete),
+            volume("..", VolumePhase::Released, ReclaimPolicy::Delete),
+        ];
+        Mock::given(method("GET"))
+            .and(path("/persistentvolumes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&volumes))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let parent = std::env::temp_dir().join(format!("cr8s-provisioner-{}", Uuid::new_v4()));
+        let root = parent.join("volumes");
+        let config = Config {
+            server_url: server.uri(),
+            persistent_volume_root: root.display().to_string(),
+            ..Default::default()
+        };
+        let state = NodeState::new_with(Some(config), Some(Box::new(TestDocker::new())));
+        std::fs::create_dir_all(root.join("other").join("data")).unwrap();
+
+        run_iteration(&state).await;
+
+        assert!(root.join("other/data").is_dir());
+        let _ = std::fs::remove_dir_all(&parent);
+    }
+}
@@ -0,0 +1,148 @@
+//! Backends of one service port and how connections are spread over them.
+
+use std::{
+    net::SocketAddr,
+    sync::{
+        Arc, Mutex, RwLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+
+use crate::models::Balancer;
+
+/// Time a backend that refused a connection is skipped for.
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(10);
+
+pub struct Backend {
+    pub addr: SocketAddr,
+    active: AtomicUsize,
+    unhealthy_until: Mutex<Option<Instant>>,
+    /// Cancelled once the backend left the endpoints and its drain timeout expired
+    drained: CancellationToken,
+}
+
+/// Counts a connection to a backend while alive.
+pub struct ConnectionGuard(Arc<Backend>);
+
+pub struct BackendPool {
+    balancer: Balancer,
+    drain_timeout: Duration,
+    backends: RwLock<Vec<Arc<Backend>>>,
+    next: AtomicUsize,
+}
+
+impl Backend {
+    fn new(addr: SocketAddr) -> Self {
+        Self {
+            addr,
+            active: AtomicUsize::new(0),
+            unhealthy_until: Mutex::new(None),
+            drained: CancellationToken::new(),
+        }
+    }
+
+    pub fn track(self: &Arc<Self>) -> ConnectionGuard {
+        self.active.fetch_add(1, Ordering::SeqCst);
+        ConnectionGuard(self.clone())
+    }
+
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    pub fn drained(&self) -> WaitForCancellationFuture<'_> {
+        self.drained.cancelled()
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.unhealthy_until
+            .lock()
+            .unwrap()
+            .is_none_or(|until| Instant::now() >= until)
+    }
+
+    pub fn mark_healthy(&self) {
+        *self.unhealthy_until.lock().unwrap() = None;
+    }
+
+    pub fn mark_unhealthy(&self) {
+        tracing::warn!(backend=%self.addr, "Backend unreachable, skipping it for a while");
+        *self.unhealthy_until.lock().unwrap() = Some(Instant::now() + UNHEALTHY_COOLDOWN);
+    }
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl BackendPool {
+    pub fn new(balancer: Balancer, drain_timeout: Duration) -> Self {
+        Self {
+            balancer,
+            drain_timeout,
+            backends: RwLock::new(Vec::new()),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Replaces the backends, the removed ones are drained.
+    pub fn update(&self, addrs: &[SocketAddr]) {
+        let mut backends = self.backends.write().unwrap();
+        let (kept, removed): (Vec<_>, Vec<_>) =
+            backends.drain(..).partition(|b| addrs.contains(&b.addr));
+        for backend in removed {
+            self.drain(backend);
+        }
+
+        *backends = addrs
+            .iter()
+            .map(|addr| match kept.iter().find(|b| &b.addr == addr) {
+                Some(backend) => backend.clone(),
+                None => Arc::new(Backend::new(*addr)),
+            })
+            .collect();
+    }
+
+    /// Closes the connections still open to a backend once the drain timeout expires.
+    fn drain(&self, backend: Arc<Backend>) {
+        if backend.active() == 0 {
+            backend.drained.cancel();
+            return;
+        }
+        tracing::debug!(backend=%backend.addr, active=backend.active(), "Draining backend");
+        let timeout = self.drain_timeout;
+        tokio::spawn(async move {
+            tokio::time::sleep(timeout).await;
+            backend.drained.cancel();
+        });
+    }
+
+    /// Picks a backend not tried yet, healthy ones first.
+    pub fn pick(&self, tried: &[SocketAddr]) -> Option<Arc<Backend>> {
+        let backends = self.backends.read().unwrap();
+        let untried: Vec<&Arc<Backend>> = backends
+            .iter()
+            .filter(|b| !tried.contains(&b.addr))
+            .collect();
+        let healthy: Vec<&Arc<Backend>> =
+            untried.iter().copied().filter(|b| b.is_healthy()).collect();
+        // every backend failing is no reason to stop trying them
+        let candidates = if healthy.is_empty() { untried } else { healthy };
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % candidates.len();
+        let rotated = candidates[start..].iter().chain(&candidates[..start]);
+        let picked = match self.balancer {
+            Balancer::RoundRobin => candidates[start],
+            Balancer::LeastConnections => rotated.min_by_key(|b| b.active()).copied()?,
+        };
+        Some(picked.clone())
+    }
+}
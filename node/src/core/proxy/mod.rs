@@ -0,0 +1,445 @@
+//! # Service Proxy
+//!
+//! Userspace TCP/UDP proxy opening the node port of every service on this
+//! node. Connections are forwarded to the ready endpoints of the service,
+//! picked round-robin or by least connections. Backends refusing connections
+//! are skipped for a while, and the ones leaving the endpoints keep their open
+//! connections until they close or the drain timeout expires.
+
+mod balancer;
+mod tcp;
+mod udp;
+
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use dashmap::DashMap;
+use shared::{
+    api::{EndpointsEvent, EventType, ServiceEvent},
+    models::{endpoints::Endpoints, pod::Protocol, service::Service},
+    utils::watch_stream,
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::{models::Balancer, state::State};
+use balancer::BackendPool;
+
+/// Watches services and endpoints and keeps the node ports in line.
+pub async fn run(state: State) -> Result<(), String> {
+    let bind: IpAddr = state
+        .config
+        .proxy_address
+        .parse()
+        .map_err(|e| format!("Invalid proxy address: {}", e))?;
+    let proxy = Arc::new(Proxy::new(
+        bind,
+        state.config.proxy_balancer,
+        Duration::from_secs(state.config.proxy_drain_timeout.into()),
+    ));
+
+    let services_url = format!("{}/services?watch=true", state.config.server_url);
+    let endpoints_url = format!("{}/endpoints?watch=true", state.config.server_url);
+    tokio::join!(
+        {
+            let proxy = proxy.clone();
+            watch_stream(&services_url, move |event| {
+                proxy.handle_service_event(event)
+            })
+        },
+        {
+            let proxy = proxy.clone();
+            watch_stream(&endpoints_url, move |event| {
+                proxy.handle_endpoints_event(event)
+            })
+        },
+    );
+    Ok(())
+}
+
+pub struct Proxy {
+    bind: IpAddr,
+    balancer: Balancer,
+    drain_timeout: Duration,
+    services: DashMap<String, Service>,
+    endpoints: DashMap<String, Endpoints>,
+    /// Open node ports
+    listeners: DashMap<(u16, Protocol), Listener>,
+    /// Both watches sync, only one may open ports at a time
+    sync_lock: Mutex<()>,
+}
+
+/// A node port and the service port it forwards to.
+struct Listener {
+    service: String,
+    port: u16,
+    local_addr: SocketAddr,
+    pool: Arc<BackendPool>,
+    cancel: CancellationToken,
+}
+
+impl Proxy {
+    pub fn new(bind: IpAddr, balancer: Balancer, drain_timeout: Duration) -> Self {
+        Self {
+            bind,
+            balancer,
+            drain_timeout,
+            services: DashMap::new(),
+            endpoints: DashMap::new(),
+            listeners: DashMap::new(),
+            sync_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn handle_service_event(&self, event: ServiceEvent) {
+        let name = event.service.metadata.name.clone();
+        match event.event_type {
+            EventType::Deleted => {
+                self.services.remove(&name);
+            }
+            EventType::Added | EventType::Modified => {
+                self.services.insert(name, event.service);
+            }
+        }
+        self.sync();
+    }
+
+    pub fn handle_endpoints_event(&self, event: EndpointsEvent) {
+        let name = event.endpoints.metadata.name.clone();
+        match event.event_type {
+            EventType::Deleted => {
+                self.endpoints.remove(&name);
+            }
+            EventType::Added | EventType::Modified => {
+                self.endpoints.insert(name, event.endpoints);
+            }
+        }
+        self.sync();
+    }
+
+    /// Opens and closes node ports to match the services, then refreshes their backends.
+    fn sync(&self) {
+        let _guard = self.sync_lock.lock().unwrap();
+        let desired: Vec<((u16, Protocol), (String, u16))> = self
+            .services
+            .iter()
+            .flat_map(|s| {
+                let name = s.metadata.name.clone();
+                s.spec
+                    .ports
+                    .iter()
+                    .filter_map(|p| Some(((p.node_port?, p.protocol), (name.clone(), p.port))))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        // closed ports stop accepting, their connections drain
+        self.listeners.retain(|key, listener| {
+            let keep = desired.iter().any(|(k, (name, port))| {
+                k == key && &listener.service == name && &listener.port == port
+            });
+            if !keep {
+                tracing::info!(service=%listener.service, node_port=key.0, "Closing node port");
+                listener.cancel.cancel();
+                listener.pool.update(&[]);
+            }
+            keep
+        });
+
+        for (key, (service, port)) in desired {
+            if !self.listeners.contains_key(&key) {
+                match self.open(key, &service, port) {
+                    Ok(listener) => {
+                        tracing::info!(%service, node_port=key.0, addr=%listener.local_addr, "Opened node port");
+                        self.listeners.insert(key, listener);
+                    }
+                    Err(err) => {
+                        tracing::warn!(error=%err, %service, node_port=key.0, "Failed to open node port");
+                        continue;
+                    }
+                }
+            }
+            let addrs: Vec<SocketAddr> = self
+                .endpoints
+                .get(&service)
+                .map(|ep| {
+                    ep.addresses(port, key.1)
+                        .iter()
+                        .filter_map(|a| Some(SocketAddr::new(a.ip.parse().ok()?, a.port)))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if let Some(listener) = self.listeners.get(&key) {
+                listener.pool.update(&addrs);
+            }
+        }
+    }
+
+    fn open(
+        &self,
+        (node_port, protocol): (u16, Protocol),
+        service: &str,
+        port: u16,
+    ) -> std::io::Result<Listener> {
+        let addr = SocketAddr::new(self.bind, node_port);
+        let pool = Arc::new(BackendPool::new(self.balancer, self.drain_timeout));
+        let cancel = CancellationToken::new();
+        let local_addr = match protocol {
+            Protocol::Tcp => {
+                let std_listener = std::net::TcpListener::bind(addr)?;
+                std_listener.set_nonblocking(true)?;
+                let listener = tokio::net::TcpListener::from_std(std_listener)?;
+                let local_addr = listener.local_addr()?;
+                tokio::spawn(tcp::serve(listener, pool.clone(), cancel.clone()));
+                local_addr
+            }
+            Protocol::Udp => {
+                let std_socket = std::net::UdpSocket::bind(addr)?;
+                std_socket.set_nonblocking(true)?;
+                let socket = tokio::net::UdpSocket::from_std(std_socket)?;
+                let local_addr = socket.local_addr()?;
+                tokio::spawn(udp::serve(socket, pool.clone(), cancel.clone()));
+                local_addr
+            }
+        };
+        Ok(Listener {
+            service: service.to_string(),
+            port,
+            local_addr,
+            pool,
+            cancel,
+        })
+    }
+
+    /// Address a node port was actually bound to.
+    #[cfg(test)]
+    fn local_addr(&self, node_port: u16, protocol: Protocol) -> Option<SocketAddr> {
+        self.listeners
+            .get(&(node_port, protocol))
+            .map(|l| l.local_addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! - test_round_robin
+    //!   connections alternate between endpoints
+    //! - test_least_connections
+    //!   a busy endpoint is passed over
+    //! - test_unreachable_endpoint_skipped
+    //! - test_removed_endpoint_drains
+    //!   open connections survive until the drain timeout, new ones go elsewhere
+    //! - test_udp
+    //! - test_service_deleted_closes_port
+
+    use super::*;
+    use shared::models::{
+        endpoints::{EndpointAddress, EndpointPort},
+        metadata::Metadata,
+        service::{ServicePort, ServiceSpec},
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream, UdpSocket},
+    };
+
+    const LOCALHOST: IpAddr = IpAddr::V4(std::net::Ipv4Addr::LOCALHOST);
+
+    /// TCP backend that greets with its id then echoes.
+    async fn tcp_backend(id: u8) -> SocketAddr {
+        let listener = TcpListener::bind((LOCALHOST, 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    stream.write_all(&[id]).await.unwrap();
+                    let mut buf = [0u8; 64];
+                    while let Ok(n) = stream.read(&mut buf).await {
+                        if n == 0 || stream.write_all(&buf[..n]).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        addr
+    }
+
+    fn service(protocol: Protocol) -> ServiceEvent {
+        ServiceEvent {
+            event_type: EventType::Added,
+            service: Service {
+                metadata: Metadata {
+                    name: "web".to_string(),
+                    ..Default::default()
+                },
+                spec: ServiceSpec {
+                    selector: None,
+                    ports: vec![ServicePort {
+                        name: None,
+                        port: 80,
+                        target_port: None,
+                        node_port: Some(0),
+                        protocol,
+                    }],
+                },
+            },
+        }
+    }
+
+    fn endpoints(protocol: Protocol, backends: &[SocketAddr]) -> EndpointsEvent {
+        EndpointsEvent {
+            event_type: EventType::Modified,
+            endpoints: Endpoints {
+                metadata: Metadata {
+                    name: "web".to_string(),
+                    ..Default::default()
+                },
+                ports: vec![EndpointPort {
+                    name: None,
+                    port: 80,
+                    protocol,
+                    addresses: backends
+                        .iter()
+                        .map(|addr| EndpointAddress {
+                            ip: addr.ip().to_string(),
+                            port: addr.port(),
+                            pod_name: format!("web-{}", addr.port()),
+                            node_name: "node-a".to_string(),
+                        })
+                        .collect(),
+                }],
+            },
+        }
+    }
+
+    fn proxy(balancer: Balancer, backends: &[SocketAddr]) -> (Proxy, SocketAddr) {
+        let proxy = Proxy::new(LOCALHOST, balancer, Duration::from_millis(200));
+        proxy.handle_service_event(service(Protocol::Tcp));
+        proxy.handle_endpoints_event(endpoints(Protocol::Tcp, backends));
+        let addr = proxy.local_addr(0, Protocol::Tcp).unwrap();
+        (proxy, addr)
+    }
+
+    /// Connects through the proxy and returns the stream with the backend id.
+    async fn connect(addr: SocketAddr) -> (TcpStream, u8) {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let mut id = [0u8; 1];
+        stream.read_exact(&mut id).await.unwrap();
+        (stream, id[0])
+    }
+
+    #[tokio::test]
+    async fn test_round_robin() {
+        let backends = [tcp_backend(1).await, tcp_backend(2).await];
+        let (_proxy, addr) = proxy(Balancer::RoundRobin, &backends);
+
+        let mut ids = Vec::new();
+        for _ in 0..4 {
+            ids.push(connect(addr).await.1);
+        }
+        assert_eq!(ids.iter().filter(|id| **id == 1).count(), 2);
+        assert_ne!(ids[0], ids[1]);
+        assert_ne!(ids[2], ids[3]);
+    }
+
+    #[tokio::test]
+    async fn test_least_connections() {
+        let backends = [tcp_backend(1).await, tcp_backend(2).await];
+        let (_proxy, addr) = proxy(Balancer::LeastConnections, &backends);
+
+        let (_held, busy) = connect(addr).await;
+        for _ in 0..3 {
+            let (stream, id) = connect(addr).await;
+            assert_ne!(id, busy);
+            drop(stream);
+            // let the proxy see the close
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_endpoint_skipped() {
+        let closed = {
+            let listener = TcpListener::bind((LOCALHOST, 0)).await.unwrap();
+            listener.local_addr().unwrap()
+        };
+        let backends = [closed, tcp_backend(2).await];
+        let (_proxy, addr) = proxy(Balancer::RoundRobin, &backends);
+
+        for _ in 0..4 {
+            assert_eq!(connect(addr).await.1, 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_removed_endpoint_drains() {
+        let (first, second) = (tcp_backend(1).await, tcp_backend(2).await);
+        let (proxy, addr) = proxy(Balancer::RoundRobin, &[first]);
+        let (mut stream, id) = connect(addr).await;
+        assert_eq!(id, 1);
+
+        proxy.handle_endpoints_event(endpoints(Protocol::Tcp, &[second]));
+        assert_eq!(connect(addr).await.1, 2);
+
+        // still served while draining
+        let mut buf = [0u8; 4];
+        stream.write_all(b"ping").await.unwrap();
+        stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"ping");
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        let mut rest = Vec::new();
+        let closed = tokio::time::timeout(Duration::from_secs(1), stream.read_to_end(&mut rest))
+            .await
+            .is_ok();
+        assert!(closed, "connection still open after the drain timeout");
+    }
+
+    #[tokio::test]
+    async fn test_udp() {
+        let backend = UdpSocket::bind((LOCALHOST, 0)).await.unwrap();
+        let backend_addr = backend.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 64];
+            while let Ok((n, peer)) = backend.recv_from(&mut buf).await {
+                let _ = backend.send_to(&buf[..n], peer).await;
+            }
+        });
+
+        let proxy = Proxy::new(LOCALHOST, Balancer::RoundRobin, Duration::from_millis(200));
+        proxy.handle_service_event(service(Protocol::Udp));
+        proxy.handle_endpoints_event(endpoints(Protocol::Udp, &[backend_addr]));
+        let addr = proxy.local_addr(0, Protocol::Udp).unwrap();
+
+        let client = UdpSocket::bind((LOCALHOST, 0)).await.unwrap();
+        for msg in [&b"one"[..], b"two"] {
+            client.send_to(msg, addr).await.unwrap();
+            let mut buf = [0u8; 64];
+            let (n, from) =
+                tokio::time::timeout(Duration::from_secs(1), client.recv_from(&mut buf))
+                    .await
+                    .unwrap()
+                    .unwrap();
+            assert_eq!(&buf[..n], msg);
+            assert_eq!(from, addr);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_service_deleted_closes_port() {
+        let backends = [tcp_backend(1).await];
+        let (proxy, addr) = proxy(Balancer::RoundRobin, &backends);
+        assert_eq!(connect(addr).await.1, 1);
+
+        let mut deleted = service(Protocol::Tcp);
+        deleted.event_type = EventType::Deleted;
+        proxy.handle_service_event(deleted);
+        assert!(proxy.local_addr(0, Protocol::Tcp).is_none());
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(TcpStream::connect(addr).await.is_err());
+    }
+}
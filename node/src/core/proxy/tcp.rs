@@ -0,0 +1,63 @@
+//! TCP forwarding of a node port.
+
+use std::{sync::Arc, time::Duration};
+
+use tokio::{
+    io::copy_bidirectional,
+    net::{TcpListener, TcpStream},
+    time::timeout,
+};
+use tokio_util::sync::CancellationToken;
+
+use super::balancer::BackendPool;
+
+/// Time given to a backend to accept a connection before trying the next one.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Accepts connections until cancelled, each forwarded to a backend.
+pub async fn serve(listener: TcpListener, pool: Arc<BackendPool>, cancel: CancellationToken) {
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            res = listener.accept() => match res {
+                Ok((stream, _)) => {
+                    tokio::spawn(forward(stream, pool.clone()));
+                }
+                Err(err) => tracing::warn!(error=%err, "Failed to accept connection"),
+            },
+        }
+    }
+}
+
+/// Connects to the first backend that answers and pipes both ways.
+async fn forward(mut client: TcpStream, pool: Arc<BackendPool>) {
+    let mut tried = Vec::new();
+    let (backend, mut upstream) = loop {
+        let Some(backend) = pool.pick(&tried) else {
+            tracing::debug!("No backend available, closing connection");
+            return;
+        };
+        match timeout(CONNECT_TIMEOUT, TcpStream::connect(backend.addr)).await {
+            Ok(Ok(stream)) => {
+                backend.mark_healthy();
+                break (backend, stream);
+            }
+            _ => {
+                backend.mark_unhealthy();
+                tried.push(backend.addr);
+            }
+        }
+    };
+
+    let _guard = backend.track();
+    tokio::select! {
+        res = copy_bidirectional(&mut client, &mut upstream) => {
+            if let Err(err) = res {
+                tracing::trace!(error=%err, backend=%backend.addr, "Connection closed");
+            }
+        }
+        _ = backend.drained() => {
+            tracing::debug!(backend=%backend.addr, "Closing connection to drained backend");
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! UDP forwarding of a node port.
+//!
+//! Each client address gets its own socket to a backend, replies are sent
+//! back from the node port. Sessions end after a while without replies.
+
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use dashmap::DashMap;
+use tokio::{net::UdpSocket, time::timeout};
+use tokio_util::sync::CancellationToken;
+
+use super::balancer::{Backend, BackendPool};
+
+/// Time a session is kept without hearing from its backend.
+const SESSION_TIMEOUT: Duration = Duration::from_secs(30);
+
+const MAX_DATAGRAM: usize = 65535;
+
+/// Relays datagrams until cancelled.
+pub async fn serve(socket: UdpSocket, pool: Arc<BackendPool>, cancel: CancellationToken) {
+    let socket = Arc::new(socket);
+    let sessions: Arc<DashMap<SocketAddr, Arc<UdpSocket>>> = Arc::new(DashMap::new());
+    let mut buf = vec![0u8; MAX_DATAGRAM];
+    loop {
+        let (len, peer) = tokio::select! {
+            _ = cancel.cancelled() => break,
+            res = socket.recv_from(&mut buf) => match res {
+                Ok(received) => received,
+                Err(err) => {
+                    tracing::warn!(error=%err, "Failed to receive datagram");
+                    continue;
+                }
+            },
+        };
+
+        let existing = sessions.get(&peer).map(|s| s.clone());
+        let upstream = match existing {
+            Some(upstream) => upstream,
+            None => match open_session(&pool).await {
+                Some((backend, upstream)) => {
+                    sessions.insert(peer, upstream.clone());
+                    tokio::spawn(relay(
+                        backend,
+                        upstream.clone(),
+                        socket.clone(),
+                        peer,
+                        sessions.clone(),
+                    ));
+                    upstream
+                }
+                None => {
+                    tracing::debug!("No backend available, dropping datagram");
+                    continue;
+                }
+            },
+        };
+        if let Err(err) = upstream.send(&buf[..len]).await {
+            tracing::debug!(error=%err, "Failed to forward datagram");
+        }
+    }
+}
+
+async fn open_session(pool: &BackendPool) -> Option<(Arc<Backend>, Arc<UdpSocket>)> {
+    let mut tried = Vec::new();
+    loop {
+        let backend = pool.pick(&tried)?;
+        let local = if backend.addr.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        match UdpSocket::bind(local).await {
+            Ok(upstream) if upstream.connect(backend.addr).await.is_ok() => {
+                return Some((backend, Arc::new(upstream)));
+            }
+            _ => {
+                backend.mark_unhealthy();
+                tried.push(backend.addr);
+            }
+        }
+    }
+}
+
+/// Sends the backend replies to the client until the session expires.
+async fn relay(
+    backend: Arc<Backend>,
+    upstream: Arc<UdpSocket>,
+    downstream: Arc<UdpSocket>,
+    peer: SocketAddr,
+    sessions: Arc<DashMap<SocketAddr, Arc<UdpSocket>>>,
+) {
+    let _guard = backend.track();
+    let mut buf = vec![0u8; MAX_DATAGRAM];
+    loop {
+        tokio::select! {
+            res = timeout(SESSION_TIMEOUT, upstream.recv(&mut buf)) => match res {
+                Ok(Ok(len)) => {
+                    backend.mark_healthy();
+                    if let Err(err) = downstream.send_to(&buf[..len], peer).await {
+                        tracing::debug!(error=%err, "Failed to send reply");
+                    }
+                }
+                // refused by the backend
+                Ok(Err(_)) => {
+                    backend.mark_unhealthy();
+                    break;
+                }
+                Err(_) => break,
+            },
+            _ = backend.drained() => break,
+        }
+    }
+    sessions.remove(&peer);
+}
@@ -0,0 +1,288 @@
+//! # Container Recovery
+//!
+//! Rebuilds the runtime state after an agent restart. Containers created by a
+//! previous run are found through their labels and matched against the pods
+//! the control plane still assigns to this node:
+//! - pods with every container up to date are adopted as they are
+//! - other pods are adopted and reconciled, recreating only what changed
+//! - containers of pods no longer assigned here, or left without their pause
+//!   container, are removed
+
+use std::collections::HashMap;
+
+use shared::{api::EventType, models::pod::Pod};
+use tokio::sync::mpsc::Sender;
+use uuid::Uuid;
+
+use crate::{
+    core::volumes,
+    models::{ManagedContainer, ORPHAN_GRACE_PERIOD_SECONDS, PodRuntime, WorkRequest, spec_hash},
+    state::State,
+};
+
+/// Recovers containers left by a previous run, queueing work for pods that need starting.
+pub async fn run(state: State, tx: &Sender<WorkRequest>) -> Result<(), String> {
+    let desired: Vec<Pod> = shared::utils::client()
+        .get(format!(
+            "{}/pods?nodeName={}",
+            state.config.server_url, state.config.name
+        ))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to list assigned pods: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse assigned pods: {}", e))?;
+    let containers = state
+        .docker_mgr
+        .list_managed_containers()
+        .await
+        .map_err(|e| format!("Failed to list containers: {}", e))?;
+
+    recover(&state, desired, containers, tx).await;
+    Ok(())
+}
+
+async fn recover(
+    state: &State,
+    desired: Vec<Pod>,
+    containers: Vec<ManagedContainer>,
+    tx: &Sender<WorkRequest>,
+) {
+    let mut runtimes = group_runtimes(containers);
+
+    for mut pod in desired {
+        let id = pod.metadata.id;
+        let up_to_date = match runtimes.remove(&id) {
+            // containers can't rejoin a network namespace, start over
+            Some(runtime) if runtime.pause.is_none() => {
+                tracing::info!(pod=%pod.metadata.name, "Pause container gone, recreating pod");
+                remove(state, &runtime).await;
+                false
+            }
+            Some(mut runtime) => {
+                if let Some(pause) = &runtime.pause {
+                    runtime.pod_ip = state.docker_mgr.get_container_ip(&pause.id).await.ok();
+                }
+                let up_to_date = is_up_to_date(&pod, &runtime);
+                if let Err(e) = state.add_pod_runtime(runtime) {
+                    tracing::error!(error=%e, "Could not adopt pod runtime");
+                    continue;
+                }
+                tracing::info!(pod=%pod.metadata.name, "Adopted pod");
+                up_to_date
+            }
+            None => false,
+        };
+
+        if up_to_date {
+            // sync picks up its status
+            pod.status.observed_generation = pod.metadata.generation;
+            state.put_pod(&pod);
+            continue;
+        }
+
+        // force reconciliation to start or update the pod
+        pod.status.observed_generation = 0;
+        state.put_pod(&pod);
+        if let Err(e) = tx.try_send(WorkRequest {
+            id,
+            event: EventType::Modified,
+        }) {
+            tracing::error!("Couldn't enqueue pod: {}", e);
+        }
+    }
+
+    for runtime in runtimes.values() {
+        tracing::info!(pod=%runtime.name, "Removing orphaned pod");
+        remove(state, runtime).await;
+        volumes::cleanup(&state.config.volume_paths(), &runtime.id);
+    }
+}
+
+/// Groups labeled containers into the pod runtimes they belong to.
+fn group_runtimes(containers: Vec<ManagedContainer>) -> HashMap<Uuid, PodRuntime> {
+    let mut runtimes: HashMap<Uuid, PodRuntime> = HashMap::new();
+    for c in containers {
+        let runtime = runtimes.entry(c.pod_id).or_insert_with(|| PodRuntime {
+            id: c.pod_id,
+            name: c.pod_name,
+            containers: HashMap::new(),
+            pause: None,
+            pod_ip: None,
+        });
+        if c.pause {
+            runtime.pause = Some(c.runtime);
+        } else {
+            runtime
+                .containers
+                .insert(c.runtime.spec_name.clone(), c.runtime);
+        }
+    }
+    runtimes
+}
+
+/// Whether the runtime has exactly the sidecars and containers in the pod spec, created from that spec.
+fn is_up_to_date(pod: &Pod, runtime: &PodRuntime) -> bool {
+    runtime.containers.len() == pod.spec.running_containers().count()
+        && pod.spec.running_containers().all(|spec| {
+            runtime
+                .containers
+                .get(&spec.name)
+                .is_some_and(|c| c.spec_hash == spec_hash(spec))
+        })
+}
+
+async fn remove(state: &State, runtime: &PodRuntime) {
+    let (main, sidecars) = runtime.stop_order();
+    for ids in [main, sidecars] {
+        if ids.is_empty() {
+            continue;
+        }
+        if let Err(e) = state
+            .docker_mgr
+            .stop_pod(&ids, ORPHAN_GRACE_PERIOD_SECONDS)
+            .await
+        {
+            tracing::error!(error=%e, pod=%runtime.name, "Failed to remove containers");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    //! - test_recover_adopts_running_pod
+    //!   runtime rebuilt from labels, nothing restarted
+    //! - test_recover_removes_orphans
+    //!   containers of unassigned pods are stopped
+    //! - test_recover_reconciles_outdated_pod
+    //!   leftover containers adopted and the pod queued for an update
+    //! - test_recover_starts_missing_pod
+    //!   assigned pod without containers is queued for start
+    //! - test_recover_recreates_pod_without_pause
+    //!   containers removed and the pod queued for start
+
+    use super::*;
+    use crate::{
+        docker::{DockerClient, test::TestDocker},
+        models::{Config, PodEnv},
+        state::NodeState,
+    };
+    use shared::models::pod::ContainerSpec;
+    use tokio::sync::mpsc;
+
+    fn pod_with(containers: &[&str]) -> Pod {
+        let mut pod = Pod::default();
+        pod.metadata.name = "p1".to_string();
+        pod.spec.containers = containers
+            .iter()
+            .map(|name| ContainerSpec {
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        pod
+    }
+
+    async fn setup(running: &Pod) -> (State, Box<TestDocker>) {
+        let docker = Box::new(TestDocker::new());
+        docker.start_pause(running, "pause").await.unwrap();
+        docker
+            .start_pod(running.clone(), &PodEnv::new())
+            .await
+            .unwrap();
+        let state = NodeState::new_with(Some(Config::default()), Some(docker.clone()));
+        (state, docker)
+    }
+
+    #[tokio::test]
+    async fn test_recover_adopts_running_pod() {
+        let pod = pod_with(&["a", "b"]);
+        let (state, docker) = setup(&pod).await;
+        let (tx, mut rx) = mpsc::channel(1);
+
+        let containers = docker.list_managed_containers().await.unwrap();
+        recover(&state, vec![pod.clone()], containers, &tx).await;
+
+        let runtime = state.get_pod_runtime(&pod.metadata.id).unwrap();
+        assert_eq!(runtime.containers.len(), 2);
+        assert!(runtime.pause.is_some());
+        assert_eq!(runtime.pod_ip.as_deref(), Some("127.0.0.1"));
+        let local = state.get_pod(&pod.metadata.id).unwrap();
+        assert_eq!(local.status.observed_generation, pod.metadata.generation);
+        assert!(rx.try_recv().is_err());
+        assert!(docker.stop_pod_calls.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recover_removes_orphans() {
+        let pod = pod_with(&["a"]);
+        let (state, docker) = setup(&pod).await;
+        let (tx, _rx) = mpsc::channel(1);
+
+        let containers = docker.list_managed_containers().await.unwrap();
+        recover(&state, Vec::new(), containers, &tx).await;
+
+        assert!(state.get_pod_runtime(&pod.metadata.id).is_none());
+        // app containers, then the pause container
+        assert_eq!(docker.stop_pod_calls.lock().await.len(), 2);
+        assert!(docker.managed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recover_reconciles_outdated_pod() {
+        let (state, docker) = setup(&pod_with(&["a"])).await;
+        let (tx, mut rx) = mpsc::channel(1);
+
+        // same pod now expects a second container
+        let mut pod = pod_with(&["a", "b"]);
+        let leftover = docker.list_managed_containers().await.unwrap();
+        pod.metadata.id = leftover[0].pod_id;
+        recover(&state, vec![pod.clone()], leftover, &tx).await;
+
+        assert!(docker.stop_pod_calls.lock().await.is_empty());
+        assert!(state.get_pod_runtime(&pod.metadata.id).is_some());
+        let req = rx.try_recv().expect("Pod should be queued");
+        assert_eq!(req.id, pod.metadata.id);
+        assert_eq!(
+            state
+                .get_pod(&pod.metadata.id)
+                .unwrap()
+                .status
+                .observed_generation,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recover_starts_missing_pod() {
+        let docker = Box::new(TestDocker::new());
+        let state = NodeState::new_with(Some(Config::default()), Some(docker));
+        let (tx, mut rx) = mpsc::channel(1);
+        let pod = pod_with(&["a"]);
+
+        recover(&state, vec![pod.clone()], Vec::new(), &tx).await;
+
+        let req = rx.try_recv().expect("Pod should be queued");
+        assert_eq!(req.id, pod.metadata.id);
+        assert_eq!(req.event, EventType::Modified);
+    }
+
+    #[tokio::test]
+    async fn test_recover_recreates_pod_without_pause() {
+        let pod = pod_with(&["a"]);
+        let docker = Box::new(TestDocker::new());
+        docker.start_pod(pod.clone(), &PodEnv::new()).await.unwrap();
+        let state = NodeState::new_with(Some(Config::default()), Some(docker.clone()));
+        let (tx, mut rx) = mpsc::channel(1);
+
+        let containers = docker.list_managed_containers().await.unwrap();
+        recover(&state, vec![pod.clone()], containers, &tx).await;
+
+        assert_eq!(docker.stop_pod_calls.lock().await.len(), 1);
+        assert!(state.get_pod_runtime(&pod.metadata.id).is_none());
+        let req = rx.try_recv().expect("Pod should be queued");
+        assert_eq!(req.id, pod.metadata.id);
+    }
+}
@@ -0,0 +1,230 @@
+//! # Graceful Shutdown
+//!
+//! Waits for SIGTERM or SIGINT and takes the node out of the cluster: stops
+//! taking new work, optionally drains its pods through the eviction API,
+//! deregisters from the control plane and stops whatever is still running.
+
+use reqwest::{Client, StatusCode};
+use shared::models::pod::Pod;
+use tokio::time::{Duration, Instant, sleep};
+
+use crate::{core::worker, state::State};
+
+/// Resolves once the node has been shut down after a termination signal.
+pub async fn run(state: State) -> Result<(), String> {
+    wait_for_signal().await?;
+    tracing::info!("Termination signal received, shutting down");
+    shutdown(state).await;
+    Ok(())
+}
+
+async fn wait_for_signal() -> Result<(), String> {
+    let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+        .map_err(|e| format!("Failed to install SIGTERM handler: {}", e))?;
+    tokio::select! {
+        _ = sigterm.recv() => {},
+        res = tokio::signal::ctrl_c() => res.map_err(|e| e.to_string())?,
+    }
+    Ok(())
+}
+
+/// Stops taking work, drains if configured, deregisters and stops local pods.
+pub async fn shutdown(state: State) {
+    state.begin_shutdown();
+    let client = shared::utils::client();
+
+    if state.config.drain_on_shutdown {
+        drain(&state, &client).await;
+    }
+    deregister(&state, &client).await;
+
+    for runtime in state.list_pod_runtimes() {
+        worker::delete(state.clone(), runtime.id).await;
+    }
+}
+
+/// Cordons the node and evicts its pods, waiting for them to stop until the drain timeout.
+///
+/// Evictions blocked by a disruption budget are retried, whatever is left at the
+/// deadline is removed by the deregistration.
+async fn drain(state: &State, client: &Client) {
+    let base = &state.config.server_url;
+    let name = &state.config.name;
+    let deadline = Instant::now() + Duration::from_secs(state.config.drain_timeout.into());
+
+    let cordon = client
+        .patch(format!("{}/nodes/{}", base, name))
+        .json(&serde_json::json!({"spec": {"unschedulable": true}}))
+        .send()
+        .await;
+    if let Err(err) = cordon {
+        tracing::warn!(error=%err, "Could not cordon node");
+    }
+
+    let mut pending: Vec<String> = match client
+        .get(format!("{}/pods?nodeName={}", base, name))
+        .send()
+        .await
+    {
+        Ok(resp) => resp
+            .json::<Vec<Pod>>()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p| p.metadata.name)
+            .collect(),
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not list pods to drain");
+            Vec::new()
+        }
+    };
+
+    while !pending.is_empty() && Instant::now() < deadline {
+        let mut blocked = Vec::new();
+        for pod_name in pending {
+            let res = client
+                .post(format!("{}/pods/{}/eviction", base, pod_name))
+                .send()
+                .await;
+            match res {
+                Ok(resp) if resp.status() == StatusCode::TOO_MANY_REQUESTS => {
+                    blocked.push(pod_name)
+                }
+                Ok(resp) if !resp.status().is_success() => {
+                    tracing::warn!(pod=%pod_name, status=%resp.status(), "Eviction failed")
+                }
+                Ok(_) => tracing::info!(pod=%pod_name, "Evicted"),
+                Err(err) => tracing::warn!(pod=%pod_name, error=%err, "Eviction failed"),
+            }
+        }
+        pending = blocked;
+        if !pending.is_empty() {
+            sleep(Duration::from_secs(1)).await;
+        }
+    }
+
+    // deletions come back through the watcher and stop the containers
+    while !state.list_pod_runtimes().is_empty() && Instant::now() < deadline {
+        sleep(Duration::from_millis(200)).await;
+    }
+}
+
+/// Removes the node from the control plane and forgets its token.
+async fn deregister(state: &State, client: &Client) {
+    let res = client
+        .delete(format!(
+            "{}/nodes/{}",
+            state.config.server_url, state.config.name
+        ))
+        .send()
+        .await;
+    match res {
+        Ok(resp) if resp.status().is_success() || resp.status() == StatusCode::NOT_FOUND => {
+            tracing::info!(name=%state.config.name, "Deregistered from the system");
+            let _ = std::fs::remove_file(&state.config.token_file);
+        }
+        Ok(resp) => tracing::warn!(status=%resp.status(), "Could not deregister node"),
+        Err(err) => tracing::warn!(error=%err, "Could not deregister node"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    //! - test_shutdown_deregisters
+    //!   node deleted, token forgotten, runtimes stopped
+    //! - test_shutdown_drains
+    //!   node cordoned and its pods evicted before deregistering
+
+    use super::*;
+    use crate::{
+        docker::test::TestDocker,
+        models::{Config, PodRuntime},
+        state::NodeState,
+    };
+    use std::collections::HashMap;
+    use uuid::Uuid;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_config(server_url: String) -> Config {
+        Config {
+            server_url,
+            name: "n1".to_string(),
+            token_file: std::env::temp_dir()
+                .join(format!("cr8s-token-{}", Uuid::new_v4()))
+                .display()
+                .to_string(),
+            drain_timeout: 2,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_deregisters() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/nodes/n1"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = test_config(server.uri());
+        std::fs::write(&config.token_file, "token").unwrap();
+        let docker = Box::new(TestDocker::new());
+        let state = NodeState::new_with(Some(config), Some(docker.clone()));
+        let runtime = PodRuntime {
+            id: Uuid::new_v4(),
+            name: "".to_string(),
+            containers: HashMap::new(),
+            pause: None,
+            pod_ip: None,
+        };
+        state.add_pod_runtime(runtime).unwrap();
+
+        shutdown(state.clone()).await;
+
+        assert!(state.is_shutting_down());
+        assert!(!std::path::Path::new(&state.config.token_file).exists());
+        assert!(state.list_pod_runtimes().is_empty());
+        assert_eq!(docker.stop_pod_calls.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_drains() {
+        let server = MockServer::start().await;
+        let mut pod = Pod::default();
+        pod.metadata.name = "p1".to_string();
+        Mock::given(method("PATCH"))
+            .and(path("/nodes/n1"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/pods"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![pod]))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/pods/p1/eviction"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/nodes/n1"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = Config {
+            drain_on_shutdown: true,
+            ..test_config(server.uri())
+        };
+        let state = NodeState::new_with(Some(config), Some(Box::new(TestDocker::new())));
+        shutdown(state).await;
+    }
+}
@@ -0,0 +1,251 @@
+//! # Config Sources
+//!
+//! Config maps, secrets and persistent volumes referenced by a pod, fetched
+//! from the apiserver before its containers are created, and again on every
+//! sync for the config maps and secrets mounted as volumes.
+
+use std::collections::HashMap;
+
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use shared::models::{
+    configmap::ConfigMap,
+    persistentvolume::PersistentVolume,
+    persistentvolumeclaim::PersistentVolumeClaim,
+    pod::{ContainerSpec, EnvFromRef, EnvVarSource, Pod},
+    secret::Secret,
+};
+
+use crate::{models::PodEnv, state::State};
+
+/// Data of the config maps and secrets a pod references that exist, and the
+/// volumes its claims are bound to.
+#[derive(Debug, Default)]
+pub struct PodSources {
+    pub configmaps: HashMap<String, HashMap<String, String>>,
+    pub secrets: HashMap<String, HashMap<String, String>>,
+    /// Bound volume of each claim, by claim name
+    pub claims: HashMap<String, PersistentVolume>,
+}
+
+impl PodSources {
+    /// Fetches every config map and secret the pod references, missing ones are left out.
+    ///
+    /// Claims must be bound to a volume on this node, the pod can't start otherwise.
+    pub async fn fetch(state: &State, pod: &Pod) -> Result<Self, String> {
+        let mut sources = Self::default();
+        let configmaps = pod.spec.configmap_refs();
+        let secrets = pod.spec.secret_refs();
+        let claims = pod.spec.claim_refs();
+        if configmaps.is_empty() && secrets.is_empty() && claims.is_empty() {
+            return Ok(sources);
+        }
+
+        let client = shared::utils::client();
+        let server = &state.config.server_url;
+        for name in configmaps {
+            let url = format!("{}/configmaps/{}", server, name);
+            if let Some(cm) = fetch_one::<ConfigMap>(&client, &url).await? {
+                sources.configmaps.insert(name.to_string(), cm.spec.data);
+            }
+        }
+        for name in secrets {
+            let url = format!("{}/secrets/{}", server, name);
+            if let Some(secret) = fetch_one::<Secret>(&client, &url).await? {
+                sources.secrets.insert(name.to_string(), secret.spec.data);
+            }
+        }
+        for name in claims {
+            let url = format!("{}/persistentvolumeclaims/{}", server, name);
+            let volume = fetch_one::<PersistentVolumeClaim>(&client, &url)
+                .await?
+                .ok_or_else(|| format!("Claim {} not found", name))?
+                .status
+                .volume_name
+                .ok_or_else(|| format!("Claim {} is not bound", name))?;
+            let url = format!("{}/persistentvolumes/{}", server, volume);
+            let pv = fetch_one::<PersistentVolume>(&client, &url)
+                .await?
+                .ok_or_else(|| format!("Volume {} of claim {} not found", volume, name))?;
+            if pv.spec.node_name != state.config.name {
+                return Err(format!(
+                    "Volume {} is on node {}",
+                    volume, pv.spec.node_name
+                ));
+            }
+            sources.claims.insert(name.to_string(), pv);
+        }
+        Ok(sources)
+    }
+
+    /// Environment of every container in the pod, init containers included.
+    pub fn pod_env(&self, pod: &Pod) -> Result<PodEnv, String> {
+        pod.spec
+            .init_containers
+            .iter()
+            .chain(&pod.spec.containers)
+            .map(|spec| Ok((spec.name.clone(), self.env(spec)?)))
+            .collect()
+    }
+
+    /// Resolves the environment of a container as `NAME=value`.
+    ///
+    /// `envFrom` sources come first, in order, then `env` entries override them.
+    /// A missing object or key fails the container unless marked optional.
+    pub fn env(&self, spec: &ContainerSpec) -> Result<Vec<String>, String> {
+        let mut vars: Vec<(String, String)> = Vec::new();
+        let mut set = |name: String, value: String| match vars.iter_mut().find(|(n, _)| *n == name)
+        {
+            Some(var) => var.1 = value,
+            None => vars.push((name, value)),
+        };
+
+        for env_from in &spec.env_from {
+            let (data, r) = match &env_from.source {
+                EnvFromRef::ConfigMapRef(r) => (self.configmaps.get(&r.name), r),
+                EnvFromRef::SecretRef(r) => (self.secrets.get(&r.name), r),
+            };
+            let Some(data) = data else {
+                if r.optional {
+                    continue;
+                }
+                return Err(format!("Container '{}': {} not found", spec.name, r.name));
+            };
+            let mut keys: Vec<_> = data.keys().collect();
+            keys.sort();
+            for key in keys {
+                set(format!("{}{}", env_from.prefix, key), data[key].clone());
+            }
+        }
+
+        for env in spec.env.iter().flatten() {
+            let (data, selector) = match &env.value_from {
+                None => {
+                    set(env.name.clone(), env.value.clone());
+                    continue;
+                }
+                Some(EnvVarSource::ConfigMapKeyRef(s)) => (self.configmaps.get(&s.name), s),
+                Some(EnvVarSource::SecretKeyRef(s)) => (self.secrets.get(&s.name), s),
+            };
+            match data.and_then(|d| d.get(&selector.key)) {
+                Some(value) => set(env.name.clone(), value.clone()),
+                None if selector.optional => {}
+                None => {
+                    return Err(format!(
+                        "Container '{}': key {} of {} not found",
+                        spec.name, selector.key, selector.name
+                    ));
+                }
+            }
+        }
+
+        Ok(vars
+            .into_iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect())
+    }
+}
+
+pub(crate) async fn fetch_one<T: DeserializeOwned>(
+    client: &Client,
+    url: &str,
+) -> Result<Option<T>, String> {
+    let resp = client.get(url).send().await.map_err(|e| e.to_string())?;
+    match resp.status() {
+        StatusCode::NOT_FOUND => Ok(None),
+        s if s.is_success() => resp.json::<T>().await.map(Some).map_err(|e| e.to_string()),
+        s => Err(format!("Failed to fetch {}: {}", url, s)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    //! - test_env_precedence
+    //!   envFrom keys prefixed, env entries win over them
+    //! - test_env_missing_reference
+    //!   missing keys fail the container unless optional
+
+    use super::*;
+    use shared::models::pod::{EnvFromSource, EnvVar, KeySelector, ObjectRef};
+
+    fn sources() -> PodSources {
+        PodSources {
+            configmaps: HashMap::from([(
+                "app".to_string(),
+                HashMap::from([
+                    ("LOG_LEVEL".to_string(), "debug".to_string()),
+                    ("PORT".to_string(), "8080".to_string()),
+                ]),
+            )]),
+            secrets: HashMap::from([(
+                "db".to_string(),
+                HashMap::from([("password".to_string(), "hunter2".to_string())]),
+            )]),
+            ..Default::default()
+        }
+    }
+
+    fn secret_var(name: &str, secret: &str, key: &str, optional: bool) -> EnvVar {
+        EnvVar {
+            name: name.to_string(),
+            value: String::new(),
+            value_from: Some(EnvVarSource::SecretKeyRef(KeySelector {
+                name: secret.to_string(),
+                key: key.to_string(),
+                optional,
+            })),
+        }
+    }
+
+    #[test]
+    fn test_env_precedence() {
+        let spec = ContainerSpec {
+            env_from: vec![EnvFromSource {
+                prefix: "APP_".to_string(),
+                source: EnvFromRef::ConfigMapRef(ObjectRef {
+                    name: "app".to_string(),
+                    optional: false,
+                }),
+            }],
+            env: Some(vec![
+                EnvVar {
+                    name: "APP_PORT".to_string(),
+                    value: "9090".to_string(),
+                    value_from: None,
+                },
+                secret_var("DB_PASSWORD", "db", "password", false),
+            ]),
+            ..Default::default()
+        };
+
+        let env = sources().env(&spec).unwrap();
+        assert_eq!(
+            env,
+            vec![
+                "APP_LOG_LEVEL=debug",
+                "APP_PORT=9090",
+                "DB_PASSWORD=hunter2"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_env_missing_reference() {
+        let mut spec = ContainerSpec {
+            env: Some(vec![secret_var("TOKEN", "db", "token", true)]),
+            env_from: vec![EnvFromSource {
+                prefix: String::new(),
+                source: EnvFromRef::SecretRef(ObjectRef {
+                    name: "missing".to_string(),
+                    optional: true,
+                }),
+            }],
+            ..Default::default()
+        };
+        assert!(sources().env(&spec).unwrap().is_empty());
+
+        spec.env = Some(vec![secret_var("TOKEN", "db", "token", false)]);
+        assert!(sources().env(&spec).is_err());
+    }
+}
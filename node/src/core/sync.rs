@@ -1,7 +1,8 @@
 //! # Pod Status Sync Loop
 //!
 //! This module defines a background task that periodically polls the state of all container
-//! runtimes and reports their status back to the control plane.
+//! runtimes and reports their status back to the control plane. Config map and secret
+//! volumes are brought up to date on the way.
 
 use std::{collections::HashMap, time::Duration};
 
@@ -9,11 +10,14 @@ use bollard::secret::ContainerStateStatusEnum;
 use reqwest::Client;
 use shared::{
     api::{PodField, PodPatch, PodStatusUpdate},
-    models::pod::{PodPhase, PodStatus},
+    models::pod::{Pod, PodPhase, PodStatus, VolumeSource},
 };
 use tokio::time;
 
-use crate::state::State;
+use crate::{
+    core::{sources::PodSources, volumes},
+    state::State,
+};
 
 /// Starts the periodic pod status sync loop.
 ///
@@ -28,7 +32,7 @@ pub async fn run(state: State) -> Result<(), String> {
 }
 
 pub async fn run_iteration(state: &State) -> Result<(), String> {
-    let client = Client::new();
+    let client = shared::utils::client();
     for p in state.list_pod_runtimes().iter() {
         let Some(pod) = state.get_pod(&p.id) else {
             tracing::warn!("Failed to get pod from runtime");
@@ -37,6 +41,7 @@ pub async fn run_iteration(state: &State) -> Result<(), String> {
         if pod.status.observed_generation != pod.metadata.generation {
             continue;
         }
+        refresh_volumes(state, &pod).await;
 
         // Get map of container statuses
         let mut container_statuses_map: HashMap<String, ContainerStateStatusEnum> = HashMap::new();
@@ -58,38 +63,75 @@ pub async fn run_iteration(state: &State) -> Result<(), String> {
             }
         };
 
-        // Build and send status update to control plane
-        let Ok(update) = serde_json::to_value(PodStatusUpdate {
-            node_name: state.config.name.clone(),
-            status: PodStatus {
-                phase,
-                container_status: container_statuses_map
-                    .iter()
-                    .map(|(k, v)| (k.clone(), v.to_string()))
-                    .collect(),
-                last_update: None,
-                observed_generation: pod.metadata.generation,
-            },
-        }) else {
-            continue;
-        };
-        let payload = PodPatch {
-            pod_field: PodField::Status,
-            value: update,
-        };
-
-        if let Err(err) = client
-            .patch(format!("{}/pods/{}", state.config.server_url, p.name))
-            .json(&payload)
-            .send()
-            .await
-        {
-            tracing::warn!(error=%err, "Status update failed");
+        // init containers still here are sidecars, the others ran to completion
+        let (init_statuses, main_statuses): (Vec<_>, Vec<_>) = container_statuses_map
+            .iter()
+            .map(|(k, v)| (k.clone(), v.to_string()))
+            .partition(|(k, _)| pod.spec.init_containers.iter().any(|c| &c.name == k));
+        let completed = pod
+            .spec
+            .init_containers
+            .iter()
+            .filter(|c| !c.is_sidecar())
+            .map(|c| (c.name.clone(), ContainerStateStatusEnum::EXITED.to_string()));
+
+        let status = PodStatus {
+            phase,
+            container_status: main_statuses,
+            init_container_status: completed.chain(init_statuses).collect(),
+            last_update: None,
+            observed_generation: pod.metadata.generation,
+            pod_ip: p.pod_ip.clone(),
+            ..Default::default()
         };
+        report_status(&client, state, &p.name, status).await;
     }
     Ok(())
 }
 
+/// Rewrites mounted config maps and secrets that changed on the apiserver.
+async fn refresh_volumes(state: &State, pod: &Pod) {
+    let projected = pod.spec.volumes.iter().any(|v| {
+        matches!(
+            v.source,
+            VolumeSource::ConfigMap(_) | VolumeSource::Secret(_)
+        )
+    });
+    if !projected {
+        return;
+    }
+    let res = match PodSources::fetch(state, pod).await {
+        Ok(sources) => volumes::render(&state.config.volume_paths(), pod, &sources),
+        Err(err) => Err(err),
+    };
+    if let Err(err) = res {
+        tracing::warn!(error=%err, pod=%pod.metadata.name, "Failed to refresh volumes");
+    }
+}
+
+/// Sends a pod status to the control plane, failures are only logged.
+pub async fn report_status(client: &Client, state: &State, pod_name: &str, status: PodStatus) {
+    let Ok(update) = serde_json::to_value(PodStatusUpdate {
+        node_name: state.config.name.clone(),
+        status,
+    }) else {
+        return;
+    };
+    let payload = PodPatch {
+        pod_field: PodField::Status,
+        value: update,
+    };
+
+    if let Err(err) = client
+        .patch(format!("{}/pods/{}", state.config.server_url, pod_name))
+        .json(&payload)
+        .send()
+        .await
+    {
+        tracing::warn!(error=%err, "Status update failed");
+    };
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -0,0 +1,488 @@
+//! # Pod Volumes
+//!
+//! Lays pod volumes out on the host before the containers start:
+//! - emptyDir volumes get a directory per pod under the volume root, or under
+//!   the memory root when backed by memory
+//! - hostPath volumes are checked against their type, or created
+//! - configMap and secret volumes get one file per key, secrets in memory,
+//!   rewritten whenever the data changes
+//! - persistentVolumeClaim volumes use the directory of their bound volume,
+//!   created under the persistent root unless an admin gave it a path
+//!
+//! Containers bind-mount them, and the pod directories go away with the pod.
+//! Persistent volume directories stay, the provisioner removes them.
+
+use std::{
+    collections::HashMap,
+    os::unix::fs::FileTypeExt,
+    path::{Path, PathBuf},
+};
+
+use shared::models::pod::{
+    ContainerSpec, HostPathType, HostPathVolume, Pod, StorageMedium, Volume, VolumeSource,
+};
+use uuid::Uuid;
+
+use crate::{core::sources::PodSources, models::VolumePaths};
+
+/// Copy of the pod with every claim replaced by the host directory of its volume.
+///
+/// Containers are left untouched so their spec hash stays the same.
+pub fn resolve_claims(paths: &VolumePaths, pod: &Pod, sources: &PodSources) -> Result<Pod, String> {
+    let mut resolved = pod.clone();
+    for volume in resolved.spec.volumes.iter_mut() {
+        let VolumeSource::PersistentVolumeClaim(claim) = &volume.source else {
+            continue;
+        };
+        let pv = sources.claims.get(&claim.claim_name).ok_or_else(|| {
+            format!(
+                "Volume '{}': claim {} is not bound",
+                volume.name, claim.claim_name
+            )
+        })?;
+        volume.source = VolumeSource::HostPath(HostPathVolume {
+            path: paths.persistent_dir(pv).display().to_string(),
+            path_type: HostPathType::DirectoryOrCreate,
+            read_only: claim.read_only,
+        });
+    }
+    Ok(resolved)
+}
+
+/// Creates the pod volumes and checks host paths, failing on the first volume that can't be used.
+///
+/// Claims must be resolved first.
+pub fn prepare(paths: &VolumePaths, pod: &Pod, sources: &PodSources) -> Result<(), String> {
+    for volume in &pod.spec.volumes {
+        let path = source(paths, &pod.metadata.id, volume);
+        let res = match &volume.source {
+            VolumeSource::EmptyDir(_) => std::fs::create_dir_all(&path).map_err(|e| e.to_string()),
+            VolumeSource::HostPath(host) => check_host_path(&path, host.path_type),
+            VolumeSource::ConfigMap(_) | VolumeSource::Secret(_) => {
+                write_data(&path, volume, sources).map(|_| ())
+            }
+            VolumeSource::PersistentVolumeClaim(c) => {
+                Err(format!("claim {} was not resolved", c.claim_name))
+            }
+        };
+        res.map_err(|e| format!("Volume '{}': {}", volume.name, e))?;
+    }
+
+    // sub paths are created inside directory volumes
+    for container in pod.spec.init_containers.iter().chain(&pod.spec.containers) {
+        for mount in &container.volume_mounts {
+            let (Some(sub_path), Some(volume)) = (&mount.sub_path, find(pod, &mount.name)) else {
+                continue;
+            };
+            let base = source(paths, &pod.metadata.id, volume);
+            if base.is_dir() {
+                std::fs::create_dir_all(base.join(sub_path))
+                    .map_err(|e| format!("Volume '{}': {}", volume.name, e))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Rewrites the config map and secret volumes whose data changed.
+///
+/// Files are replaced atomically, containers mounting the whole volume see
+/// the new content while subPath mounts keep the file they started with.
+pub fn render(paths: &VolumePaths, pod: &Pod, sources: &PodSources) -> Result<(), String> {
+    for volume in &pod.spec.volumes {
+        if !matches!(
+            volume.source,
+            VolumeSource::ConfigMap(_) | VolumeSource::Secret(_)
+        ) {
+            continue;
+        }
+        let path = source(paths, &pod.metadata.id, volume);
+        let changed = write_data(&path, volume, sources)
+            .map_err(|e| format!("Volume '{}': {}", volume.name, e))?;
+        if changed {
+            tracing::info!(pod=%pod.metadata.name, volume=%volume.name, "Updated volume files");
+        }
+    }
+    Ok(())
+}
+
+/// Docker binds for the container mounts, as `source:target[:ro]`.
+pub fn binds(paths: &VolumePaths, pod: &Pod, spec: &ContainerSpec) -> Vec<String> {
+    spec.volume_mounts
+        .iter()
+        .filter_map(|mount| {
+            let volume = find(pod, &mount.name)?;
+            let mut host = source(paths, &pod.metadata.id, volume);
+            if let Some(sub_path) = &mount.sub_path {
+                host = host.join(sub_path);
+            }
+            let read_only = match &volume.source {
+                VolumeSource::ConfigMap(_) | VolumeSource::Secret(_) => true,
+                VolumeSource::HostPath(host) => host.read_only,
+                VolumeSource::PersistentVolumeClaim(c) => c.read_only,
+                VolumeSource::EmptyDir(_) => false,
+            };
+            let mode = if mount.read_only || read_only {
+                ":ro"
+            } else {
+                ""
+            };
+            Some(format!("{}:{}{}", host.display(), mount.mount_path, mode))
+        })
+        .collect()
+}
+
+/// Removes the directories of every emptyDir, config map and secret volume of the pod.
+pub fn cleanup(paths: &VolumePaths, pod_id: &Uuid) {
+    for dir in [paths.pod_dir(pod_id), paths.memory_dir(pod_id)] {
+        match std::fs::remove_dir_all(&dir) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => tracing::warn!(error=%e, dir=%dir.display(), "Failed to remove pod volumes"),
+        }
+    }
+}
+
+fn find<'a>(pod: &'a Pod, name: &str) -> Option<&'a Volume> {
+    pod.spec.volumes.iter().find(|v| v.name == name)
+}
+
+/// Host path backing a volume.
+fn source(paths: &VolumePaths, pod_id: &Uuid, volume: &Volume) -> PathBuf {
+    match &volume.source {
+        VolumeSource::EmptyDir(dir) if dir.medium == StorageMedium::Memory => {
+            paths.memory_dir(pod_id).join(&volume.name)
+        }
+        // claims are resolved to host paths before anything is mounted
+        VolumeSource::EmptyDir(_)
+        | VolumeSource::ConfigMap(_)
+        | VolumeSource::PersistentVolumeClaim(_) => {
+            paths.pod_dir(pod_id).join("volumes").join(&volume.name)
+        }
+        // kept off the node disk
+        VolumeSource::Secret(_) => paths.memory_dir(pod_id).join(&volume.name),
+        VolumeSource::HostPath(host) => PathBuf::from(&host.path),
+    }
+}
+
+/// Writes the data of a config map or secret volume, returning whether any file changed.
+fn write_data(dir: &Path, volume: &Volume, sources: &PodSources) -> Result<bool, String> {
+    let (data, name, optional) = match &volume.source {
+        VolumeSource::ConfigMap(r) => (sources.configmaps.get(&r.name), &r.name, r.optional),
+        VolumeSource::Secret(s) => (
+            sources.secrets.get(&s.secret_name),
+            &s.secret_name,
+            s.optional,
+        ),
+        _ => return Ok(false),
+    };
+    let empty = HashMap::new();
+    let data = match data {
+        Some(data) => data,
+        None if optional => &empty,
+        None => return Err(format!("{} not found", name)),
+    };
+    write_files(dir, data).map_err(|e| e.to_string())
+}
+
+/// Leaves one file per key in the directory, replacing changed ones through a rename.
+fn write_files(dir: &Path, data: &HashMap<String, String>) -> std::io::Result<bool> {
+    std::fs::create_dir_all(dir)?;
+    let mut changed = false;
+    for (key, value) in data {
+        let path = dir.join(key);
+        if std::fs::read(&path).is_ok_and(|current| current == value.as_bytes()) {
+            continue;
+        }
+        let tmp = dir.join(format!(".{}.tmp", key));
+        std::fs::write(&tmp, value)?;
+        std::fs::rename(&tmp, &path)?;
+        changed = true;
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let stale = entry
+            .file_name()
+            .to_str()
+            .is_none_or(|name| !data.contains_key(name));
+        if stale && entry.file_type()?.is_file() {
+            std::fs::remove_file(entry.path())?;
+            changed = true;
+        }
+    }
+    Ok(changed)
+}
+
+fn check_host_path(path: &Path, path_type: HostPathType) -> Result<(), String> {
+    let metadata = std::fs::metadata(path).ok();
+    let wrong_type = |expected: &str| Err(format!("{} is not a {}", path.display(), expected));
+    match path_type {
+        HostPathType::Unset => Ok(()),
+        HostPathType::DirectoryOrCreate if metadata.is_none() => {
+            std::fs::create_dir_all(path).map_err(|e| e.to_string())
+        }
+        HostPathType::DirectoryOrCreate | HostPathType::Directory => match metadata {
+            Some(m) if m.is_dir() => Ok(()),
+            _ => wrong_type("directory"),
+        },
+        HostPathType::FileOrCreate if metadata.is_none() => {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            std::fs::File::create(path)
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
+        HostPathType::FileOrCreate | HostPathType::File => match metadata {
+            Some(m) if m.is_file() => Ok(()),
+            _ => wrong_type("file"),
+        },
+        HostPathType::Socket => match metadata {
+            Some(m) if m.file_type().is_socket() => Ok(()),
+            _ => wrong_type("socket"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    //! - test_prepare_and_cleanup_empty_dir
+    //!   directories created under the roots and removed with the pod
+    //! - test_binds
+    //!   sub paths and read only mounts
+    //! - test_host_path_types
+    //!   missing paths rejected or created depending on the type
+    //! - test_render_configmap_and_secret
+    //!   files follow the data, secrets in memory, mounts read only
+    //! - test_resolve_claims
+    //!   claims mount their volume directory, created on prepare
+
+    use super::*;
+    use shared::models::{
+        metadata::Metadata,
+        persistentvolume::{PersistentVolume, PersistentVolumeSpec, ReclaimPolicy},
+        pod::{ClaimVolume, EmptyDirVolume, HostPathVolume, ObjectRef, SecretVolume, VolumeMount},
+    };
+
+    fn temp_paths() -> VolumePaths {
+        let base = std::env::temp_dir().join(format!("cr8s-volumes-{}", Uuid::new_v4()));
+        VolumePaths {
+            root: base.join("disk"),
+            memory_root: base.join("memory"),
+            persistent_root: base.join("persistent"),
+        }
+    }
+
+    fn empty_dir(name: &str, medium: StorageMedium) -> Volume {
+        Volume {
+            name: name.to_string(),
+            source: VolumeSource::EmptyDir(EmptyDirVolume { medium }),
+        }
+    }
+
+    fn host_path(path: &Path, path_type: HostPathType) -> Volume {
+        Volume {
+            name: "host".to_string(),
+            source: VolumeSource::HostPath(HostPathVolume {
+                path: path.display().to_string(),
+                path_type,
+                read_only: false,
+            }),
+        }
+    }
+
+    fn mount(name: &str, mount_path: &str, sub_path: Option<&str>, read_only: bool) -> VolumeMount {
+        VolumeMount {
+            name: name.to_string(),
+            mount_path: mount_path.to_string(),
+            read_only,
+            sub_path: sub_path.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_prepare_and_cleanup_empty_dir() {
+        let paths = temp_paths();
+        let mut pod = Pod::default();
+        pod.spec.volumes = vec![
+            empty_dir("data", StorageMedium::Default),
+            empty_dir("shm", StorageMedium::Memory),
+        ];
+        pod.spec.containers[0].volume_mounts = vec![mount("data", "/data", Some("logs"), false)];
+
+        prepare(&paths, &pod, &PodSources::default()).unwrap();
+
+        let data = paths.pod_dir(&pod.metadata.id).join("volumes/data");
+        assert!(data.join("logs").is_dir());
+        assert!(paths.memory_dir(&pod.metadata.id).join("shm").is_dir());
+
+        cleanup(&paths, &pod.metadata.id);
+        assert!(!paths.pod_dir(&pod.metadata.id).exists());
+        assert!(!paths.memory_dir(&pod.metadata.id).exists());
+    }
+
+    #[test]
+    fn test_binds() {
+        let paths = temp_paths();
+        let mut pod = Pod::default();
+        pod.spec.volumes = vec![
+            empty_dir("data", StorageMedium::Default),
+            host_path(Path::new("/etc/hosts"), HostPathType::File),
+        ];
+        pod.spec.containers[0].volume_mounts = vec![
+            mount("data", "/data", Some("logs"), false),
+            mount("host", "/etc/hosts", None, true),
+        ];
+
+        let binds = binds(&paths, &pod, &pod.spec.containers[0]);
+
+        let data = paths.pod_dir(&pod.metadata.id).join("volumes/data/logs");
+        assert_eq!(
+            binds,
+            vec![
+                format!("{}:/data", data.display()),
+                "/etc/hosts:/etc/hosts:ro".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_host_path_types() {
+        let paths = temp_paths();
+        let missing = paths.root.join("missing");
+        let mut pod = Pod::default();
+
+        pod.spec.volumes = vec![host_path(&missing, HostPathType::Directory)];
+        assert!(prepare(&paths, &pod, &PodSources::default()).is_err());
+
+        pod.spec.volumes = vec![host_path(&missing, HostPathType::DirectoryOrCreate)];
+        prepare(&paths, &pod, &PodSources::default()).unwrap();
+        assert!(missing.is_dir());
+
+        // now a directory, not a file
+        pod.spec.volumes = vec![host_path(&missing, HostPathType::File)];
+        assert!(prepare(&paths, &pod, &PodSources::default()).is_err());
+
+        let file = paths.root.join("config/app.toml");
+        pod.spec.volumes = vec![host_path(&file, HostPathType::FileOrCreate)];
+        prepare(&paths, &pod, &PodSources::default()).unwrap();
+        assert!(file.is_file());
+
+        let _ = std::fs::remove_dir_all(paths.root.parent().unwrap());
+    }
+
+    #[test]
+    fn test_render_configmap_and_secret() {
+        let paths = temp_paths();
+        let mut pod = Pod::default();
+        pod.spec.volumes = vec![
+            Volume {
+                name: "config".to_string(),
+                source: VolumeSource::ConfigMap(ObjectRef {
+                    name: "app".to_string(),
+                    optional: false,
+                }),
+            },
+            Volume {
+                name: "creds".to_string(),
+                source: VolumeSource::Secret(SecretVolume {
+                    secret_name: "db".to_string(),
+                    optional: false,
+                }),
+            },
+        ];
+        pod.spec.containers[0].volume_mounts = vec![mount("config", "/etc/app", None, false)];
+        let mut sources = PodSources::default();
+        assert!(prepare(&paths, &pod, &sources).is_err());
+
+        let data = |entries: &[(&str, &str)]| -> HashMap<String, String> {
+            entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        sources.configmaps.insert(
+            "app".to_string(),
+            data(&[("app.toml", "level = 'debug'"), ("extra", "1")]),
+        );
+        sources
+            .secrets
+            .insert("db".to_string(), data(&[("password", "hunter2")]));
+        prepare(&paths, &pod, &sources).unwrap();
+
+        let config = paths.pod_dir(&pod.metadata.id).join("volumes/config");
+        let creds = paths.memory_dir(&pod.metadata.id).join("creds");
+        assert_eq!(
+            std::fs::read_to_string(config.join("app.toml")).unwrap(),
+            "level = 'debug'"
+        );
+        assert_eq!(
+            std::fs::read_to_string(creds.join("password")).unwrap(),
+            "hunter2"
+        );
+        assert_eq!(
+            binds(&paths, &pod, &pod.spec.containers[0]),
+            vec![format!("{}:/etc/app:ro", config.display())]
+        );
+
+        // changed key rewritten, removed key gone
+        sources
+            .configmaps
+            .insert("app".to_string(), data(&[("app.toml", "level = 'info'")]));
+        render(&paths, &pod, &sources).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(config.join("app.toml")).unwrap(),
+            "level = 'info'"
+        );
+        assert!(!config.join("extra").exists());
+
+        cleanup(&paths, &pod.metadata.id);
+    }
+
+    #[test]
+    fn test_resolve_claims() {
+        let paths = temp_paths();
+        let mut pod = Pod::default();
+        pod.spec.volumes = vec![Volume {
+            name: "data".to_string(),
+            source: VolumeSource::PersistentVolumeClaim(ClaimVolume {
+                claim_name: "db".to_string(),
+                read_only: true,
+            }),
+        }];
+        pod.spec.containers[0].volume_mounts = vec![mount("data", "/var/lib/db", None, false)];
+        let mut sources = PodSources::default();
+        assert!(resolve_claims(&paths, &pod, &sources).is_err());
+
+        let pv = PersistentVolume {
+            metadata: Metadata {
+                name: "pvc-db".to_string(),
+                ..Default::default()
+            },
+            spec: PersistentVolumeSpec {
+                capacity: "1Gi".to_string(),
+                node_name: "node".to_string(),
+                path: None,
+                reclaim_policy: ReclaimPolicy::Delete,
+                claim_ref: Some("db".to_string()),
+            },
+            status: Default::default(),
+        };
+        sources.claims.insert("db".to_string(), pv);
+        let resolved = resolve_claims(&paths, &pod, &sources).unwrap();
+        prepare(&paths, &resolved, &sources).unwrap();
+
+        let dir = paths.persistent_root.join("pvc-db");
+        assert!(dir.is_dir());
+        assert_eq!(
+            binds(&paths, &resolved, &resolved.spec.containers[0]),
+            vec![format!("{}:/var/lib/db:ro", dir.display())]
+        );
+
+        // the data outlives the pod
+        cleanup(&paths, &pod.metadata.id);
+        assert!(dir.is_dir());
+        let _ = std::fs::remove_dir_all(paths.root.parent().unwrap());
+    }
+}
@@ -1,19 +1,21 @@
 //! # Assignment Watcher
 //!
-//! Handles node registration with the control plane and listens for pod assignment
+//! Handles node registration with the control plane, recovers containers left by a
+//! previous run and listens for pod assignment
 //! events via a streaming HTTP API. It updates local state and dispatches work to the worker
 //! subsystem via a channel.
 
+use crate::core::recovery;
 use crate::models::WorkRequest;
 use crate::state::State;
-use reqwest::Client;
-use shared::api::{EventType, NodeRegisterReq, PodEvent};
+use rcgen::KeyPair;
+use shared::api::{EventType, NodeRegisterReq, NodeRegisterResp, PodEvent};
 use shared::utils::watch_stream;
 use tokio::sync::mpsc::Sender;
 use tokio::time::{Duration, sleep};
 
 pub async fn run(state: State, tx: Sender<WorkRequest>) -> Result<(), String> {
-    register(state.clone()).await?;
+    recovery::run(state.clone(), &tx).await?;
     let url = format!(
         "{}/pods?watch=true&nodeName={}",
         state.config.server_url, state.config.name
@@ -27,12 +29,38 @@ pub async fn run(state: State, tx: Sender<WorkRequest>) -> Result<(), String> {
 }
 
 /// Registers the node with the control plane server.
-async fn register(state: State) -> Result<(), String> {
-    let client = Client::new();
+///
+/// A token saved by a previous registration is sent along so a restarted
+/// agent can reclaim its name, the token returned is saved for the next run.
+/// The request authenticates with the bootstrap token, or the saved one without it,
+/// afterwards every request to the apiserver carries the new node token.
+/// A node without its own serving certificate sends a fresh public key,
+/// the certificate the cluster CA issues for it is kept for the node API.
+pub async fn register(state: State) -> Result<(), String> {
     let name = &state.config.name;
+    let saved_token = std::fs::read_to_string(&state.config.token_file)
+        .ok()
+        .map(|t| t.trim().to_string());
+    shared::utils::set_bearer_token(
+        state
+            .config
+            .bootstrap_token
+            .clone()
+            .or_else(|| saved_token.clone()),
+    );
+    let client = shared::utils::client();
+    let serving_key = match state.config.tls_cert {
+        Some(_) => None,
+        None => KeyPair::generate()
+            .inspect_err(|err| tracing::warn!(error=%err, "Could not generate serving key"))
+            .ok(),
+    };
     let node_info = NodeRegisterReq {
         port: state.config.port,
         name: state.config.name.clone(),
+        taints: state.config.taints.clone(),
+        token: saved_token,
+        serving_key: serving_key.as_ref().map(KeyPair::public_key_pem),
     };
 
     for attempt in 1..=state.config.register_retries {
@@ -44,6 +72,16 @@ async fn register(state: State) -> Result<(), String> {
         match response {
             Ok(resp) if resp.status().is_success() => {
                 tracing::info!("Registered in the system: {}", name);
+                match resp.json::<NodeRegisterResp>().await {
+                    Ok(body) => {
+                        save_token(&state.config.token_file, &body.token);
+                        shared::utils::set_bearer_token(Some(body.token));
+                        if let (Some(cert), Some(key)) = (body.serving_cert, &serving_key) {
+                            state.set_serving_cert(cert, key.serialize_pem());
+                        }
+                    }
+                    Err(err) => tracing::warn!(error=%err, "Invalid register response"),
+                }
                 return Ok(());
             }
             Ok(resp) => tracing::warn!(
@@ -60,6 +98,17 @@ async fn register(state: State) -> Result<(), String> {
     Err("Failed to register".to_string())
 }
 
+/// Persists the registration token, failures only cost the ability to reclaim the name.
+fn save_token(path: &str, token: &str) {
+    let path = std::path::Path::new(path);
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Err(err) = std::fs::write(path, token) {
+        tracing::warn!(error=%err, path=%path.display(), "Could not save node token");
+    }
+}
+
 /// Processes a single pod event by updating local state and forwarding the event to the worker.
 fn handle_event(state: State, event: PodEvent, tx: &Sender<WorkRequest>) {
     let req = WorkRequest {
@@ -67,6 +116,13 @@ fn handle_event(state: State, event: PodEvent, tx: &Sender<WorkRequest>) {
         event: event.event_type.clone(),
     };
     match event.event_type {
+        // no new work once shutting down, deletions and terminations still stop containers
+        EventType::Modified
+            if state.is_shutting_down() && event.pod.metadata.deletion_timestamp.is_none() =>
+        {
+            tracing::debug!(pod=%event.pod.metadata.name, "Shutting down, ignoring pod");
+            return;
+        }
         EventType::Modified => state.put_pod(&event.pod),
         EventType::Deleted => state.delete_pod(&event.pod.metadata.id),
         _ => {
@@ -89,6 +145,12 @@ mod tests {
     //!     send message and delete pod
     //! - test_added_event
     //!     not supported
+    //! - test_modified_event_shutting_down
+    //!   ignored, no message sent
+    //! - test_terminating_event_shutting_down
+    //!   still forwarded so the pod is stopped
+    //! - test_register_serving_cert
+    //!   certificate returned by the apiserver kept with the generated key
 
     use super::*;
     use crate::{docker::test::TestDocker, models::Config, state::NodeState};
@@ -97,6 +159,8 @@ mod tests {
         models::pod::Pod,
     };
     use tokio::sync::mpsc;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[tokio::test]
     async fn test_modified_event() {
@@ -157,4 +221,76 @@ mod tests {
 
         assert!(rx.try_recv().is_err(), "Added events are not handled");
     }
+
+    #[tokio::test]
+    async fn test_modified_event_shutting_down() {
+        let docker = Box::new(TestDocker::new());
+        let state = NodeState::new_with(Some(Config::default()), Some(docker));
+        state.begin_shutdown();
+        let pod = Pod::default();
+
+        let (tx, mut rx) = mpsc::channel(1);
+        let event = PodEvent {
+            pod: pod.clone(),
+            event_type: EventType::Modified,
+        };
+
+        handle_event(state.clone(), event, &tx);
+
+        assert!(rx.try_recv().is_err());
+        assert!(state.get_pod(&pod.metadata.id).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_terminating_event_shutting_down() {
+        let docker = Box::new(TestDocker::new());
+        let state = NodeState::new_with(Some(Config::default()), Some(docker));
+        state.begin_shutdown();
+        let mut pod = Pod::default();
+        pod.metadata.deletion_timestamp = Some(chrono::Utc::now());
+
+        let (tx, mut rx) = mpsc::channel(1);
+        let event = PodEvent {
+            pod: pod.clone(),
+            event_type: EventType::Modified,
+        };
+
+        handle_event(state.clone(), event, &tx);
+
+        let req = rx.try_recv().expect("Should receive a work request");
+        assert_eq!(req.id, pod.metadata.id);
+    }
+
+    #[tokio::test]
+    async fn test_register_serving_cert() {
+        let server = MockServer::start().await;
+        let resp = NodeRegisterResp {
+            id: uuid::Uuid::new_v4(),
+            token: "token".to_string(),
+            serving_cert: Some("cert".to_string()),
+        };
+        Mock::given(method("POST"))
+            .and(path("/nodes"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(&resp))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = Config {
+            server_url: server.uri(),
+            token_file: std::env::temp_dir()
+                .join(format!("cr8s-token-{}", uuid::Uuid::new_v4()))
+                .display()
+                .to_string(),
+            ..Default::default()
+        };
+        let state = NodeState::new_with(Some(config), Some(Box::new(TestDocker::new())));
+
+        register(state.clone()).await.unwrap();
+
+        let (cert, key) = state.serving_cert().unwrap();
+        assert_eq!(cert, "cert");
+        assert!(KeyPair::from_pem(key).is_ok());
+        let _ = std::fs::remove_file(&state.config.token_file);
+    }
 }
@@ -3,12 +3,29 @@
 //! Handles `WorkRequest`s from the controller.
 //! Each work item triggers reconciliation logic for a pod
 
-use crate::{models::WorkRequest, state::State};
+use crate::{
+    core::{sources::PodSources, sync::report_status, volumes},
+    models::{ContainerRuntime, PodEnv, PodRuntime, WorkRequest, spec_hash},
+    state::State,
+};
 use bollard::secret::ContainerStateStatusEnum;
-use shared::api::EventType;
+use futures_util::future::join_all;
+use shared::{
+    api::EventType,
+    models::pod::{
+        ContainerSpec, DEFAULT_GRACE_PERIOD_SECONDS, LifecycleHandler, Pod, PodPhase, PodStatus,
+        RestartPolicy,
+    },
+};
+use std::collections::HashMap;
 use tokio::sync::mpsc::Receiver;
+use tokio::time::{Duration, Instant, sleep, timeout};
 use uuid::Uuid;
 
+/// Wait before rerunning a failed init container, doubled on every failure
+const INIT_BACKOFF: Duration = Duration::from_secs(1);
+const INIT_BACKOFF_MAX: Duration = Duration::from_secs(30);
+
 /// Starts the reconciliation worker loop.
 ///
 /// Listens for `WorkRequest`s on the channel and processes them concurrently.
@@ -29,7 +46,9 @@ pub async fn run(state: State, mut rx: Receiver<WorkRequest>) -> Result<(), Stri
 
 /// Handles reconciliation for a given pod ID by starting the pod if needed.
 ///
-/// Skips reconciliation if the runtime already exists.
+/// A pod that already has a runtime is updated in place instead, one marked
+/// for deletion is terminated. The config maps, secrets and claims it references
+/// are fetched first, containers don't start while a required one is missing.
 /// If Docker fails to start the pod, logs the error and exits gracefully.
 pub async fn reconciliate(state: State, id: Uuid) {
     let Some(mut pod) = state.get_pod(&id) else {
@@ -37,37 +56,106 @@ pub async fn reconciliate(state: State, id: Uuid) {
         return;
     };
 
-    if pod.status.observed_generation == pod.metadata.generation {
+    if pod.metadata.deletion_timestamp.is_some() {
+        terminate(state, pod).await;
         return;
     }
 
-    // Check runtime state
-    if let Some(_) = state.get_pod_runtime(&pod.metadata.id) {
-        tracing::warn!("Pod already stored in runtime state, not implemented");
+    if pod.status.observed_generation == pod.metadata.generation {
         return;
     }
 
-    let runtime = match state.docker_mgr.start_pod(pod.clone()).await {
-        Ok(runtime) => runtime,
+    let sources = match PodSources::fetch(&state, &pod).await {
+        Ok(sources) => sources,
+        Err(err) => {
+            tracing::error!(error=%err, pod=%pod.metadata.name, "Failed to fetch config sources");
+            return;
+        }
+    };
+    let env = match sources.pod_env(&pod) {
+        Ok(env) => env,
+        Err(err) => {
+            tracing::error!(error=%err, pod=%pod.metadata.name, "Failed to resolve environment");
+            return;
+        }
+    };
+    // containers mount the host directories of the claimed volumes
+    let resolved = match volumes::resolve_claims(&state.config.volume_paths(), &pod, &sources) {
+        Ok(resolved) => resolved,
         Err(err) => {
-            tracing::error!(error=%err, "Failed to start pod");
+            tracing::error!(error=%err, pod=%pod.metadata.name, "Failed to resolve claims");
             return;
         }
     };
 
-    runtime.containers.values().for_each(|c| match c.status {
-        ContainerStateStatusEnum::RUNNING
-        | ContainerStateStatusEnum::CREATED
-        | ContainerStateStatusEnum::EXITED => {}
-        _ => {
-            tracing::warn!(name=%c.name, "Container didn't start");
+    // Check runtime state
+    if let Some(runtime) = state.get_pod_runtime(&pod.metadata.id) {
+        if let Err(err) = update(&state, &resolved, runtime, &env).await {
+            tracing::error!(error=%err, pod=%pod.metadata.name, "Failed to update pod");
+            return;
+        }
+    } else {
+        if let Err(err) = volumes::prepare(&state.config.volume_paths(), &resolved, &sources) {
+            tracing::error!(error=%err, pod=%pod.metadata.name, "Failed to prepare volumes");
+            return;
         }
-    });
+        // owns the network namespace every other container joins
+        let pause = match state
+            .docker_mgr
+            .start_pause(&pod, &state.config.pause_image)
+            .await
+        {
+            Ok(pause) => pause,
+            Err(err) => {
+                tracing::error!(error=%err, "Failed to start pause container");
+                return;
+            }
+        };
+        let sidecars = match run_init(&state, &resolved, &env).await {
+            Ok(sidecars) => sidecars,
+            Err(err) => {
+                tracing::error!(error=%err, pod=%pod.metadata.name, "Init failed");
+                stop_containers(&state, &pod, std::iter::once(&pause)).await;
+                if pod.spec.restart_policy == RestartPolicy::Never {
+                    // failed for good, don't try again
+                    pod.status.observed_generation = pod.metadata.generation;
+                    state.put_pod(&pod);
+                }
+                return;
+            }
+        };
+        let mut runtime = match state.docker_mgr.start_pod(resolved, &env).await {
+            Ok(runtime) => runtime,
+            Err(err) => {
+                tracing::error!(error=%err, "Failed to start pod");
+                stop_containers(&state, &pod, sidecars.values()).await;
+                stop_containers(&state, &pod, std::iter::once(&pause)).await;
+                return;
+            }
+        };
+        runtime.containers.extend(sidecars);
+        runtime.pod_ip = state
+            .docker_mgr
+            .get_container_ip(&pause.id)
+            .await
+            .inspect_err(|err| tracing::warn!(error=%err, "Could not get pod IP"))
+            .ok();
+        runtime.pause = Some(pause);
 
-    // store runtime, should be new
-    if let Err(msg) = state.add_pod_runtime(runtime) {
-        tracing::error!(error=%msg, "Could not add pod runtime to state");
-        return;
+        runtime.containers.values().for_each(|c| match c.status {
+            ContainerStateStatusEnum::RUNNING
+            | ContainerStateStatusEnum::CREATED
+            | ContainerStateStatusEnum::EXITED => {}
+            _ => {
+                tracing::warn!(name=%c.name, "Container didn't start");
+            }
+        });
+
+        // store runtime, should be new
+        if let Err(msg) = state.add_pod_runtime(runtime) {
+            tracing::error!(error=%msg, "Could not add pod runtime to state");
+            return;
+        }
     }
 
     // Update observed generation and store new status
@@ -75,24 +163,343 @@ pub async fn reconciliate(state: State, id: Uuid) {
     state.put_pod(&pod);
 }
 
+/// Runs the init containers in order, returning the sidecars left running.
+///
+/// Each regular init container must exit successfully before the next one
+/// starts. A failed one fails the pod under the `Never` restart policy and is
+/// retried with backoff otherwise, for as long as the pod stays unchanged.
+async fn run_init(
+    state: &State,
+    pod: &Pod,
+    env: &PodEnv,
+) -> Result<HashMap<String, ContainerRuntime>, String> {
+    let mut sidecars = HashMap::new();
+    let mut statuses = Vec::new();
+
+    for spec in &pod.spec.init_containers {
+        let container_env = container_env(env, spec);
+        let res = if spec.is_sidecar() {
+            state
+                .docker_mgr
+                .start_container(pod, spec, container_env)
+                .await
+                .map(|c| {
+                    sidecars.insert(spec.name.clone(), c);
+                })
+                .map_err(|e| e.to_string())
+        } else {
+            run_to_completion(state, pod, spec, container_env).await
+        };
+
+        if let Err(err) = res {
+            stop_containers(state, pod, sidecars.values()).await;
+            if pod.spec.restart_policy == RestartPolicy::Never {
+                statuses.push((spec.name.clone(), "failed".to_string()));
+                let status = PodStatus {
+                    phase: PodPhase::Failed,
+                    init_container_status: statuses,
+                    observed_generation: pod.metadata.generation,
+                    ..Default::default()
+                };
+                report_status(&shared::utils::client(), state, &pod.metadata.name, status).await;
+            }
+            return Err(err);
+        }
+        statuses.push((spec.name.clone(), "exited".to_string()));
+    }
+    Ok(sidecars)
+}
+
+/// Runs an init container until it exits successfully.
+async fn run_to_completion(
+    state: &State,
+    pod: &Pod,
+    spec: &ContainerSpec,
+    env: &[String],
+) -> Result<(), String> {
+    let mut backoff = INIT_BACKOFF;
+    loop {
+        let container = state
+            .docker_mgr
+            .start_container(pod, spec, env)
+            .await
+            .map_err(|e| e.to_string())?;
+        let exit = state.docker_mgr.wait_container(&container.id).await;
+        if let Err(err) = state.docker_mgr.stop_pod(&[container.id], 0).await {
+            tracing::warn!(error=%err, container=%spec.name, "Failed to remove init container");
+        }
+
+        let err = match exit {
+            Ok(0) => {
+                tracing::info!(pod=%pod.metadata.name, container=%spec.name, "Init container completed");
+                return Ok(());
+            }
+            Ok(code) => format!("Init container {} exited with code {}", spec.name, code),
+            Err(err) => err.to_string(),
+        };
+        if pod.spec.restart_policy == RestartPolicy::Never {
+            return Err(err);
+        }
+
+        tracing::warn!(error=%err, retry_in=?backoff, "Init container failed");
+        sleep(backoff).await;
+        backoff = (backoff * 2).min(INIT_BACKOFF_MAX);
+
+        // give up once the pod was deleted or changed, newer work takes over
+        let current = state.get_pod(&pod.metadata.id);
+        if current.is_none_or(|p| {
+            p.metadata.generation != pod.metadata.generation
+                || p.metadata.deletion_timestamp.is_some()
+        }) {
+            return Err(format!("Pod changed while running {}", spec.name));
+        }
+    }
+}
+
+fn container_env<'a>(env: &'a PodEnv, spec: &ContainerSpec) -> &'a [String] {
+    env.get(&spec.name).map_or(&[], |e| e)
+}
+
+async fn stop_containers(
+    state: &State,
+    pod: &Pod,
+    containers: impl Iterator<Item = &ContainerRuntime>,
+) {
+    let ids: Vec<String> = containers.map(|c| c.id.clone()).collect();
+    if ids.is_empty() {
+        return;
+    }
+    if let Err(err) = state
+        .docker_mgr
+        .stop_pod(&ids, pod.spec.termination_grace_period_seconds)
+        .await
+    {
+        tracing::error!(error=%err, "Failed to stop containers");
+    }
+}
+
+/// Brings an existing runtime in line with the pod spec.
+///
+/// Only containers whose spec changed, or that are missing, are recreated.
+/// Containers no longer in the spec are removed. The runtime is stored even
+/// on failure so it matches what is left on the host.
+async fn update(
+    state: &State,
+    pod: &Pod,
+    mut runtime: PodRuntime,
+    env: &PodEnv,
+) -> Result<(), String> {
+    let res = update_containers(state, pod, &mut runtime, env).await;
+    state.put_pod_runtime(runtime);
+    res
+}
+
+async fn update_containers(
+    state: &State,
+    pod: &Pod,
+    runtime: &mut PodRuntime,
+    env: &PodEnv,
+) -> Result<(), String> {
+    let stale: Vec<String> = runtime
+        .containers
+        .keys()
+        .filter(|name| !pod.spec.running_containers().any(|c| &c.name == *name))
+        .cloned()
+        .collect();
+    for name in stale {
+        if let Some(old) = runtime.containers.remove(&name) {
+            state
+                .docker_mgr
+                .stop_pod(&[old.id], pod.spec.termination_grace_period_seconds)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    for spec in pod.spec.running_containers() {
+        let hash = spec_hash(spec);
+        if let Some(current) = runtime.containers.get(&spec.name) {
+            if current.spec_hash == hash {
+                continue;
+            }
+            state
+                .docker_mgr
+                .stop_pod(
+                    std::slice::from_ref(&current.id),
+                    pod.spec.termination_grace_period_seconds,
+                )
+                .await
+                .map_err(|e| e.to_string())?;
+            runtime.containers.remove(&spec.name);
+        }
+        let container = state
+            .docker_mgr
+            .start_container(pod, spec, container_env(env, spec))
+            .await
+            .map_err(|e| e.to_string())?;
+        tracing::info!(pod=%pod.metadata.name, container=%spec.name, "Recreated container");
+        runtime.containers.insert(spec.name.clone(), container);
+    }
+    Ok(())
+}
+
+/// Gracefully stops a pod marked for deletion and confirms its removal.
+///
+/// Every container runs its preStop hook and is then sent SIGTERM, whatever
+/// is still running once the grace period is over gets killed. Only then is
+/// the pod deleted for good on the control plane.
+async fn terminate(state: State, pod: Pod) {
+    let id = pod.metadata.id;
+    if !state.mark_terminating(&id) {
+        return;
+    }
+
+    if let Some(runtime) = state.get_pod_runtime(&id) {
+        let deadline =
+            Instant::now() + Duration::from_secs(pod.spec.termination_grace_period_seconds.into());
+        // sidecars outlive the main containers
+        for sidecars in [false, true] {
+            let stops = runtime
+                .containers
+                .values()
+                .filter(|c| c.sidecar == sidecars)
+                .map(|container| {
+                    let spec = pod
+                        .spec
+                        .running_containers()
+                        .find(|c| c.name == container.spec_name);
+                    stop_container(&state, spec, container, runtime.pod_ip.as_deref(), deadline)
+                });
+            join_all(stops).await;
+        }
+        if let Some(pause) = &runtime.pause
+            && let Err(err) = state
+                .docker_mgr
+                .stop_pod(std::slice::from_ref(&pause.id), 0)
+                .await
+        {
+            tracing::error!(error=%err, "Failed to stop pause container");
+        }
+        state.delete_pod_runtime(&id);
+        volumes::cleanup(&state.config.volume_paths(), &id);
+        tracing::info!(pod=%pod.metadata.name, "Terminated pod");
+    }
+
+    // the apiserver deletes the pod right away and the watcher forgets it
+    let res = shared::utils::client()
+        .delete(format!(
+            "{}/pods/{}?gracePeriodSeconds=0",
+            state.config.server_url, pod.metadata.name
+        ))
+        .send()
+        .await;
+    match res {
+        Ok(resp) if resp.status().is_success() || resp.status() == 404 => {}
+        Ok(resp) => {
+            tracing::error!(status=%resp.status(), pod=%pod.metadata.name, "Failed to confirm deletion");
+            state.unmark_terminating(&id);
+        }
+        Err(err) => {
+            tracing::error!(error=%err, pod=%pod.metadata.name, "Failed to confirm deletion");
+            state.unmark_terminating(&id);
+        }
+    }
+}
+
+/// Runs the container preStop hook then stops it with what is left of the grace period.
+async fn stop_container(
+    state: &State,
+    spec: Option<&ContainerSpec>,
+    container: &ContainerRuntime,
+    pod_ip: Option<&str>,
+    deadline: Instant,
+) {
+    let hook = spec
+        .and_then(|s| s.lifecycle.as_ref())
+        .and_then(|l| l.pre_stop.as_ref());
+    if let Some(handler) = hook {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match timeout(remaining, run_hook(state, &container.id, pod_ip, handler)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                tracing::warn!(error=%err, container=%container.name, "preStop hook failed")
+            }
+            Err(_) => tracing::warn!(container=%container.name, "preStop hook timed out"),
+        }
+    }
+
+    let grace = deadline.saturating_duration_since(Instant::now()).as_secs() as u32;
+    if let Err(err) = state
+        .docker_mgr
+        .stop_pod(std::slice::from_ref(&container.id), grace)
+        .await
+    {
+        tracing::error!(error=%err, container=%container.name, "Failed to stop container");
+    }
+}
+
+async fn run_hook(
+    state: &State,
+    container_id: &str,
+    pod_ip: Option<&str>,
+    handler: &LifecycleHandler,
+) -> Result<(), String> {
+    match handler {
+        LifecycleHandler::Exec { command } => state
+            .docker_mgr
+            .exec(container_id, command)
+            .await
+            .map_err(|e| e.to_string()),
+        LifecycleHandler::HttpGet { path, port } => {
+            let ip = match pod_ip {
+                Some(ip) => ip.to_string(),
+                None => state
+                    .docker_mgr
+                    .get_container_ip(container_id)
+                    .await
+                    .map_err(|e| e.to_string())?,
+            };
+            let resp = shared::utils::client()
+                .get(format!("http://{}:{}{}", ip, port, path))
+                .send()
+                .await
+                .map_err(|e| e.to_string())?;
+            if !resp.status().is_success() {
+                return Err(format!("HTTP {}", resp.status()));
+            }
+            Ok(())
+        }
+    }
+}
+
 /// Stops and removes a running pod.
 ///
-/// Deletes the runtime entry from local state, then stops its containers via docker.
-async fn delete(state: State, id: Uuid) {
+/// Deletes the runtime entry from local state, then stops its containers via docker
+/// and removes the pod volumes.
+/// Pods already terminated have no runtime left and are just forgotten.
+pub async fn delete(state: State, id: Uuid) {
+    state.unmark_terminating(&id);
     let Some(pod_runtime) = state.get_pod_runtime(&id) else {
-        tracing::error!("Pod runtime not found");
+        tracing::debug!("Pod runtime not found");
+        state.delete_pod(&id);
         return;
     };
-    let container_ids: Vec<String> = pod_runtime
-        .containers
-        .iter()
-        .map(|(_, c)| c.id.clone())
-        .collect();
+    let (container_ids, sidecar_ids) = pod_runtime.stop_order();
+    let grace = state
+        .get_pod(&id)
+        .map(|p| p.spec.termination_grace_period_seconds)
+        .unwrap_or(DEFAULT_GRACE_PERIOD_SECONDS);
     state.delete_pod_runtime(&id);
-    match state.docker_mgr.stop_pod(&container_ids).await {
+    match state.docker_mgr.stop_pod(&container_ids, grace).await {
         Ok(()) => {}
         Err(err) => tracing::error!(error=%err, "Failed to delete pod"),
     };
+    if !sidecar_ids.is_empty()
+        && let Err(err) = state.docker_mgr.stop_pod(&sidecar_ids, grace).await
+    {
+        tracing::error!(error=%err, "Failed to stop sidecars");
+    }
+    volumes::cleanup(&state.config.volume_paths(), &id);
 
     state.delete_pod(&id);
     tracing::info!(pod_name=%pod_runtime.name, "Deleted pod");
@@ -102,21 +509,100 @@ async fn delete(state: State, id: Uuid) {
 mod tests {
 
     //! - test_reconciliate_existing_runtime
-    //!     not supported, should skip
+    //!   updated in place, pod not restarted
+    //! - test_reconciliate_changed_container
+    //!   only the changed container is recreated
     //! - test_reconciliate_non_existent_pod
-    //!     should skip
+    //!   should skip
     //! - test_reconciliate_new_runtime
-    //!     start pod and insert runtime
-    //!     
+    //!   start pod and insert runtime
+    //! - test_reconciliate_env_from_sources
+    //!   env resolved from the config map and secret served by the apiserver
+    //!
     //! - test_delete_runtime_not_found
-    //!     should skip
+    //!   should skip
     //! - test_delete
-    //!     stop pod and delete runtime
+    //!   stop pod and delete runtime
+    //! - test_delete_stops_sidecars_last
+    //!   main containers stopped before sidecars
+    //! - test_reconciliate_init_containers
+    //!   init run to completion, sidecar kept alongside the containers
+    //! - test_reconciliate_init_retry
+    //!   failed init container rerun before the containers start
+    //! - test_reconciliate_init_failure_never
+    //!   pod reported failed, containers never started
+    //! - test_terminate_exec_hook
+    //!   preStop exec runs before the stop, deletion confirmed
+    //! - test_terminate_http_hook
+    //!   preStop httpGet sent to the container address
 
     use super::*;
-    use crate::{docker::test::TestDocker, models::PodRuntime, state::NodeState};
-    use shared::models::pod::Pod;
-    use std::collections::HashMap;
+    use crate::{
+        docker::{DockerClient, test::TestDocker},
+        state::NodeState,
+    };
+    use chrono::Utc;
+    use shared::models::{metadata::Metadata, pod::Lifecycle};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn container(name: &str) -> ContainerSpec {
+        ContainerSpec {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Pod with a `migrate` init container and a `proxy` sidecar in front of `app`.
+    fn init_pod(restart_policy: RestartPolicy) -> Pod {
+        let mut pod = Pod::default();
+        pod.metadata.name = "p1".to_string();
+        pod.metadata.generation += 1;
+        pod.spec.restart_policy = restart_policy;
+        pod.spec.init_containers = vec![
+            container("migrate"),
+            ContainerSpec {
+                restart_policy: Some(RestartPolicy::Always),
+                ..container("proxy")
+            },
+        ];
+        pod.spec.containers = vec![container("app")];
+        pod
+    }
+
+    /// Running pod marked for deletion with a preStop hook, plus the server confirming it.
+    async fn terminating_pod(hook: LifecycleHandler) -> (Pod, State, Box<TestDocker>, MockServer) {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/pods/p1"))
+            .and(query_param("gracePeriodSeconds", "0"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut pod = Pod::default();
+        pod.metadata.name = "p1".to_string();
+        pod.spec.containers = vec![ContainerSpec {
+            name: "a".to_string(),
+            lifecycle: Some(Lifecycle {
+                pre_stop: Some(hook),
+            }),
+            ..Default::default()
+        }];
+        let docker = Box::new(TestDocker::new());
+        let config = crate::models::Config {
+            server_url: server.uri(),
+            ..Default::default()
+        };
+        let state = NodeState::new_with(Some(config), Some(docker.clone()));
+        let runtime = docker.start_pod(pod.clone(), &PodEnv::new()).await.unwrap();
+        state.add_pod_runtime(runtime).unwrap();
+
+        pod.metadata.deletion_timestamp = Some(Utc::now());
+        state.put_pod(&pod);
+        (pod, state, docker, server)
+    }
 
     #[tokio::test]
     async fn test_reconciliate_non_existent_pod() {
@@ -142,13 +628,50 @@ mod tests {
             id: pod.metadata.id,
             name: "".to_string(),
             containers: HashMap::new(),
+            pause: None,
+            pod_ip: None,
         };
         state.add_pod_runtime(runtime).unwrap();
         reconciliate(state.clone(), pod.metadata.id).await;
-        // should not call docker api
+        // should not restart the pod
         assert_eq!(docker.start_pod_calls.lock().await.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_reconciliate_changed_container() {
+        let mut pod = Pod::default();
+        pod.spec.containers = ["a", "b"]
+            .iter()
+            .map(|name| ContainerSpec {
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let docker = Box::new(TestDocker::new());
+        let state =
+            NodeState::new_with(Some(crate::models::Config::default()), Some(docker.clone()));
+        let runtime = docker.start_pod(pod.clone(), &PodEnv::new()).await.unwrap();
+        let old_a = runtime.containers["a"].id.clone();
+        let old_b = runtime.containers["b"].id.clone();
+        state.add_pod_runtime(runtime).unwrap();
+
+        pod.spec.containers[1].image = "nginx:latest".to_string();
+        pod.metadata.generation += 1;
+        state.put_pod(&pod);
+        reconciliate(state.clone(), pod.metadata.id).await;
+
+        assert_eq!(
+            *docker.stop_pod_calls.lock().await,
+            vec![vec![old_b.clone()]]
+        );
+        assert_eq!(*docker.start_container_calls.lock().await, vec!["b"]);
+        let runtime = state.get_pod_runtime(&pod.metadata.id).unwrap();
+        assert_eq!(runtime.containers["a"].id, old_a);
+        assert_ne!(runtime.containers["b"].id, old_b);
+        let local = state.get_pod(&pod.metadata.id).unwrap();
+        assert_eq!(local.status.observed_generation, pod.metadata.generation);
+    }
+
     #[tokio::test]
     async fn test_reconciliate_new_runtime() {
         let mut pod = Pod::default();
@@ -161,6 +684,55 @@ mod tests {
 
         reconciliate(state.clone(), pod.metadata.id).await;
         assert_eq!(docker.start_pod_calls.lock().await.len(), 1);
+        assert_eq!(docker.start_pause_calls.lock().await.len(), 1);
+        let runtime = state.get_pod_runtime(&pod.metadata.id).unwrap();
+        assert!(runtime.pause.is_some());
+        assert_eq!(runtime.pod_ip.as_deref(), Some("127.0.0.1"));
+    }
+
+    #[tokio::test]
+    async fn test_reconciliate_env_from_sources() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/configmaps/app"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "metadata": Metadata::default(),
+                "spec": {"data": {"LOG_LEVEL": "debug"}}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/secrets/db"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "metadata": Metadata::default(),
+                "spec": {"data": {"password": "hunter2"}}
+            })))
+            .mount(&server)
+            .await;
+
+        let mut pod = Pod::default();
+        pod.metadata.generation += 1;
+        pod.spec.containers[0] = serde_json::from_value(serde_json::json!({
+            "name": "app",
+            "image": "busybox:latest",
+            "ports": null,
+            "envFrom": [{"configMapRef": {"name": "app"}}],
+            "env": [{"name": "DB_PASSWORD", "valueFrom": {"secretKeyRef": {"name": "db", "key": "password"}}}]
+        }))
+        .unwrap();
+        let docker = Box::new(TestDocker::new());
+        let config = crate::models::Config {
+            server_url: server.uri(),
+            ..Default::default()
+        };
+        let state = NodeState::new_with(Some(config), Some(docker.clone()));
+        state.put_pod(&pod);
+
+        reconciliate(state.clone(), pod.metadata.id).await;
+        assert_eq!(
+            docker.envs.get("app").unwrap().clone(),
+            vec!["LOG_LEVEL=debug", "DB_PASSWORD=hunter2"]
+        );
     }
 
     #[tokio::test]
@@ -182,10 +754,142 @@ mod tests {
             id: Uuid::new_v4(),
             name: "".to_string(),
             containers: HashMap::new(),
+            pause: None,
+            pod_ip: None,
         };
         state.add_pod_runtime(runtime.clone()).unwrap();
         delete(state.clone(), runtime.id).await;
         assert_eq!(docker.stop_pod_calls.lock().await.len(), 1);
         assert!(state.get_pod_runtime(&runtime.id).is_none());
     }
+
+    #[tokio::test]
+    async fn test_terminate_exec_hook() {
+        let command = vec!["sh".to_string(), "-c".to_string(), "sleep 1".to_string()];
+        let (pod, state, docker, _server) = terminating_pod(LifecycleHandler::Exec {
+            command: command.clone(),
+        })
+        .await;
+
+        reconciliate(state.clone(), pod.metadata.id).await;
+
+        let execs = docker.exec_calls.lock().await;
+        assert_eq!(execs.len(), 1);
+        assert_eq!(execs[0].1, command);
+        assert_eq!(
+            *docker.stop_pod_calls.lock().await,
+            vec![vec![execs[0].0.clone()]]
+        );
+        assert!(docker.stop_grace_periods.lock().await[0] <= DEFAULT_GRACE_PERIOD_SECONDS);
+        assert!(state.get_pod_runtime(&pod.metadata.id).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_terminate_http_hook() {
+        let hook_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/shutdown"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&hook_server)
+            .await;
+        let (pod, state, docker, _server) = terminating_pod(LifecycleHandler::HttpGet {
+            path: "/shutdown".to_string(),
+            port: hook_server.address().port(),
+        })
+        .await;
+
+        reconciliate(state.clone(), pod.metadata.id).await;
+
+        assert_eq!(docker.stop_pod_calls.lock().await.len(), 1);
+        assert!(docker.exec_calls.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_stops_sidecars_last() {
+        let docker = Box::new(TestDocker::new());
+        let state =
+            NodeState::new_with(Some(crate::models::Config::default()), Some(docker.clone()));
+        let pod = init_pod(RestartPolicy::Always);
+        let mut runtime = docker.start_pod(pod.clone(), &PodEnv::new()).await.unwrap();
+        let proxy = docker
+            .start_container(&pod, &pod.spec.init_containers[1], &[])
+            .await
+            .unwrap();
+        runtime
+            .containers
+            .insert("proxy".to_string(), proxy.clone());
+        state.add_pod_runtime(runtime.clone()).unwrap();
+
+        delete(state.clone(), runtime.id).await;
+
+        let calls = docker.stop_pod_calls.lock().await;
+        assert_eq!(
+            *calls,
+            vec![vec![runtime.containers["app"].id.clone()], vec![proxy.id]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reconciliate_init_containers() {
+        let docker = Box::new(TestDocker::new());
+        let state =
+            NodeState::new_with(Some(crate::models::Config::default()), Some(docker.clone()));
+        let pod = init_pod(RestartPolicy::Always);
+        state.put_pod(&pod);
+
+        reconciliate(state.clone(), pod.metadata.id).await;
+
+        assert_eq!(*docker.wait_calls.lock().await, vec!["migrate"]);
+        assert_eq!(
+            *docker.start_container_calls.lock().await,
+            vec!["migrate", "proxy"]
+        );
+        let runtime = state.get_pod_runtime(&pod.metadata.id).unwrap();
+        assert!(!runtime.containers.contains_key("migrate"));
+        assert!(runtime.containers["proxy"].sidecar);
+        assert!(!runtime.containers["app"].sidecar);
+    }
+
+    #[tokio::test]
+    async fn test_reconciliate_init_retry() {
+        let docker = Box::new(TestDocker::new());
+        docker.exit_codes.insert("migrate".to_string(), vec![1]);
+        let state =
+            NodeState::new_with(Some(crate::models::Config::default()), Some(docker.clone()));
+        let pod = init_pod(RestartPolicy::OnFailure);
+        state.put_pod(&pod);
+
+        reconciliate(state.clone(), pod.metadata.id).await;
+
+        assert_eq!(*docker.wait_calls.lock().await, vec!["migrate", "migrate"]);
+        assert!(state.get_pod_runtime(&pod.metadata.id).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_reconciliate_init_failure_never() {
+        let server = MockServer::start().await;
+        Mock::given(method("PATCH"))
+            .and(path("/pods/p1"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let docker = Box::new(TestDocker::new());
+        docker.exit_codes.insert("migrate".to_string(), vec![1]);
+        let config = crate::models::Config {
+            server_url: server.uri(),
+            ..Default::default()
+        };
+        let state = NodeState::new_with(Some(config), Some(docker.clone()));
+        let pod = init_pod(RestartPolicy::Never);
+        state.put_pod(&pod);
+
+        reconciliate(state.clone(), pod.metadata.id).await;
+
+        assert!(state.get_pod_runtime(&pod.metadata.id).is_none());
+        assert!(docker.start_pod_calls.lock().await.is_empty());
+        let local = state.get_pod(&pod.metadata.id).unwrap();
+        assert_eq!(local.status.observed_generation, pod.metadata.generation);
+    }
 }
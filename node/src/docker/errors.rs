@@ -12,6 +12,7 @@ pub enum DockerError {
     ContainerStopError(String),
     LogsError(String),
     StreamLogsError(String),
+    ExecError(String),
 }
 
 impl fmt::Display for DockerError {
@@ -33,6 +34,7 @@ impl fmt::Display for DockerError {
             }
             DockerError::LogsError(msg) => write!(f, "Logs error: {}", msg),
             DockerError::StreamLogsError(msg) => write!(f, "Stream logs error: {}", msg),
+            DockerError::ExecError(msg) => write!(f, "Exec error: {}", msg),
         }
     }
 }
@@ -5,25 +5,33 @@
 //! create, start, stop containers, and retrieve logs.
 
 use crate::{
+    core::volumes,
     docker::errors::DockerError,
-    models::{ContainerRuntime, PodRuntime},
+    models::{
+        CONTAINER_NAME_LABEL, ContainerRuntime, DnsSettings, ManagedContainer, PAUSE_LABEL,
+        POD_NAME_LABEL, POD_UID_LABEL, PodEnv, PodRuntime, SIDECAR_LABEL, SPEC_HASH_LABEL,
+        VolumePaths, spec_hash,
+    },
 };
 use async_trait::async_trait;
 use bollard::{
     Docker,
     container::LogOutput,
+    exec::{StartExecOptions, StartExecResults},
     query_parameters::{
-        CreateContainerOptions, CreateImageOptions, InspectContainerOptions, LogsOptions,
-        RemoveContainerOptions, StartContainerOptions, StopContainerOptions,
+        CreateContainerOptions, CreateImageOptions, InspectContainerOptions, ListContainersOptions,
+        LogsOptions, RemoveContainerOptions, StartContainerOptions, StopContainerOptions,
+        WaitContainerOptions,
     },
-    secret::{ContainerCreateBody, ContainerStateStatusEnum},
+    secret::{ContainerCreateBody, ContainerStateStatusEnum, ExecConfig, HostConfig, PortBinding},
 };
 use bytes::Bytes;
 use dashmap::DashSet;
 use futures_util::StreamExt;
 use futures_util::stream::{BoxStream, TryStreamExt};
-use shared::models::pod::Pod;
+use shared::models::pod::{ContainerSpec, ImagePullPolicy, Pod, Port};
 use std::collections::HashMap;
+use uuid::Uuid;
 
 /// A trait for interacting with container operations needed by the scheduler runtime.
 #[async_trait]
@@ -34,11 +42,44 @@ pub trait DockerClient: Send + Sync {
         id: &String,
     ) -> Result<ContainerStateStatusEnum, DockerError>;
 
+    /// Start the pause container owning the pod network namespace.
+    async fn start_pause(&self, pod: &Pod, image: &str) -> Result<ContainerRuntime, DockerError>;
+
     /// Start a pod by pulling its images and launching all specified containers.
-    async fn start_pod(&self, pod: Pod) -> Result<PodRuntime, DockerError>;
+    ///
+    /// The pause container must be running already, containers join its network.
+    async fn start_pod(&self, pod: Pod, env: &PodEnv) -> Result<PodRuntime, DockerError>;
+
+    /// Create and start a single container of a pod, pulling its image if needed.
+    ///
+    /// `env` is the resolved environment of the container, as `NAME=value`.
+    async fn start_container(
+        &self,
+        pod: &Pod,
+        spec: &ContainerSpec,
+        env: &[String],
+    ) -> Result<ContainerRuntime, DockerError>;
+
+    /// List every container created by the agent, running or not.
+    async fn list_managed_containers(&self) -> Result<Vec<ManagedContainer>, DockerError>;
+
+    /// Stop and remove all containers in a pod.
+    ///
+    /// Containers get SIGTERM and are killed if still running after the grace period.
+    async fn stop_pod(
+        &self,
+        container_ids: &[String],
+        grace_period: u32,
+    ) -> Result<(), DockerError>;
+
+    /// Wait for a container to exit, returning its exit code.
+    async fn wait_container(&self, container_id: &str) -> Result<i64, DockerError>;
 
-    /// Stop and remove all containers in a pod
-    async fn stop_pod(&self, container_ids: &Vec<String>) -> Result<(), DockerError>;
+    /// Run a command inside a container and wait for it, failing on a non-zero exit code.
+    async fn exec(&self, container_id: &str, command: &[String]) -> Result<(), DockerError>;
+
+    /// Address of the container on its network.
+    async fn get_container_ip(&self, container_id: &str) -> Result<String, DockerError>;
 
     /// Fetch the full logs for a container.
     async fn get_logs(&self, container_id: &str) -> Result<String, DockerError>;
@@ -50,22 +91,32 @@ pub trait DockerClient: Send + Sync {
     ) -> Result<BoxStream<'static, Result<Bytes, DockerError>>, DockerError>;
 }
 
+/// Name of the pause container, `cr8s-` keeps it apart from the `cr8s_` app containers.
+fn pause_name(pod: &Pod) -> String {
+    format!("cr8s-pause_{}", pod.metadata.name)
+}
+
 /// Tracks pulled images and handles bollard docker client
 #[derive(Debug)]
 pub struct DockerManager {
     images: DashSet<String>,
     client: Docker,
+    volumes: VolumePaths,
+    /// Resolver set on the pod network, docker's own when unset
+    dns: Option<DnsSettings>,
 }
 
 impl DockerManager {
     /// Initialize a new `DockerManager` using local Docker defaults.
-    pub fn start() -> Result<Self, DockerError> {
+    pub fn start(volumes: VolumePaths, dns: Option<DnsSettings>) -> Result<Self, DockerError> {
         let client = Docker::connect_with_local_defaults()
             .map_err(|e| DockerError::ConnectionError(e.to_string()))?;
 
         Ok(DockerManager {
             images: DashSet::new(),
             client,
+            volumes,
+            dns,
         })
     }
 
@@ -81,10 +132,53 @@ impl DockerManager {
         self.images.insert(image);
     }
 
-    /// Check and pull image if needed
-    async fn ensure_image(&self, docker: &Docker, image: &str) -> Result<(), DockerError> {
-        if self.has_image(image) {
-            return Ok(());
+    /// Create a container and start it, returning its id.
+    async fn create_and_start(
+        &self,
+        docker: &Docker,
+        name: &str,
+        config: ContainerCreateBody,
+    ) -> Result<String, DockerError> {
+        let options = Some(CreateContainerOptions {
+            name: Some(name.to_string()),
+            platform: "linux/amd64".to_string(),
+        });
+
+        let container_id = docker
+            .create_container(options, config)
+            .await
+            .map_err(|e| DockerError::ContainerCreationError(e.to_string()))?
+            .id;
+
+        docker
+            .start_container(&container_id, None::<StartContainerOptions>)
+            .await
+            .map_err(|e| DockerError::ContainerStartError(e.to_string()))?;
+        Ok(container_id)
+    }
+
+    /// Check and pull image as the pull policy requires
+    async fn ensure_image(
+        &self,
+        docker: &Docker,
+        image: &str,
+        policy: &ImagePullPolicy,
+    ) -> Result<(), DockerError> {
+        if *policy != ImagePullPolicy::Always {
+            if self.has_image(image) {
+                return Ok(());
+            }
+            // may have been pulled outside of the agent
+            if docker.inspect_image(image).await.is_ok() {
+                self.mark_image_as_pulled(image.to_string());
+                return Ok(());
+            }
+            if *policy == ImagePullPolicy::Never {
+                return Err(DockerError::ImagePullError(format!(
+                    "Image {} not present and pull policy is Never",
+                    image
+                )));
+            }
         }
 
         let options = Some(CreateImageOptions {
@@ -126,63 +220,72 @@ impl DockerClient for DockerManager {
             .unwrap_or_else(|| ContainerStateStatusEnum::EMPTY))
     }
 
-    async fn start_pod(&self, pod: Pod) -> Result<PodRuntime, DockerError> {
+    async fn start_pause(&self, pod: &Pod, image: &str) -> Result<ContainerRuntime, DockerError> {
         let docker = self.client();
-        let mut container_runtimes = HashMap::new();
-
-        // for every container spec in the pod
-        for container_spec in &pod.spec.containers {
-            self.ensure_image(&docker, &container_spec.image).await?;
-
-            // build unique name
-            // NOTE: without namespaces or restarts
-            let container_name = format!("cr8s_{}_{}", container_spec.name, pod.metadata.name);
-
-            // build container config from spec
-            let config = ContainerCreateBody {
-                image: Some(container_spec.image.clone()),
-                env: container_spec.env.as_ref().map(|envs| {
-                    envs.iter()
-                        .map(|env| format!("{}={}", env.name, env.value))
-                        .collect()
-                }),
-                exposed_ports: container_spec.ports.as_ref().map(|ports| {
-                    ports
-                        .iter()
-                        .map(|p| (format!("{}/tcp", p.container_port), HashMap::new()))
-                        .collect()
-                }),
+        self.ensure_image(&docker, image, &ImagePullPolicy::IfNotPresent)
+            .await?;
+
+        let name = pause_name(pod);
+        let port_spec = |p: &Port| format!("{}/{}", p.container_port, p.protocol.as_str());
+        let exposed_ports = pod
+            .spec
+            .ports()
+            .map(|p| (port_spec(p), HashMap::new()))
+            .collect();
+        let port_bindings = pod
+            .spec
+            .ports()
+            .filter_map(|p| {
+                let binding = PortBinding {
+                    host_ip: None,
+                    host_port: Some(p.host_port?.to_string()),
+                };
+                Some((port_spec(p), Some(vec![binding])))
+            })
+            .collect();
+        let config = ContainerCreateBody {
+            image: Some(image.to_string()),
+            hostname: Some(pod.metadata.name.clone()),
+            exposed_ports: Some(exposed_ports),
+            // containers sharing the network use its resolver too
+            host_config: Some(HostConfig {
+                port_bindings: Some(port_bindings),
+                dns: self.dns.as_ref().map(|d| vec![d.server.clone()]),
+                dns_search: self.dns.as_ref().map(|d| d.search.clone()),
+                dns_options: self.dns.as_ref().map(|_| vec!["ndots:5".to_string()]),
                 ..Default::default()
-            };
+            }),
+            labels: Some(HashMap::from([
+                (POD_UID_LABEL.to_string(), pod.metadata.id.to_string()),
+                (POD_NAME_LABEL.to_string(), pod.metadata.name.clone()),
+                (CONTAINER_NAME_LABEL.to_string(), "pause".to_string()),
+                (PAUSE_LABEL.to_string(), "true".to_string()),
+            ])),
+            ..Default::default()
+        };
 
-            let options = Some(CreateContainerOptions {
-                name: Some(container_name.clone()),
-                platform: "linux/amd64".to_string(),
-            });
+        let id = self.create_and_start(&docker, &name, config).await?;
+        let status = self.get_container_status(&id).await?;
+        Ok(ContainerRuntime {
+            id,
+            spec_name: "pause".to_string(),
+            name,
+            status,
+            spec_hash: String::new(),
+            sidecar: false,
+        })
+    }
 
-            // create and start container
-            let container_id = docker
-                .create_container(options, config)
-                .await
-                .map_err(|e| DockerError::ContainerCreationError(e.to_string()))?
-                .id;
+    async fn start_pod(&self, pod: Pod, env: &PodEnv) -> Result<PodRuntime, DockerError> {
+        let mut container_runtimes = HashMap::new();
 
-            docker
-                .start_container(&container_id, None::<StartContainerOptions>)
-                .await
-                .map_err(|e| DockerError::ContainerStartError(e.to_string()))?;
-
-            let status = self.get_container_status(&container_id).await?;
-
-            container_runtimes.insert(
-                container_spec.name.clone(),
-                ContainerRuntime {
-                    id: container_id,
-                    spec_name: container_spec.name.clone(),
-                    name: container_name,
-                    status,
-                },
-            );
+        // for every container spec in the pod
+        for container_spec in &pod.spec.containers {
+            let container_env = env.get(&container_spec.name).map_or(&[][..], |e| e);
+            let runtime = self
+                .start_container(&pod, container_spec, container_env)
+                .await?;
+            container_runtimes.insert(container_spec.name.clone(), runtime);
         }
 
         tracing::info!(
@@ -195,17 +298,137 @@ impl DockerClient for DockerManager {
             id: pod.metadata.id,
             name: pod.metadata.name,
             containers: container_runtimes,
+            pause: None,
+            pod_ip: None,
         })
     }
 
-    async fn stop_pod(&self, container_ids: &Vec<String>) -> Result<(), DockerError> {
+    async fn start_container(
+        &self,
+        pod: &Pod,
+        container_spec: &ContainerSpec,
+        env: &[String],
+    ) -> Result<ContainerRuntime, DockerError> {
         let docker = self.client();
+        self.ensure_image(
+            &docker,
+            &container_spec.image,
+            &container_spec.image_pull_policy,
+        )
+        .await?;
+
+        // build unique name
+        // NOTE: without namespaces or restarts
+        let container_name = format!("cr8s_{}_{}", container_spec.name, pod.metadata.name);
+        let hash = spec_hash(container_spec);
+
+        // build container config from spec
+        let config = ContainerCreateBody {
+            image: Some(container_spec.image.clone()),
+            entrypoint: container_spec.command.clone(),
+            cmd: container_spec.args.clone(),
+            working_dir: container_spec.working_dir.clone(),
+            user: container_spec.effective_user(),
+            tty: Some(container_spec.tty),
+            open_stdin: Some(container_spec.stdin),
+            env: Some(env.to_vec()),
+            // ports are exposed by the pause container owning the network
+            host_config: Some(HostConfig {
+                network_mode: Some(format!("container:{}", pause_name(pod))),
+                binds: Some(volumes::binds(&self.volumes, pod, container_spec)),
+                ..Default::default()
+            }),
+            labels: Some(HashMap::from([
+                (POD_UID_LABEL.to_string(), pod.metadata.id.to_string()),
+                (POD_NAME_LABEL.to_string(), pod.metadata.name.clone()),
+                (
+                    CONTAINER_NAME_LABEL.to_string(),
+                    container_spec.name.clone(),
+                ),
+                (SPEC_HASH_LABEL.to_string(), hash.clone()),
+                (
+                    SIDECAR_LABEL.to_string(),
+                    container_spec.is_sidecar().to_string(),
+                ),
+            ])),
+            ..Default::default()
+        };
+
+        let container_id = self
+            .create_and_start(&docker, &container_name, config)
+            .await?;
+        let status = self.get_container_status(&container_id).await?;
+
+        Ok(ContainerRuntime {
+            id: container_id,
+            spec_name: container_spec.name.clone(),
+            name: container_name,
+            status,
+            spec_hash: hash,
+            sidecar: container_spec.is_sidecar(),
+        })
+    }
+
+    async fn list_managed_containers(&self) -> Result<Vec<ManagedContainer>, DockerError> {
+        let options = ListContainersOptions {
+            all: true,
+            filters: Some(HashMap::from([(
+                "label".to_string(),
+                vec![POD_UID_LABEL.to_string()],
+            )])),
+            ..Default::default()
+        };
+        let summaries = self
+            .client()
+            .list_containers(Some(options))
+            .await
+            .map_err(|e| DockerError::ContainerInspectError(e.to_string()))?;
+
+        // skip containers whose labels were tampered with
+        Ok(summaries
+            .into_iter()
+            .filter_map(|c| {
+                let labels = c.labels?;
+                Some(ManagedContainer {
+                    pod_id: Uuid::parse_str(labels.get(POD_UID_LABEL)?).ok()?,
+                    pod_name: labels.get(POD_NAME_LABEL)?.clone(),
+                    runtime: ContainerRuntime {
+                        id: c.id?,
+                        spec_name: labels.get(CONTAINER_NAME_LABEL)?.clone(),
+                        name: c
+                            .names
+                            .and_then(|n| n.first().map(|n| n.trim_start_matches('/').to_string()))
+                            .unwrap_or_default(),
+                        status: c
+                            .state
+                            .and_then(|s| s.to_string().parse().ok())
+                            .unwrap_or(ContainerStateStatusEnum::EMPTY),
+                        spec_hash: labels.get(SPEC_HASH_LABEL).cloned().unwrap_or_default(),
+                        sidecar: labels.get(SIDECAR_LABEL).is_some_and(|v| v == "true"),
+                    },
+                    pause: labels.get(PAUSE_LABEL).is_some_and(|v| v == "true"),
+                })
+            })
+            .collect())
+    }
+
+    async fn stop_pod(
+        &self,
+        container_ids: &[String],
+        grace_period: u32,
+    ) -> Result<(), DockerError> {
+        let docker = self.client();
+        // docker sends SIGTERM and follows with SIGKILL after `t` seconds
+        let options = StopContainerOptions {
+            t: Some(grace_period as i32),
+            signal: None,
+        };
 
         // stop and remove all containers passing along errors
         for cid in container_ids {
             let id = short_id(cid);
             docker
-                .stop_container(cid, None::<StopContainerOptions>)
+                .stop_container(cid, Some(options.clone()))
                 .await
                 .map_err(|e| {
                     tracing::warn!(id=%id, error=%e, "Failed to stop container");
@@ -225,6 +448,73 @@ impl DockerClient for DockerManager {
         Ok(())
     }
 
+    async fn wait_container(&self, container_id: &str) -> Result<i64, DockerError> {
+        let mut stream = self
+            .client()
+            .wait_container(container_id, None::<WaitContainerOptions>);
+        match stream.next().await {
+            Some(Ok(resp)) => Ok(resp.status_code),
+            // non-zero exits come back as errors
+            Some(Err(bollard::errors::Error::DockerContainerWaitError { code, .. })) => Ok(code),
+            Some(Err(e)) => Err(DockerError::ContainerInspectError(e.to_string())),
+            None => Err(DockerError::ContainerInspectError(
+                "Wait stream ended early".to_string(),
+            )),
+        }
+    }
+
+    async fn exec(&self, container_id: &str, command: &[String]) -> Result<(), DockerError> {
+        let docker = self.client();
+        let config = ExecConfig {
+            cmd: Some(command.to_vec()),
+            attach_stdout: Some(true),
+            attach_stderr: Some(true),
+            ..Default::default()
+        };
+        let exec_id = docker
+            .create_exec(container_id, config)
+            .await
+            .map_err(|e| DockerError::ExecError(e.to_string()))?
+            .id;
+
+        // drain the output so the command runs to completion
+        if let StartExecResults::Attached { mut output, .. } = docker
+            .start_exec(&exec_id, None::<StartExecOptions>)
+            .await
+            .map_err(|e| DockerError::ExecError(e.to_string()))?
+        {
+            while output.next().await.is_some() {}
+        }
+
+        let inspection = docker
+            .inspect_exec(&exec_id)
+            .await
+            .map_err(|e| DockerError::ExecError(e.to_string()))?;
+        match inspection.exit_code {
+            Some(0) | None => Ok(()),
+            Some(code) => Err(DockerError::ExecError(format!(
+                "Command exited with code {}",
+                code
+            ))),
+        }
+    }
+
+    async fn get_container_ip(&self, container_id: &str) -> Result<String, DockerError> {
+        let inspection = self
+            .client()
+            .inspect_container(container_id, None::<InspectContainerOptions>)
+            .await
+            .map_err(|e| DockerError::ContainerInspectError(e.to_string()))?;
+        let settings = inspection.network_settings.unwrap_or_default();
+        settings
+            .networks
+            .unwrap_or_default()
+            .into_values()
+            .filter_map(|n| n.ip_address)
+            .find(|ip| !ip.is_empty())
+            .ok_or_else(|| DockerError::NotFound("Container has no address".to_string()))
+    }
+
     async fn get_logs(&self, container_id: &str) -> Result<String, DockerError> {
         let docker = self.client();
         let mut logs_stream = docker.logs(
@@ -3,22 +3,26 @@
 
 use crate::docker::errors::DockerError;
 use crate::docker::manager::DockerClient;
-use crate::models::{ContainerRuntime, PodRuntime};
+use crate::models::{ContainerRuntime, ManagedContainer, PodEnv, PodRuntime, spec_hash};
 use async_trait::async_trait;
 use bollard::secret::ContainerStateStatusEnum;
 use dashmap::DashMap;
 use futures_util::lock::Mutex;
 use futures_util::stream::BoxStream;
-use shared::models::pod::Pod;
+use shared::models::pod::{ContainerSpec, Pod};
 use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
-/// A mock Docker client for testing, simulating container operations with optional failure modes.
+/// Container id and command of an exec call
+pub type ExecCall = (String, Vec<String>);
 
+/// A mock Docker client for testing, simulating container operations with optional failure modes.
 #[derive(Debug, Clone)]
 pub struct TestDocker {
     pub containers: Arc<DashMap<String, ContainerStateStatusEnum>>,
+    /// Labeled containers by id, as returned by `list_managed_containers`
+    pub managed: Arc<DashMap<String, ManagedContainer>>,
     pub fail_start: bool,
     pub fail_stop: bool,
     pub fail_remove: bool,
@@ -27,7 +31,19 @@ pub struct TestDocker {
 
     pub get_container_status_calls: Arc<Mutex<Vec<String>>>,
     pub start_pod_calls: Arc<Mutex<Vec<Pod>>>,
+    /// Images of the pause containers started
+    pub start_pause_calls: Arc<Mutex<Vec<String>>>,
+    pub start_container_calls: Arc<Mutex<Vec<String>>>,
+    /// Environment each container was last started with, by container spec name
+    pub envs: Arc<DashMap<String, Vec<String>>>,
     pub stop_pod_calls: Arc<Mutex<Vec<Vec<String>>>>,
+    pub stop_grace_periods: Arc<Mutex<Vec<u32>>>,
+    pub exec_calls: Arc<Mutex<Vec<ExecCall>>>,
+    /// Exit codes returned by `wait_container` by container spec name, in order, zero once used up
+    pub exit_codes: Arc<DashMap<String, Vec<i64>>>,
+    pub wait_calls: Arc<Mutex<Vec<String>>>,
+    /// Address handed out by `get_container_ip`
+    pub container_ip: String,
     pub get_logs_calls: Arc<Mutex<Vec<String>>>,
     pub stream_logs_calls: Arc<Mutex<Vec<String>>>,
 }
@@ -36,6 +52,7 @@ impl TestDocker {
     pub fn new() -> Self {
         Self {
             containers: Arc::new(DashMap::new()),
+            managed: Arc::new(DashMap::new()),
             fail_start: false,
             fail_stop: false,
             fail_remove: false,
@@ -44,7 +61,15 @@ impl TestDocker {
 
             get_container_status_calls: Arc::new(Mutex::new(Vec::new())),
             start_pod_calls: Arc::new(Mutex::new(Vec::new())),
+            start_pause_calls: Arc::new(Mutex::new(Vec::new())),
+            start_container_calls: Arc::new(Mutex::new(Vec::new())),
+            envs: Arc::new(DashMap::new()),
             stop_pod_calls: Arc::new(Mutex::new(Vec::new())),
+            stop_grace_periods: Arc::new(Mutex::new(Vec::new())),
+            exec_calls: Arc::new(Mutex::new(Vec::new())),
+            exit_codes: Arc::new(DashMap::new()),
+            wait_calls: Arc::new(Mutex::new(Vec::new())),
+            container_ip: "127.0.0.1".to_string(),
             get_logs_calls: Arc::new(Mutex::new(Vec::new())),
             stream_logs_calls: Arc::new(Mutex::new(Vec::new())),
         }
@@ -57,6 +82,40 @@ impl TestDocker {
     fn generate_container_id(name: &str) -> String {
         format!("{}-{}", name, Uuid::new_v4())
     }
+    /// Records a new labeled container for the pod
+    fn create_container(
+        &self,
+        pod: &Pod,
+        spec: &ContainerSpec,
+        env: &[String],
+    ) -> ContainerRuntime {
+        self.envs.insert(spec.name.clone(), env.to_vec());
+        let container_id = Self::generate_container_id(&spec.name);
+        let status = self
+            .start_pod_default_status
+            .clone()
+            .unwrap_or(ContainerStateStatusEnum::RUNNING);
+        self.containers.insert(container_id.clone(), status.clone());
+
+        let runtime = ContainerRuntime {
+            id: container_id.clone(),
+            spec_name: spec.name.clone(),
+            name: spec.name.clone(),
+            status,
+            spec_hash: spec_hash(spec),
+            sidecar: spec.is_sidecar(),
+        };
+        self.managed.insert(
+            container_id,
+            ManagedContainer {
+                pod_id: pod.metadata.id,
+                pod_name: pod.metadata.name.clone(),
+                runtime: runtime.clone(),
+                pause: false,
+            },
+        );
+        runtime
+    }
 }
 
 #[async_trait]
@@ -80,7 +139,36 @@ impl DockerClient for TestDocker {
         }
     }
 
-    async fn start_pod(&self, pod: Pod) -> Result<PodRuntime, DockerError> {
+    async fn start_pause(&self, pod: &Pod, image: &str) -> Result<ContainerRuntime, DockerError> {
+        self.start_pause_calls.lock().await.push(image.to_string());
+
+        if self.fail_start {
+            return Err(DockerError::ContainerStartError("Forced error".into()));
+        }
+        let id = Self::generate_container_id("pause");
+        self.containers
+            .insert(id.clone(), ContainerStateStatusEnum::RUNNING);
+        let runtime = ContainerRuntime {
+            id: id.clone(),
+            spec_name: "pause".to_string(),
+            name: format!("cr8s-pause_{}", pod.metadata.name),
+            status: ContainerStateStatusEnum::RUNNING,
+            spec_hash: String::new(),
+            sidecar: false,
+        };
+        self.managed.insert(
+            id,
+            ManagedContainer {
+                pod_id: pod.metadata.id,
+                pod_name: pod.metadata.name.clone(),
+                runtime: runtime.clone(),
+                pause: true,
+            },
+        );
+        Ok(runtime)
+    }
+
+    async fn start_pod(&self, pod: Pod, env: &PodEnv) -> Result<PodRuntime, DockerError> {
         self.start_pod_calls.lock().await.push(pod.clone());
 
         if self.fail_start {
@@ -90,35 +178,55 @@ impl DockerClient for TestDocker {
         let mut containers_runtime = HashMap::new();
 
         for container_spec in &pod.spec.containers {
-            let container_id = Self::generate_container_id(&container_spec.name);
-
-            let status = self
-                .start_pod_default_status
-                .clone()
-                .unwrap_or(ContainerStateStatusEnum::RUNNING);
-
-            self.containers.insert(container_id.clone(), status.clone());
-
-            containers_runtime.insert(
-                container_spec.name.clone(),
-                ContainerRuntime {
-                    id: container_id.clone(),
-                    spec_name: container_spec.name.clone(),
-                    name: container_spec.name.clone(),
-                    status,
-                },
-            );
+            let container_env = env.get(&container_spec.name).map_or(&[][..], |e| e);
+            let runtime = self.create_container(&pod, container_spec, container_env);
+            containers_runtime.insert(container_spec.name.clone(), runtime);
         }
 
         Ok(PodRuntime {
             id: pod.metadata.id,
             name: pod.metadata.name,
             containers: containers_runtime,
+            pause: None,
+            pod_ip: None,
         })
     }
 
-    async fn stop_pod(&self, container_ids: &Vec<String>) -> Result<(), DockerError> {
-        self.stop_pod_calls.lock().await.push(container_ids.clone());
+    async fn start_container(
+        &self,
+        pod: &Pod,
+        spec: &ContainerSpec,
+        env: &[String],
+    ) -> Result<ContainerRuntime, DockerError> {
+        self.start_container_calls
+            .lock()
+            .await
+            .push(spec.name.clone());
+
+        if self.fail_start {
+            return Err(DockerError::ContainerStartError("Forced error".into()));
+        }
+        Ok(self.create_container(pod, spec, env))
+    }
+
+    async fn list_managed_containers(&self) -> Result<Vec<ManagedContainer>, DockerError> {
+        Ok(self
+            .managed
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn stop_pod(
+        &self,
+        container_ids: &[String],
+        grace_period: u32,
+    ) -> Result<(), DockerError> {
+        self.stop_pod_calls
+            .lock()
+            .await
+            .push(container_ids.to_vec());
+        self.stop_grace_periods.lock().await.push(grace_period);
 
         if self.fail_stop {
             return Err(DockerError::ContainerStopError("Forced error".into()));
@@ -130,11 +238,40 @@ impl DockerClient for TestDocker {
 
         for id in container_ids {
             self.containers.remove(id);
+            self.managed.remove(id);
         }
 
         Ok(())
     }
 
+    async fn wait_container(&self, container_id: &str) -> Result<i64, DockerError> {
+        let spec_name = self
+            .managed
+            .get(container_id)
+            .map(|c| c.runtime.spec_name.clone())
+            .ok_or_else(|| DockerError::NotFound("Container not found".into()))?;
+        self.wait_calls.lock().await.push(spec_name.clone());
+        self.containers
+            .insert(container_id.to_string(), ContainerStateStatusEnum::EXITED);
+        Ok(self
+            .exit_codes
+            .get_mut(&spec_name)
+            .and_then(|mut codes| (!codes.is_empty()).then(|| codes.remove(0)))
+            .unwrap_or(0))
+    }
+
+    async fn exec(&self, container_id: &str, command: &[String]) -> Result<(), DockerError> {
+        self.exec_calls
+            .lock()
+            .await
+            .push((container_id.to_string(), command.to_vec()));
+        Ok(())
+    }
+
+    async fn get_container_ip(&self, _container_id: &str) -> Result<String, DockerError> {
+        Ok(self.container_ip.clone())
+    }
+
     async fn get_logs(&self, container_id: &str) -> Result<String, DockerError> {
         // Record argument (clone &str to String)
         self.get_logs_calls
@@ -3,7 +3,12 @@
 //! - Worker loop
 //! - Sync logic
 //! - Watcher loop
+//! - Local-path provisioner
+//! - Service proxy
+//! - Cluster DNS, on nodes given a DNS or cluster DNS address
+//! - Ingress gateway, on nodes given an ingress address
 //!
+//! On SIGTERM or SIGINT the subsystems are stopped and the node leaves the cluster.
 //! Each subsystem communicates via a shared application state and message channels.
 
 use tokio::sync::mpsc;
@@ -26,15 +31,24 @@ async fn main() -> Result<(), String> {
 
     let (tx, rx) = mpsc::channel::<WorkRequest>(100);
     let state = NodeState::new();
+    // every subsystem calls the apiserver with the credentials registration returns
+    core::watcher::register(state.clone()).await?;
 
-    tokio::try_join!(
-        api::run(state.clone()),
-        core::sync::run(state.clone()),
-        core::worker::run(state.clone(), rx),
-        core::watcher::run(state.clone(), tx),
-    )?;
-
-    Ok(())
+    tokio::select! {
+        res = async {
+            tokio::try_join!(
+                api::run(state.clone()),
+                core::sync::run(state.clone()),
+                core::worker::run(state.clone(), rx),
+                core::watcher::run(state.clone(), tx),
+                core::provisioner::run(state.clone()),
+                core::proxy::run(state.clone()),
+                core::dns::run(state.clone()),
+                core::ingress::run(state.clone()),
+            )
+        } => res.map(|_| ()),
+        res = core::shutdown::run(state.clone()) => res,
+    }
 }
 
 #[cfg(test)]
@@ -1,10 +1,41 @@
-use std::{collections::HashMap, env};
+use std::{collections::HashMap, env, net::SocketAddr, path::PathBuf};
 
 use bollard::secret::ContainerStateStatusEnum;
+use ring::digest::{SHA256, digest};
 use serde::{Deserialize, Serialize};
-use shared::api::EventType;
+use shared::{
+    api::EventType,
+    models::{node::Taint, persistentvolume::PersistentVolume, pod::ContainerSpec},
+};
 use uuid::Uuid;
 
+// --- Container labels ---
+
+/// Labels set on every container the agent creates, used to find them again after a restart.
+pub const POD_UID_LABEL: &str = "cr8s.io/pod-uid";
+pub const POD_NAME_LABEL: &str = "cr8s.io/pod-name";
+pub const CONTAINER_NAME_LABEL: &str = "cr8s.io/container-name";
+pub const SPEC_HASH_LABEL: &str = "cr8s.io/spec-hash";
+pub const SIDECAR_LABEL: &str = "cr8s.io/sidecar";
+pub const PAUSE_LABEL: &str = "cr8s.io/pause";
+
+/// Seconds containers of pods no longer assigned here get to stop, docker's own default.
+pub const ORPHAN_GRACE_PERIOD_SECONDS: u32 = 10;
+
+/// Fingerprint of a container spec, a container is recreated when it changes.
+/// Kept in a container label, so it must not change between agent builds.
+pub fn spec_hash(spec: &ContainerSpec) -> String {
+    let json = serde_json::to_vec(spec).unwrap_or_default();
+    digest(&SHA256, &json)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Resolved environment of each container of a pod, as `NAME=value`.
+pub type PodEnv = HashMap<String, Vec<String>>;
+
 // --- State objects ---
 
 /// Runtime information for a pod
@@ -13,6 +44,23 @@ pub struct PodRuntime {
     pub id: Uuid,
     pub name: String,
     pub containers: HashMap<String, ContainerRuntime>,
+    /// Infrastructure container holding the network namespace shared by the pod
+    #[serde(default)]
+    pub pause: Option<ContainerRuntime>,
+    #[serde(default)]
+    pub pod_ip: Option<String>,
+}
+
+impl PodRuntime {
+    /// Container ids in the order they are stopped, main containers then sidecars.
+    /// The pause container goes last, with the sidecars.
+    pub fn stop_order(&self) -> (Vec<String>, Vec<String>) {
+        let (sidecars, main): (Vec<_>, Vec<_>) = self.containers.values().partition(|c| c.sidecar);
+        let ids = |list: Vec<&ContainerRuntime>| list.iter().map(|c| c.id.clone()).collect();
+        let mut last: Vec<String> = ids(sidecars);
+        last.extend(self.pause.iter().map(|p| p.id.clone()));
+        (ids(main), last)
+    }
 }
 
 /// Runtime information for a container
@@ -22,6 +70,21 @@ pub struct ContainerRuntime {
     pub spec_name: String,
     pub name: String,
     pub status: ContainerStateStatusEnum,
+    /// Hash of the spec the container was created from
+    #[serde(default)]
+    pub spec_hash: String,
+    /// Started before and stopped after the main containers
+    #[serde(default)]
+    pub sidecar: bool,
+}
+
+/// A container found on the host carrying the agent's labels
+#[derive(Debug, Clone)]
+pub struct ManagedContainer {
+    pub pod_id: Uuid,
+    pub pod_name: String,
+    pub runtime: ContainerRuntime,
+    pub pause: bool,
 }
 
 // --- Thread communication ---
@@ -43,9 +106,118 @@ pub struct Config {
     pub register_retries: u16,
     pub node_api_workers: usize,
     pub sync_loop: u16,
+    pub taints: Vec<Taint>,
+    /// File holding the token used to reclaim the node name after a restart.
+    pub token_file: String,
+    /// Token authenticating the first registration, before the node has its own.
+    pub bootstrap_token: Option<String>,
+    /// PEM serving certificate and key files, the cluster CA issues one when unset.
+    pub tls_cert: Option<String>,
+    pub tls_key: Option<String>,
+    /// Evict the node's pods before deregistering on shutdown.
+    pub drain_on_shutdown: bool,
+    pub drain_timeout: u16,
+    /// Image of the container holding each pod's network namespace.
+    pub pause_image: String,
+    /// Directory holding the emptyDir volumes of every pod.
+    pub volume_root: String,
+    /// Memory backed directory holding the emptyDir volumes with medium Memory.
+    pub memory_volume_root: String,
+    /// Directory the local-path provisioner creates persistent volumes in.
+    pub persistent_volume_root: String,
+    /// Address the service proxy opens node ports on.
+    pub proxy_address: String,
+    pub proxy_balancer: Balancer,
+    /// Seconds connections to a removed endpoint are kept open.
+    pub proxy_drain_timeout: u16,
+    /// Zone the cluster DNS answers for.
+    pub cluster_domain: String,
+    /// Address the cluster DNS listens on, port 53 of `cluster_dns` when unset.
+    pub dns_address: Option<String>,
+    /// Resolver queries outside the cluster zone go to, from resolv.conf when unset.
+    pub dns_upstream: Option<String>,
+    /// Address containers reach the cluster DNS on, they keep docker's resolver when unset.
+    pub cluster_dns: Option<String>,
+    /// HTTP address of the ingress gateway, the node runs no gateway when unset.
+    pub ingress_address: Option<String>,
+    /// HTTPS address of the ingress gateway, serving the hosts of the ingress TLS secrets.
+    pub ingress_tls_address: Option<String>,
+    /// Seconds a backend gets to answer a proxied request.
+    pub ingress_timeout: u16,
+    /// Other backends tried when one cannot be reached.
+    pub ingress_retries: u8,
+}
+
+/// Resolver settings given to the pods.
+#[derive(Debug, Clone)]
+pub struct DnsSettings {
+    pub server: String,
+    pub search: Vec<String>,
+}
+
+/// How the service proxy spreads connections over the endpoints of a service.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Balancer {
+    #[default]
+    RoundRobin,
+    LeastConnections,
+}
+
+/// Host directories pod volumes are created in.
+#[derive(Debug, Clone)]
+pub struct VolumePaths {
+    pub root: PathBuf,
+    pub memory_root: PathBuf,
+    pub persistent_root: PathBuf,
+}
+
+impl VolumePaths {
+    pub fn pod_dir(&self, pod_id: &Uuid) -> PathBuf {
+        self.root.join(pod_id.to_string())
+    }
+    pub fn memory_dir(&self, pod_id: &Uuid) -> PathBuf {
+        self.memory_root.join(pod_id.to_string())
+    }
+    /// Host directory of a persistent volume, its own path when an admin set one.
+    pub fn persistent_dir(&self, pv: &PersistentVolume) -> PathBuf {
+        match &pv.spec.path {
+            Some(path) => PathBuf::from(path),
+            None => self.persistent_root.join(&pv.metadata.name),
+        }
+    }
 }
 
 impl Config {
+    /// Resolver of the pods, when the cluster DNS is reachable from them.
+    pub fn dns_settings(&self) -> Option<DnsSettings> {
+        let server = self.cluster_dns.clone()?;
+        let domain = &self.cluster_domain;
+        Some(DnsSettings {
+            server,
+            search: vec![
+                format!("default.svc.{}", domain),
+                format!("svc.{}", domain),
+                domain.clone(),
+            ],
+        })
+    }
+
+    /// Address the cluster DNS binds, the node serves no DNS without one.
+    pub fn dns_bind_address(&self) -> Option<String> {
+        self.dns_address.clone().or_else(|| {
+            let ip = self.cluster_dns.as_ref()?.parse().ok()?;
+            Some(SocketAddr::new(ip, 53).to_string())
+        })
+    }
+
+    pub fn volume_paths(&self) -> VolumePaths {
+        VolumePaths {
+            root: PathBuf::from(&self.volume_root),
+            memory_root: PathBuf::from(&self.memory_volume_root),
+            persistent_root: PathBuf::from(&self.persistent_volume_root),
+        }
+    }
+
     /// Loads node configuration from environment variables.
     ///
     /// Falls back to defaults when applicable.
@@ -57,7 +229,12 @@ impl Config {
                 .ok()
                 .and_then(|s| s.parse::<u16>().ok())
                 .unwrap_or(7620);
-            config.server_url = format!("http://{}:{}", addr, port);
+            let scheme = if env::var("CR8S_CA_CERT").is_ok() {
+                "https"
+            } else {
+                "http"
+            };
+            config.server_url = format!("{}://{}:{}", scheme, addr, port);
         }
 
         if let Some(p) = env::var("NODE_PORT")
@@ -93,6 +270,100 @@ impl Config {
             config.node_api_workers = val;
         }
 
+        // comma separated `key=value:Effect`, invalid entries are skipped
+        if let Ok(val) = env::var("NODE_TAINTS") {
+            config.taints = val
+                .split(',')
+                .filter(|s| !s.trim().is_empty())
+                .filter_map(|s| {
+                    Taint::try_from(s)
+                        .inspect_err(|_| tracing::warn!(taint=%s, "Ignoring invalid taint"))
+                        .ok()
+                })
+                .collect();
+        }
+
+        if let Ok(val) = env::var("NODE_TOKEN_FILE") {
+            config.token_file = val;
+        }
+
+        if let Ok(val) = env::var("NODE_BOOTSTRAP_TOKEN") {
+            config.bootstrap_token = Some(val);
+        }
+
+        config.tls_cert = env::var("NODE_TLS_CERT").ok();
+        config.tls_key = env::var("NODE_TLS_KEY").ok();
+
+        if let Ok(val) = env::var("NODE_DRAIN_ON_SHUTDOWN") {
+            config.drain_on_shutdown = matches!(val.as_str(), "1" | "true");
+        }
+
+        if let Ok(val) = env::var("NODE_PAUSE_IMAGE") {
+            config.pause_image = val;
+        }
+
+        if let Ok(val) = env::var("NODE_VOLUME_ROOT") {
+            config.volume_root = val;
+        }
+
+        if let Ok(val) = env::var("NODE_MEMORY_VOLUME_ROOT") {
+            config.memory_volume_root = val;
+        }
+
+        if let Ok(val) = env::var("NODE_PERSISTENT_VOLUME_ROOT") {
+            config.persistent_volume_root = val;
+        }
+
+        if let Ok(val) = env::var("NODE_PROXY_ADDRESS") {
+            config.proxy_address = val;
+        }
+
+        if let Ok(val) = env::var("NODE_PROXY_BALANCER") {
+            match val.as_str() {
+                "round-robin" => config.proxy_balancer = Balancer::RoundRobin,
+                "least-connections" => config.proxy_balancer = Balancer::LeastConnections,
+                _ => tracing::warn!(balancer=%val, "Ignoring unknown proxy balancer"),
+            }
+        }
+
+        if let Some(val) = env::var("NODE_PROXY_DRAIN_TIMEOUT")
+            .ok()
+            .and_then(|s| s.parse().ok())
+        {
+            config.proxy_drain_timeout = val;
+        }
+
+        if let Ok(val) = env::var("NODE_CLUSTER_DOMAIN") {
+            config.cluster_domain = val;
+        }
+
+        config.dns_address = env::var("NODE_DNS_ADDRESS").ok();
+        config.dns_upstream = env::var("NODE_DNS_UPSTREAM").ok();
+        config.cluster_dns = env::var("NODE_CLUSTER_DNS").ok();
+        config.ingress_address = env::var("NODE_INGRESS_ADDRESS").ok();
+        config.ingress_tls_address = env::var("NODE_INGRESS_TLS_ADDRESS").ok();
+
+        if let Some(val) = env::var("NODE_INGRESS_TIMEOUT")
+            .ok()
+            .and_then(|s| s.parse().ok())
+        {
+            config.ingress_timeout = val;
+        }
+
+        if let Some(val) = env::var("NODE_INGRESS_RETRIES")
+            .ok()
+            .and_then(|s| s.parse().ok())
+        {
+            config.ingress_retries = val;
+        }
+
+        if let Some(val) = env::var("NODE_DRAIN_TIMEOUT")
+            .ok()
+            .and_then(|s| s.parse().ok())
+        {
+            config.drain_timeout = val;
+        }
+
         config
     }
 }
@@ -107,6 +378,28 @@ impl Default for Config {
             sync_loop: 15,
             register_retries: 3,
             node_api_workers: 2,
+            taints: Vec::new(),
+            token_file: "/var/lib/cr8s/node-token".to_string(),
+            bootstrap_token: None,
+            tls_cert: None,
+            tls_key: None,
+            drain_on_shutdown: false,
+            drain_timeout: 30,
+            pause_image: "registry.k8s.io/pause:3.9".to_string(),
+            volume_root: "/var/lib/cr8s/pods".to_string(),
+            memory_volume_root: "/dev/shm/cr8s".to_string(),
+            persistent_volume_root: "/var/lib/cr8s/volumes".to_string(),
+            proxy_address: "0.0.0.0".to_string(),
+            proxy_balancer: Balancer::RoundRobin,
+            proxy_drain_timeout: 30,
+            cluster_domain: "cr8s.local".to_string(),
+            dns_address: None,
+            dns_upstream: None,
+            cluster_dns: None,
+            ingress_address: None,
+            ingress_tls_address: None,
+            ingress_timeout: 30,
+            ingress_retries: 2,
         }
     }
 }
@@ -4,10 +4,14 @@
 //! Including its config, known pods, runtime container info and docker
 
 use std::collections::HashMap;
+use std::sync::{
+    OnceLock,
+    atomic::{AtomicBool, Ordering},
+};
 
 use actix_web::web::Data;
 use bollard::secret::ContainerStateStatusEnum;
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 use shared::models::pod::{Pod, PodPhase};
 use uuid::Uuid;
 
@@ -25,6 +29,10 @@ pub struct NodeState {
     pub docker_mgr: Box<dyn DockerClient + Send + Sync>,
     pods: DashMap<Uuid, Pod>,
     pod_runtimes: DashMap<Uuid, PodRuntime>,
+    terminating: DashSet<Uuid>,
+    shutting_down: AtomicBool,
+    /// PEM certificate and key issued by the cluster CA at registration.
+    serving_cert: OnceLock<(String, String)>,
 }
 
 impl NodeState {
@@ -33,9 +41,10 @@ impl NodeState {
         config_in: Option<Config>,
         docker_in: Option<Box<dyn DockerClient + Send + Sync>>,
     ) -> State {
+        let config = config_in.unwrap_or_else(Config::from_env);
         let docker_mgr = docker_in.unwrap_or_else(|| {
             Box::new(
-                DockerManager::start()
+                DockerManager::start(config.volume_paths(), config.dns_settings())
                     .inspect_err(
                         |err| tracing::error!(error = %err, "Failed to start docker manager"),
                     )
@@ -43,18 +52,39 @@ impl NodeState {
             )
         });
 
-        let config = config_in.unwrap_or_else(Config::from_env);
         Data::new(Self {
             config,
             docker_mgr,
             pods: DashMap::new(),
             pod_runtimes: DashMap::new(),
+            terminating: DashSet::new(),
+            shutting_down: AtomicBool::new(false),
+            serving_cert: OnceLock::new(),
         })
     }
     pub fn new() -> State {
         Self::new_with(None, None)
     }
 
+    // --- Lifecycle ---
+
+    /// Whether the node is shutting down and should not take new work.
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+    pub fn begin_shutdown(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+    }
+
+    // --- Serving certificate ---
+
+    pub fn serving_cert(&self) -> Option<&(String, String)> {
+        self.serving_cert.get()
+    }
+    pub fn set_serving_cert(&self, cert: String, key: String) {
+        let _ = self.serving_cert.set((cert, key));
+    }
+
     // --- Pods ---
 
     pub fn get_pod(&self, id: &Uuid) -> Option<Pod> {
@@ -67,6 +97,14 @@ impl NodeState {
         self.pods.remove(id);
     }
 
+    /// Marks a pod as terminating, false if its termination already started.
+    pub fn mark_terminating(&self, id: &Uuid) -> bool {
+        self.terminating.insert(*id)
+    }
+    pub fn unmark_terminating(&self, id: &Uuid) {
+        self.terminating.remove(id);
+    }
+
     // --- Pod Runtimes ---
 
     pub fn get_pod_runtime(&self, id: &Uuid) -> Option<PodRuntime> {
@@ -82,6 +120,9 @@ impl NodeState {
     pub fn delete_pod_runtime(&self, id: &Uuid) {
         self.pod_runtimes.remove(id);
     }
+    pub fn put_pod_runtime(&self, pod_runtime: PodRuntime) {
+        self.pod_runtimes.insert(pod_runtime.id, pod_runtime);
+    }
     pub fn add_pod_runtime(&self, pod_runtime: PodRuntime) -> Result<(), String> {
         if self.pod_runtimes.contains_key(&pod_runtime.id) {
             return Err(format!(
@@ -106,7 +147,8 @@ impl NodeState {
             for (spec_name, status) in container_statuses {
                 if let Some(container) = pod_runtime.containers.get_mut(&spec_name) {
                     container.status = status.clone();
-                    if container.status != ContainerStateStatusEnum::RUNNING {
+                    // sidecars don't decide the pod phase
+                    if !container.sidecar && container.status != ContainerStateStatusEnum::RUNNING {
                         pod_status = PodPhase::Succeeded;
                     }
                 }
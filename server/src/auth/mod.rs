@@ -0,0 +1,233 @@
+//! # Authentication
+//!
+//! Callers of the apiserver are identified by, in order:
+//! - a client certificate signed by the client CA, see [`tls`]
+//! - a static bearer token from the file at `CR8S_AUTH_TOKEN_FILE`
+//! - a bearer token signed by the apiserver, see [`tokens`]
+//!
+//! Nodes get a signed token for `system:node:<name>` when they register, it
+//! stops working once the node registers again or is removed. Authentication
+//! is only enforced when a token file or a client CA is configured, the
+//! authenticated caller is then authorized by its roles, see [`rbac`].
+
+pub mod ca;
+pub mod rbac;
+pub mod tls;
+pub mod tokens;
+
+use std::{collections::HashMap, time::Duration};
+
+use ring::rand::{SecureRandom, SystemRandom};
+
+use tokens::TokenSigner;
+
+/// Members may do anything.
+pub const MASTERS_GROUP: &str = "system:masters";
+/// Node agents.
+pub const NODES_GROUP: &str = "system:nodes";
+/// Service accounts issued a token.
+pub const SERVICE_ACCOUNTS_GROUP: &str = "system:serviceaccounts";
+
+const NODE_PREFIX: &str = "system:node:";
+pub(crate) const SERVICE_ACCOUNT_PREFIX: &str = "system:serviceaccount:";
+
+/// Authenticated caller of a request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserInfo {
+    pub name: String,
+    pub groups: Vec<String>,
+}
+
+impl UserInfo {
+    pub fn node(node_name: &str) -> Self {
+        Self {
+            name: format!("{}{}", NODE_PREFIX, node_name),
+            groups: vec![NODES_GROUP.to_string()],
+        }
+    }
+
+    pub fn service_account(name: &str, groups: Vec<String>) -> Self {
+        let mut groups = groups;
+        groups.push(SERVICE_ACCOUNTS_GROUP.to_string());
+        Self {
+            name: format!("{}{}", SERVICE_ACCOUNT_PREFIX, name),
+            groups,
+        }
+    }
+
+    pub fn in_group(&self, group: &str) -> bool {
+        self.groups.iter().any(|g| g == group)
+    }
+
+    /// Name of the node this user is the agent of.
+    pub fn node_name(&self) -> Option<&str> {
+        self.name
+            .strip_prefix(NODE_PREFIX)
+            .filter(|_| self.in_group(NODES_GROUP))
+    }
+
+    /// Whether the user may report for the node, only the node itself and masters can.
+    pub fn may_act_as_node(&self, node_name: &str) -> bool {
+        self.node_name() == Some(node_name) || self.in_group(MASTERS_GROUP)
+    }
+}
+
+pub struct Authenticator {
+    /// Requests without valid credentials are rejected
+    pub enabled: bool,
+    /// Static tokens, including the loopback one of the controllers
+    tokens: HashMap<String, UserInfo>,
+    signer: TokenSigner,
+}
+
+impl Authenticator {
+    /// Accepts every request, tokens are still issued with a random key.
+    #[cfg(test)]
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            tokens: HashMap::new(),
+            signer: TokenSigner::generate(),
+        }
+    }
+
+    /// Enabled with the given static tokens.
+    #[cfg(test)]
+    pub fn with_tokens(tokens: &[(&str, UserInfo)]) -> Self {
+        Self {
+            enabled: true,
+            tokens: tokens
+                .iter()
+                .map(|(token, user)| (token.to_string(), user.clone()))
+                .collect(),
+            signer: TokenSigner::generate(),
+        }
+    }
+
+    /// Reads the token file and signing key, enabled by a token file or client CA.
+    pub fn from_env() -> Result<Self, String> {
+        let mut auth = Self {
+            enabled: false,
+            tokens: HashMap::new(),
+            signer: TokenSigner::from_env()?,
+        };
+        if let Ok(path) = std::env::var("CR8S_AUTH_TOKEN_FILE") {
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read CR8S_AUTH_TOKEN_FILE: {}", e))?;
+            auth.tokens = parse_token_file(&content)?;
+            auth.enabled = true;
+        }
+        auth.enabled |= std::env::var("CR8S_CLIENT_CA").is_ok();
+        if !auth.enabled {
+            tracing::warn!(
+                "Neither CR8S_AUTH_TOKEN_FILE nor CR8S_CLIENT_CA set, authentication is disabled"
+            );
+        }
+        Ok(auth)
+    }
+
+    /// Adds a random token for the controllers of this process, members of the masters group.
+    pub fn add_loopback_token(&mut self) -> String {
+        let mut bytes = [0u8; 32];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .expect("Failed to generate loopback token");
+        let token: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        let user = UserInfo {
+            name: "system:apiserver".to_string(),
+            groups: vec![MASTERS_GROUP.to_string()],
+        };
+        self.tokens.insert(token.clone(), user);
+        token
+    }
+
+    /// Issues a signed token for the user.
+    pub fn issue(&self, user: &UserInfo, ttl: Option<Duration>) -> String {
+        self.signer.sign(user, ttl)
+    }
+
+    pub fn static_token(&self, token: &str) -> Option<UserInfo> {
+        self.tokens.get(token).cloned()
+    }
+
+    pub fn signed_token(&self, token: &str) -> Option<UserInfo> {
+        self.signer.verify(token)
+    }
+}
+
+/// Parses `token,user[,group...]` lines, blank lines and `#` comments are skipped.
+fn parse_token_file(content: &str) -> Result<HashMap<String, UserInfo>, String> {
+    let mut tokens = HashMap::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split(',').map(str::trim);
+        let (Some(token), Some(name)) = (fields.next(), fields.next()) else {
+            return Err(format!(
+                "Token file line {} needs a token and a user",
+                i + 1
+            ));
+        };
+        if token.is_empty() || name.is_empty() {
+            return Err(format!(
+                "Token file line {} needs a token and a user",
+                i + 1
+            ));
+        }
+        let user = UserInfo {
+            name: name.to_string(),
+            groups: fields
+                .filter(|g| !g.is_empty())
+                .map(str::to_string)
+                .collect(),
+        };
+        tokens.insert(token.to_string(), user);
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    //! - test_parse_token_file
+    //! - test_signed_token
+    //!   tampered, expired and foreign tokens are rejected
+
+    use super::*;
+
+    #[test]
+    fn test_parse_token_file() {
+        let tokens = parse_token_file(
+            "# admins\nabc, alice, system:masters, dev\n\nbootstrap,kubelet-bootstrap\n",
+        )
+        .unwrap();
+        assert_eq!(
+            tokens["abc"],
+            UserInfo {
+                name: "alice".to_string(),
+                groups: vec!["system:masters".to_string(), "dev".to_string()],
+            }
+        );
+        assert!(tokens["bootstrap"].groups.is_empty());
+        assert!(parse_token_file("lonely").is_err());
+    }
+
+    #[test]
+    fn test_signed_token() {
+        let signer = TokenSigner::generate();
+        let node = UserInfo::node("n1");
+        let token = signer.sign(&node, None);
+        assert_eq!(signer.verify(&token), Some(node.clone()));
+        assert_eq!(signer.verify(&token).unwrap().node_name(), Some("n1"));
+
+        let (payload, _) = token.rsplit_once('.').unwrap();
+        let forged = signer.sign(&UserInfo::node("n2"), None);
+        let (_, signature) = forged.rsplit_once('.').unwrap();
+        assert_eq!(signer.verify(&format!("{}.{}", payload, signature)), None);
+
+        assert_eq!(TokenSigner::generate().verify(&token), None);
+        let expired = signer.sign(&node, Some(Duration::ZERO));
+        assert_eq!(signer.verify(&expired), None);
+    }
+}
@@ -0,0 +1,406 @@
+//! Role-based access control.
+//!
+//! Every request is described by a verb, a resource and, for namespaced
+//! resources, a namespace. Members of `system:masters` may do anything, other
+//! callers need a binding that grants them a role with a matching rule.
+//! Nodes and bootstrap tokens are bound to built-in cluster roles, the state
+//! further limits nodes to their own node, pods, volumes and the objects their pods use.
+
+use std::collections::HashMap;
+
+use actix_web::http::Method;
+use shared::models::rbac::{
+    ClusterRole, ClusterRoleBinding, DEFAULT_NAMESPACE, PolicyRule, Role, RoleBinding, RoleKind,
+    Subject, SubjectKind, WILDCARD,
+};
+
+use super::{MASTERS_GROUP, NODES_GROUP, SERVICE_ACCOUNT_PREFIX, UserInfo};
+
+/// Group of the bootstrap tokens nodes register with.
+pub const BOOTSTRAPPERS_GROUP: &str = "system:bootstrappers";
+
+/// Resources living in a namespace, the rest are cluster-scoped.
+const NAMESPACED: &[&str] = &[
+    "pods",
+    "replicasets",
+    "poddisruptionbudgets",
+    "podgroups",
+    "configmaps",
+    "secrets",
+    "persistentvolumeclaims",
+    "services",
+    "endpoints",
+    "ingresses",
+    "roles",
+    "rolebindings",
+];
+
+/// Action a request performs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attributes {
+    pub verb: String,
+    /// Collection, with the subresource for paths such as `/pods/{name}/logs`
+    pub resource: String,
+    pub name: Option<String>,
+    /// Set for namespaced resources
+    pub namespace: Option<String>,
+}
+
+impl Attributes {
+    pub fn new(verb: &str, resource: &str, name: Option<&str>) -> Self {
+        let collection = resource.split('/').next().unwrap_or_default();
+        Self {
+            verb: verb.to_string(),
+            resource: resource.to_string(),
+            name: name.map(str::to_string),
+            namespace: NAMESPACED
+                .contains(&collection)
+                .then(|| DEFAULT_NAMESPACE.to_string()),
+        }
+    }
+
+    /// Maps a route to its action, `GET /pods?watch=true` is a watch of pods.
+    pub fn from_request(method: &Method, path: &str, query: &str) -> Option<Self> {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        let watch = query.split('&').any(|pair| pair == "watch=true");
+        let (verb, resource, name) = match (method.as_str(), segments.as_slice()) {
+            ("GET", [resource]) if watch => ("watch", resource.to_string(), None),
+            ("GET", [resource]) => ("list", resource.to_string(), None),
+            ("POST", [resource]) => ("create", resource.to_string(), None),
+            (method, [resource, name]) => {
+                let verb = match method {
+                    "GET" => "get",
+                    "POST" => "create",
+                    "PUT" => "update",
+                    "PATCH" => "patch",
+                    "DELETE" => "delete",
+                    _ => return None,
+                };
+                (verb, resource.to_string(), Some(*name))
+            }
+            (method, [resource, name, subresource]) => {
+                let verb = match method {
+                    "GET" => "get",
+                    "POST" => "create",
+                    _ => return None,
+                };
+                (verb, format!("{}/{}", resource, subresource), Some(*name))
+            }
+            _ => return None,
+        };
+        if resource.is_empty() {
+            return None;
+        }
+        Some(Self::new(verb, &resource, name))
+    }
+}
+
+/// Whether a cluster role or binding name belongs to the built-in policy.
+pub fn is_builtin(name: &str) -> bool {
+    name == "cluster-admin" || name.starts_with("system:")
+}
+
+fn rule(verbs: &[&str], resources: &[&str]) -> PolicyRule {
+    PolicyRule {
+        verbs: verbs.iter().map(|v| v.to_string()).collect(),
+        resources: resources.iter().map(|r| r.to_string()).collect(),
+        resource_names: vec![],
+    }
+}
+
+/// Built-in cluster roles and the group each is granted to.
+fn builtin_roles() -> Vec<(&'static str, &'static str, Vec<PolicyRule>)> {
+    vec![
+        (
+            "cluster-admin",
+            MASTERS_GROUP,
+            vec![rule(&[WILDCARD], &[WILDCARD])],
+        ),
+        (
+            "system:node",
+            NODES_GROUP,
+            vec![
+                rule(&["create", "get", "list", "patch", "delete"], &["nodes"]),
+                rule(&["get", "list", "watch", "patch", "delete"], &["pods"]),
+                rule(&["create"], &["pods/eviction"]),
+                rule(&["get"], &["configmaps", "secrets"]),
+                rule(
+                    &["get", "list"],
+                    &["persistentvolumes", "persistentvolumeclaims"],
+                ),
+                rule(&["delete"], &["persistentvolumes"]),
+                rule(&["list", "watch"], &["services", "endpoints", "ingresses"]),
+            ],
+        ),
+        (
+            "system:node-bootstrapper",
+            BOOTSTRAPPERS_GROUP,
+            vec![rule(&["create"], &["nodes"])],
+        ),
+    ]
+}
+
+/// Snapshot of the roles and bindings requests are checked against.
+pub struct Policy {
+    roles: HashMap<String, Role>,
+    cluster_roles: HashMap<String, Vec<PolicyRule>>,
+    bindings: Vec<RoleBinding>,
+    cluster_bindings: Vec<(String, String, Vec<Subject>)>,
+}
+
+impl Policy {
+    pub fn new(
+        roles: Vec<Role>,
+        cluster_roles: Vec<ClusterRole>,
+        bindings: Vec<RoleBinding>,
+        cluster_bindings: Vec<ClusterRoleBinding>,
+    ) -> Self {
+        let mut policy = Self {
+            roles: roles
+                .into_iter()
+                .map(|r| (r.metadata.name.clone(), r))
+                .collect(),
+            cluster_roles: cluster_roles
+                .into_iter()
+                .map(|r| (r.metadata.name, r.spec.rules))
+                .collect(),
+            bindings,
+            cluster_bindings: cluster_bindings
+                .into_iter()
+                .filter(|b| b.spec.role_ref.kind == RoleKind::ClusterRole)
+                .map(|b| (b.metadata.name, b.spec.role_ref.name, b.spec.subjects))
+                .collect(),
+        };
+        for (name, group, rules) in builtin_roles() {
+            policy.cluster_roles.insert(name.to_string(), rules);
+            let subject = Subject {
+                kind: SubjectKind::Group,
+                name: group.to_string(),
+            };
+            policy
+                .cluster_bindings
+                .push((name.to_string(), name.to_string(), vec![subject]));
+        }
+        policy
+    }
+
+    /// Name of the binding allowing the action, or why it is denied.
+    pub fn authorize(&self, user: &UserInfo, attrs: &Attributes) -> Result<String, String> {
+        if user.in_group(MASTERS_GROUP) {
+            return Ok(format!("{} is a member of {}", user.name, MASTERS_GROUP));
+        }
+        let allows = |rules: &[PolicyRule]| {
+            rules
+                .iter()
+                .any(|r| r.allows(&attrs.verb, &attrs.resource, attrs.name.as_deref()))
+        };
+
+        for (name, role, subjects) in &self.cluster_bindings {
+            if subjects.iter().any(|s| subject_matches(s, user))
+                && self.cluster_roles.get(role).is_some_and(|r| allows(r))
+            {
+                return Ok(format!("allowed by ClusterRoleBinding {}", name));
+            }
+        }
+        if let Some(namespace) = &attrs.namespace {
+            for binding in &self.bindings {
+                if &binding.spec.namespace != namespace
+                    || !binding
+                        .spec
+                        .subjects
+                        .iter()
+                        .any(|s| subject_matches(s, user))
+                {
+                    continue;
+                }
+                let role_ref = &binding.spec.role_ref;
+                let rules = match role_ref.kind {
+                    RoleKind::Role => self
+                        .roles
+                        .get(&role_ref.name)
+                        .filter(|role| &role.spec.namespace == namespace)
+                        .map(|role| role.spec.rules.as_slice()),
+                    RoleKind::ClusterRole => {
+                        self.cluster_roles.get(&role_ref.name).map(Vec::as_slice)
+                    }
+                };
+                if rules.is_some_and(allows) {
+                    return Ok(format!("allowed by RoleBinding {}", binding.metadata.name));
+                }
+            }
+        }
+
+        let target = match &attrs.name {
+            Some(name) => format!("{} {}", attrs.resource, name),
+            None => attrs.resource.clone(),
+        };
+        Err(match &attrs.namespace {
+            Some(namespace) => format!(
+                "{} cannot {} {} in namespace {}",
+                user.name, attrs.verb, target, namespace
+            ),
+            None => format!("{} cannot {} {}", user.name, attrs.verb, target),
+        })
+    }
+}
+
+fn subject_matches(subject: &Subject, user: &UserInfo) -> bool {
+    match subject.kind {
+        SubjectKind::User => subject.name == user.name,
+        SubjectKind::Group => user.in_group(&subject.name),
+        SubjectKind::ServiceAccount => user
+            .name
+            .strip_prefix(SERVICE_ACCOUNT_PREFIX)
+            .is_some_and(|name| name == subject.name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! - test_attributes
+    //!   verbs, subresources and namespaces derived from routes
+    //! - test_authorize
+    //!   role in the binding namespace, cluster role, names and built-in node role
+
+    use super::*;
+    use shared::models::{
+        metadata::Metadata,
+        rbac::{ClusterRoleSpec, RoleBindingSpec, RoleRef, RoleSpec},
+    };
+
+    #[test]
+    fn test_attributes() {
+        let attrs = Attributes::from_request(&Method::GET, "/pods", "watch=true&nodeName=n1");
+        assert_eq!(attrs, Some(Attributes::new("watch", "pods", None)));
+        assert_eq!(attrs.unwrap().namespace.as_deref(), Some(DEFAULT_NAMESPACE));
+
+        let attrs = Attributes::from_request(&Method::POST, "/pods/web/eviction", "").unwrap();
+        assert_eq!(
+            attrs,
+            Attributes::new("create", "pods/eviction", Some("web"))
+        );
+
+        let attrs = Attributes::from_request(&Method::DELETE, "/nodes/n1", "").unwrap();
+        assert_eq!(attrs.verb, "delete");
+        assert_eq!(attrs.namespace, None);
+
+        assert_eq!(
+            Attributes::from_request(&Method::PUT, "/pods/a/logs", ""),
+            None
+        );
+        assert_eq!(Attributes::from_request(&Method::GET, "/", ""), None);
+    }
+
+    fn metadata(name: &str) -> Metadata {
+        Metadata {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn binding(name: &str, namespace: &str, kind: RoleKind, role: &str) -> RoleBinding {
+        RoleBinding {
+            metadata: metadata(name),
+            spec: RoleBindingSpec {
+                namespace: namespace.to_string(),
+                role_ref: RoleRef {
+                    kind,
+                    name: role.to_string(),
+                },
+                subjects: vec![Subject {
+                    kind: SubjectKind::ServiceAccount,
+                    name: "ci".to_string(),
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn test_authorize() {
+        let deployer = Role {
+            metadata: metadata("deployer"),
+            spec: RoleSpec {
+                namespace: DEFAULT_NAMESPACE.to_string(),
+                rules: vec![rule(&["create", "list"], &["replicasets"])],
+            },
+        };
+        let elsewhere = Role {
+            metadata: metadata("elsewhere"),
+            spec: RoleSpec {
+                namespace: "staging".to_string(),
+                rules: vec![rule(&[WILDCARD], &[WILDCARD])],
+            },
+        };
+        let reader = ClusterRole {
+            metadata: metadata("config-reader"),
+            spec: ClusterRoleSpec {
+                rules: vec![PolicyRule {
+                    resource_names: vec!["app".to_string()],
+                    ..rule(&["get"], &["configmaps"])
+                }],
+            },
+        };
+        let policy = Policy::new(
+            vec![deployer, elsewhere],
+            vec![reader],
+            vec![
+                binding("ci-deployer", DEFAULT_NAMESPACE, RoleKind::Role, "deployer"),
+                binding(
+                    "ci-elsewhere",
+                    DEFAULT_NAMESPACE,
+                    RoleKind::Role,
+                    "elsewhere",
+                ),
+                binding(
+                    "ci-reader",
+                    DEFAULT_NAMESPACE,
+                    RoleKind::ClusterRole,
+                    "config-reader",
+                ),
+            ],
+            vec![],
+        );
+        let ci = UserInfo::service_account("ci", vec![]);
+
+        let create = Attributes::new("create", "replicasets", None);
+        assert_eq!(
+            policy.authorize(&ci, &create),
+            Ok("allowed by RoleBinding ci-deployer".to_string())
+        );
+        assert!(
+            policy
+                .authorize(&ci, &Attributes::new("delete", "nodes", Some("n1")))
+                .is_err()
+        );
+        assert!(
+            policy
+                .authorize(&ci, &Attributes::new("get", "configmaps", Some("app")))
+                .is_ok()
+        );
+        assert!(
+            policy
+                .authorize(&ci, &Attributes::new("get", "configmaps", Some("db")))
+                .is_err()
+        );
+        // the role lives in another namespace than its binding
+        assert!(
+            policy
+                .authorize(&ci, &Attributes::new("delete", "pods", Some("web")))
+                .is_err()
+        );
+        let other = UserInfo::service_account("other", vec![]);
+        assert!(policy.authorize(&other, &create).is_err());
+
+        let node = UserInfo::node("n1");
+        assert!(
+            policy
+                .authorize(&node, &Attributes::new("watch", "pods", None))
+                .is_ok()
+        );
+        assert!(
+            policy
+                .authorize(&node, &Attributes::new("create", "replicasets", None))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! TLS serving and client certificate authentication.
+//!
+//! The apiserver serves HTTPS when `CR8S_TLS_CERT` and `CR8S_TLS_KEY` are set,
+//! or with a certificate from the cluster CA for `localhost`, its hostname and
+//! the comma separated `CR8S_TLS_SANS`.
+//! With `CR8S_CLIENT_CA` too, clients may present a certificate signed by that
+//! CA: the common name is the user and the organizations are its groups.
+//! Clients without one fall back to bearer tokens.
+
+use std::{any::Any, sync::Arc};
+
+use actix_tls::accept::rustls_0_23::TlsStream;
+use actix_web::{dev::Extensions, rt::net::TcpStream};
+use rustls::{
+    RootCertStore, ServerConfig,
+    crypto::ring,
+    pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+    server::WebPkiClientVerifier,
+};
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+use super::{UserInfo, ca::ClusterCa};
+
+/// Leaf certificate a client presented, already verified against the client CA.
+#[derive(Clone)]
+pub struct PeerCertificate(pub CertificateDer<'static>);
+
+/// Server config from the environment, `None` to serve plain HTTP.
+pub fn server_config(ca: Option<&ClusterCa>) -> Result<Option<ServerConfig>, String> {
+    let (chain, key) = match (
+        std::env::var("CR8S_TLS_CERT"),
+        std::env::var("CR8S_TLS_KEY"),
+    ) {
+        (Ok(cert), Ok(key)) => {
+            let chain = CertificateDer::pem_file_iter(&cert)
+                .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+                .map_err(|e| format!("Invalid CR8S_TLS_CERT: {}", e))?;
+            let key = PrivateKeyDer::from_pem_file(&key)
+                .map_err(|e| format!("Invalid CR8S_TLS_KEY: {}", e))?;
+            (chain, key)
+        }
+        _ => match ca {
+            Some(ca) => ca.issue_apiserver(apiserver_sans())?,
+            None => {
+                tracing::warn!(
+                    "Neither a serving certificate nor a cluster CA, serving plain HTTP"
+                );
+                return Ok(None);
+            }
+        },
+    };
+    let client_ca = std::env::var("CR8S_CLIENT_CA").ok();
+    build_config(chain, key, client_ca.as_deref()).map(Some)
+}
+
+pub fn build_config(
+    chain: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
+    client_ca: Option<&str>,
+) -> Result<ServerConfig, String> {
+    let provider = Arc::new(ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| e.to_string())?;
+    let builder = match client_ca {
+        Some(path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in CertificateDer::pem_file_iter(path)
+                .map_err(|e| format!("Invalid CR8S_CLIENT_CA: {}", e))?
+            {
+                let cert = cert.map_err(|e| format!("Invalid CR8S_CLIENT_CA: {}", e))?;
+                roots.add(cert).map_err(|e| e.to_string())?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .allow_unauthenticated()
+                .build()
+                .map_err(|e| e.to_string())?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+    builder
+        .with_single_cert(chain, key)
+        .map_err(|e| e.to_string())
+}
+
+/// Hosts and IPs the apiserver certificate is issued for.
+pub fn apiserver_sans() -> Vec<String> {
+    let mut sans = vec!["localhost".to_string(), "127.0.0.1".to_string()];
+    sans.extend(std::env::var("HOSTNAME").ok());
+    if let Ok(extra) = std::env::var("CR8S_TLS_SANS") {
+        sans.extend(
+            extra
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string),
+        );
+    }
+    sans
+}
+
+/// Keeps the client certificate of a TLS connection for the authentication middleware.
+pub fn on_connect(conn: &dyn Any, ext: &mut Extensions) {
+    let Some(stream) = conn.downcast_ref::<TlsStream<TcpStream>>() else {
+        return;
+    };
+    let (_, session) = stream.get_ref();
+    if let Some(cert) = session.peer_certificates().and_then(|certs| certs.first()) {
+        ext.insert(PeerCertificate(cert.clone().into_owned()));
+    }
+}
+
+/// User named by a client certificate, CN as the name and O as the groups.
+pub fn identity(cert: &CertificateDer<'_>) -> Option<UserInfo> {
+    let (_, cert) = X509Certificate::from_der(cert.as_ref()).ok()?;
+    let subject = cert.subject();
+    let name = subject
+        .iter_common_name()
+        .next()?
+        .as_str()
+        .ok()?
+        .to_string();
+    let groups = subject
+        .iter_organization()
+        .filter_map(|o| o.as_str().ok().map(str::to_string))
+        .collect();
+    Some(UserInfo { name, groups })
+}
+
+#[cfg(test)]
+mod tests {
+    //! - test_identity
+    //!   common name is the user, organizations its groups
+
+    use super::*;
+    use rcgen::{CertificateParams, DnType, KeyPair};
+
+    #[test]
+    fn test_identity() {
+        let mut params = CertificateParams::new(vec![]).unwrap();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "system:node:n1");
+        params
+            .distinguished_name
+            .push(DnType::OrganizationName, "system:nodes");
+        let cert = params.self_signed(&KeyPair::generate().unwrap()).unwrap();
+
+        let user = identity(cert.der()).unwrap();
+        assert_eq!(user, UserInfo::node("n1"));
+        assert_eq!(user.node_name(), Some("n1"));
+    }
+}
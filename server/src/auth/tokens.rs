@@ -0,0 +1,109 @@
+//! Signed bearer tokens, JWTs with an HMAC-SHA256 signature.
+//!
+//! The apiserver issues them to nodes when they register and to service
+//! accounts on request. The signing key is read from `CR8S_AUTH_SIGNING_KEY`,
+//! 32 bytes encoded as base64, without one the tokens die with the process.
+
+use std::time::Duration;
+
+use base64::{
+    Engine,
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+};
+use chrono::Utc;
+use ring::{
+    hmac,
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::UserInfo;
+
+/// Base64 of `{"alg":"HS256","typ":"JWT"}`.
+const HEADER: &str = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9";
+
+#[derive(Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    #[serde(default)]
+    groups: Vec<String>,
+    iat: i64,
+    /// Random id, tokens issued in the same second still differ
+    #[serde(default)]
+    jti: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    exp: Option<i64>,
+}
+
+pub struct TokenSigner {
+    key: hmac::Key,
+}
+
+impl TokenSigner {
+    pub fn new(secret: &[u8]) -> Self {
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, secret),
+        }
+    }
+
+    /// Signer with a random key.
+    pub fn generate() -> Self {
+        let mut secret = [0u8; 32];
+        SystemRandom::new()
+            .fill(&mut secret)
+            .expect("Failed to generate signing key");
+        Self::new(&secret)
+    }
+
+    /// Reads the base64 key from `CR8S_AUTH_SIGNING_KEY`, a random one is used without it.
+    pub fn from_env() -> Result<Self, String> {
+        let Ok(encoded) = std::env::var("CR8S_AUTH_SIGNING_KEY") else {
+            tracing::warn!("CR8S_AUTH_SIGNING_KEY not set, signed tokens expire on restart");
+            return Ok(Self::generate());
+        };
+        let secret = STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| format!("CR8S_AUTH_SIGNING_KEY is not valid base64: {}", e))?;
+        if secret.len() < 32 {
+            return Err("CR8S_AUTH_SIGNING_KEY must be at least 32 bytes".to_string());
+        }
+        Ok(Self::new(&secret))
+    }
+
+    /// Token for the user, valid for `ttl` or until the key changes.
+    pub fn sign(&self, user: &UserInfo, ttl: Option<Duration>) -> String {
+        let now = Utc::now().timestamp();
+        let claims = Claims {
+            sub: user.name.clone(),
+            groups: user.groups.clone(),
+            iat: now,
+            jti: Uuid::new_v4().simple().to_string(),
+            exp: ttl.map(|ttl| now.saturating_add(ttl.as_secs() as i64)),
+        };
+        let claims = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims).unwrap_or_default());
+        let payload = format!("{}.{}", HEADER, claims);
+        let signature = hmac::sign(&self.key, payload.as_bytes());
+        format!("{}.{}", payload, URL_SAFE_NO_PAD.encode(signature.as_ref()))
+    }
+
+    /// User of a token signed with this key that has not expired.
+    pub fn verify(&self, token: &str) -> Option<UserInfo> {
+        let (payload, signature) = token.rsplit_once('.')?;
+        let (header, claims) = payload.split_once('.')?;
+        if header != HEADER {
+            return None;
+        }
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+        hmac::verify(&self.key, payload.as_bytes(), &signature).ok()?;
+
+        let claims: Claims = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims).ok()?).ok()?;
+        if claims.exp.is_some_and(|exp| exp <= Utc::now().timestamp()) {
+            return None;
+        }
+        Some(UserInfo {
+            name: claims.sub,
+            groups: claims.groups,
+        })
+    }
+}
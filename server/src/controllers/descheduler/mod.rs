@@ -0,0 +1,392 @@
+//! Descheduler controller
+//!
+//! Periodically evaluates the configured policies against the scheduler's
+//! view of the cluster and evicts controller owned pods so they get
+//! recreated and placed again. Evictions go through the eviction
+//! subresource so disruption budgets are respected.
+
+use std::{collections::HashSet, env, sync::Arc, time::Duration};
+
+use reqwest::StatusCode;
+use uuid::Uuid;
+
+use crate::controllers::scheduler;
+
+use policies::{Policy, Thresholds, evictable};
+
+mod policies;
+
+/// Descheduler settings loaded from environment variables.
+#[derive(Debug, Clone)]
+pub struct DeschedulerConfig {
+    pub interval: Duration,
+    pub max_evictions_per_cycle: usize,
+    pub policies: Vec<Policy>,
+    pub thresholds: Thresholds,
+}
+
+impl DeschedulerConfig {
+    /// - `CR8S_DESCHEDULER_INTERVAL`: seconds between cycles
+    /// - `CR8S_DESCHEDULER_MAX_EVICTIONS`: evictions allowed per cycle
+    /// - `CR8S_DESCHEDULER_POLICIES`: comma separated policy names
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Some(secs) = env::var("CR8S_DESCHEDULER_INTERVAL")
+            .ok()
+            .and_then(|s| s.parse().ok())
+        {
+            config.interval = Duration::from_secs(secs);
+        }
+
+        if let Some(max) = env::var("CR8S_DESCHEDULER_MAX_EVICTIONS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+        {
+            config.max_evictions_per_cycle = max;
+        }
+
+        if let Ok(val) = env::var("CR8S_DESCHEDULER_POLICIES") {
+            config.policies = val
+                .split(',')
+                .filter_map(|s| {
+                    Policy::try_from(s)
+                        .inspect_err(|_| tracing::warn!(policy=%s, "Unknown descheduler policy"))
+                        .ok()
+                })
+                .collect();
+        }
+
+        config
+    }
+}
+
+impl Default for DeschedulerConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60),
+            max_evictions_per_cycle: 5,
+            policies: Policy::ALL.to_vec(),
+            thresholds: Thresholds {
+                low: 0.2,
+                high: 0.5,
+            },
+        }
+    }
+}
+
+pub struct Descheduler {
+    state: scheduler::State,
+    config: DeschedulerConfig,
+    pods_uri: String,
+}
+
+impl Descheduler {
+    fn new(apiserver: String, state: scheduler::State, config: DeschedulerConfig) -> Arc<Self> {
+        Arc::new(Self {
+            state,
+            config,
+            pods_uri: format!("{}/pods", apiserver),
+        })
+    }
+
+    /// Runs the descheduler on the scheduler's cluster view.
+    pub async fn run(apiserver: String, state: scheduler::State) {
+        tracing::debug!("Running");
+        let desched = Descheduler::new(apiserver, state, DeschedulerConfig::from_env());
+        let mut ticker = tokio::time::interval(desched.config.interval);
+        // skip the immediate first tick, the scheduler is still catching up
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            let evicted = desched.cycle().await;
+            if !evicted.is_empty() {
+                tracing::info!(count=%evicted.len(), "Descheduler cycle evicted pods");
+            }
+        }
+    }
+
+    /// Evaluates every policy and evicts up to the per cycle limit.
+    /// Returns the names of the evicted pods.
+    async fn cycle(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut evicted = Vec::new();
+        for policy in self.config.policies.iter() {
+            for id in policy.evaluate(&self.state, &self.config.thresholds) {
+                if evicted.len() >= self.config.max_evictions_per_cycle {
+                    tracing::debug!("Eviction limit reached for this cycle");
+                    return evicted;
+                }
+                if !seen.insert(id) {
+                    continue;
+                }
+                if let Some(name) = self.evict(&id, policy).await {
+                    evicted.push(name);
+                }
+            }
+        }
+        evicted
+    }
+
+    async fn evict(&self, id: &Uuid, policy: &Policy) -> Option<String> {
+        let pod = self.state.pods.get(id).map(|p| p.clone())?;
+        if !evictable(&pod) {
+            return None;
+        }
+        let name = pod.metadata.name;
+        let url = format!("{}/{}/eviction", self.pods_uri, name);
+        match shared::utils::client().post(&url).send().await {
+            Ok(resp) if resp.status().is_success() => {
+                tracing::info!(pod=%name, ?policy, "Evicted");
+                Some(name)
+            }
+            Ok(resp) if resp.status() == StatusCode::TOO_MANY_REQUESTS => {
+                tracing::debug!(pod=%name, "Eviction blocked by disruption budget");
+                None
+            }
+            Ok(resp) => {
+                tracing::warn!(pod=%name, status=%resp.status(), "Eviction refused");
+                None
+            }
+            Err(err) => {
+                tracing::error!(pod=%name, error=%err, "Eviction failed");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! - test_remove_duplicates
+    //!   only the newest of two replicas on one node is evicted
+    //! - test_low_node_utilization
+    //!   moves pods off the hot node until it is under the high threshold
+    //! - test_untolerated_taint
+    //! - test_anti_affinity_violation
+    //! - test_skips_pods_without_controller
+    //! - test_skips_terminating_pods
+    //!   pods with a deletion timestamp are not evicted again
+    //! - test_max_evictions_per_cycle
+    //! - test_respects_disruption_budget
+    //!   a 429 from the eviction endpoint does not count as an eviction
+
+    use super::*;
+    use shared::models::{
+        metadata::{LabelSelector, OwnerKind, OwnerReference},
+        node::{Node, NodeSpec, Taint, TaintEffect},
+        pod::Pod,
+    };
+    use std::collections::HashMap;
+    use wiremock::matchers::{method, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::controllers::scheduler::{SchedulerState, SimResources};
+
+    async fn setup(policies: Vec<Policy>) -> (MockServer, Arc<Descheduler>) {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/pods/[^/]+/eviction$"))
+            .respond_with(ResponseTemplate::new(201))
+            .mount(&server)
+            .await;
+
+        let state = SchedulerState::new_with_extenders(&server.uri(), vec![]);
+        let config = DeschedulerConfig {
+            policies,
+            ..Default::default()
+        };
+        let desched = Descheduler::new(server.uri(), state, config);
+        (server, desched)
+    }
+
+    fn add_node(desched: &Descheduler, name: &str, taints: Vec<Taint>) {
+        desched.state.add_node(&Node {
+            name: name.to_string(),
+            spec: NodeSpec {
+                taints,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        desched.state.node_resources.insert(
+            name.to_string(),
+            SimResources {
+                cpu: 1000,
+                mem: 1000,
+            },
+        );
+    }
+
+    fn bind_pod(desched: &Descheduler, node: &str, owner: Option<Uuid>, cpu: u64) -> Pod {
+        let mut pod = Pod::default();
+        pod.metadata.owner_reference = owner.map(|id| OwnerReference {
+            id,
+            name: "rs".to_string(),
+            kind: OwnerKind::ReplicaSet,
+            controller: true,
+        });
+        desched.state.add_pod(&pod);
+        desched
+            .state
+            .pod_resources
+            .insert(pod.metadata.id, SimResources { cpu, mem: cpu });
+        desched.state.assign_pod(&pod.metadata.id, node);
+        pod
+    }
+
+    async fn evicted_paths(server: &MockServer) -> Vec<String> {
+        server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|r| r.url.path().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_remove_duplicates() {
+        let (server, desched) = setup(vec![Policy::RemoveDuplicates]).await;
+        add_node(&desched, "n1", vec![]);
+        add_node(&desched, "n2", vec![]);
+        let owner = Some(Uuid::new_v4());
+        let _first = bind_pod(&desched, "n1", owner, 100);
+        let mut second = bind_pod(&desched, "n1", owner, 100);
+        second.metadata.created_at += chrono::Duration::seconds(1);
+        desched
+            .state
+            .pods
+            .insert(second.metadata.id, second.clone());
+
+        let evicted = desched.cycle().await;
+
+        assert_eq!(evicted, vec![second.metadata.name.clone()]);
+        assert_eq!(
+            evicted_paths(&server).await,
+            vec![format!("/pods/{}/eviction", second.metadata.name)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_low_node_utilization() {
+        let (_server, desched) = setup(vec![Policy::LowNodeUtilization]).await;
+        add_node(&desched, "hot", vec![]);
+        add_node(&desched, "cold", vec![]);
+        let owner = Some(Uuid::new_v4());
+        for _ in 0..4 {
+            bind_pod(&desched, "hot", owner, 200);
+        }
+
+        // 800/1000 used, two pods bring it down to 400/1000
+        let evicted = desched.cycle().await;
+        assert_eq!(evicted.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_untolerated_taint() {
+        let (_server, desched) = setup(vec![Policy::RemovePodsViolatingNodeTaints]).await;
+        let taint = Taint {
+            key: "gpu".to_string(),
+            value: "".to_string(),
+            effect: TaintEffect::NoExecute,
+        };
+        add_node(&desched, "n1", vec![taint]);
+        let owner = Some(Uuid::new_v4());
+        let victim = bind_pod(&desched, "n1", owner, 100);
+
+        let mut tolerating = bind_pod(&desched, "n1", owner, 100);
+        tolerating.spec.tolerations = vec![shared::models::pod::Toleration {
+            key: "gpu".to_string(),
+            ..Default::default()
+        }];
+        desched
+            .state
+            .pods
+            .insert(tolerating.metadata.id, tolerating.clone());
+
+        assert_eq!(desched.cycle().await, vec![victim.metadata.name]);
+    }
+
+    #[tokio::test]
+    async fn test_anti_affinity_violation() {
+        let (_server, desched) = setup(vec![Policy::RemovePodsViolatingAntiAffinity]).await;
+        add_node(&desched, "n1", vec![]);
+        let mut db = bind_pod(&desched, "n1", Some(Uuid::new_v4()), 100);
+        db.metadata
+            .labels
+            .insert("app".to_string(), "db".to_string());
+        db.spec.anti_affinity = Some(LabelSelector {
+            match_labels: HashMap::from([("app".to_string(), "db".to_string())]),
+        });
+        desched.state.pods.insert(db.metadata.id, db.clone());
+        let mut other = bind_pod(&desched, "n1", Some(Uuid::new_v4()), 100);
+        other.metadata.labels = db.metadata.labels.clone();
+        desched.state.pods.insert(other.metadata.id, other.clone());
+
+        assert_eq!(desched.cycle().await, vec![db.metadata.name]);
+    }
+
+    #[tokio::test]
+    async fn test_skips_pods_without_controller() {
+        let (server, desched) = setup(vec![Policy::RemovePodsViolatingNodeTaints]).await;
+        let taint = Taint::try_from("dedicated=infra:NoSchedule").unwrap();
+        add_node(&desched, "n1", vec![taint]);
+        bind_pod(&desched, "n1", None, 100);
+
+        assert!(desched.cycle().await.is_empty());
+        assert!(evicted_paths(&server).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_skips_terminating_pods() {
+        let (server, desched) = setup(vec![Policy::RemovePodsViolatingNodeTaints]).await;
+        let taint = Taint::try_from("dedicated:NoExecute").unwrap();
+        add_node(&desched, "n1", vec![taint]);
+        let mut terminating = bind_pod(&desched, "n1", Some(Uuid::new_v4()), 10);
+        terminating.metadata.deletion_timestamp = Some(chrono::Utc::now());
+        desched
+            .state
+            .pods
+            .insert(terminating.metadata.id, terminating.clone());
+        let victim = bind_pod(&desched, "n1", Some(Uuid::new_v4()), 10);
+
+        assert_eq!(desched.cycle().await, vec![victim.metadata.name.clone()]);
+        assert_eq!(
+            evicted_paths(&server).await,
+            vec![format!("/pods/{}/eviction", victim.metadata.name)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_max_evictions_per_cycle() {
+        let (server, desched) = setup(vec![Policy::RemovePodsViolatingNodeTaints]).await;
+        let taint = Taint::try_from("dedicated:NoExecute").unwrap();
+        add_node(&desched, "n1", vec![taint]);
+        for _ in 0..8 {
+            bind_pod(&desched, "n1", Some(Uuid::new_v4()), 10);
+        }
+
+        let evicted = desched.cycle().await;
+        assert_eq!(evicted.len(), desched.config.max_evictions_per_cycle);
+        assert_eq!(evicted_paths(&server).await.len(), evicted.len());
+    }
+
+    #[tokio::test]
+    async fn test_respects_disruption_budget() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/pods/[^/]+/eviction$"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&server)
+            .await;
+        let state = SchedulerState::new_with_extenders(&server.uri(), vec![]);
+        let desched = Descheduler::new(server.uri(), state, DeschedulerConfig::default());
+        let taint = Taint::try_from("dedicated:NoExecute").unwrap();
+        add_node(&desched, "n1", vec![taint]);
+        bind_pod(&desched, "n1", Some(Uuid::new_v4()), 10);
+
+        assert!(desched.cycle().await.is_empty());
+        assert_eq!(evicted_paths(&server).await.len(), 1);
+    }
+}
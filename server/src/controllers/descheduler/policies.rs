@@ -0,0 +1,211 @@
+//! Descheduler policies
+//!
+//! Each policy inspects the scheduler's view of the cluster and proposes
+//! pods to evict. Only pods managed by a controller are ever proposed.
+
+use std::collections::{HashMap, HashSet};
+
+use shared::models::pod::Pod;
+use uuid::Uuid;
+
+use crate::controllers::scheduler::{SimResources, State};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Policy {
+    /// Move pods from overutilized nodes while underutilized ones exist
+    LowNodeUtilization,
+    /// Spread pods of the same owner that ended up on one node
+    RemoveDuplicates,
+    /// Evict pods sharing a node with pods their anti-affinity selects
+    RemovePodsViolatingAntiAffinity,
+    /// Evict pods on nodes with taints they do not tolerate
+    RemovePodsViolatingNodeTaints,
+}
+
+impl Policy {
+    pub const ALL: [Policy; 4] = [
+        Policy::LowNodeUtilization,
+        Policy::RemoveDuplicates,
+        Policy::RemovePodsViolatingAntiAffinity,
+        Policy::RemovePodsViolatingNodeTaints,
+    ];
+
+    /// Pods proposed for eviction, most relevant first.
+    pub fn evaluate(&self, state: &State, thresholds: &Thresholds) -> Vec<Uuid> {
+        match self {
+            Policy::LowNodeUtilization => low_node_utilization(state, thresholds),
+            Policy::RemoveDuplicates => remove_duplicates(state),
+            Policy::RemovePodsViolatingAntiAffinity => violating_anti_affinity(state),
+            Policy::RemovePodsViolatingNodeTaints => violating_taints(state),
+        }
+    }
+}
+
+impl TryFrom<&str> for Policy {
+    type Error = ();
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        Policy::ALL
+            .into_iter()
+            .find(|p| format!("{:?}", p) == input.trim())
+            .ok_or(())
+    }
+}
+
+/// Utilization fractions for LowNodeUtilization.
+/// Nodes below `low` in cpu and memory are underutilized,
+/// nodes above `high` in either are overutilized.
+#[derive(Debug, Clone)]
+pub struct Thresholds {
+    pub low: f64,
+    pub high: f64,
+}
+
+/// Only pods owned by a controller get recreated after eviction,
+/// pods already terminating are on their way out.
+pub fn evictable(pod: &Pod) -> bool {
+    pod.metadata.deletion_timestamp.is_none()
+        && pod
+            .metadata
+            .owner_reference
+            .as_ref()
+            .is_some_and(|owner| owner.controller)
+}
+
+/// Pods bound to each node, by node name.
+fn pods_by_node(state: &State) -> HashMap<String, Vec<Pod>> {
+    let mut by_node: HashMap<String, Vec<Pod>> = HashMap::new();
+    for entry in state.nodes.iter() {
+        let pods = state
+            .pod_map
+            .get(entry.key())
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| state.pods.get(&id).map(|p| p.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        by_node.insert(entry.key().clone(), pods);
+    }
+    by_node
+}
+
+fn low_node_utilization(state: &State, thresholds: &Thresholds) -> Vec<Uuid> {
+    let mut usage = Vec::new();
+    for (node, pods) in pods_by_node(state) {
+        let Some(free) = state.node_resources.get(&node).map(|r| r.clone()) else {
+            continue;
+        };
+        let mut used = SimResources { cpu: 0, mem: 0 };
+        for pod in pods.iter() {
+            if let Some(res) = state.pod_resources.get(&pod.metadata.id) {
+                used.add(&res);
+            }
+        }
+        let mut capacity = free;
+        capacity.add(&used);
+        usage.push((node, pods, used, capacity));
+    }
+
+    let ratio = |used: u64, capacity: u64| match capacity {
+        0 => 0.0,
+        c => used as f64 / c as f64,
+    };
+    let utilization = |used: &SimResources, capacity: &SimResources| {
+        (ratio(used.cpu, capacity.cpu), ratio(used.mem, capacity.mem))
+    };
+
+    // room left on underutilized nodes before they become overutilized
+    let mut headroom = SimResources { cpu: 0, mem: 0 };
+    for (_, _, used, capacity) in usage.iter() {
+        let (cpu, mem) = utilization(used, capacity);
+        if cpu < thresholds.low && mem < thresholds.low {
+            headroom.add(&SimResources {
+                cpu: ((capacity.cpu as f64 * thresholds.high) as u64).saturating_sub(used.cpu),
+                mem: ((capacity.mem as f64 * thresholds.high) as u64).saturating_sub(used.mem),
+            });
+        }
+    }
+    if headroom.cpu == 0 && headroom.mem == 0 {
+        return Vec::new();
+    }
+
+    let mut victims = Vec::new();
+    for (node, mut pods, mut used, capacity) in usage {
+        let (cpu, mem) = utilization(&used, &capacity);
+        if cpu <= thresholds.high && mem <= thresholds.high {
+            continue;
+        }
+        pods.retain(evictable);
+        pods.sort_by_key(|p| p.spec.priority);
+        for pod in pods {
+            let (cpu, mem) = utilization(&used, &capacity);
+            if cpu <= thresholds.high && mem <= thresholds.high {
+                break;
+            }
+            let Some(res) = state.pod_resources.get(&pod.metadata.id).map(|r| r.clone()) else {
+                continue;
+            };
+            if !headroom.fits(&res) {
+                break;
+            }
+            tracing::debug!(%node, pod=%pod.metadata.name, "Node overutilized");
+            headroom.sub(&res);
+            used.sub(&res);
+            victims.push(pod.metadata.id);
+        }
+    }
+    victims
+}
+
+fn remove_duplicates(state: &State) -> Vec<Uuid> {
+    // nowhere else to go
+    if state.nodes.len() < 2 {
+        return Vec::new();
+    }
+    let mut victims = Vec::new();
+    for (_, mut pods) in pods_by_node(state) {
+        pods.retain(evictable);
+        pods.sort_by_key(|p| p.metadata.created_at);
+        let mut owners = HashSet::new();
+        for pod in pods {
+            let Some(owner) = pod.metadata.owner_reference.as_ref().map(|o| o.id) else {
+                continue;
+            };
+            if !owners.insert(owner) {
+                victims.push(pod.metadata.id);
+            }
+        }
+    }
+    victims
+}
+
+fn violating_anti_affinity(state: &State) -> Vec<Uuid> {
+    let mut victims = Vec::new();
+    for (_, pods) in pods_by_node(state) {
+        for pod in pods.iter().filter(|p| evictable(p)) {
+            let conflict = pods
+                .iter()
+                .any(|other| other.metadata.id != pod.metadata.id && pod.repels(other));
+            if conflict {
+                victims.push(pod.metadata.id);
+            }
+        }
+    }
+    victims
+}
+
+fn violating_taints(state: &State) -> Vec<Uuid> {
+    let mut victims = Vec::new();
+    for (node, pods) in pods_by_node(state) {
+        let Some(taints) = state.nodes.get(&node).map(|n| n.spec.taints.clone()) else {
+            continue;
+        };
+        for pod in pods.into_iter().filter(evictable) {
+            if !pod.spec.tolerates(&taints) {
+                victims.push(pod.metadata.id);
+            }
+        }
+    }
+    victims
+}
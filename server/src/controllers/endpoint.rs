@@ -0,0 +1,279 @@
+//! Endpoints controller
+//!
+//! Watch services and pods, keep the ready backends of each service published.
+//! Pods are looked up by the service selector through the apiserver label index.
+
+use std::sync::Arc;
+
+use dashmap::{DashMap, DashSet};
+use shared::{
+    api::{EventType, PodEvent, ServiceEvent},
+    models::{
+        endpoints::{EndpointAddress, EndpointPort},
+        pod::Pod,
+        service::Service,
+    },
+    utils::watch_stream,
+};
+use tokio::sync::mpsc;
+
+pub struct EndpointsController {
+    services: DashMap<String, Service>,
+    /// Services waiting in the channel, so bursts of pod events sync once
+    pending: DashSet<String>,
+    pods_uri: String,
+    services_uri: String,
+    endpoints_uri: String,
+    tx: mpsc::Sender<String>,
+}
+
+impl EndpointsController {
+    fn new(apiserver: String) -> (Arc<Self>, mpsc::Receiver<String>) {
+        let (tx, rx) = mpsc::channel::<String>(100);
+        (
+            Arc::new(Self {
+                services: DashMap::new(),
+                pending: DashSet::new(),
+                tx,
+                pods_uri: format!("{}/pods", apiserver),
+                services_uri: format!("{}/services", apiserver),
+                endpoints_uri: format!("{}/endpoints", apiserver),
+            }),
+            rx,
+        )
+    }
+
+    pub async fn run(apiserver: String) {
+        tracing::debug!("Running");
+        let (epc, mut rx) = EndpointsController::new(apiserver);
+        let _ = tokio::try_join!(
+            // Watch services
+            {
+                let epc = epc.clone();
+                tokio::spawn(async move {
+                    watch_stream(&format!("{}?watch=true", epc.services_uri), move |event| {
+                        epc.handle_service_event(event)
+                    })
+                    .await;
+                })
+            },
+            // Watch pods
+            {
+                let epc = epc.clone();
+                tokio::spawn(async move {
+                    watch_stream(&format!("{}?watch=true", epc.pods_uri), move |event| {
+                        epc.handle_pod_event(event)
+                    })
+                    .await;
+                })
+            },
+            // Pull services and reconciliate
+            {
+                let epc = epc.clone();
+                tokio::spawn(async move {
+                    while let Some(name) = rx.recv().await {
+                        epc.pending.remove(&name);
+                        epc.sync_service(&name).await;
+                    }
+                })
+            }
+        );
+    }
+
+    fn handle_service_event(&self, event: ServiceEvent) {
+        let name = event.service.metadata.name.clone();
+        match event.event_type {
+            EventType::Deleted => {
+                self.services.remove(&name);
+            }
+            EventType::Added | EventType::Modified => {
+                self.services.insert(name.clone(), event.service);
+                self.enqueue(name);
+            }
+        }
+    }
+
+    /// Queues every service selecting the pod.
+    fn handle_pod_event(&self, event: PodEvent) {
+        let names: Vec<String> = self
+            .services
+            .iter()
+            .filter(|s| {
+                s.spec
+                    .selector
+                    .as_ref()
+                    .is_some_and(|selector| selector.matches(&event.pod.metadata.labels))
+            })
+            .map(|s| s.key().clone())
+            .collect();
+        for name in names {
+            self.enqueue(name);
+        }
+    }
+
+    fn enqueue(&self, name: String) {
+        if self.pending.insert(name.clone()) && self.tx.try_send(name.clone()).is_err() {
+            tracing::warn!(service=%name, "Queue full, dropping sync");
+            self.pending.remove(&name);
+        }
+    }
+
+    /// Recomputes the endpoints of a service from its selected pods.
+    async fn sync_service(&self, name: &str) {
+        let Some(service) = self.services.get(name).map(|s| s.clone()) else {
+            return;
+        };
+        // services without a selector have their endpoints managed by hand
+        let Some(selector) = service.spec.selector.clone() else {
+            return;
+        };
+        if selector.match_labels.is_empty() {
+            return;
+        }
+
+        let client = shared::utils::client();
+        let param: String = selector.into();
+        let url = format!("{}?labelSelector={}", self.pods_uri, param);
+        let pods = match client.get(&url).send().await {
+            Ok(resp) if resp.status().is_success() => match resp.json::<Vec<Pod>>().await {
+                Ok(pods) => pods,
+                Err(err) => {
+                    tracing::error!(error=%err, "Couldnt parse pods");
+                    return;
+                }
+            },
+            Ok(resp) => {
+                tracing::error!("Failed to get pods: {}", resp.status());
+                return;
+            }
+            Err(err) => {
+                tracing::error!("Failed to get pods: {}", err);
+                return;
+            }
+        };
+
+        let ports = build_endpoints(&service, &pods);
+        let url = format!("{}/{}", self.endpoints_uri, name);
+        match client.put(&url).json(&ports).send().await {
+            Ok(resp) if resp.status().is_success() => {
+                tracing::trace!(service=%name, "Synced endpoints")
+            }
+            Ok(resp) => tracing::error!("Failed to update endpoints: {}", resp.status()),
+            Err(err) => tracing::error!("Failed to update endpoints: {}", err),
+        }
+    }
+}
+
+/// Ready addresses of the pods for each service port.
+///
+/// Pods not declaring a named target port are left out of that port.
+pub fn build_endpoints(service: &Service, pods: &[Pod]) -> Vec<EndpointPort> {
+    service
+        .spec
+        .ports
+        .iter()
+        .map(|port| {
+            let mut addresses: Vec<EndpointAddress> = pods
+                .iter()
+                .filter(|pod| pod.is_ready())
+                .filter_map(|pod| {
+                    Some(EndpointAddress {
+                        ip: pod.status.pod_ip.clone()?,
+                        port: port.target_for(pod)?,
+                        pod_name: pod.metadata.name.clone(),
+                        node_name: pod.spec.node_name.clone(),
+                    })
+                })
+                .collect();
+            addresses.sort();
+            EndpointPort {
+                name: port.name.clone(),
+                port: port.port,
+                protocol: port.protocol,
+                addresses,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    //! - test_build_endpoints
+    //!   only ready pods, named target ports resolved per pod
+
+    use super::*;
+    use shared::models::{
+        metadata::{LabelSelector, Metadata},
+        pod::{PodPhase, Port, Protocol},
+        service::{ServicePort, ServiceSpec, TargetPort},
+    };
+
+    fn pod(name: &str, ip: &str, ready: bool, http_port: Option<u16>) -> Pod {
+        let mut pod = Pod {
+            metadata: Metadata {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        pod.spec.node_name = "node-a".to_string();
+        pod.spec.containers = vec![Default::default()];
+        pod.spec.containers[0].ports = http_port.map(|port| {
+            vec![Port {
+                container_port: port,
+                host_port: None,
+                protocol: Protocol::Tcp,
+                name: Some("http".to_string()),
+            }]
+        });
+        pod.status.phase = PodPhase::Running;
+        pod.status.pod_ip = Some(ip.to_string());
+        let status = if ready { "running" } else { "exited" };
+        pod.status.container_status = vec![("app".to_string(), status.to_string())];
+        pod
+    }
+
+    #[test]
+    fn test_build_endpoints() {
+        let service = Service {
+            metadata: Metadata::default(),
+            spec: ServiceSpec {
+                selector: Some(LabelSelector {
+                    match_labels: [("app".to_string(), "web".to_string())].into(),
+                }),
+                ports: vec![
+                    ServicePort {
+                        name: Some("web".to_string()),
+                        port: 80,
+                        target_port: Some(TargetPort::Name("http".to_string())),
+                        node_port: None,
+                        protocol: Protocol::Tcp,
+                    },
+                    ServicePort {
+                        name: Some("metrics".to_string()),
+                        port: 9090,
+                        target_port: None,
+                        node_port: None,
+                        protocol: Protocol::Tcp,
+                    },
+                ],
+            },
+        };
+        let pods = [
+            pod("b", "10.0.0.3", true, Some(8081)),
+            pod("a", "10.0.0.2", true, Some(8080)),
+            pod("down", "10.0.0.4", false, Some(8080)),
+            pod("unnamed", "10.0.0.5", true, None),
+        ];
+
+        let ports = build_endpoints(&service, &pods);
+        let web: Vec<_> = ports[0]
+            .addresses
+            .iter()
+            .map(|a| (a.ip.as_str(), a.port))
+            .collect();
+        assert_eq!(web, [("10.0.0.2", 8080), ("10.0.0.3", 8081)]);
+        let metrics: Vec<_> = ports[1].addresses.iter().map(|a| a.port).collect();
+        assert_eq!(metrics, [9090, 9090, 9090]);
+    }
+}
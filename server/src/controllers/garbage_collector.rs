@@ -68,7 +68,7 @@ impl GCController {
                     let url = format!("http://localhost:7620/pods/{}", pod);
                     tracing::info!(%pod, "Deleting");
 
-                    if let Err(err) = reqwest::Client::new().delete(&url).send().await {
+                    if let Err(err) = shared::utils::client().delete(&url).send().await {
                         tracing::error!("Failed to delete pod {}: {}", pod, err);
                         return;
                     }
@@ -1,13 +1,24 @@
 use crate::controllers::{
-    garbage_collector::GCController, replicaset::RSController, scheduler::Scheduler,
+    descheduler::Descheduler,
+    endpoint::EndpointsController,
+    garbage_collector::GCController,
+    replicaset::RSController,
+    scheduler::{Scheduler, SchedulerState},
 };
 
+mod descheduler;
+mod endpoint;
 mod garbage_collector;
 mod replicaset;
-mod scheduler;
+pub mod scheduler;
 
-pub fn run(apiserver: String) {
-    tokio::spawn(Scheduler::run(apiserver.clone()));
+/// Spawns every controller, returns the scheduler state for dry runs.
+pub fn run(apiserver: String) -> scheduler::State {
+    let scheduler_state = SchedulerState::new(&apiserver);
+    tokio::spawn(Scheduler::run(apiserver.clone(), scheduler_state.clone()));
     tokio::spawn(GCController::run(apiserver.clone()));
     tokio::spawn(RSController::run(apiserver.clone()));
+    tokio::spawn(EndpointsController::run(apiserver.clone()));
+    tokio::spawn(Descheduler::run(apiserver.clone(), scheduler_state.clone()));
+    scheduler_state
 }
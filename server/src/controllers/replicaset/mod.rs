@@ -4,7 +4,6 @@
 
 use std::sync::Arc;
 
-use reqwest::Client;
 use shared::{
     api::{EventType, PodEvent, PodManifest, ReplicaSetEvent},
     models::{metadata::OwnerKind, pod::Pod},
@@ -88,7 +87,7 @@ impl RSController {
         }
 
         // Create pods
-        let client = Client::new();
+        let client = shared::utils::client();
         let url = format!("{}?controller=true", self.pods_uri);
         for _ in 0..(rs.spec.replicas - rs.status.ready_replicas) {
             // regenerate manifest if 409?
@@ -126,7 +125,7 @@ impl RSController {
                 owner.kind == OwnerKind::ReplicaSet && self.state.rs_id_exists(&owner.id)
             })
         {
-            let client = Client::new();
+            let client = shared::utils::client();
             let rs_id = event.pod.metadata.owner_reference.unwrap().id;
             let Some(rs) = self.state.get_replicaset(&rs_id) else {
                 tracing::error!(id=%rs_id, "Replicaset not in state");
@@ -140,7 +139,12 @@ impl RSController {
                         tracing::error!("Couldnt parse pods");
                         return;
                     };
-                    if pods.len() != rs.spec.replicas as usize {
+                    // terminating pods no longer count as replicas
+                    let active = pods
+                        .iter()
+                        .filter(|p| p.metadata.deletion_timestamp.is_none())
+                        .count();
+                    if active != rs.spec.replicas as usize {
                         tracing::error!("Replicas dont match spec");
                     }
                 }
@@ -0,0 +1,373 @@
+//! Scheduler extenders
+//!
+//! External HTTP services consulted during filter, score and optionally bind.
+//! Configured through a JSON list read from `CR8S_SCHEDULER_EXTENDERS`:
+//!
+//! ```json
+//! [{ "urlPrefix": "http://inventory:8080/sched", "filterVerb": "filter",
+//!    "prioritizeVerb": "prioritize", "weight": 2.0, "timeoutMs": 500, "ignorable": true }]
+//! ```
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use shared::models::pod::Pod;
+
+/// Configuration of a single extender.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExtenderConfig {
+    #[serde(rename = "urlPrefix")]
+    pub url_prefix: String,
+    #[serde(rename = "filterVerb", default)]
+    pub filter_verb: Option<String>,
+    #[serde(rename = "prioritizeVerb", default)]
+    pub prioritize_verb: Option<String>,
+    /// When set, binding is delegated to the extender instead of the API server
+    #[serde(rename = "bindVerb", default)]
+    pub bind_verb: Option<String>,
+    /// Multiplier applied to prioritize scores before merging
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    #[serde(rename = "timeoutMs", default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Failures and timeouts are skipped instead of failing the pod
+    #[serde(default)]
+    pub ignorable: bool,
+}
+
+/// Payload sent to filter and prioritize verbs.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ExtenderArgs {
+    pub pod: Pod,
+    #[serde(rename = "nodeNames")]
+    pub node_names: Vec<String>,
+}
+
+/// Nodes that passed the extender filter, and reasons for the ones that did not.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct ExtenderFilterResult {
+    #[serde(rename = "nodeNames", default)]
+    pub node_names: Vec<String>,
+    #[serde(rename = "failedNodes", default)]
+    pub failed_nodes: HashMap<String, String>,
+    #[serde(default)]
+    pub error: String,
+}
+
+/// Score given by an extender to a single node.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct HostPriority {
+    pub host: String,
+    pub score: i64,
+}
+
+/// Payload sent to the bind verb.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ExtenderBindingArgs {
+    #[serde(rename = "podName")]
+    pub pod_name: String,
+    #[serde(rename = "podUID")]
+    pub pod_uid: uuid::Uuid,
+    pub node: String,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct ExtenderBindingResult {
+    #[serde(default)]
+    pub error: String,
+}
+
+/// HTTP client for a configured extender.
+#[derive(Debug, Clone)]
+pub struct Extender {
+    pub config: ExtenderConfig,
+    client: Client,
+}
+
+impl Extender {
+    pub fn new(config: ExtenderConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .unwrap_or_default();
+        Self { config, client }
+    }
+
+    /// Loads extenders from the file in `CR8S_SCHEDULER_EXTENDERS`, none if unset.
+    pub fn from_env() -> Vec<Self> {
+        let Ok(path) = std::env::var("CR8S_SCHEDULER_EXTENDERS") else {
+            return Vec::new();
+        };
+        let configs = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| {
+                serde_json::from_str::<Vec<ExtenderConfig>>(&s).map_err(|e| e.to_string())
+            });
+        match configs {
+            Ok(configs) => {
+                tracing::info!(%path, count = configs.len(), "Loaded scheduler extenders");
+                configs.into_iter().map(Extender::new).collect()
+            }
+            Err(error) => {
+                tracing::error!(%path, %error, "Failed to load scheduler extenders");
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn is_binder(&self) -> bool {
+        self.config.bind_verb.is_some()
+    }
+
+    /// Calls the filter verb, `None` if the extender does not filter.
+    pub async fn filter(
+        &self,
+        pod: &Pod,
+        node_names: Vec<String>,
+    ) -> Option<Result<ExtenderFilterResult, String>> {
+        let verb = self.config.filter_verb.as_ref()?;
+        let args = ExtenderArgs {
+            pod: pod.clone(),
+            node_names,
+        };
+        Some(
+            self.call::<_, ExtenderFilterResult>(verb, &args)
+                .await
+                .and_then(|result| match result.error.is_empty() {
+                    true => Ok(result),
+                    false => Err(result.error),
+                }),
+        )
+    }
+
+    /// Calls the prioritize verb, `None` if the extender does not score.
+    pub async fn prioritize(
+        &self,
+        pod: &Pod,
+        node_names: Vec<String>,
+    ) -> Option<Result<Vec<HostPriority>, String>> {
+        let verb = self.config.prioritize_verb.as_ref()?;
+        let args = ExtenderArgs {
+            pod: pod.clone(),
+            node_names,
+        };
+        Some(self.call(verb, &args).await)
+    }
+
+    /// Delegates binding of the pod to the extender.
+    pub async fn bind(&self, pod: &Pod, node: &str) -> Result<(), String> {
+        let Some(verb) = &self.config.bind_verb else {
+            return Err("Extender has no bind verb".to_string());
+        };
+        let args = ExtenderBindingArgs {
+            pod_name: pod.metadata.name.clone(),
+            pod_uid: pod.metadata.id,
+            node: node.to_string(),
+        };
+        let result: ExtenderBindingResult = self.call(verb, &args).await?;
+        match result.error.is_empty() {
+            true => Ok(()),
+            false => Err(result.error),
+        }
+    }
+
+    async fn call<A, R>(&self, verb: &str, args: &A) -> Result<R, String>
+    where
+        A: Serialize,
+        R: DeserializeOwned,
+    {
+        let url = format!("{}/{}", self.config.url_prefix.trim_end_matches('/'), verb);
+        let resp = self
+            .client
+            .post(&url)
+            .json(args)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !resp.status().is_success() {
+            return Err(format!("HTTP {}", resp.status()));
+        }
+        resp.json::<R>().await.map_err(|e| e.to_string())
+    }
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+fn default_timeout_ms() -> u64 {
+    1000
+}
+
+#[cfg(test)]
+mod tests {
+
+    //! - test_extender_filter_and_prioritize
+    //!   filtered nodes are dropped and extender scores decide the winner
+    //! - test_extender_ignorable_timeout
+    //!   slow ignorable extender is skipped and the pod is still bound
+    //! - test_extender_failure
+    //!   failing non ignorable extender leaves the pod unscheduled
+    //! - test_extender_delegated_bind
+    //!   binding goes to the extender instead of the API server
+
+    use super::*;
+    use crate::controllers::scheduler::{
+        flow::SchedulerFlow,
+        state::{SchedulerState, SimResources, State},
+    };
+    use serde_json::json;
+    use shared::models::node::Node;
+    use wiremock::matchers::{method, path, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn config(server: &MockServer) -> ExtenderConfig {
+        ExtenderConfig {
+            url_prefix: format!("{}/sched", server.uri()),
+            filter_verb: Some("filter".to_string()),
+            prioritize_verb: None,
+            bind_verb: None,
+            weight: 1.0,
+            timeout_ms: 200,
+            ignorable: false,
+        }
+    }
+
+    async fn start_apiserver() -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("PATCH"))
+            .and(path_regex(r"^/pods/.*$"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    /// State with identical nodes n1..n3 and a single pending pod.
+    fn setup(apiserver: &MockServer, configs: Vec<ExtenderConfig>) -> (State, Pod) {
+        let state = SchedulerState::new_with_extenders(
+            &apiserver.uri(),
+            configs.into_iter().map(Extender::new).collect(),
+        );
+        for name in ["n1", "n2", "n3"] {
+            state.add_node(&Node {
+                name: name.to_string(),
+                ..Default::default()
+            });
+            state.node_resources.insert(
+                name.to_string(),
+                SimResources {
+                    cpu: 4000,
+                    mem: 1024,
+                },
+            );
+        }
+        let pod = Pod::default();
+        state.add_pod(&pod);
+        state
+            .pod_resources
+            .insert(pod.metadata.id, SimResources { cpu: 100, mem: 64 });
+        (state, pod)
+    }
+
+    #[tokio::test]
+    async fn test_extender_filter_and_prioritize() {
+        let apiserver = start_apiserver().await;
+        let extender = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/sched/filter"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "nodeNames": ["n1", "n2"],
+                "failedNodes": { "n3": "out of inventory" }
+            })))
+            .mount(&extender)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/sched/prioritize"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "host": "n2", "score": 10 },
+                { "host": "n3", "score": 100 }
+            ])))
+            .mount(&extender)
+            .await;
+
+        let mut cfg = config(&extender);
+        cfg.prioritize_verb = Some("prioritize".to_string());
+        let (state, pod) = setup(&apiserver, vec![cfg]);
+
+        let flow = SchedulerFlow::new(&state, pod, None, None).execute().await;
+        assert!(flow.accepted);
+        assert_eq!(flow.chosen.as_deref(), Some("n2"));
+
+        let requests = extender.received_requests().await.unwrap();
+        let args: ExtenderArgs = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(args.node_names.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_extender_ignorable_timeout() {
+        let apiserver = start_apiserver().await;
+        let extender = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "nodeNames": [] }))
+                    .set_delay(std::time::Duration::from_millis(500)),
+            )
+            .mount(&extender)
+            .await;
+
+        let mut cfg = config(&extender);
+        cfg.ignorable = true;
+        let (state, pod) = setup(&apiserver, vec![cfg]);
+
+        let flow = SchedulerFlow::new(&state, pod, None, None).execute().await;
+        assert!(flow.accepted);
+    }
+
+    #[tokio::test]
+    async fn test_extender_failure() {
+        let apiserver = start_apiserver().await;
+        let extender = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&extender)
+            .await;
+
+        let (state, pod) = setup(&apiserver, vec![config(&extender)]);
+
+        let flow = SchedulerFlow::new(&state, pod, None, None).execute().await;
+        assert!(!flow.accepted);
+        assert!(flow.chosen.is_none());
+        assert!(apiserver.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_extender_delegated_bind() {
+        let apiserver = start_apiserver().await;
+        let extender = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/sched/bind"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "error": "" })))
+            .mount(&extender)
+            .await;
+
+        let mut cfg = config(&extender);
+        cfg.filter_verb = None;
+        cfg.bind_verb = Some("bind".to_string());
+        let (state, pod) = setup(&apiserver, vec![cfg]);
+
+        let flow = SchedulerFlow::new(&state, pod.clone(), None, None)
+            .execute()
+            .await;
+        assert!(flow.accepted);
+
+        let requests = extender.received_requests().await.unwrap();
+        let args: ExtenderBindingArgs = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(args.pod_uid, pod.metadata.id);
+        assert_eq!(Some(args.node), flow.chosen);
+        assert!(apiserver.received_requests().await.unwrap().is_empty());
+    }
+}
@@ -1,4 +1,6 @@
-use shared::models::pod::Pod;
+use std::collections::HashSet;
+
+use shared::models::{node::Node, pod::Pod};
 
 use super::{scorer::Score, state::State};
 
@@ -8,7 +10,14 @@ pub enum FilterOptions {
 }
 
 impl FilterOptions {
-    pub fn filter(&self, state: &State, pod: &Pod, candidates: &mut Vec<(String, Score)>) {
+    /// Push feasible nodes to `candidates` and the rest with a reason to `rejected`.
+    pub fn filter(
+        &self,
+        state: &State,
+        pod: &Pod,
+        candidates: &mut Vec<(String, Score)>,
+        rejected: &mut Vec<(String, String)>,
+    ) {
         match self {
             FilterOptions::Basic => {
                 let Some(pod_res) = state.pod_resources.get(&pod.metadata.id).map(|r| r.clone())
@@ -19,13 +28,89 @@ impl FilterOptions {
 
                 for entry in state.nodes.iter() {
                     let node_name = entry.key();
-                    if let Some(node_res) = state.node_resources.get(node_name) {
-                        if node_res.cpu >= pod_res.cpu && node_res.mem >= pod_res.mem {
+                    if let Some(reason) = node_conflict(state, pod, entry.value()) {
+                        rejected.push((node_name.clone(), reason.to_string()));
+                        continue;
+                    }
+                    let reason = match state.node_resources.get(node_name) {
+                        Some(node_res) if node_res.fits(&pod_res) => {
                             candidates.push((node_name.clone(), 0.0));
+                            continue;
                         }
-                    }
+                        Some(node_res) if node_res.cpu < pod_res.cpu => "Insufficient cpu",
+                        Some(_) => "Insufficient memory",
+                        None => "No resource information",
+                    };
+                    rejected.push((node_name.clone(), reason.to_string()));
                 }
             }
         }
     }
 }
+
+/// Why the pod can't run on the node whatever its free resources, checked before
+/// fitting the pod and before evicting anything for it.
+pub fn node_conflict(state: &State, pod: &Pod, node: &Node) -> Option<&'static str> {
+    if node.spec.unschedulable {
+        return Some("Node is unschedulable");
+    }
+    if !pod.spec.tolerates(&node.spec.taints) {
+        return Some("Untolerated taint");
+    }
+    if anti_affinity_conflict(state, pod, &node.name) {
+        return Some("Pod anti-affinity conflict");
+    }
+    if host_port_conflict(state, pod, &node.name) {
+        return Some("Host port in use");
+    }
+    volume_conflict(state, pod, &node.name)
+}
+
+/// True if the pod and any pod on the node repel each other.
+fn anti_affinity_conflict(state: &State, pod: &Pod, node_name: &str) -> bool {
+    let Some(ids) = state.pod_map.get(node_name) else {
+        return false;
+    };
+    ids.iter().any(|id| {
+        *id != pod.metadata.id
+            && state
+                .pods
+                .get(&id)
+                .is_some_and(|other| pod.repels(&other) || other.repels(pod))
+    })
+}
+
+/// True if a pod on the node already binds one of the pod's host ports.
+fn host_port_conflict(state: &State, pod: &Pod, node_name: &str) -> bool {
+    let wanted: HashSet<_> = pod.spec.host_ports().collect();
+    if wanted.is_empty() {
+        return false;
+    }
+    let Some(ids) = state.pod_map.get(node_name) else {
+        return false;
+    };
+    ids.iter().any(|id| {
+        *id != pod.metadata.id
+            && state
+                .pods
+                .get(&id)
+                .is_some_and(|other| other.spec.host_ports().any(|p| wanted.contains(&p)))
+    })
+}
+
+/// Why the pod's claims can't be used on the node: a claim is missing or bound to a
+/// volume on another node. Unbound claims get a volume wherever the pod lands.
+fn volume_conflict(state: &State, pod: &Pod, node_name: &str) -> Option<&'static str> {
+    pod.spec
+        .claim_refs()
+        .into_iter()
+        .find_map(|name| match state.claims.get(name) {
+            None => Some("Persistent volume claim not found"),
+            Some(claim) => claim
+                .status
+                .node_name
+                .as_ref()
+                .filter(|node| *node != node_name)
+                .map(|_| "Volume node affinity conflict"),
+        })
+}
@@ -1,4 +1,3 @@
-use reqwest::Client;
 use serde_json::Value;
 use shared::{
     api::{PodField, PodPatch},
@@ -12,11 +11,14 @@ use super::{
 };
 
 /// Scheduling flow for a single pod: filters candidate nodes,
-/// scores them, and binds the pod if a node is chosen
+/// scores them, and binds the pod if a node is chosen.
+/// Configured extenders run after the built-in filter and scorer.
 pub struct SchedulerFlow {
     state: State,
     pod: Pod,
-    candidates: Vec<(String, f64)>,
+    pub candidates: Vec<(String, f64)>,
+    /// Nodes dropped by a filter with the reason
+    pub rejected: Vec<(String, String)>,
     pub chosen: Option<String>,
     pub accepted: bool,
     filter_option: FilterOptions,
@@ -34,6 +36,7 @@ impl SchedulerFlow {
             state: state.clone(),
             pod,
             candidates: Vec::new(),
+            rejected: Vec::new(),
             chosen: None,
             accepted: false,
             filter_option: filter_option.unwrap_or(FilterOptions::Basic),
@@ -42,17 +45,79 @@ impl SchedulerFlow {
     }
 
     pub async fn execute(self) -> Self {
-        self.filter().score().bind().await
+        self.simulate().await.bind().await
+    }
+
+    /// Run every stage except binding, used for dry runs.
+    pub async fn simulate(self) -> Self {
+        self.filter()
+            .filter_extenders()
+            .await
+            .score()
+            .prioritize_extenders()
+            .await
+            .select()
     }
 
     /// Apply the filter to generate an initial set of candidate nodes.
     fn filter(mut self) -> Self {
-        self.filter_option
-            .filter(&self.state, &self.pod, &mut self.candidates);
+        self.filter_option.filter(
+            &self.state,
+            &self.pod,
+            &mut self.candidates,
+            &mut self.rejected,
+        );
+        self
+    }
+
+    /// Narrow candidates down with every filtering extender.
+    /// A failing extender empties the candidate list unless it is ignorable.
+    async fn filter_extenders(mut self) -> Self {
+        let state = self.state.clone();
+        for extender in state.extenders.iter() {
+            if self.candidates.is_empty() {
+                break;
+            }
+            let node_names = self.candidates.iter().map(|(n, _)| n.clone()).collect();
+            match extender.filter(&self.pod, node_names).await {
+                None => {}
+                Some(Ok(result)) => {
+                    let (kept, dropped) = std::mem::take(&mut self.candidates)
+                        .into_iter()
+                        .partition(|(n, _)| result.node_names.contains(n));
+                    self.candidates = kept;
+                    for (node, _) in dropped {
+                        let reason = result
+                            .failed_nodes
+                            .get(&node)
+                            .cloned()
+                            .unwrap_or_else(|| "Filtered by extender".to_string());
+                        tracing::debug!(pod=%self.pod.metadata.name, %node, %reason, "Filtered by extender");
+                        self.rejected.push((node, reason));
+                    }
+                }
+                Some(Err(error)) if extender.config.ignorable => {
+                    tracing::warn!(url=%extender.config.url_prefix, %error, "Ignoring extender filter failure");
+                }
+                Some(Err(error)) => {
+                    tracing::error!(url=%extender.config.url_prefix, %error, "Extender filter failed");
+                    for (node, _) in std::mem::take(&mut self.candidates) {
+                        self.rejected
+                            .push((node, format!("Extender error: {}", error)));
+                    }
+                }
+            }
+        }
         self
     }
 
-    /// Score candidate nodes and pick the best one (if any).
+    /// Run only the extender filters over the given nodes, used to vet preemption targets.
+    pub async fn filter_extenders_on(mut self, nodes: Vec<String>) -> Self {
+        self.candidates = nodes.into_iter().map(|node| (node, 0.0)).collect();
+        self.filter_extenders().await
+    }
+
+    /// Score candidate nodes with the built-in scorer.
     fn score(mut self) -> Self {
         if self.candidates.is_empty() {
             return self;
@@ -68,8 +133,6 @@ impl SchedulerFlow {
             return self;
         };
 
-        let mut best: Option<(String, Score)> = None;
-
         for (node_name, score) in self.candidates.iter_mut() {
             if let Some(node_res) = self.state.node_resources.get(node_name) {
                 let free_cpu = node_res.cpu.saturating_sub(pod_res.cpu);
@@ -83,16 +146,50 @@ impl SchedulerFlow {
                     .unwrap_or(0);
 
                 *score = self.scorer.score(pod_count, free_cpu, free_mem);
+            }
+        }
+        self
+    }
 
-                match &best {
-                    None => best = Some((node_name.clone(), *score)),
-                    Some((_, best_score)) if *score > *best_score => {
-                        best = Some((node_name.clone(), *score))
+    /// Add weighted extender scores, failures only drop that extender's contribution.
+    async fn prioritize_extenders(mut self) -> Self {
+        let state = self.state.clone();
+        for extender in state.extenders.iter() {
+            if self.candidates.is_empty() {
+                break;
+            }
+            let node_names = self.candidates.iter().map(|(n, _)| n.clone()).collect();
+            match extender.prioritize(&self.pod, node_names).await {
+                None => {}
+                Some(Ok(priorities)) => {
+                    for host in priorities {
+                        if let Some((_, score)) =
+                            self.candidates.iter_mut().find(|(n, _)| *n == host.host)
+                        {
+                            *score += extender.config.weight * host.score as Score;
+                        }
                     }
-                    _ => {}
+                }
+                Some(Err(error)) => {
+                    tracing::warn!(url=%extender.config.url_prefix, %error, "Extender prioritize failed");
                 }
             }
         }
+        self
+    }
+
+    /// Pick the best scored candidate (if any).
+    fn select(mut self) -> Self {
+        let mut best: Option<(String, Score)> = None;
+        for (node_name, score) in self.candidates.iter() {
+            match &best {
+                None => best = Some((node_name.clone(), *score)),
+                Some((_, best_score)) if *score > *best_score => {
+                    best = Some((node_name.clone(), *score))
+                }
+                _ => {}
+            }
+        }
 
         if let Some((node, _)) = best {
             self.chosen = Some(node);
@@ -100,19 +197,31 @@ impl SchedulerFlow {
         self
     }
 
-    /// Bind the pod to the chosen node by patching the API server.
-    async fn bind(mut self) -> Self {
+    /// Bind the pod to the chosen node by patching the API server,
+    /// or through the first extender with a bind verb.
+    pub async fn bind(mut self) -> Self {
         let Some(ref node) = self.chosen else {
             return self;
         };
 
+        if let Some(binder) = self.state.extenders.iter().find(|e| e.is_binder()) {
+            match binder.bind(&self.pod, node).await {
+                Ok(()) => {
+                    tracing::info!(pod=%self.pod.metadata.name, %node, "Scheduled by extender");
+                    self.accepted = true;
+                }
+                Err(error) => tracing::error!(%error, "Extender failed to bind pod"),
+            }
+            return self;
+        }
+
         // make patch call to api server
         let patch = PodPatch {
             pod_field: PodField::NodeName,
             value: Value::String(node.clone()),
         };
 
-        let client = Client::new();
+        let client = shared::utils::client();
         let url = format!("{}/{}", self.state.pods_uri, self.pod.metadata.name);
 
         match client.patch(&url).json(&patch).send().await {
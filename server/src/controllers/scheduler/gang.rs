@@ -0,0 +1,190 @@
+//! Gang scheduling
+//!
+//! Pods labelled with a pod group are placed all together or not at all.
+//! Capacity is reserved in the scheduler state for every pending member
+//! before any binding happens, and released if a single member does not fit.
+//! Members already bound are deleted again when binding another one fails.
+
+use std::time::Duration;
+
+use dashmap::mapref::entry::Entry;
+use shared::models::{
+    pod::Pod,
+    podgroup::{POD_GROUP_LABEL, PodGroup},
+};
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use super::{Scheduler, flow::SchedulerFlow};
+
+/// Time a group waits before being retried after failing to schedule.
+pub const GROUP_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Name of the pod group a pod belongs to, if any.
+pub fn group_of(pod: &Pod) -> Option<&String> {
+    pod.metadata.labels.get(POD_GROUP_LABEL)
+}
+
+impl Scheduler {
+    /// Schedules every pending member of a group, or none of them.
+    pub(super) async fn schedule_group(&self, name: &str) {
+        let Some(group) = self.state.pod_groups.get(name).map(|g| g.clone()) else {
+            tracing::debug!(group=%name, "Pod group not known yet, waiting");
+            return;
+        };
+        if self
+            .state
+            .group_backoff_until
+            .get(name)
+            .is_some_and(|until| Instant::now() < *until)
+        {
+            return;
+        }
+
+        let (pending, bound) = self.group_members(name);
+        let min_member = group.spec.min_member as usize;
+        if pending.len() + bound < min_member {
+            let timeout = Duration::from_secs(group.spec.schedule_timeout_seconds);
+            let since = match self.state.group_waiting_since.entry(name.to_string()) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => {
+                    // no new member may come to retry the group, wake it up at the deadline
+                    let ids = pending.iter().take(1).map(|p| p.metadata.id).collect();
+                    self.requeue_after(timeout, ids);
+                    *entry.insert(Instant::now())
+                }
+            };
+            if since.elapsed() >= timeout {
+                let message = format!(
+                    "Only {}/{} group members created",
+                    pending.len() + bound,
+                    min_member
+                );
+                self.back_off_group(&group, &pending, "PodGroupTimeout", &message)
+                    .await;
+            }
+            return;
+        }
+
+        // reserve capacity for the whole group before binding anything
+        let mut flows = Vec::with_capacity(pending.len());
+        for pod in pending.iter() {
+            let flow = SchedulerFlow::new(&self.state, pod.clone(), None, None)
+                .simulate()
+                .await;
+            match &flow.chosen {
+                Some(node) => {
+                    self.state.assign_pod(&pod.metadata.id, node);
+                    flows.push((pod, flow));
+                }
+                None => {
+                    for (pod, _) in flows.iter() {
+                        self.state.release_pod(&pod.metadata.id);
+                    }
+                    let message =
+                        format!("Not enough capacity for {} group members", pending.len());
+                    self.back_off_group(&group, &pending, "Unschedulable", &message)
+                        .await;
+                    return;
+                }
+            }
+        }
+
+        let mut flows = flows.into_iter();
+        let mut bound = Vec::new();
+        for (pod, flow) in flows.by_ref() {
+            if flow.bind().await.accepted {
+                bound.push(pod);
+                continue;
+            }
+            tracing::error!(group=%name, pod=%pod.metadata.name, "Could not bind group member");
+            // the group must not run below its minimum, undo the members bound so far
+            self.state.release_pod(&pod.metadata.id);
+            for (pod, _) in flows {
+                self.state.release_pod(&pod.metadata.id);
+            }
+            for pod in bound.iter() {
+                self.state.release_pod(&pod.metadata.id);
+                self.delete_member(pod).await;
+            }
+            let remaining: Vec<Pod> = pending
+                .iter()
+                .filter(|p| bound.iter().all(|b| b.metadata.id != p.metadata.id))
+                .cloned()
+                .collect();
+            let message = format!("Could not bind group member {}", pod.metadata.name);
+            self.back_off_group(&group, &remaining, "BindFailed", &message)
+                .await;
+            return;
+        }
+        self.state.group_waiting_since.remove(name);
+        self.state.group_backoff_until.remove(name);
+        tracing::info!(group=%name, members=%pending.len(), "Pod group scheduled");
+    }
+
+    /// Pending members sorted by priority, and the number already bound.
+    fn group_members(&self, name: &str) -> (Vec<Pod>, usize) {
+        let mut pending = Vec::new();
+        let mut bound = 0;
+        for pod in self.state.pods.iter() {
+            if group_of(&pod).is_none_or(|g| g != name) {
+                continue;
+            }
+            if pod.spec.node_name.is_empty() {
+                pending.push(pod.clone());
+            } else {
+                bound += 1;
+            }
+        }
+        pending.sort_by(|a, b| {
+            b.spec
+                .priority
+                .cmp(&a.spec.priority)
+                .then_with(|| a.metadata.name.cmp(&b.metadata.name))
+        });
+        (pending, bound)
+    }
+
+    /// Marks the group members unschedulable and retries them after a delay.
+    async fn back_off_group(&self, group: &PodGroup, pending: &[Pod], reason: &str, message: &str) {
+        let name = &group.metadata.name;
+        tracing::warn!(group=%name, %reason, %message, "Backing off pod group");
+        self.state.group_waiting_since.remove(name);
+        self.state
+            .group_backoff_until
+            .insert(name.clone(), Instant::now() + GROUP_BACKOFF);
+
+        for pod in pending {
+            self.record_condition(pod, reason, message, None).await;
+        }
+        self.requeue_after(
+            GROUP_BACKOFF,
+            pending.iter().map(|p| p.metadata.id).collect(),
+        );
+    }
+
+    /// Sends the pods back to the queue once the delay has passed.
+    fn requeue_after(&self, delay: Duration, ids: Vec<Uuid>) {
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            for id in ids {
+                let _ = tx.send(id).await;
+            }
+        });
+    }
+
+    /// Deletes a bound member through the API server, its owner recreates it.
+    async fn delete_member(&self, pod: &Pod) {
+        let url = format!("{}/{}", self.state.pods_uri, pod.metadata.name);
+        match shared::utils::client().delete(&url).send().await {
+            Ok(resp) if resp.status().is_success() => {}
+            Ok(resp) => {
+                tracing::error!(status=%resp.status(), pod=%pod.metadata.name, "Failed to delete group member")
+            }
+            Err(err) => {
+                tracing::error!(error=%err, pod=%pod.metadata.name, "Failed to delete group member")
+            }
+        }
+    }
+}
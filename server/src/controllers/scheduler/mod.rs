@@ -1,42 +1,66 @@
+mod extender;
 mod filter;
 mod flow;
+mod gang;
+mod preemption;
+mod queue;
 mod scorer;
+mod simulation;
 mod state;
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use shared::api::{EventType, NodeEvent, PodEvent};
+use shared::api::{
+    EventType, NodeEvent, PersistentVolumeClaimEvent, PodConditionUpdate, PodEvent, PodField,
+    PodGroupEvent, PodPatch,
+};
+use shared::models::pod::{Pod, PodCondition, PodConditionType};
 use shared::utils::watch_stream;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use flow::SchedulerFlow;
-use state::{SchedulerState, State};
+use preemption::{Outcome, Preemption};
+use queue::SchedulingQueue;
+pub use simulation::simulate;
+pub use state::{SchedulerState, SimResources, State};
 
 pub struct Scheduler {
     state: State,
     tx: mpsc::Sender<Uuid>,
     pods_uri: String,
     nodes_uri: String,
+    podgroups_uri: String,
+    claims_uri: String,
 }
 
 impl Scheduler {
+    #[cfg(test)]
     fn new(apiserver: String) -> (Arc<Self>, mpsc::Receiver<Uuid>) {
+        let state = SchedulerState::new(&apiserver);
+        Self::new_with_state(apiserver, state)
+    }
+
+    fn new_with_state(apiserver: String, state: State) -> (Arc<Self>, mpsc::Receiver<Uuid>) {
         let (tx, rx) = mpsc::channel::<Uuid>(100);
         (
             Arc::new(Self {
-                state: SchedulerState::new(&apiserver),
+                state,
                 tx,
                 pods_uri: format!("{}/pods?watch=true", apiserver),
                 nodes_uri: format!("{}/nodes?watch=true", apiserver),
+                podgroups_uri: format!("{}/podgroups?watch=true", apiserver),
+                claims_uri: format!("{}/persistentvolumeclaims?watch=true", apiserver),
             }),
             rx,
         )
     }
 
-    pub async fn run(apiserver: String) {
+    /// Runs the scheduler on a state shared with the simulation endpoint.
+    pub async fn run(apiserver: String, state: State) {
         tracing::debug!("Running");
-        let (sched, mut rx) = Scheduler::new(apiserver);
+        let (sched, mut rx) = Scheduler::new_with_state(apiserver, state);
 
         let _ = tokio::try_join!(
             // Watch nodes
@@ -61,18 +85,67 @@ impl Scheduler {
                     .await;
                 })
             },
+            // Watch pod groups
+            {
+                let sched = sched.clone();
+                let podgroups_uri = sched.podgroups_uri.clone();
+                tokio::spawn(async move {
+                    watch_stream(&podgroups_uri, move |event| {
+                        sched.handle_podgroup_event(event);
+                    })
+                    .await;
+                })
+            },
+            // Watch persistent volume claims
+            {
+                let sched = sched.clone();
+                let claims_uri = sched.claims_uri.clone();
+                tokio::spawn(async move {
+                    watch_stream(&claims_uri, move |event| {
+                        sched.handle_claim_event(event);
+                    })
+                    .await;
+                })
+            },
             // Pull jobs and schedule pods
             {
                 let sched = sched.clone();
                 tokio::spawn(async move {
-                    while let Some(pod_id) = rx.recv().await {
-                        sched.schedule(pod_id).await;
-                    }
+                    sched.process_queue(&mut rx).await;
                 })
             }
         );
     }
 
+    /// Drains the channel into a priority queue and schedules the most important pod first.
+    async fn process_queue(&self, rx: &mut mpsc::Receiver<Uuid>) {
+        let mut queue = SchedulingQueue::new();
+        loop {
+            if queue.is_empty() {
+                match rx.recv().await {
+                    Some(id) => self.enqueue(&mut queue, id),
+                    None => return,
+                }
+            }
+            while let Ok(id) = rx.try_recv() {
+                self.enqueue(&mut queue, id);
+            }
+            if let Some(id) = queue.pop() {
+                self.schedule(id).await;
+            }
+        }
+    }
+
+    fn enqueue(&self, queue: &mut SchedulingQueue, id: Uuid) {
+        let priority = self
+            .state
+            .pods
+            .get(&id)
+            .map(|p| p.spec.priority)
+            .unwrap_or_default();
+        queue.push(id, priority);
+    }
+
     async fn schedule(&self, id: Uuid) {
         let pod = match self.state.pods.get(&id) {
             Some(p) => p.clone(),
@@ -81,15 +154,90 @@ impl Scheduler {
                 return;
             }
         };
+        if !pod.spec.node_name.is_empty() {
+            return;
+        }
+        if let Some(group) = gang::group_of(&pod) {
+            self.schedule_group(group).await;
+            return;
+        }
 
-        let flow = SchedulerFlow::new(&self.state, pod, None, None)
+        let flow = SchedulerFlow::new(&self.state, pod.clone(), None, None)
             .execute()
             .await;
 
         if let (true, Some(node)) = (flow.accepted, &flow.chosen) {
             self.state.assign_pod(&id, node);
-        } else {
+        } else if flow.chosen.is_some() {
             tracing::error!("Could not schedule pod");
+        } else if let Some(preemption) = self.preempt(&pod).await {
+            let message = format!(
+                "Preempting {} lower priority pod(s) on {}",
+                preemption.victims.len(),
+                preemption.node
+            );
+            self.record_condition(&pod, "Preempting", &message, Some(preemption.node))
+                .await;
+        } else {
+            let message = unschedulable_message(self.state.nodes.len(), &flow.rejected);
+            tracing::warn!(pod=%pod.metadata.name, %message, "No node fits pod");
+            self.record_condition(&pod, "Unschedulable", &message, None)
+                .await;
+        }
+    }
+
+    /// Evicts the victims on the best node whose disruption budgets allow it.
+    /// Victims' delete events free resources and requeue the pod.
+    async fn preempt(&self, pod: &Pod) -> Option<Preemption> {
+        for preemption in Preemption::select(&self.state, pod).await {
+            match preemption.execute(&self.state).await {
+                Outcome::Evicted => return Some(preemption),
+                Outcome::Blocked => continue,
+                Outcome::Failed => return None,
+            }
+        }
+        None
+    }
+
+    /// Records a failed `PodScheduled` condition on the pod through the API server.
+    async fn record_condition(
+        &self,
+        pod: &Pod,
+        reason: &str,
+        message: &str,
+        nominated_node_name: Option<String>,
+    ) {
+        let update = PodConditionUpdate {
+            condition: PodCondition::new(PodConditionType::PodScheduled, false, reason, message),
+            nominated_node_name,
+        };
+        let Ok(value) = serde_json::to_value(update) else {
+            return;
+        };
+        let patch = PodPatch {
+            pod_field: PodField::Condition,
+            value,
+        };
+        let url = format!("{}/{}", self.state.pods_uri, pod.metadata.name);
+        match shared::utils::client()
+            .patch(&url)
+            .json(&patch)
+            .send()
+            .await
+        {
+            Ok(resp) if resp.status().is_success() => {}
+            Ok(resp) => tracing::warn!(status=%resp.status(), "Failed to record pod condition"),
+            Err(err) => tracing::warn!(error=%err, "Failed to record pod condition"),
+        }
+    }
+
+    /// Sends every unassigned pod back to the queue.
+    fn requeue_pending(&self) {
+        if let Some(pods) = self.state.pod_map.get("") {
+            for pod_id in pods.iter() {
+                let res = self.tx.try_send(*pod_id);
+                tracing::debug!("RES: {res:?}");
+            }
         }
     }
 
@@ -101,23 +249,80 @@ impl Scheduler {
                     let _ = self.tx.try_send(event.pod.metadata.id);
                 }
             }
-            EventType::Deleted => self.state.delete_pod(&event.pod.metadata.id),
+            EventType::Deleted => {
+                // freed resources may fit pending pods
+                self.state.delete_pod(&event.pod.metadata.id);
+                self.requeue_pending();
+            }
             EventType::Modified => { /*TODO*/ }
         }
     }
 
-    fn handle_node_event(&self, event: NodeEvent) {
-        if event.event_type != EventType::Added {
-            tracing::warn!("Scheduler only implements `Add` node events");
-            return;
+    fn handle_podgroup_event(&self, event: PodGroupEvent) {
+        let name = event.podgroup.metadata.name.clone();
+        match event.event_type {
+            EventType::Deleted => {
+                self.state.pod_groups.remove(&name);
+                self.state.group_waiting_since.remove(&name);
+                self.state.group_backoff_until.remove(&name);
+            }
+            _ => {
+                self.state.pod_groups.insert(name, event.podgroup);
+                self.requeue_pending();
+            }
         }
-        self.state.add_node(&event.node);
-        if let Some(pods) = self.state.pod_map.get("") {
-            for pod_id in pods.iter() {
-                let res = self.tx.try_send(*pod_id);
-                tracing::debug!("RES: {res:?}");
+    }
+
+    fn handle_claim_event(&self, event: PersistentVolumeClaimEvent) {
+        let name = event.claim.metadata.name.clone();
+        match event.event_type {
+            EventType::Deleted => {
+                self.state.claims.remove(&name);
+            }
+            EventType::Added => {
+                self.state.claims.insert(name, event.claim);
+                // pods waiting on the claim can go now
+                self.requeue_pending();
+            }
+            EventType::Modified => {
+                self.state.claims.insert(name, event.claim);
+            }
+        }
+    }
+
+    fn handle_node_event(&self, event: NodeEvent) {
+        match event.event_type {
+            EventType::Added => self.state.add_node(&event.node),
+            // spec changes keep the simulated resources
+            EventType::Modified => {
+                self.state
+                    .nodes
+                    .insert(event.node.name.clone(), event.node.clone());
+            }
+            EventType::Deleted => {
+                self.state.delete_node(&event.node.name);
+                return;
             }
         }
+        self.requeue_pending();
+    }
+}
+
+/// Summary of why every node was rejected, like `0/3 nodes are available: 2 Insufficient cpu, 1 Untolerated taint.`
+fn unschedulable_message(nodes: usize, rejected: &[(String, String)]) -> String {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for (_, reason) in rejected {
+        *counts.entry(reason.as_str()).or_default() += 1;
+    }
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    let reasons: Vec<String> = counts
+        .into_iter()
+        .map(|(reason, count)| format!("{} {}", count, reason))
+        .collect();
+    match reasons.is_empty() {
+        true => format!("0/{} nodes are available.", nodes),
+        false => format!("0/{} nodes are available: {}.", nodes, reasons.join(", ")),
     }
 }
 
@@ -128,11 +333,53 @@ mod tests {
     //!     ensures a pod is inserted and scheduled upon receiving a pod event.
     //! - test_handle_node_event_schedule_unscheduled_pods
     //!     verifies that unscheduled pods are scheduled when a node is added.
+    //! - test_queue_orders_by_priority
+    //!   higher priority first, FIFO among equals, no duplicates
+    //! - test_preempt_lower_priority_pod
+    //!   evicts the low priority pod and nominates its node
+    //! - test_no_preemption_of_equal_priority
+    //!   records the pod as unschedulable instead, with the reason of each node
+    //! - test_unschedulable_message
+    //!   rejection reasons counted, most common first
+    //! - test_no_preemption_on_cordoned_or_tainted_node
+    //!   nothing is evicted where the pod could not be placed anyway
+    //! - test_preemption_respects_disruption_budget
+    //!   a node whose victims a budget protects is passed over for the next best
+    //! - test_gang_binds_all_members
+    //!   every member is reserved and bound together
+    //! - test_gang_releases_when_group_does_not_fit
+    //!   reservations are released and the group backs off without binding
+    //! - test_gang_waits_for_min_member
+    //!   nothing is placed until enough members exist
+    //! - test_gang_unbinds_when_a_bind_fails
+    //!   members bound before the failure are deleted and the group backs off
+    //! - test_gang_times_out_without_new_members
+    //!   the group is retried at its deadline and marked timed out
+    //! - test_filter_taints_and_anti_affinity
+    //!   tainted and conflicting nodes are rejected with a reason
+    //! - test_filter_host_port_conflict
+    //!   nodes already binding the host port are rejected
+    //! - test_filter_volume_topology
+    //!   bound claims pin the pod to their node, missing claims fit nowhere
+    //! - test_cordoned_node_is_skipped
+    //!   pods go to another node until the node is uncordoned
+    //! - test_deleted_node_is_forgotten
+    //!   pods stay pending once their only node leaves
 
     use super::*;
     use shared::api::EventType;
-    use shared::models::{node::Node, pod::Pod};
-    use wiremock::matchers::{method, path_regex};
+    use shared::models::metadata::LabelSelector;
+    use shared::models::metadata::Metadata;
+    use shared::models::node::{Node, NodeSpec, Taint};
+    use shared::models::persistentvolume::ReclaimPolicy;
+    use shared::models::persistentvolumeclaim::{
+        ClaimPhase, PersistentVolumeClaim, PersistentVolumeClaimSpec,
+    };
+    use shared::models::pod::{ClaimVolume, Port, Protocol, Volume, VolumeSource};
+    use shared::models::podgroup::{POD_GROUP_LABEL, PodGroup, PodGroupSpec};
+    use state::SimResources;
+    use std::time::Duration;
+    use wiremock::matchers::{method, path, path_regex};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     async fn start_mock_server() -> MockServer {
@@ -144,9 +391,58 @@ mod tests {
             .mount(&server)
             .await;
 
+        Mock::given(method("DELETE"))
+            .and(path_regex(r"^/pods/.*$"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/pods/[^/]+/eviction$"))
+            .respond_with(ResponseTemplate::new(201))
+            .mount(&server)
+            .await;
+
         server
     }
 
+    /// Node with room for a single 1000m pod and one low priority pod bound to it.
+    fn full_node(sched: &Scheduler, low_priority: i32) -> (Node, Pod) {
+        let node = Node::default();
+        sched.state.add_node(&node);
+        sched.state.node_resources.insert(
+            node.name.clone(),
+            SimResources {
+                cpu: 1000,
+                mem: 1024,
+            },
+        );
+
+        let mut low = Pod::default();
+        low.spec.priority = low_priority;
+        sched.state.add_pod(&low);
+        sched.state.pod_resources.insert(
+            low.metadata.id,
+            SimResources {
+                cpu: 1000,
+                mem: 512,
+            },
+        );
+        sched.state.assign_pod(&low.metadata.id, &node.name);
+        (node, low)
+    }
+
+    fn add_pending_pod(sched: &Scheduler, priority: i32) -> Pod {
+        let mut pod = Pod::default();
+        pod.spec.priority = priority;
+        sched.state.add_pod(&pod);
+        sched
+            .state
+            .pod_resources
+            .insert(pod.metadata.id, SimResources { cpu: 500, mem: 512 });
+        pod
+    }
+
     #[tokio::test]
     async fn test_handle_pod_event_schedule_pod() {
         // Setup state and mocked patch endpoint
@@ -220,4 +516,579 @@ mod tests {
         let unscheduled = sched.state.pod_map.get("");
         assert!(!unscheduled.unwrap().contains(&pod.metadata.id));
     }
+
+    #[test]
+    fn test_queue_orders_by_priority() {
+        let mut queue = SchedulingQueue::new();
+        let (low, first, second) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        queue.push(low, -5);
+        queue.push(first, 100);
+        queue.push(second, 100);
+        queue.push(first, 100);
+
+        assert_eq!(queue.pop(), Some(first));
+        assert_eq!(queue.pop(), Some(second));
+        assert_eq!(queue.pop(), Some(low));
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_preempt_lower_priority_pod() {
+        let mock_server = start_mock_server().await;
+        let (sched, _rx) = Scheduler::new(mock_server.uri());
+        let (node, low) = full_node(&sched, 0);
+        let high = add_pending_pod(&sched, 1000);
+
+        sched.schedule(high.metadata.id).await;
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let eviction = requests
+            .iter()
+            .find(|r| r.method == wiremock::http::Method::POST)
+            .expect("victim should be evicted");
+        assert_eq!(
+            eviction.url.path(),
+            format!("/pods/{}/eviction", low.metadata.name)
+        );
+
+        let patch = requests
+            .iter()
+            .find(|r| r.method == wiremock::http::Method::PATCH)
+            .expect("condition should be recorded");
+        let body: PodPatch = serde_json::from_slice(&patch.body).unwrap();
+        let update: PodConditionUpdate = serde_json::from_value(body.value).unwrap();
+        assert_eq!(update.nominated_node_name, Some(node.name));
+        assert_eq!(update.condition.reason, "Preempting");
+    }
+
+    #[tokio::test]
+    async fn test_no_preemption_of_equal_priority() {
+        let mock_server = start_mock_server().await;
+        let (sched, _rx) = Scheduler::new(mock_server.uri());
+        let _ = full_node(&sched, 1000);
+        let pod = add_pending_pod(&sched, 1000);
+
+        sched.schedule(pod.metadata.id).await;
+
+        let requests = mock_server.received_requests().await.unwrap();
+        assert!(
+            requests
+                .iter()
+                .all(|r| r.method != wiremock::http::Method::POST)
+        );
+        let body: PodPatch = serde_json::from_slice(&requests[0].body).unwrap();
+        let update: PodConditionUpdate = serde_json::from_value(body.value).unwrap();
+        assert_eq!(update.condition.reason, "Unschedulable");
+        assert_eq!(
+            update.condition.message,
+            "0/1 nodes are available: 1 Insufficient cpu."
+        );
+        assert!(update.nominated_node_name.is_none());
+    }
+
+    #[test]
+    fn test_unschedulable_message() {
+        let rejected: Vec<(String, String)> = [
+            ("a", "Untolerated taint"),
+            ("b", "Insufficient cpu"),
+            ("c", "Insufficient cpu"),
+            ("d", "Host port in use"),
+        ]
+        .iter()
+        .map(|(node, reason)| (node.to_string(), reason.to_string()))
+        .collect();
+        assert_eq!(
+            unschedulable_message(4, &rejected),
+            "0/4 nodes are available: 2 Insufficient cpu, 1 Host port in use, 1 Untolerated taint."
+        );
+        assert_eq!(unschedulable_message(0, &[]), "0/0 nodes are available.");
+    }
+
+    #[tokio::test]
+    async fn test_no_preemption_on_cordoned_or_tainted_node() {
+        let mock_server = start_mock_server().await;
+        let (sched, _rx) = Scheduler::new(mock_server.uri());
+        let (mut node, _) = full_node(&sched, 0);
+        node.spec.unschedulable = true;
+        sched.state.nodes.insert(node.name.clone(), node.clone());
+        let high = add_pending_pod(&sched, 1000);
+
+        sched.schedule(high.metadata.id).await;
+
+        node.spec.unschedulable = false;
+        node.spec.taints = vec![Taint::try_from("gpu:NoSchedule").unwrap()];
+        sched.state.nodes.insert(node.name.clone(), node.clone());
+        sched.schedule(high.metadata.id).await;
+
+        let requests = mock_server.received_requests().await.unwrap();
+        assert!(
+            requests
+                .iter()
+                .all(|r| r.method != wiremock::http::Method::POST)
+        );
+        for request in requests {
+            let body: PodPatch = serde_json::from_slice(&request.body).unwrap();
+            let update: PodConditionUpdate = serde_json::from_value(body.value).unwrap();
+            assert_eq!(update.condition.reason, "Unschedulable");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_preemption_respects_disruption_budget() {
+        let mock_server = start_mock_server().await;
+        let (sched, _rx) = Scheduler::new(mock_server.uri());
+        let (_, protected) = full_node(&sched, 0);
+        let (other, low) = full_node(&sched, 10);
+        Mock::given(method("POST"))
+            .and(path(format!("/pods/{}/eviction", protected.metadata.name)))
+            .respond_with(ResponseTemplate::new(429))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        let high = add_pending_pod(&sched, 1000);
+
+        sched.schedule(high.metadata.id).await;
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let evictions: Vec<String> = requests
+            .iter()
+            .filter(|r| r.method == wiremock::http::Method::POST)
+            .map(|r| r.url.path().to_string())
+            .collect();
+        assert_eq!(
+            evictions,
+            vec![
+                format!("/pods/{}/eviction", protected.metadata.name),
+                format!("/pods/{}/eviction", low.metadata.name),
+            ]
+        );
+
+        let patch = requests
+            .iter()
+            .find(|r| r.method == wiremock::http::Method::PATCH)
+            .expect("condition should be recorded");
+        let body: PodPatch = serde_json::from_slice(&patch.body).unwrap();
+        let update: PodConditionUpdate = serde_json::from_value(body.value).unwrap();
+        assert_eq!(update.condition.reason, "Preempting");
+        assert_eq!(update.nominated_node_name, Some(other.name));
+    }
+
+    /// Registers a group and an empty 1000m node, returns the node name.
+    fn gang_setup(sched: &Scheduler, min_member: u16) -> String {
+        let metadata = Metadata {
+            name: "train".to_string(),
+            ..Default::default()
+        };
+        sched.handle_podgroup_event(PodGroupEvent {
+            event_type: EventType::Added,
+            podgroup: PodGroup {
+                metadata,
+                spec: PodGroupSpec {
+                    min_member,
+                    schedule_timeout_seconds: 60,
+                },
+            },
+        });
+
+        let node = Node::default();
+        sched.state.add_node(&node);
+        sched.state.node_resources.insert(
+            node.name.clone(),
+            SimResources {
+                cpu: 1000,
+                mem: 4096,
+            },
+        );
+        node.name
+    }
+
+    fn add_group_member(sched: &Scheduler) -> Pod {
+        let mut pod = Pod::default();
+        pod.metadata
+            .labels
+            .insert(POD_GROUP_LABEL.to_string(), "train".to_string());
+        sched.state.add_pod(&pod);
+        sched
+            .state
+            .pod_resources
+            .insert(pod.metadata.id, SimResources { cpu: 500, mem: 512 });
+        pod
+    }
+
+    async fn patches(mock_server: &MockServer) -> Vec<PodPatch> {
+        mock_server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|r| r.method == wiremock::http::Method::PATCH)
+            .map(|r| serde_json::from_slice(&r.body).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_gang_binds_all_members() {
+        let mock_server = start_mock_server().await;
+        let (sched, _rx) = Scheduler::new(mock_server.uri());
+        let node = gang_setup(&sched, 2);
+        let members = [add_group_member(&sched), add_group_member(&sched)];
+
+        sched.schedule(members[0].metadata.id).await;
+
+        let node_pods = sched.state.pod_map.get(&node).unwrap();
+        assert!(members.iter().all(|p| node_pods.contains(&p.metadata.id)));
+        let binds = patches(&mock_server).await;
+        assert_eq!(binds.len(), 2);
+        assert!(
+            binds
+                .iter()
+                .all(|p| matches!(p.pod_field, PodField::NodeName))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gang_releases_when_group_does_not_fit() {
+        let mock_server = start_mock_server().await;
+        let (sched, _rx) = Scheduler::new(mock_server.uri());
+        let node = gang_setup(&sched, 3);
+        let members: Vec<Pod> = (0..3).map(|_| add_group_member(&sched)).collect();
+
+        sched.schedule(members[0].metadata.id).await;
+
+        let pending = sched.state.pod_map.get("").unwrap();
+        assert!(members.iter().all(|p| pending.contains(&p.metadata.id)));
+        assert_eq!(sched.state.node_resources.get(&node).unwrap().cpu, 1000);
+        assert!(sched.state.group_backoff_until.contains_key("train"));
+
+        let conditions = patches(&mock_server).await;
+        assert_eq!(conditions.len(), 3);
+        assert!(
+            conditions
+                .iter()
+                .all(|p| matches!(p.pod_field, PodField::Condition))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gang_waits_for_min_member() {
+        let mock_server = start_mock_server().await;
+        let (sched, _rx) = Scheduler::new(mock_server.uri());
+        let _ = gang_setup(&sched, 3);
+        let member = add_group_member(&sched);
+        let _ = add_group_member(&sched);
+
+        sched.schedule(member.metadata.id).await;
+
+        assert_eq!(sched.state.pod_map.get("").unwrap().len(), 2);
+        assert!(sched.state.group_waiting_since.contains_key("train"));
+        assert!(patches(&mock_server).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_gang_unbinds_when_a_bind_fails() {
+        let mock_server = MockServer::start().await;
+        let (sched, _rx) = Scheduler::new(mock_server.uri());
+        let node = gang_setup(&sched, 2);
+        let mut members = [add_group_member(&sched), add_group_member(&sched)];
+        members.sort_by(|a, b| a.metadata.name.cmp(&b.metadata.name));
+
+        // members bind in name order, the second one fails
+        Mock::given(method("PATCH"))
+            .and(path(format!("/pods/{}", members[1].metadata.name)))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PATCH"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path(format!("/pods/{}", members[0].metadata.name)))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        sched.schedule(members[0].metadata.id).await;
+
+        let pending = sched.state.pod_map.get("").unwrap();
+        assert!(members.iter().all(|p| pending.contains(&p.metadata.id)));
+        assert_eq!(sched.state.node_resources.get(&node).unwrap().cpu, 1000);
+        assert!(sched.state.group_backoff_until.contains_key("train"));
+    }
+
+    #[tokio::test]
+    async fn test_gang_times_out_without_new_members() {
+        let mock_server = start_mock_server().await;
+        let (sched, mut rx) = Scheduler::new(mock_server.uri());
+        let _ = gang_setup(&sched, 3);
+        sched
+            .state
+            .pod_groups
+            .get_mut("train")
+            .unwrap()
+            .spec
+            .schedule_timeout_seconds = 1;
+        let member = add_group_member(&sched);
+
+        sched.schedule(member.metadata.id).await;
+        assert!(patches(&mock_server).await.is_empty());
+
+        let id = tokio::time::timeout(Duration::from_secs(3), rx.recv())
+            .await
+            .expect("group should be retried at its deadline");
+        assert_eq!(id, Some(member.metadata.id));
+        sched.schedule(member.metadata.id).await;
+
+        let conditions = patches(&mock_server).await;
+        assert_eq!(conditions.len(), 1);
+        let update: PodConditionUpdate =
+            serde_json::from_value(conditions[0].value.clone()).unwrap();
+        assert_eq!(update.condition.reason, "PodGroupTimeout");
+    }
+
+    #[tokio::test]
+    async fn test_filter_taints_and_anti_affinity() {
+        let (sched, _rx) = Scheduler::new("http://localhost".to_string());
+        for (name, taints) in [
+            ("tainted", vec![Taint::try_from("gpu:NoSchedule").unwrap()]),
+            ("busy", vec![]),
+            ("free", vec![]),
+        ] {
+            sched.state.add_node(&Node {
+                name: name.to_string(),
+                spec: NodeSpec {
+                    taints,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        }
+        let mut db = Pod::default();
+        db.metadata
+            .labels
+            .insert("app".to_string(), "db".to_string());
+        sched.state.add_pod(&db);
+        sched.state.assign_pod(&db.metadata.id, "busy");
+
+        let mut pod = Pod::default();
+        pod.spec.anti_affinity = Some(LabelSelector::try_from("app=db".to_string()).unwrap());
+        sched.state.add_pod(&pod);
+        sched
+            .state
+            .pod_resources
+            .insert(pod.metadata.id, SimResources { cpu: 0, mem: 0 });
+
+        let flow = SchedulerFlow::new(&sched.state, pod, None, None)
+            .simulate()
+            .await;
+
+        assert_eq!(flow.chosen.as_deref(), Some("free"));
+        let reason = |node: &str| {
+            flow.rejected
+                .iter()
+                .find(|(n, _)| n == node)
+                .map(|(_, r)| r.clone())
+        };
+        assert_eq!(reason("tainted").as_deref(), Some("Untolerated taint"));
+        assert_eq!(
+            reason("busy").as_deref(),
+            Some("Pod anti-affinity conflict")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_filter_host_port_conflict() {
+        let (sched, _rx) = Scheduler::new("http://localhost".to_string());
+        for name in ["busy", "free"] {
+            sched.state.add_node(&Node {
+                name: name.to_string(),
+                ..Default::default()
+            });
+        }
+        let with_host_port = |protocol: Protocol| {
+            let mut pod = Pod::default();
+            pod.spec.containers[0].ports = Some(vec![Port {
+                container_port: 80,
+                host_port: Some(8080),
+                protocol,
+                name: None,
+            }]);
+            pod
+        };
+        let web = with_host_port(Protocol::Tcp);
+        sched.state.add_pod(&web);
+        sched.state.assign_pod(&web.metadata.id, "busy");
+
+        let pod = with_host_port(Protocol::Tcp);
+        sched.state.add_pod(&pod);
+        sched
+            .state
+            .pod_resources
+            .insert(pod.metadata.id, SimResources { cpu: 0, mem: 0 });
+
+        let flow = SchedulerFlow::new(&sched.state, pod, None, None)
+            .simulate()
+            .await;
+
+        assert_eq!(flow.chosen.as_deref(), Some("free"));
+        assert!(
+            flow.rejected
+                .iter()
+                .any(|(n, r)| n == "busy" && r == "Host port in use")
+        );
+
+        // same port over another protocol doesn't conflict
+        let udp = with_host_port(Protocol::Udp);
+        sched.state.add_pod(&udp);
+        sched
+            .state
+            .pod_resources
+            .insert(udp.metadata.id, SimResources { cpu: 0, mem: 0 });
+        let flow = SchedulerFlow::new(&sched.state, udp, None, None)
+            .simulate()
+            .await;
+        assert!(flow.rejected.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_filter_volume_topology() {
+        let (sched, _rx) = Scheduler::new("http://localhost".to_string());
+        for name in ["a", "b"] {
+            sched.state.add_node(&Node {
+                name: name.to_string(),
+                ..Default::default()
+            });
+        }
+        let mut pod = Pod::default();
+        pod.spec.volumes = vec![Volume {
+            name: "data".to_string(),
+            source: VolumeSource::PersistentVolumeClaim(ClaimVolume {
+                claim_name: "db".to_string(),
+                read_only: false,
+            }),
+        }];
+        sched.state.add_pod(&pod);
+        sched
+            .state
+            .pod_resources
+            .insert(pod.metadata.id, SimResources { cpu: 0, mem: 0 });
+
+        let flow = SchedulerFlow::new(&sched.state, pod.clone(), None, None)
+            .simulate()
+            .await;
+        assert!(flow.chosen.is_none());
+        assert!(
+            flow.rejected
+                .iter()
+                .all(|(_, r)| r == "Persistent volume claim not found")
+        );
+
+        let mut claim = PersistentVolumeClaim {
+            metadata: Metadata {
+                name: "db".to_string(),
+                ..Default::default()
+            },
+            spec: PersistentVolumeClaimSpec {
+                storage: "1Gi".to_string(),
+                volume_name: None,
+                reclaim_policy: ReclaimPolicy::Delete,
+            },
+            status: Default::default(),
+        };
+        sched.handle_claim_event(PersistentVolumeClaimEvent {
+            event_type: EventType::Added,
+            claim: claim.clone(),
+        });
+        let flow = SchedulerFlow::new(&sched.state, pod.clone(), None, None)
+            .simulate()
+            .await;
+        assert!(flow.rejected.is_empty());
+
+        claim.status.phase = ClaimPhase::Bound;
+        claim.status.node_name = Some("b".to_string());
+        sched.handle_claim_event(PersistentVolumeClaimEvent {
+            event_type: EventType::Modified,
+            claim,
+        });
+        let flow = SchedulerFlow::new(&sched.state, pod, None, None)
+            .simulate()
+            .await;
+        assert_eq!(flow.chosen.as_deref(), Some("b"));
+        assert_eq!(
+            flow.rejected,
+            vec![("a".to_string(), "Volume node affinity conflict".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cordoned_node_is_skipped() {
+        let mock_server = start_mock_server().await;
+        let (sched, _rx) = Scheduler::new(mock_server.uri());
+        let mut node = Node::default();
+        sched.handle_node_event(NodeEvent {
+            node: node.clone(),
+            event_type: EventType::Added,
+        });
+        node.spec.unschedulable = true;
+        sched.handle_node_event(NodeEvent {
+            node: node.clone(),
+            event_type: EventType::Modified,
+        });
+
+        let pod = add_pending_pod(&sched, 0);
+        sched.schedule(pod.metadata.id).await;
+        assert!(
+            sched
+                .state
+                .pod_map
+                .get("")
+                .unwrap()
+                .contains(&pod.metadata.id)
+        );
+
+        node.spec.unschedulable = false;
+        sched.handle_node_event(NodeEvent {
+            node: node.clone(),
+            event_type: EventType::Modified,
+        });
+        sched.schedule(pod.metadata.id).await;
+        assert!(
+            sched
+                .state
+                .pod_map
+                .get(&node.name)
+                .unwrap()
+                .contains(&pod.metadata.id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deleted_node_is_forgotten() {
+        let mock_server = start_mock_server().await;
+        let (sched, _rx) = Scheduler::new(mock_server.uri());
+        let node = Node::default();
+        sched.handle_node_event(NodeEvent {
+            node: node.clone(),
+            event_type: EventType::Added,
+        });
+        sched.handle_node_event(NodeEvent {
+            node: node.clone(),
+            event_type: EventType::Deleted,
+        });
+        assert!(!sched.state.nodes.contains_key(&node.name));
+        assert!(!sched.state.node_resources.contains_key(&node.name));
+
+        let pod = add_pending_pod(&sched, 0);
+        sched.schedule(pod.metadata.id).await;
+        assert!(
+            sched
+                .state
+                .pod_map
+                .get("")
+                .unwrap()
+                .contains(&pod.metadata.id)
+        );
+    }
 }
@@ -0,0 +1,129 @@
+use reqwest::StatusCode;
+use shared::models::pod::Pod;
+
+use super::{filter::node_conflict, flow::SchedulerFlow, state::State};
+
+/// Lower priority pods to evict from a node so a pending pod fits.
+pub struct Preemption {
+    pub node: String,
+    pub victims: Vec<Pod>,
+}
+
+/// Result of evicting the victims of a preemption.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Evicted,
+    /// A disruption budget refused an eviction, the node can't be preempted now
+    Blocked,
+    Failed,
+}
+
+impl Preemption {
+    /// Nodes where the preemptor fits by evicting pods of strictly lower priority, best first:
+    /// the lowest highest-victim priority, then the fewest victims.
+    /// Only nodes passing every filter but the resource check, extenders included, are considered.
+    pub async fn select(state: &State, pod: &Pod) -> Vec<Self> {
+        let Some(pod_res) = state.pod_resources.get(&pod.metadata.id).map(|r| r.clone()) else {
+            return Vec::new();
+        };
+        let mut options: Vec<((i32, usize), Preemption)> = Vec::new();
+
+        for entry in state.nodes.iter() {
+            let node_name = entry.key();
+            // evictions can't lift a cordon, a taint or the pod's volume topology
+            if node_conflict(state, pod, entry.value()).is_some() {
+                continue;
+            }
+            let Some(mut free) = state.node_resources.get(node_name).map(|r| r.clone()) else {
+                continue;
+            };
+
+            // lowest priority pods are evicted first
+            let mut candidates: Vec<Pod> = state
+                .pod_map
+                .get(node_name)
+                .map(|set| {
+                    set.iter()
+                        .filter_map(|id| state.pods.get(&*id).map(|p| p.clone()))
+                        .filter(|p| p.spec.priority < pod.spec.priority)
+                        .collect()
+                })
+                .unwrap_or_default();
+            candidates.sort_by_key(|p| p.spec.priority);
+
+            let mut victims = Vec::new();
+            for victim in candidates {
+                if free.fits(&pod_res) {
+                    break;
+                }
+                if let Some(res) = state.pod_resources.get(&victim.metadata.id) {
+                    free.add(&res);
+                }
+                victims.push(victim);
+            }
+
+            if victims.is_empty() || !free.fits(&pod_res) {
+                continue;
+            }
+
+            let cost = (
+                victims
+                    .iter()
+                    .map(|p| p.spec.priority)
+                    .max()
+                    .unwrap_or(i32::MIN),
+                victims.len(),
+            );
+            options.push((
+                cost,
+                Preemption {
+                    node: node_name.clone(),
+                    victims,
+                },
+            ));
+        }
+        if options.is_empty() {
+            return Vec::new();
+        }
+
+        let nodes = options.iter().map(|(_, p)| p.node.clone()).collect();
+        let allowed = SchedulerFlow::new(state, pod.clone(), None, None)
+            .filter_extenders_on(nodes)
+            .await
+            .candidates;
+        options.retain(|(_, p)| allowed.iter().any(|(node, _)| *node == p.node));
+        options.sort_by_key(|(cost, _)| *cost);
+        options
+            .into_iter()
+            .map(|(_, preemption)| preemption)
+            .collect()
+    }
+
+    /// Evicts every victim through the eviction API, so disruption budgets apply.
+    pub async fn execute(&self, state: &State) -> Outcome {
+        let client = shared::utils::client();
+        let mut outcome = Outcome::Evicted;
+
+        for victim in &self.victims {
+            let url = format!("{}/{}/eviction", state.pods_uri, victim.metadata.name);
+            match client.post(&url).send().await {
+                Ok(resp) if resp.status().is_success() => {
+                    tracing::info!(pod=%victim.metadata.name, node=%self.node, "Preempted");
+                }
+                Ok(resp) if resp.status() == StatusCode::TOO_MANY_REQUESTS => {
+                    tracing::debug!(pod=%victim.metadata.name, node=%self.node, "Preemption blocked by disruption budget");
+                    return Outcome::Blocked;
+                }
+                Ok(resp) => {
+                    tracing::error!(status=%resp.status(), pod=%victim.metadata.name, "Failed to evict pod");
+                    outcome = Outcome::Failed;
+                }
+                Err(err) => {
+                    tracing::error!(error=%err, pod=%victim.metadata.name, "Failed to evict pod");
+                    outcome = Outcome::Failed;
+                }
+            }
+        }
+        outcome
+    }
+}
@@ -0,0 +1,73 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+use uuid::Uuid;
+
+/// Pending pods ordered by priority, FIFO among equal priorities.
+pub struct SchedulingQueue {
+    heap: BinaryHeap<QueuedPod>,
+    queued: HashSet<Uuid>,
+    seq: u64,
+}
+
+struct QueuedPod {
+    priority: i32,
+    seq: u64,
+    id: Uuid,
+}
+
+impl SchedulingQueue {
+    pub fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            queued: HashSet::new(),
+            seq: 0,
+        }
+    }
+
+    /// Adds a pod unless it is already waiting in the queue.
+    pub fn push(&mut self, id: Uuid, priority: i32) {
+        if !self.queued.insert(id) {
+            return;
+        }
+        self.seq += 1;
+        self.heap.push(QueuedPod {
+            priority,
+            seq: self.seq,
+            id,
+        });
+    }
+
+    /// Removes the highest priority pod.
+    pub fn pop(&mut self) -> Option<Uuid> {
+        let next = self.heap.pop()?;
+        self.queued.remove(&next.id);
+        Some(next.id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+impl Ord for QueuedPod {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for QueuedPod {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for QueuedPod {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedPod {}
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use shared::{
+    api::{NodeVerdict, PodSimulation, SimulationResult},
+    models::pod::Pod,
+};
+
+use super::{flow::SchedulerFlow, state::State};
+
+/// Place pods one after another on a snapshot of the scheduler state.
+///
+/// Each placement reduces the snapshot resources so later pods see
+/// the cluster as it would be, nothing is bound or sent to the API server.
+pub async fn simulate(state: &State, pods: Vec<Pod>) -> SimulationResult {
+    let snapshot = state.snapshot();
+    let mut result = SimulationResult::default();
+
+    for pod in pods {
+        snapshot.add_pod(&pod);
+
+        let flow = SchedulerFlow::new(&snapshot, pod.clone(), None, None)
+            .simulate()
+            .await;
+
+        let scores: HashMap<_, _> = flow.candidates.iter().cloned().collect();
+        let reasons: HashMap<_, _> = flow.rejected.iter().cloned().collect();
+        let mut nodes: Vec<NodeVerdict> = snapshot
+            .nodes
+            .iter()
+            .map(|entry| {
+                let node_name = entry.key().clone();
+                NodeVerdict {
+                    feasible: scores.contains_key(&node_name),
+                    score: scores.get(&node_name).copied(),
+                    reason: reasons.get(&node_name).cloned(),
+                    node_name,
+                }
+            })
+            .collect();
+        nodes.sort_by(|a, b| a.node_name.cmp(&b.node_name));
+
+        if let Some(node) = &flow.chosen {
+            snapshot.assign_pod(&pod.metadata.id, node);
+        }
+        result.pods.push(PodSimulation {
+            pod_name: pod.metadata.name,
+            node_name: flow.chosen,
+            nodes,
+        });
+    }
+    result
+}
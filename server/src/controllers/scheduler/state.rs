@@ -1,10 +1,15 @@
 use dashmap::{DashMap, DashSet};
 use rand::Rng;
 use rand::prelude::IndexedRandom;
-use shared::models::{node::Node, pod::Pod};
+use shared::models::{
+    node::Node, persistentvolumeclaim::PersistentVolumeClaim, pod::Pod, podgroup::PodGroup,
+};
 use std::sync::Arc;
+use tokio::time::Instant;
 use uuid::Uuid;
 
+use super::extender::Extender;
+
 pub type State = Arc<SchedulerState>;
 
 /// In-memory scheduler state shared across tasks.
@@ -17,18 +22,53 @@ pub struct SchedulerState {
     pub pod_resources: DashMap<Uuid, SimResources>,
     pub node_resources: DashMap<String, SimResources>,
 
+    /// Gang scheduling bookkeeping by pod group name
+    pub pod_groups: DashMap<String, PodGroup>,
+    pub group_waiting_since: DashMap<String, Instant>,
+    pub group_backoff_until: DashMap<String, Instant>,
+
+    /// Claims by name, bound ones pin their pods to a node
+    pub claims: DashMap<String, PersistentVolumeClaim>,
+
     pub pods_uri: String,
+    pub extenders: Vec<Extender>,
 }
 
 impl SchedulerState {
     pub fn new(apiserver: &str) -> State {
+        Self::new_with_extenders(apiserver, Extender::from_env())
+    }
+
+    pub fn new_with_extenders(apiserver: &str, extenders: Vec<Extender>) -> State {
         Arc::new(Self {
             nodes: DashMap::new(),
             pods: DashMap::new(),
             pod_map: DashMap::new(),
             node_resources: DashMap::new(),
             pod_resources: DashMap::new(),
+            pod_groups: DashMap::new(),
+            group_waiting_since: DashMap::new(),
+            group_backoff_until: DashMap::new(),
+            claims: DashMap::new(),
             pods_uri: format!("{}/pods", apiserver),
+            extenders,
+        })
+    }
+
+    /// Independent copy of the current state, changes do not affect the original.
+    pub fn snapshot(&self) -> State {
+        Arc::new(Self {
+            nodes: self.nodes.clone(),
+            pods: self.pods.clone(),
+            pod_map: self.pod_map.clone(),
+            pod_resources: self.pod_resources.clone(),
+            node_resources: self.node_resources.clone(),
+            pod_groups: self.pod_groups.clone(),
+            group_waiting_since: self.group_waiting_since.clone(),
+            group_backoff_until: self.group_backoff_until.clone(),
+            claims: self.claims.clone(),
+            pods_uri: self.pods_uri.clone(),
+            extenders: self.extenders.clone(),
         })
     }
 
@@ -51,6 +91,12 @@ impl SchedulerState {
             .insert(node.name.clone(), SimResources::new_node_res());
     }
 
+    pub fn delete_node(&self, name: &str) {
+        // bound pods are deleted by the apiserver and arrive as pod events
+        self.nodes.remove(name);
+        self.node_resources.remove(name);
+    }
+
     pub fn delete_pod(&self, id: &Uuid) {
         // remove pod
         if let Some((_, pod)) = self.pods.remove(id) {
@@ -116,6 +162,11 @@ impl SchedulerState {
             pod.spec.node_name = node.to_string();
         }
     }
+
+    /// Moves an assigned pod back to the unassigned bucket, freeing its node resources.
+    pub fn release_pod(&self, id: &Uuid) {
+        self.assign_pod(id, "");
+    }
 }
 
 // -------------------------
@@ -153,6 +204,11 @@ impl SimResources {
         }
     }
 
+    /// True if `other` can be placed within these resources
+    pub fn fits(&self, other: &Self) -> bool {
+        self.cpu >= other.cpu && self.mem >= other.mem
+    }
+
     pub fn add(&mut self, other: &Self) {
         self.cpu += other.cpu;
         self.mem += other.mem;
@@ -0,0 +1,260 @@
+//! ConfigMap
+//!
+//! ## Routes
+//! - `GET    /configmaps`          — List config maps
+//! - `POST   /configmaps`          — Create a new config map
+//! - `GET    /configmaps/{name}`   — Get a config map
+//! - `PUT    /configmaps/{name}`   — Replace the data of a config map
+//! - `DELETE /configmaps/{name}`   — Delete a config map
+
+use crate::state::State;
+use actix_web::{HttpResponse, Responder, web};
+use shared::{
+    api::{ConfigMapManifest, CreateResponse},
+    models::configmap::ConfigMapSpec,
+};
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get))
+        .route("", web::post().to(create))
+        .route("/{name}", web::get().to(get_one))
+        .route("/{name}", web::put().to(update))
+        .route("/{name}", web::delete().to(delete));
+}
+
+/// List config maps
+///
+/// # Returns
+/// - 200 list of config maps
+async fn get(state: State) -> impl Responder {
+    HttpResponse::Ok().json(state.get_configmaps().await)
+}
+
+/// Get a config map by name.
+///
+/// # Returns
+/// - 200: The config map
+/// - 404: Config map not found
+async fn get_one(state: State, path_string: web::Path<String>) -> impl Responder {
+    match state.get_configmap(&path_string.into_inner()).await {
+        Ok(obj) => HttpResponse::Ok().json(obj),
+        Err(err) => err.to_http_response(),
+    }
+}
+
+/// Create a new config map.
+///
+/// # Arguments
+/// - `body`: ConfigMap manifest JSON.
+///
+/// # Returns
+/// - 201: Config map created.
+/// - 400: Invalid manifest format or keys
+/// - 409: Repeat name
+async fn create(state: State, payload: web::Json<ConfigMapManifest>) -> impl Responder {
+    let manifest = payload.into_inner();
+
+    if manifest.metadata.owner_reference.is_some() {
+        return HttpResponse::BadRequest().finish();
+    }
+
+    let name = manifest.metadata.name.clone();
+    match state
+        .add_configmap(manifest.spec, manifest.metadata.into())
+        .await
+    {
+        Ok(id) => {
+            tracing::info!(%name, "Config map created");
+            let response = CreateResponse {
+                id,
+                status: "Accepted".into(),
+            };
+            HttpResponse::Created().json(response)
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not create config map");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Replace the data of a config map, pods mounting it get the new files.
+///
+/// # Returns
+/// - 204: Config map updated
+/// - 400: Invalid keys
+/// - 404: Config map not found
+async fn update(
+    state: State,
+    path_string: web::Path<String>,
+    payload: web::Json<ConfigMapSpec>,
+) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.update_configmap(&name, payload.into_inner()).await {
+        Ok(()) => {
+            tracing::info!(%name, "Config map updated");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not update config map");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Delete a config map by name.
+///
+/// # Returns
+/// - 204: Config map deleted
+/// - 404: Config map not found
+async fn delete(state: State, path_string: web::Path<String>) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.delete_configmap(&name).await {
+        Ok(_) => {
+            tracing::info!(%name, "Config map deleted");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not delete config map");
+            err.to_http_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //!  CREATE
+    //!  - test_create_configmap
+    //!  - test_create_configmap_invalid_key
+    //!
+    //!  UPDATE
+    //!  - test_update_configmap
+    //!
+    //!  DELETE
+    //!  - test_delete_configmap_not_found
+
+    use std::collections::HashMap;
+
+    use crate::state::{ApiServerState, test_store::TestStore};
+
+    use super::*;
+    use actix_web::body::BoxBody;
+    use actix_web::dev::Service;
+    use actix_web::{
+        App,
+        http::StatusCode,
+        test::{TestRequest, call_service, init_service, read_body_json},
+    };
+    use shared::models::{configmap::ConfigMap, metadata::ObjectMetadata};
+
+    async fn configmap_service(
+        state: &State,
+    ) -> impl Service<
+        actix_http::Request,
+        Response = actix_web::dev::ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    > {
+        init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/configmaps", web::get().to(get))
+                .route("/configmaps", web::post().to(create))
+                .route("/configmaps/{name}", web::get().to(get_one))
+                .route("/configmaps/{name}", web::put().to(update))
+                .route("/configmaps/{name}", web::delete().to(delete)),
+        )
+        .await
+    }
+
+    fn spec(entries: &[(&str, &str)]) -> ConfigMapSpec {
+        ConfigMapSpec {
+            data: entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    fn manifest(name: &str, entries: &[(&str, &str)]) -> ConfigMapManifest {
+        ConfigMapManifest {
+            metadata: ObjectMetadata {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            spec: spec(entries),
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_configmap() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = configmap_service(&state).await;
+
+        for expected in [StatusCode::CREATED, StatusCode::CONFLICT] {
+            let req = TestRequest::post()
+                .uri("/configmaps")
+                .set_json(manifest("app", &[("log_level", "debug")]))
+                .to_request();
+            let res = call_service(&app, req).await;
+            assert_eq!(res.status(), expected);
+        }
+
+        let req = TestRequest::get().uri("/configmaps").to_request();
+        let res = call_service(&app, req).await;
+        let maps: Vec<ConfigMap> = read_body_json(res).await;
+        assert_eq!(maps.len(), 1);
+        assert_eq!(maps[0].spec.data["log_level"], "debug");
+    }
+
+    #[actix_web::test]
+    async fn test_create_configmap_invalid_key() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = configmap_service(&state).await;
+
+        for key in ["../passwd", "a/b", ".."] {
+            let req = TestRequest::post()
+                .uri("/configmaps")
+                .set_json(manifest("app", &[(key, "x")]))
+                .to_request();
+            let res = call_service(&app, req).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "key {}", key);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_update_configmap() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = configmap_service(&state).await;
+
+        let req = TestRequest::post()
+            .uri("/configmaps")
+            .set_json(manifest("app", &[("log_level", "debug")]))
+            .to_request();
+        call_service(&app, req).await;
+
+        let req = TestRequest::put()
+            .uri("/configmaps/app")
+            .set_json(spec(&[("log_level", "info")]))
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+        let req = TestRequest::get().uri("/configmaps/app").to_request();
+        let res = call_service(&app, req).await;
+        let cm: ConfigMap = read_body_json(res).await;
+        assert_eq!(cm.spec.data["log_level"], "info");
+        assert_eq!(cm.metadata.generation, 2);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_configmap_not_found() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = configmap_service(&state).await;
+
+        let req = TestRequest::delete()
+            .uri("/configmaps/made-up")
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+}
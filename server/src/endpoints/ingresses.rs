@@ -0,0 +1,319 @@
+//! Ingress
+//!
+//! ## Routes
+//! - `GET    /ingresses`          — List or watch ingresses
+//! - `POST   /ingresses`          — Create a new ingress
+//! - `GET    /ingresses/{name}`   — Get an ingress
+//! - `PUT    /ingresses/{name}`   — Replace the rules of an ingress
+//! - `DELETE /ingresses/{name}`   — Delete an ingress
+
+use crate::state::State;
+use actix_web::{
+    HttpResponse, Responder,
+    web::{self, Bytes},
+};
+use serde::Deserialize;
+use shared::{
+    api::{CreateResponse, EventType, IngressEvent, IngressManifest},
+    models::ingress::IngressSpec,
+};
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get))
+        .route("", web::post().to(create))
+        .route("/{name}", web::get().to(get_one))
+        .route("/{name}", web::put().to(update))
+        .route("/{name}", web::delete().to(delete));
+}
+
+#[derive(Deserialize)]
+pub struct IngressQuery {
+    watch: Option<bool>,
+}
+
+/// List or watch ingresses
+///
+/// # Arguments
+/// - `query`: Query parameters:
+///    - `watch` (bool, optional): If true, opens a watch stream of ingress events.
+///
+/// # Returns
+/// - 200 list of ingresses or stream of ingress events
+async fn get(state: State, query: web::Query<IngressQuery>) -> impl Responder {
+    let ingresses = state.get_ingresses().await;
+    if query.watch.unwrap_or(false) {
+        let mut rx = state.ingress_tx.subscribe();
+        let stream = async_stream::stream! {
+            for ingress in ingresses {
+                let event = IngressEvent {
+                    ingress,
+                    event_type: EventType::Added
+                };
+                let json = serde_json::to_string(&event).unwrap();
+                yield Ok::<_, actix_web::Error>(Bytes::from(json + "\n"));
+            }
+            while let Ok(event) = rx.recv().await {
+                let json = serde_json::to_string(&event).unwrap();
+                yield Ok::<_, actix_web::Error>(Bytes::from(json + "\n"));
+            }
+        };
+
+        HttpResponse::Ok()
+            .content_type("application/json")
+            .streaming(stream)
+    } else {
+        HttpResponse::Ok().json(&ingresses)
+    }
+}
+
+/// Get an ingress by name.
+///
+/// # Returns
+/// - 200: The ingress
+/// - 404: Ingress not found
+async fn get_one(state: State, path_string: web::Path<String>) -> impl Responder {
+    match state.get_ingress(&path_string.into_inner()).await {
+        Ok(obj) => HttpResponse::Ok().json(obj),
+        Err(err) => err.to_http_response(),
+    }
+}
+
+/// Create a new ingress.
+///
+/// # Arguments
+/// - `body`: Ingress manifest JSON.
+///
+/// # Returns
+/// - 201: Ingress created
+/// - 400: Invalid manifest format, hosts or paths
+/// - 409: Repeat name
+async fn create(state: State, payload: web::Json<IngressManifest>) -> impl Responder {
+    let manifest = payload.into_inner();
+
+    if manifest.metadata.owner_reference.is_some() {
+        return HttpResponse::BadRequest().finish();
+    }
+
+    let name = manifest.metadata.name.clone();
+    match state
+        .add_ingress(manifest.spec, manifest.metadata.into())
+        .await
+    {
+        Ok(id) => {
+            tracing::info!(%name, "Ingress created");
+            let response = CreateResponse {
+                id,
+                status: "Accepted".into(),
+            };
+            HttpResponse::Created().json(response)
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not create ingress");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Replace the rules of an ingress, gateways switch to them without dropping connections.
+///
+/// # Returns
+/// - 204: Ingress updated
+/// - 400: Invalid hosts or paths
+/// - 404: Ingress not found
+async fn update(
+    state: State,
+    path_string: web::Path<String>,
+    payload: web::Json<IngressSpec>,
+) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.update_ingress(&name, payload.into_inner()).await {
+        Ok(()) => {
+            tracing::info!(%name, "Ingress updated");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not update ingress");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Delete an ingress by name.
+///
+/// # Returns
+/// - 204: Ingress deleted
+/// - 404: Ingress not found
+async fn delete(state: State, path_string: web::Path<String>) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.delete_ingress(&name).await {
+        Ok(_) => {
+            tracing::info!(%name, "Ingress deleted");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not delete ingress");
+            err.to_http_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //!  CREATE
+    //!  - test_create_ingress
+    //!  - test_create_ingress_invalid_rules
+    //!
+    //!  UPDATE
+    //!  - test_update_ingress
+    //!    emits a modified event
+
+    use crate::state::{ApiServerState, test_store::TestStore};
+
+    use super::*;
+    use actix_web::body::BoxBody;
+    use actix_web::{
+        App,
+        http::StatusCode,
+        test::{TestRequest, call_service, init_service, read_body_json},
+    };
+    use shared::models::{
+        ingress::{Ingress, IngressBackend, IngressPath, IngressRule, IngressTls},
+        metadata::ObjectMetadata,
+    };
+
+    async fn ingress_service(
+        state: &State,
+    ) -> impl actix_web::dev::Service<
+        actix_http::Request,
+        Response = actix_web::dev::ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    > {
+        init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/ingresses", web::post().to(create))
+                .route("/ingresses/{name}", web::get().to(get_one))
+                .route("/ingresses/{name}", web::put().to(update)),
+        )
+        .await
+    }
+
+    fn rule(host: Option<&str>, path: &str, service: &str) -> IngressRule {
+        IngressRule {
+            host: host.map(str::to_string),
+            paths: vec![IngressPath {
+                path: path.to_string(),
+                backend: IngressBackend {
+                    service: service.to_string(),
+                    port: 80,
+                },
+            }],
+        }
+    }
+
+    fn spec(rules: Vec<IngressRule>) -> IngressSpec {
+        IngressSpec { rules, tls: vec![] }
+    }
+
+    fn manifest(name: &str, spec: IngressSpec) -> IngressManifest {
+        IngressManifest {
+            metadata: ObjectMetadata {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            spec,
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_ingress() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = ingress_service(&state).await;
+
+        let web = spec(vec![
+            rule(Some("shop.example.com"), "/", "shop"),
+            rule(Some("shop.example.com"), "/api", "api"),
+            rule(None, "/", "default"),
+        ]);
+        for expected in [StatusCode::CREATED, StatusCode::CONFLICT] {
+            let req = TestRequest::post()
+                .uri("/ingresses")
+                .set_json(manifest("web", web.clone()))
+                .to_request();
+            assert_eq!(call_service(&app, req).await.status(), expected);
+        }
+
+        let req = TestRequest::get().uri("/ingresses/web").to_request();
+        let ingress: Ingress = read_body_json(call_service(&app, req).await).await;
+        assert_eq!(ingress.spec.rules.len(), 3);
+    }
+
+    #[actix_web::test]
+    async fn test_create_ingress_invalid_rules() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = ingress_service(&state).await;
+
+        let mut no_port = rule(None, "/", "web");
+        no_port.paths[0].backend.port = 0;
+        let mut no_secret = spec(vec![rule(None, "/", "web")]);
+        no_secret.tls = vec![IngressTls {
+            hosts: vec!["example.com".to_string()],
+            secret_name: String::new(),
+        }];
+        for spec in [
+            spec(vec![]),
+            spec(vec![rule(None, "api", "web")]),
+            spec(vec![rule(Some("Bad_Host"), "/", "web")]),
+            spec(vec![rule(None, "/", "")]),
+            spec(vec![no_port]),
+            spec(vec![rule(None, "/a", "web"), rule(None, "/a/", "api")]),
+            no_secret,
+        ] {
+            let req = TestRequest::post()
+                .uri("/ingresses")
+                .set_json(manifest("web", spec.clone()))
+                .to_request();
+            let res = call_service(&app, req).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{:?}", spec);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_update_ingress() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = ingress_service(&state).await;
+
+        let req = TestRequest::put()
+            .uri("/ingresses/web")
+            .set_json(spec(vec![rule(None, "/", "web")]))
+            .to_request();
+        assert_eq!(
+            call_service(&app, req).await.status(),
+            StatusCode::NOT_FOUND
+        );
+
+        let req = TestRequest::post()
+            .uri("/ingresses")
+            .set_json(manifest("web", spec(vec![rule(None, "/", "web")])))
+            .to_request();
+        call_service(&app, req).await;
+
+        let mut rx = state.ingress_tx.subscribe();
+        let req = TestRequest::put()
+            .uri("/ingresses/web")
+            .set_json(spec(vec![rule(None, "/", "web-v2")]))
+            .to_request();
+        assert_eq!(
+            call_service(&app, req).await.status(),
+            StatusCode::NO_CONTENT
+        );
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.event_type, EventType::Modified);
+        assert_eq!(event.ingress.metadata.generation, 2);
+        assert_eq!(
+            event.ingress.spec.rules[0].paths[0].backend.service,
+            "web-v2"
+        );
+    }
+}
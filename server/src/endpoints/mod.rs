@@ -1,18 +1,54 @@
+mod configmaps;
+mod ingresses;
 mod nodes;
+mod persistentvolumeclaims;
+mod persistentvolumes;
+mod poddisruptionbudgets;
+mod podgroups;
 mod pods;
+mod priorityclasses;
+mod rbac;
 mod replicasets;
+mod scheduler;
+mod secrets;
+mod serviceendpoints;
+mod services;
+mod tokens;
 
-use actix_web::Error;
+use actix_web::body::EitherBody;
 use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::AUTHORIZATION;
 use actix_web::web::{self, scope};
+use actix_web::{Error, HttpMessage, HttpResponse};
 use futures_util::future::{LocalBoxFuture, Ready, ok};
+use std::rc::Rc;
 use std::task::{Context, Poll};
+
+use crate::auth::{UserInfo, rbac::Attributes, tls};
+use crate::state::State;
 use tracing::{error, trace, warn};
 
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(scope("/nodes").configure(nodes::config))
         .service(scope("/pods").configure(pods::config))
-        .service(scope("/replicasets").configure(replicasets::config));
+        .service(scope("/replicasets").configure(replicasets::config))
+        .service(scope("/poddisruptionbudgets").configure(poddisruptionbudgets::config))
+        .service(scope("/podgroups").configure(podgroups::config))
+        .service(scope("/priorityclasses").configure(priorityclasses::config))
+        .service(scope("/configmaps").configure(configmaps::config))
+        .service(scope("/secrets").configure(secrets::config))
+        .service(scope("/persistentvolumes").configure(persistentvolumes::config))
+        .service(scope("/persistentvolumeclaims").configure(persistentvolumeclaims::config))
+        .service(scope("/services").configure(services::config))
+        .service(scope("/endpoints").configure(serviceendpoints::config))
+        .service(scope("/ingresses").configure(ingresses::config))
+        .service(scope("/tokens").configure(tokens::config))
+        .service(scope("/roles").configure(rbac::roles_config))
+        .service(scope("/clusterroles").configure(rbac::clusterroles_config))
+        .service(scope("/rolebindings").configure(rbac::rolebindings_config))
+        .service(scope("/clusterrolebindings").configure(rbac::clusterrolebindings_config))
+        .service(scope("/accessreviews").configure(rbac::accessreviews_config))
+        .service(scope("/scheduler").configure(scheduler::config));
 }
 
 pub struct Logging;
@@ -76,6 +112,159 @@ where
     }
 }
 
+/// Rejects requests without valid credentials with 401 when authentication is enabled.
+/// The caller is stored as [`UserInfo`] in the request extensions for the handlers.
+pub struct Authentication;
+
+impl<S, B> Transform<S, ServiceRequest> for Authentication
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = AuthenticationMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(AuthenticationMiddleware {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct AuthenticationMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for AuthenticationMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let Some(state) = req.app_data::<State>().cloned() else {
+                return service.call(req).await.map(|res| res.map_into_left_body());
+            };
+            if !state.auth.enabled {
+                return service.call(req).await.map(|res| res.map_into_left_body());
+            }
+
+            match authenticate(&state, &req).await {
+                Some(user) => {
+                    req.extensions_mut().insert(user);
+                    service.call(req).await.map(|res| res.map_into_left_body())
+                }
+                None => {
+                    let res = HttpResponse::Unauthorized().body("Missing or invalid credentials");
+                    Ok(req.into_response(res).map_into_right_body())
+                }
+            }
+        })
+    }
+}
+
+/// Rejects requests the authenticated caller has no role for with 403.
+/// Runs inside [`Authentication`], requests without a caller pass when authentication is disabled.
+pub struct Authorization;
+
+impl<S, B> Transform<S, ServiceRequest> for Authorization
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = AuthorizationMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(AuthorizationMiddleware {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct AuthorizationMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for AuthorizationMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let user = req.extensions().get::<UserInfo>().cloned();
+            let (Some(user), Some(state)) = (user, req.app_data::<State>().cloned()) else {
+                return service.call(req).await.map(|res| res.map_into_left_body());
+            };
+            // callers may always ask what they are allowed to do
+            if req.path() == "/accessreviews" {
+                return service.call(req).await.map(|res| res.map_into_left_body());
+            }
+
+            let denied =
+                match Attributes::from_request(req.method(), req.path(), req.query_string()) {
+                    Some(attrs) => match state.authorize(&user, &attrs).await {
+                        Ok(Ok(_)) => None,
+                        Ok(Err(reason)) => Some(HttpResponse::Forbidden().body(reason)),
+                        Err(err) => Some(err.to_http_response()),
+                    },
+                    None => Some(HttpResponse::Forbidden().body("Unknown resource")),
+                };
+            match denied {
+                Some(res) => {
+                    warn!(user=%user.name, path=%req.path(), "Request denied");
+                    Ok(req.into_response(res).map_into_right_body())
+                }
+                None => service.call(req).await.map(|res| res.map_into_left_body()),
+            }
+        })
+    }
+}
+
+/// Identifies the caller by its client certificate, else by its bearer token.
+async fn authenticate(state: &State, req: &ServiceRequest) -> Option<UserInfo> {
+    let cert = req
+        .conn_data::<tls::PeerCertificate>()
+        .and_then(|cert| tls::identity(&cert.0));
+    if cert.is_some() {
+        return cert;
+    }
+    let token = req
+        .headers()
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")?;
+    state.authenticate_token(token.trim()).await
+}
+
 #[cfg(test)]
 pub mod helpers {
     use actix_web::{body::MessageBody, dev::ServiceResponse};
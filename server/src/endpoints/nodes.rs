@@ -1,27 +1,34 @@
 //! Node Controller
 //!
 //! This module defines HTTP handlers for managing cluster nodes. It provides
-//! endpoints for node registration and for listing or watching registered nodes.
+//! endpoints for node registration, removal and for listing or watching registered nodes.
 //!
 //! ## Routes
 //! - `GET  /nodes`  — List or watch all registered nodes
 //! - `POST /nodes`  — Register a new node with the control plane
+//! - `PATCH /nodes/{name}` — Update the node spec (cordon, uncordon, taints)
+//! - `DELETE /nodes/{name}` — Deregister a node and delete its pods
 
-use crate::state::State;
+use crate::{
+    auth::{UserInfo, tls},
+    state::State,
+};
 use actix_web::{
     HttpRequest, HttpResponse, Responder,
     web::{self, Bytes},
 };
 use serde::Deserialize;
 use shared::{
-    api::{EventType, NodeEvent, NodeRegisterReq},
-    models::node::{Node, NodeStatus},
+    api::{EventType, NodeEvent, NodePatch, NodeRegisterReq, NodeRegisterResp},
+    models::node::{Node, NodeSpec, NodeStatus},
 };
 use uuid::Uuid;
 
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.route("", web::get().to(get))
-        .route("", web::post().to(register));
+        .route("", web::post().to(register))
+        .route("/{name}", web::patch().to(update))
+        .route("/{name}", web::delete().to(delete));
 }
 
 #[derive(Deserialize)]
@@ -69,49 +76,124 @@ async fn get(state: State, query: web::Query<NodeQuery>) -> impl Responder {
 
 /// Register a new node with the control plane.
 ///
+/// A node whose name is already registered may reclaim it by presenting the
+/// token handed out on its previous registration.
+///
 /// # Arguments
 /// - `payload`: Node register JSON
 ///
 /// # Returns
-/// - 201: Node successfully registered.
-/// - 400: Emtpy node name
+/// - 201: Node successfully registered, with its id, token and serving certificate
+/// - 200: Known node re-registered, with its id, a new token and serving certificate
+/// - 400: Emtpy node name or invalid serving key
+/// - 403: Node registering under another name
 /// - 409: Duplicate name or address
 async fn register(
     req: HttpRequest,
     state: State,
+    user: Option<web::ReqData<UserInfo>>,
     payload: web::Json<NodeRegisterReq>,
 ) -> impl Responder {
     let address = req
         .peer_addr()
         .map(|addr| addr.ip().to_string())
         .unwrap_or_else(|| "unknown".to_string());
+    let payload = payload.into_inner();
+    let addr = format!("{}:{}", address, payload.port);
+
+    // validate node name
+    if payload.name.is_empty() {
+        return HttpResponse::BadRequest().body("Node name is empty");
+    };
+    // a node credential only registers its own node, bootstrap tokens any new one
+    if let Some(node) = user.as_ref().and_then(|user| user.node_name())
+        && node != payload.name
+    {
+        return HttpResponse::Forbidden().body("Caller is not the registered node");
+    }
+
+    // the CA also signs the apiserver, a node must not get a certificate for its names
+    if state.ca.is_some()
+        && payload.serving_key.is_some()
+        && tls::apiserver_sans()
+            .iter()
+            .any(|san| san.eq_ignore_ascii_case(&payload.name))
+    {
+        return HttpResponse::BadRequest().body("Node name is reserved for the apiserver");
+    }
+
+    // known node presenting its token reclaims its identity
+    if state.cache.node_name_exists(&payload.name) {
+        let reclaim = match &payload.token {
+            Some(token) => state.node_token_matches(&payload.name, token).await,
+            None => false,
+        };
+        if !reclaim {
+            return HttpResponse::Conflict().body("Duplicate node name or address");
+        }
+        let serving_cert = match serving_cert(&state, &payload, &address) {
+            Ok(cert) => cert,
+            Err(err) => return HttpResponse::BadRequest().body(err),
+        };
+        return match state
+            .reregister_node(&payload.name, addr, payload.taints)
+            .await
+        {
+            Ok(node) => match state.issue_node_token(&node.name).await {
+                Ok(token) => {
+                    tracing::info!(ip=%address, name=%node.name, "Node re-registered");
+                    HttpResponse::Ok().json(NodeRegisterResp {
+                        id: node.id,
+                        token,
+                        serving_cert,
+                    })
+                }
+                Err(err) => err.to_http_response(),
+            },
+            Err(err) => {
+                tracing::warn!(error=%err, "Could not re-register node");
+                err.to_http_response()
+            }
+        };
+    }
+    if state.cache.node_addr_exists(&addr) {
+        return HttpResponse::Conflict().body("Duplicate node name or address");
+    };
+    let serving_cert = match serving_cert(&state, &payload, &address) {
+        Ok(cert) => cert,
+        Err(err) => return HttpResponse::BadRequest().body(err),
+    };
 
     let node = Node {
         id: Uuid::new_v4(),
-        name: payload.name.clone(),
-        addr: format!("{}:{}", address, payload.port),
+        name: payload.name,
+        addr,
         status: NodeStatus::Ready,
         started_at: chrono::Utc::now(),
         last_heartbeat: chrono::Utc::now(),
-    };
-
-    // validate node name and check for name and addr duplicates
-    if node.name.is_empty() {
-        return HttpResponse::BadRequest().body("Node name is empty");
-    };
-    if state.cache.node_addr_exists(&node.addr) || state.cache.node_name_exists(&node.name) {
-        return HttpResponse::Conflict().body("Duplicate node name or address");
+        spec: NodeSpec {
+            unschedulable: false,
+            taints: payload.taints,
+        },
     };
 
     // Store node
-    match state.add_node(&node).await {
-        Ok(()) => {
+    let registered = match state.add_node(&node).await {
+        Ok(()) => state.issue_node_token(&node.name).await,
+        Err(err) => Err(err),
+    };
+    match registered {
+        Ok(token) => {
             tracing::info!(
                 ip=%address,
                 name=%node.name,
                 "Node registered"
             );
-            HttpResponse::Created().finish()
+            HttpResponse::Created().json(NodeRegisterResp {
+                id: node.id,
+                token,
+                serving_cert,
+            })
         }
         Err(err) => {
             tracing::warn!(
@@ -123,6 +205,71 @@ async fn register(
     }
 }
 
+/// Serving certificate for a node whose registration passed every check.
+/// The apiserver reaches the node by name, clients may use its address.
+fn serving_cert(
+    state: &State,
+    payload: &NodeRegisterReq,
+    address: &str,
+) -> Result<Option<String>, String> {
+    let (Some(ca), Some(key)) = (&state.ca, &payload.serving_key) else {
+        return Ok(None);
+    };
+    let mut sans = vec![payload.name.clone()];
+    if !tls::apiserver_sans().iter().any(|san| san == address) {
+        sans.push(address.to_string());
+    }
+    ca.issue_node(&payload.name, sans, key).map(Some)
+}
+
+/// Update the spec of a node.
+///
+/// # Arguments
+/// - `payload`: Node patch JSON, only the given spec fields change
+///
+/// # Returns
+/// - 200: Updated node
+/// - 404: Node not found
+async fn update(
+    state: State,
+    path_string: web::Path<String>,
+    payload: web::Json<NodePatch>,
+) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.update_node_spec(&name, payload.into_inner()).await {
+        Ok(node) => {
+            tracing::info!(%name, unschedulable=%node.spec.unschedulable, "Node updated");
+            HttpResponse::Ok().json(node)
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not update node");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Deregister a node.
+///
+/// Pods bound to the node are deleted and the name and address become
+/// available again.
+///
+/// # Returns
+/// - 204: Node removed
+/// - 404: Node not found
+async fn delete(state: State, path_string: web::Path<String>) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.delete_node(&name).await {
+        Ok(()) => {
+            tracing::info!(%name, "Node deregistered");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not delete node");
+            err.to_http_response()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     //!  GET
@@ -136,7 +283,21 @@ mod tests {
     //!  - test_register_node_empty_name
     //!  - test_register_node_repeat_name
     //!  - test_register_node_repeat_addr
+    //!  - test_reregister_node_with_token
+    //!  - test_reregister_node_wrong_token
+    //!  - test_register_node_serving_cert
+    //!    certificate issued by the cluster CA for the key sent, only once registration passes
+    //!
+    //!  UPDATE
+    //!  - test_cordon_node
+    //!  - test_update_node_not_found
+    //!
+    //!  DELETE
+    //!  - test_delete_node
+    //!    pods on the node are deleted, name can be registered again
+    //!  - test_delete_node_not_found
 
+    use crate::auth::{Authenticator, ca::ClusterCa};
     use crate::endpoints::helpers::collect_stream_events;
     use crate::state::{ApiServerState, test_store::TestStore};
 
@@ -148,6 +309,7 @@ mod tests {
         http::StatusCode,
         test::{self, TestRequest, call_service, init_service, read_body_json},
     };
+    use shared::models::{metadata::ObjectMetadata, pod::PodSpec};
 
     async fn node_service(
         state: &State,
@@ -160,7 +322,9 @@ mod tests {
             App::new()
                 .app_data(state.clone())
                 .route("/nodes", web::get().to(get))
-                .route("/nodes", web::post().to(register)),
+                .route("/nodes", web::post().to(register))
+                .route("/nodes/{name}", web::patch().to(update))
+                .route("/nodes/{name}", web::delete().to(delete)),
         )
         .await
     }
@@ -233,6 +397,9 @@ mod tests {
         let payload = NodeRegisterReq {
             port: 1000,
             name: "n1".to_string(),
+            taints: Vec::new(),
+            token: None,
+            serving_key: None,
         };
         let req = TestRequest::post()
             .uri("/nodes")
@@ -250,6 +417,9 @@ mod tests {
         let payload = NodeRegisterReq {
             port: 1000,
             name: "".to_string(),
+            taints: Vec::new(),
+            token: None,
+            serving_key: None,
         };
         let req = TestRequest::post()
             .uri("/nodes")
@@ -272,6 +442,9 @@ mod tests {
         let payload = NodeRegisterReq {
             port: 1000,
             name: "n1".to_string(),
+            taints: Vec::new(),
+            token: None,
+            serving_key: None,
         };
         let req = TestRequest::post()
             .uri("/nodes")
@@ -295,6 +468,9 @@ mod tests {
         let payload = NodeRegisterReq {
             port: 1000,
             name: "n2".to_string(),
+            taints: Vec::new(),
+            token: None,
+            serving_key: None,
         };
         let req = TestRequest::post()
             .uri("/nodes")
@@ -303,4 +479,248 @@ mod tests {
         let res = call_service(&app, req).await;
         assert_eq!(res.status(), StatusCode::CONFLICT,);
     }
+
+    #[actix_web::test]
+    async fn test_cordon_node() {
+        let n1 = Node {
+            name: "n1".to_string(),
+            ..Default::default()
+        };
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        assert!(state.add_node(&n1).await.is_ok());
+        let mut rx = state.node_tx.subscribe();
+
+        let app = node_service(&state).await;
+        let req = TestRequest::patch()
+            .uri("/nodes/n1")
+            .set_json(serde_json::json!({"spec": {"unschedulable": true}}))
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let node: Node = read_body_json(res).await;
+        assert!(node.spec.unschedulable);
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.event_type, EventType::Modified);
+        assert!(event.node.spec.unschedulable);
+    }
+
+    #[actix_web::test]
+    async fn test_update_node_not_found() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = node_service(&state).await;
+        let req = TestRequest::patch()
+            .uri("/nodes/made-up")
+            .set_json(NodePatch::default())
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn test_reregister_node_with_token() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = node_service(&state).await;
+
+        let payload = NodeRegisterReq {
+            port: 1000,
+            name: "n1".to_string(),
+            taints: Vec::new(),
+            token: None,
+            serving_key: None,
+        };
+        let req = TestRequest::post()
+            .uri("/nodes")
+            .set_json(&payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let first: NodeRegisterResp = read_body_json(res).await;
+
+        let payload = NodeRegisterReq {
+            port: 2000,
+            name: "n1".to_string(),
+            taints: Vec::new(),
+            token: Some(first.token.clone()),
+            serving_key: None,
+        };
+        let req = TestRequest::post()
+            .uri("/nodes")
+            .set_json(&payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let second: NodeRegisterResp = read_body_json(res).await;
+        assert_eq!(second.id, first.id);
+        assert_ne!(second.token, first.token);
+
+        let node = state.get_node("n1").await.unwrap().unwrap();
+        assert_eq!(node.addr, "unknown:2000");
+        assert!(!state.cache.node_addr_exists("unknown:1000"));
+    }
+
+    #[actix_web::test]
+    async fn test_register_node_serving_cert() {
+        let ca = ClusterCa::generate().unwrap();
+        let state = ApiServerState::new_with(
+            Box::new(TestStore::new()),
+            Authenticator::disabled(),
+            Some(ca),
+        )
+        .await;
+        let app = node_service(&state).await;
+
+        let key = rcgen::KeyPair::generate().unwrap();
+        let payload = NodeRegisterReq {
+            port: 1000,
+            name: "n1".to_string(),
+            taints: Vec::new(),
+            token: None,
+            serving_key: Some(key.public_key_pem()),
+        };
+        let req = TestRequest::post()
+            .uri("/nodes")
+            .set_json(&payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let body: NodeRegisterResp = read_body_json(res).await;
+        assert!(
+            body.serving_cert
+                .unwrap()
+                .starts_with("-----BEGIN CERTIFICATE-----")
+        );
+
+        // a rejected registration gets no certificate
+        let req = TestRequest::post()
+            .uri("/nodes")
+            .set_json(&payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let body = actix_web::test::read_body(res).await;
+        assert!(!String::from_utf8_lossy(&body).contains("CERTIFICATE"));
+
+        let payload = NodeRegisterReq {
+            port: 1001,
+            name: "n2".to_string(),
+            taints: Vec::new(),
+            token: None,
+            serving_key: Some("not a key".to_string()),
+        };
+        let req = TestRequest::post()
+            .uri("/nodes")
+            .set_json(&payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert!(!state.cache.node_name_exists("n2"));
+
+        // a certificate for localhost would be accepted for the apiserver
+        let payload = NodeRegisterReq {
+            port: 1000,
+            name: "LocalHost".to_string(),
+            taints: Vec::new(),
+            token: None,
+            serving_key: Some(key.public_key_pem()),
+        };
+        let req = TestRequest::post()
+            .uri("/nodes")
+            .set_json(&payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert!(!state.cache.node_name_exists("LocalHost"));
+    }
+
+    #[actix_web::test]
+    async fn test_reregister_node_wrong_token() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = node_service(&state).await;
+
+        let n1 = Node {
+            name: "n1".to_string(),
+            ..Default::default()
+        };
+        assert!(state.add_node(&n1).await.is_ok());
+        assert!(state.issue_node_token("n1").await.is_ok());
+
+        let payload = NodeRegisterReq {
+            port: 1000,
+            name: "n1".to_string(),
+            taints: Vec::new(),
+            token: Some("made-up".to_string()),
+            serving_key: None,
+        };
+        let req = TestRequest::post()
+            .uri("/nodes")
+            .set_json(&payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_node() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = node_service(&state).await;
+
+        let n1 = Node {
+            name: "n1".to_string(),
+            addr: "unknown:1000".to_string(),
+            ..Default::default()
+        };
+        assert!(state.add_node(&n1).await.is_ok());
+        let metadata = ObjectMetadata::default();
+        assert!(
+            state
+                .add_pod(PodSpec::default(), metadata.clone().into())
+                .await
+                .is_ok()
+        );
+        assert!(
+            state
+                .assign_pod(&metadata.name, "n1".to_string())
+                .await
+                .is_ok()
+        );
+        let mut rx = state.node_tx.subscribe();
+
+        let req = TestRequest::delete().uri("/nodes/n1").to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.event_type, EventType::Deleted);
+        assert!(state.get_node("n1").await.unwrap().is_none());
+        assert!(
+            state
+                .get_pods(&Some("n1".to_string()), &Default::default())
+                .await
+                .is_empty()
+        );
+
+        let payload = NodeRegisterReq {
+            port: 1000,
+            name: "n1".to_string(),
+            taints: Vec::new(),
+            token: None,
+            serving_key: None,
+        };
+        let req = TestRequest::post()
+            .uri("/nodes")
+            .set_json(&payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_node_not_found() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = node_service(&state).await;
+        let req = TestRequest::delete().uri("/nodes/made-up").to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
 }
@@ -0,0 +1,299 @@
+//! PersistentVolumeClaim
+//!
+//! ## Routes
+//! - `GET    /persistentvolumeclaims`          — List or watch claims
+//! - `POST   /persistentvolumeclaims`          — Create a new claim
+//! - `GET    /persistentvolumeclaims/{name}`   — Get a claim
+//! - `DELETE /persistentvolumeclaims/{name}`   — Delete a claim
+
+use crate::state::State;
+use actix_web::{
+    HttpResponse, Responder,
+    web::{self, Bytes},
+};
+use serde::Deserialize;
+use shared::api::{
+    CreateResponse, EventType, PersistentVolumeClaimEvent, PersistentVolumeClaimManifest,
+};
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get))
+        .route("", web::post().to(create))
+        .route("/{name}", web::get().to(get_one))
+        .route("/{name}", web::delete().to(delete));
+}
+
+#[derive(Deserialize)]
+pub struct PersistentVolumeClaimQuery {
+    watch: Option<bool>,
+}
+
+/// List or watch persistent volume claims
+///
+/// # Arguments
+/// - `query`: Query parameters:
+///    - `watch` (bool, optional): If true, opens a watch stream of claim events.
+///
+/// # Returns
+/// - 200 list of claims or stream of claim events
+async fn get(state: State, query: web::Query<PersistentVolumeClaimQuery>) -> impl Responder {
+    let claims = state.get_persistentvolumeclaims().await;
+    if query.watch.unwrap_or(false) {
+        let mut rx = state.claim_tx.subscribe();
+        let stream = async_stream::stream! {
+            for claim in claims {
+                let event = PersistentVolumeClaimEvent {
+                    claim,
+                    event_type: EventType::Added
+                };
+                let json = serde_json::to_string(&event).unwrap();
+                yield Ok::<_, actix_web::Error>(Bytes::from(json + "\n"));
+            }
+            while let Ok(event) = rx.recv().await {
+                let json = serde_json::to_string(&event).unwrap();
+                yield Ok::<_, actix_web::Error>(Bytes::from(json + "\n"));
+            }
+        };
+
+        HttpResponse::Ok()
+            .content_type("application/json")
+            .streaming(stream)
+    } else {
+        HttpResponse::Ok().json(&claims)
+    }
+}
+
+/// Get a persistent volume claim by name.
+///
+/// # Returns
+/// - 200: The claim
+/// - 404: Claim not found
+async fn get_one(state: State, path_string: web::Path<String>) -> impl Responder {
+    match state
+        .get_persistentvolumeclaim(&path_string.into_inner())
+        .await
+    {
+        Ok(obj) => HttpResponse::Ok().json(obj),
+        Err(err) => err.to_http_response(),
+    }
+}
+
+/// Create a new persistent volume claim.
+///
+/// # Arguments
+/// - `body`: PersistentVolumeClaim manifest JSON.
+///
+/// # Returns
+/// - 201: Claim created, bound if it names a volume
+/// - 400: Invalid manifest format, storage size or volume too small
+/// - 409: Repeat name or volume already bound
+/// - 422: Named volume does not exist
+async fn create(state: State, payload: web::Json<PersistentVolumeClaimManifest>) -> impl Responder {
+    let manifest = payload.into_inner();
+
+    if manifest.metadata.owner_reference.is_some() {
+        return HttpResponse::BadRequest().finish();
+    }
+
+    let name = manifest.metadata.name.clone();
+    match state
+        .add_persistentvolumeclaim(manifest.spec, manifest.metadata.into())
+        .await
+    {
+        Ok(id) => {
+            tracing::info!(%name, "Persistent volume claim created");
+            let response = CreateResponse {
+                id,
+                status: "Accepted".into(),
+            };
+            HttpResponse::Created().json(response)
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not create persistent volume claim");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Delete a persistent volume claim by name, releasing its volume.
+///
+/// # Returns
+/// - 204: Claim deleted
+/// - 404: Claim not found
+/// - 409: Claim used by a pod
+async fn delete(state: State, path_string: web::Path<String>) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.delete_persistentvolumeclaim(&name).await {
+        Ok(_) => {
+            tracing::info!(%name, "Persistent volume claim deleted");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not delete persistent volume claim");
+            err.to_http_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //!  CREATE
+    //!  - test_create_claim
+    //!    pending until a pod using it is scheduled
+    //!  - test_create_claim_named_volume
+    //!    bound right away, missing, small or taken volumes rejected
+    //!
+    //!  DELETE
+    //!  - test_delete_claim_in_use
+
+    use crate::state::{ApiServerState, test_store::TestStore};
+
+    use super::*;
+    use actix_web::body::BoxBody;
+    use actix_web::dev::Service;
+    use actix_web::{
+        App,
+        http::StatusCode,
+        test::{TestRequest, call_service, init_service, read_body_json},
+    };
+    use shared::models::{
+        metadata::ObjectMetadata,
+        persistentvolume::{PersistentVolumeSpec, ReclaimPolicy},
+        persistentvolumeclaim::{ClaimPhase, PersistentVolumeClaim, PersistentVolumeClaimSpec},
+        pod::{ClaimVolume, PodSpec, Volume, VolumeSource},
+    };
+
+    async fn claim_service(
+        state: &State,
+    ) -> impl Service<
+        actix_http::Request,
+        Response = actix_web::dev::ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    > {
+        init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/persistentvolumeclaims", web::get().to(get))
+                .route("/persistentvolumeclaims", web::post().to(create))
+                .route("/persistentvolumeclaims/{name}", web::get().to(get_one))
+                .route("/persistentvolumeclaims/{name}", web::delete().to(delete)),
+        )
+        .await
+    }
+
+    fn manifest(
+        name: &str,
+        storage: &str,
+        volume_name: Option<&str>,
+    ) -> PersistentVolumeClaimManifest {
+        PersistentVolumeClaimManifest {
+            metadata: ObjectMetadata {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            spec: PersistentVolumeClaimSpec {
+                storage: storage.to_string(),
+                volume_name: volume_name.map(str::to_string),
+                reclaim_policy: ReclaimPolicy::Delete,
+            },
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_claim() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = claim_service(&state).await;
+
+        for expected in [StatusCode::CREATED, StatusCode::CONFLICT] {
+            let req = TestRequest::post()
+                .uri("/persistentvolumeclaims")
+                .set_json(manifest("db", "1Gi", None))
+                .to_request();
+            assert_eq!(call_service(&app, req).await.status(), expected);
+        }
+
+        let req = TestRequest::get()
+            .uri("/persistentvolumeclaims/db")
+            .to_request();
+        let claim: PersistentVolumeClaim = read_body_json(call_service(&app, req).await).await;
+        assert_eq!(claim.status.phase, ClaimPhase::Pending);
+        assert!(claim.status.volume_name.is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_create_claim_named_volume() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = claim_service(&state).await;
+        let spec = PersistentVolumeSpec {
+            capacity: "1Gi".to_string(),
+            node_name: "node-a".to_string(),
+            path: Some("/mnt/disks/data".to_string()),
+            reclaim_policy: ReclaimPolicy::Retain,
+            claim_ref: None,
+        };
+        let metadata = ObjectMetadata {
+            name: "data".to_string(),
+            ..Default::default()
+        };
+        state
+            .add_persistentvolume(spec, metadata.into())
+            .await
+            .unwrap();
+
+        for (name, storage, volume, expected) in [
+            ("missing", "1Gi", "other", StatusCode::UNPROCESSABLE_ENTITY),
+            ("big", "2Gi", "data", StatusCode::BAD_REQUEST),
+            ("db", "512Mi", "data", StatusCode::CREATED),
+            ("late", "512Mi", "data", StatusCode::CONFLICT),
+        ] {
+            let req = TestRequest::post()
+                .uri("/persistentvolumeclaims")
+                .set_json(manifest(name, storage, Some(volume)))
+                .to_request();
+            assert_eq!(call_service(&app, req).await.status(), expected, "{}", name);
+        }
+
+        let claim = state.get_persistentvolumeclaim("db").await.unwrap();
+        assert_eq!(claim.status.phase, ClaimPhase::Bound);
+        assert_eq!(claim.status.node_name.as_deref(), Some("node-a"));
+        let pv = state.get_persistentvolume("data").await.unwrap();
+        assert_eq!(pv.spec.claim_ref.as_deref(), Some("db"));
+    }
+
+    #[actix_web::test]
+    async fn test_delete_claim_in_use() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = claim_service(&state).await;
+        let req = TestRequest::post()
+            .uri("/persistentvolumeclaims")
+            .set_json(manifest("db", "1Gi", None))
+            .to_request();
+        call_service(&app, req).await;
+
+        let spec = PodSpec {
+            volumes: vec![Volume {
+                name: "data".to_string(),
+                source: VolumeSource::PersistentVolumeClaim(ClaimVolume {
+                    claim_name: "db".to_string(),
+                    read_only: false,
+                }),
+            }],
+            ..Default::default()
+        };
+        let metadata = ObjectMetadata::default();
+        state.add_pod(spec, metadata.clone().into()).await.unwrap();
+
+        let req = TestRequest::delete()
+            .uri("/persistentvolumeclaims/db")
+            .to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::CONFLICT);
+
+        state.delete_pod(&metadata.name).await.unwrap();
+        for expected in [StatusCode::NO_CONTENT, StatusCode::NOT_FOUND] {
+            let req = TestRequest::delete()
+                .uri("/persistentvolumeclaims/db")
+                .to_request();
+            assert_eq!(call_service(&app, req).await.status(), expected);
+        }
+    }
+}
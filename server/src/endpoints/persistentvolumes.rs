@@ -0,0 +1,273 @@
+//! PersistentVolume
+//!
+//! ## Routes
+//! - `GET    /persistentvolumes`          — List persistent volumes
+//! - `POST   /persistentvolumes`          — Create a persistent volume
+//! - `GET    /persistentvolumes/{name}`   — Get a persistent volume
+//! - `DELETE /persistentvolumes/{name}`   — Delete a persistent volume
+
+use crate::state::State;
+use actix_web::{HttpResponse, Responder, web};
+use serde::Deserialize;
+use shared::api::{CreateResponse, PersistentVolumeManifest};
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get))
+        .route("", web::post().to(create))
+        .route("/{name}", web::get().to(get_one))
+        .route("/{name}", web::delete().to(delete));
+}
+
+#[derive(Deserialize)]
+pub struct PersistentVolumeQuery {
+    #[serde(rename = "nodeName")]
+    node_name: Option<String>,
+}
+
+/// List persistent volumes
+///
+/// # Arguments
+/// - `query`: Query parameters:
+///    - `nodeName` (string, optional): Only the volumes on that node.
+///
+/// # Returns
+/// - 200 list of persistent volumes
+async fn get(state: State, query: web::Query<PersistentVolumeQuery>) -> impl Responder {
+    HttpResponse::Ok().json(state.get_persistentvolumes(&query.node_name).await)
+}
+
+/// Get a persistent volume by name.
+///
+/// # Returns
+/// - 200: The persistent volume
+/// - 404: Persistent volume not found
+async fn get_one(state: State, path_string: web::Path<String>) -> impl Responder {
+    match state.get_persistentvolume(&path_string.into_inner()).await {
+        Ok(obj) => HttpResponse::Ok().json(obj),
+        Err(err) => err.to_http_response(),
+    }
+}
+
+/// Create a persistent volume an admin prepared on a node.
+///
+/// # Arguments
+/// - `body`: PersistentVolume manifest JSON.
+///
+/// # Returns
+/// - 201: Persistent volume created.
+/// - 400: Invalid manifest format or capacity
+/// - 409: Repeat name
+async fn create(state: State, payload: web::Json<PersistentVolumeManifest>) -> impl Responder {
+    let manifest = payload.into_inner();
+
+    if manifest.metadata.owner_reference.is_some() {
+        return HttpResponse::BadRequest().finish();
+    }
+
+    let name = manifest.metadata.name.clone();
+    match state
+        .add_persistentvolume(manifest.spec, manifest.metadata.into())
+        .await
+    {
+        Ok(id) => {
+            tracing::info!(%name, "Persistent volume created");
+            let response = CreateResponse {
+                id,
+                status: "Accepted".into(),
+            };
+            HttpResponse::Created().json(response)
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not create persistent volume");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Delete a persistent volume by name, the data stays on the node.
+///
+/// # Returns
+/// - 204: Persistent volume deleted
+/// - 404: Persistent volume not found
+/// - 409: Volume bound to a claim
+async fn delete(state: State, path_string: web::Path<String>) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.delete_persistentvolume(&name).await {
+        Ok(_) => {
+            tracing::info!(%name, "Persistent volume deleted");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not delete persistent volume");
+            err.to_http_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //!  CREATE
+    //!  - test_create_persistentvolume
+    //!    listed for its node only
+    //!  - test_create_persistentvolume_invalid_capacity
+    //!  - test_create_persistentvolume_invalid_name
+    //!    empty, path-like and non DNS label names refused
+    //!
+    //!  DELETE
+    //!  - test_delete_bound_persistentvolume
+
+    use crate::state::{ApiServerState, test_store::TestStore};
+
+    use super::*;
+    use actix_web::body::BoxBody;
+    use actix_web::dev::Service;
+    use actix_web::{
+        App,
+        http::StatusCode,
+        test::{TestRequest, call_service, init_service, read_body_json},
+    };
+    use shared::models::{
+        metadata::ObjectMetadata,
+        persistentvolume::{PersistentVolume, PersistentVolumeSpec, ReclaimPolicy, VolumePhase},
+        persistentvolumeclaim::PersistentVolumeClaimSpec,
+    };
+
+    async fn persistentvolume_service(
+        state: &State,
+    ) -> impl Service<
+        actix_http::Request,
+        Response = actix_web::dev::ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    > {
+        init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/persistentvolumes", web::get().to(get))
+                .route("/persistentvolumes", web::post().to(create))
+                .route("/persistentvolumes/{name}", web::get().to(get_one))
+                .route("/persistentvolumes/{name}", web::delete().to(delete)),
+        )
+        .await
+    }
+
+    fn manifest(name: &str, capacity: &str) -> PersistentVolumeManifest {
+        PersistentVolumeManifest {
+            metadata: ObjectMetadata {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            spec: PersistentVolumeSpec {
+                capacity: capacity.to_string(),
+                node_name: "node-a".to_string(),
+                path: Some("/mnt/disks/data".to_string()),
+                reclaim_policy: ReclaimPolicy::Retain,
+                claim_ref: None,
+            },
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_persistentvolume() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = persistentvolume_service(&state).await;
+
+        let req = TestRequest::post()
+            .uri("/persistentvolumes")
+            .set_json(manifest("data", "10Gi"))
+            .to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::CREATED);
+
+        let req = TestRequest::get()
+            .uri("/persistentvolumes?nodeName=node-a")
+            .to_request();
+        let volumes: Vec<PersistentVolume> = read_body_json(call_service(&app, req).await).await;
+        assert_eq!(volumes.len(), 1);
+        assert_eq!(volumes[0].status.phase, VolumePhase::Available);
+
+        let req = TestRequest::get()
+            .uri("/persistentvolumes?nodeName=node-b")
+            .to_request();
+        let volumes: Vec<PersistentVolume> = read_body_json(call_service(&app, req).await).await;
+        assert!(volumes.is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_create_persistentvolume_invalid_capacity() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = persistentvolume_service(&state).await;
+
+        for capacity in ["", "0", "1.5Gi", "10GB"] {
+            let req = TestRequest::post()
+                .uri("/persistentvolumes")
+                .set_json(manifest("data", capacity))
+                .to_request();
+            assert_eq!(
+                call_service(&app, req).await.status(),
+                StatusCode::BAD_REQUEST
+            );
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_persistentvolume_invalid_name() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = persistentvolume_service(&state).await;
+
+        for name in ["", "..", "a/b", "Data", "-data", &"a".repeat(64)] {
+            let req = TestRequest::post()
+                .uri("/persistentvolumes")
+                .set_json(manifest(name, "10Gi"))
+                .to_request();
+            assert_eq!(
+                call_service(&app, req).await.status(),
+                StatusCode::BAD_REQUEST,
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_delete_bound_persistentvolume() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = persistentvolume_service(&state).await;
+
+        let req = TestRequest::post()
+            .uri("/persistentvolumes")
+            .set_json(manifest("data", "10Gi"))
+            .to_request();
+        call_service(&app, req).await;
+        let spec = PersistentVolumeClaimSpec {
+            storage: "1Gi".to_string(),
+            volume_name: Some("data".to_string()),
+            reclaim_policy: ReclaimPolicy::Delete,
+        };
+        let metadata = ObjectMetadata {
+            name: "db".to_string(),
+            ..Default::default()
+        };
+        state
+            .add_persistentvolumeclaim(spec, metadata.into())
+            .await
+            .unwrap();
+
+        let req = TestRequest::delete()
+            .uri("/persistentvolumes/data")
+            .to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::CONFLICT);
+
+        // released once the claim is gone
+        state.delete_persistentvolumeclaim("db").await.unwrap();
+        let req = TestRequest::get()
+            .uri("/persistentvolumes/data")
+            .to_request();
+        let pv: PersistentVolume = read_body_json(call_service(&app, req).await).await;
+        assert_eq!(pv.status.phase, VolumePhase::Released);
+        for expected in [StatusCode::NO_CONTENT, StatusCode::NOT_FOUND] {
+            let req = TestRequest::delete()
+                .uri("/persistentvolumes/data")
+                .to_request();
+            assert_eq!(call_service(&app, req).await.status(), expected);
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! PodDisruptionBudget
+//!
+//! ## Routes
+//! - `GET    /poddisruptionbudgets`          — List disruption budgets with current status
+//! - `POST   /poddisruptionbudgets`          — Create a new disruption budget
+//! - `DELETE /poddisruptionbudgets/{name}`   — Delete a disruption budget
+
+use crate::state::State;
+use actix_web::{HttpResponse, Responder, web};
+use shared::api::{CreateResponse, PodDisruptionBudgetManifest};
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get))
+        .route("", web::post().to(create))
+        .route("/{name}", web::delete().to(delete));
+}
+
+/// List disruption budgets
+///
+/// # Returns
+/// - 200 list of disruption budgets
+async fn get(state: State) -> impl Responder {
+    HttpResponse::Ok().json(state.get_poddisruptionbudgets().await)
+}
+
+/// Create a new disruption budget.
+///
+/// # Arguments
+/// - `body`: PodDisruptionBudget manifest JSON.
+///
+/// # Returns
+/// - 201: Disruption budget created.
+/// - 400: Invalid manifest format or not exactly one of minAvailable/maxUnavailable
+/// - 409: Repeat name
+async fn create(state: State, payload: web::Json<PodDisruptionBudgetManifest>) -> impl Responder {
+    let manifest = payload.into_inner();
+
+    if manifest.metadata.owner_reference.is_some() {
+        return HttpResponse::BadRequest().finish();
+    }
+
+    let name = manifest.metadata.name.clone();
+    match state
+        .add_poddisruptionbudget(manifest.spec, manifest.metadata.into())
+        .await
+    {
+        Ok(id) => {
+            tracing::info!(%name, "Disruption budget created");
+            let response = CreateResponse {
+                id,
+                status: "Accepted".into(),
+            };
+            HttpResponse::Created().json(response)
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not create disruption budget");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Delete a disruption budget by name.
+///
+/// # Returns
+/// - 204: Disruption budget deleted
+/// - 404: Disruption budget not found
+async fn delete(state: State, path_string: web::Path<String>) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.delete_poddisruptionbudget(&name).await {
+        Ok(_) => {
+            tracing::info!(%name, "Disruption budget deleted");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not delete disruption budget");
+            err.to_http_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //!  CREATE
+    //!  - test_create_pdb
+    //!  - test_create_pdb_needs_one_bound
+    //!    both or neither of minAvailable and maxUnavailable is rejected
+    //!
+    //!  GET
+    //!  - test_get_pdb_status
+    //!
+    //!  DELETE
+    //!  - test_delete_pdb_not_found
+
+    use crate::state::{ApiServerState, test_store::TestStore};
+
+    use super::*;
+    use actix_web::body::BoxBody;
+    use actix_web::dev::Service;
+    use actix_web::{
+        App,
+        http::StatusCode,
+        test::{TestRequest, call_service, init_service, read_body_json},
+    };
+    use shared::models::{
+        metadata::{LabelSelector, ObjectMetadata},
+        pod::{PodPhase, PodSpec, PodStatus},
+        poddisruptionbudget::{PodDisruptionBudget, PodDisruptionBudgetSpec},
+    };
+
+    async fn pdb_service(
+        state: &State,
+    ) -> impl Service<
+        actix_http::Request,
+        Response = actix_web::dev::ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    > {
+        init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/poddisruptionbudgets", web::get().to(get))
+                .route("/poddisruptionbudgets", web::post().to(create))
+                .route("/poddisruptionbudgets/{name}", web::delete().to(delete)),
+        )
+        .await
+    }
+
+    fn manifest(
+        min_available: Option<u16>,
+        max_unavailable: Option<u16>,
+    ) -> PodDisruptionBudgetManifest {
+        PodDisruptionBudgetManifest {
+            metadata: ObjectMetadata {
+                name: "web".to_string(),
+                ..Default::default()
+            },
+            spec: PodDisruptionBudgetSpec {
+                min_available,
+                max_unavailable,
+                selector: LabelSelector::try_from("app=web".to_string()).unwrap(),
+            },
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_pdb() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = pdb_service(&state).await;
+
+        for expected in [StatusCode::CREATED, StatusCode::CONFLICT] {
+            let req = TestRequest::post()
+                .uri("/poddisruptionbudgets")
+                .set_json(manifest(Some(1), None))
+                .to_request();
+            let res = call_service(&app, req).await;
+            assert_eq!(res.status(), expected);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_pdb_needs_one_bound() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = pdb_service(&state).await;
+
+        for payload in [manifest(None, None), manifest(Some(1), Some(1))] {
+            let req = TestRequest::post()
+                .uri("/poddisruptionbudgets")
+                .set_json(payload)
+                .to_request();
+            let res = call_service(&app, req).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_get_pdb_status() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = pdb_service(&state).await;
+
+        let req = TestRequest::post()
+            .uri("/poddisruptionbudgets")
+            .set_json(manifest(None, Some(1)))
+            .to_request();
+        call_service(&app, req).await;
+
+        // three matching pods, two of them running
+        for running in [true, true, false] {
+            let mut metadata = ObjectMetadata::default();
+            metadata.labels.insert("app".to_string(), "web".to_string());
+            let id = state
+                .add_pod(PodSpec::default(), metadata.into())
+                .await
+                .unwrap();
+            if running {
+                let mut status = PodStatus {
+                    phase: PodPhase::Running,
+                    ..Default::default()
+                };
+                assert!(state.update_pod_status(&id, &mut status).await.is_ok());
+            }
+        }
+
+        let req = TestRequest::get().uri("/poddisruptionbudgets").to_request();
+        let res = call_service(&app, req).await;
+        let pdbs: Vec<PodDisruptionBudget> = read_body_json(res).await;
+        assert_eq!(pdbs[0].status.expected_pods, 3);
+        assert_eq!(pdbs[0].status.current_healthy, 2);
+        assert_eq!(pdbs[0].status.desired_healthy, 2);
+        assert_eq!(pdbs[0].status.disruptions_allowed, 0);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_pdb_not_found() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = pdb_service(&state).await;
+
+        let req = TestRequest::delete()
+            .uri("/poddisruptionbudgets/made-up")
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,236 @@
+//! PodGroup
+//!
+//! ## Routes
+//! - `GET    /podgroups`          — List or watch pod groups
+//! - `POST   /podgroups`          — Create a new pod group
+//! - `DELETE /podgroups/{name}`   — Delete a pod group
+
+use crate::state::State;
+use actix_web::{
+    HttpResponse, Responder,
+    web::{self, Bytes},
+};
+use serde::Deserialize;
+use shared::api::{CreateResponse, EventType, PodGroupEvent, PodGroupManifest};
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get))
+        .route("", web::post().to(create))
+        .route("/{name}", web::delete().to(delete));
+}
+
+#[derive(Deserialize)]
+pub struct PodGroupQuery {
+    watch: Option<bool>,
+}
+
+/// List or watch pod groups
+///
+/// # Arguments
+/// - `query`: Query parameters:
+///    - `watch` (bool, optional): If true, opens a watch stream of pod group events.
+///
+/// # Returns
+/// - 200 list of pod groups or stream of pod group events
+async fn get(state: State, query: web::Query<PodGroupQuery>) -> impl Responder {
+    let groups = state.get_podgroups().await;
+    if query.watch.unwrap_or(false) {
+        let mut rx = state.podgroup_tx.subscribe();
+        let stream = async_stream::stream! {
+            for group in groups {
+                let event = PodGroupEvent {
+                    podgroup: group,
+                    event_type: EventType::Added
+                };
+                let json = serde_json::to_string(&event).unwrap();
+                yield Ok::<_, actix_web::Error>(Bytes::from(json + "\n"));
+            }
+            while let Ok(event) = rx.recv().await {
+                let json = serde_json::to_string(&event).unwrap();
+                yield Ok::<_, actix_web::Error>(Bytes::from(json + "\n"));
+            }
+        };
+
+        HttpResponse::Ok()
+            .content_type("application/json")
+            .streaming(stream)
+    } else {
+        HttpResponse::Ok().json(&groups)
+    }
+}
+
+/// Create a new pod group.
+///
+/// # Arguments
+/// - `body`: PodGroup manifest JSON.
+///
+/// # Returns
+/// - 201: Pod group created.
+/// - 400: Invalid manifest format
+/// - 409: Repeat name
+async fn create(state: State, payload: web::Json<PodGroupManifest>) -> impl Responder {
+    let manifest = payload.into_inner();
+
+    if manifest.metadata.owner_reference.is_some() {
+        return HttpResponse::BadRequest().finish();
+    }
+
+    let name = manifest.metadata.name.clone();
+    match state
+        .add_podgroup(manifest.spec, manifest.metadata.into())
+        .await
+    {
+        Ok(id) => {
+            tracing::info!(%name, "Pod group created");
+            let response = CreateResponse {
+                id,
+                status: "Accepted".into(),
+            };
+            HttpResponse::Created().json(response)
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not create pod group");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Delete a pod group by name.
+///
+/// # Returns
+/// - 204: Pod group deleted
+/// - 404: Pod group not found
+async fn delete(state: State, path_string: web::Path<String>) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.delete_podgroup(&name).await {
+        Ok(_) => {
+            tracing::info!(%name, "Pod group deleted");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not delete pod group");
+            err.to_http_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //!  CREATE
+    //!  - test_create_podgroup
+    //!  - test_create_podgroup_repeat_name
+    //!  - test_create_podgroup_zero_members
+    //!
+    //!  DELETE
+    //!  - test_delete_podgroup
+
+    use crate::state::{ApiServerState, test_store::TestStore};
+
+    use super::*;
+    use actix_web::body::BoxBody;
+    use actix_web::dev::Service;
+    use actix_web::{
+        App,
+        http::StatusCode,
+        test::{TestRequest, call_service, init_service, read_body_json},
+    };
+    use shared::models::{
+        metadata::ObjectMetadata,
+        podgroup::{PodGroup, PodGroupSpec},
+    };
+
+    async fn podgroup_service(
+        state: &State,
+    ) -> impl Service<
+        actix_http::Request,
+        Response = actix_web::dev::ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    > {
+        init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/podgroups", web::get().to(get))
+                .route("/podgroups", web::post().to(create))
+                .route("/podgroups/{name}", web::delete().to(delete)),
+        )
+        .await
+    }
+
+    fn manifest(name: &str, min_member: u16) -> PodGroupManifest {
+        PodGroupManifest {
+            metadata: ObjectMetadata {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            spec: PodGroupSpec {
+                min_member,
+                schedule_timeout_seconds: 30,
+            },
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_podgroup() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = podgroup_service(&state).await;
+
+        let req = TestRequest::post()
+            .uri("/podgroups")
+            .set_json(manifest("train", 3))
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+
+        let req = TestRequest::get().uri("/podgroups").to_request();
+        let res = call_service(&app, req).await;
+        let groups: Vec<PodGroup> = read_body_json(res).await;
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].spec.min_member, 3);
+    }
+
+    #[actix_web::test]
+    async fn test_create_podgroup_repeat_name() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = podgroup_service(&state).await;
+
+        for expected in [StatusCode::CREATED, StatusCode::CONFLICT] {
+            let req = TestRequest::post()
+                .uri("/podgroups")
+                .set_json(manifest("train", 2))
+                .to_request();
+            let res = call_service(&app, req).await;
+            assert_eq!(res.status(), expected);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_podgroup_zero_members() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = podgroup_service(&state).await;
+
+        let req = TestRequest::post()
+            .uri("/podgroups")
+            .set_json(manifest("train", 0))
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_podgroup() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = podgroup_service(&state).await;
+
+        let req = TestRequest::post()
+            .uri("/podgroups")
+            .set_json(manifest("train", 2))
+            .to_request();
+        call_service(&app, req).await;
+
+        for expected in [StatusCode::NO_CONTENT, StatusCode::NOT_FOUND] {
+            let req = TestRequest::delete().uri("/podgroups/train").to_request();
+            let res = call_service(&app, req).await;
+            assert_eq!(res.status(), expected);
+        }
+    }
+}
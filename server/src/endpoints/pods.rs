@@ -6,16 +6,19 @@
 //! - `DELETE /pods/{pod_name}`         — Delete a pod
 //! - `PATCH  /pods/{pod_name}`         — Update pod fields
 //! - `PATCH  /pods/{pod_name}/status`  — Update the pod's status
+//! - `POST   /pods/{pod_name}/eviction`— Delete a pod respecting disruption budgets
 //! - `GET    /pods/{pod_name}/logs`    — Get or stream pods logs
 
+use crate::auth::UserInfo;
 use crate::state::State;
 use actix_web::{HttpResponse, Responder, web};
 use bytes::Bytes;
 use futures_util::StreamExt;
 use shared::{
     api::{
-        CreatePodParams, CreateResponse, EventType, LogsQueryParams, PodEvent, PodField,
-        PodManifest, PodPatch, PodQueryParams, PodStatusUpdate,
+        CreatePodParams, CreateResponse, DeletePodParams, EventType, LogsQueryParams,
+        PodConditionUpdate, PodEvent, PodField, PodManifest, PodPatch, PodQueryParams,
+        PodStatusUpdate,
     },
     models::{metadata::LabelSelector, pod::PodSpec},
 };
@@ -25,6 +28,7 @@ pub fn config(cfg: &mut web::ServiceConfig) {
         .route("/{pod_name}", web::patch().to(update))
         .route("/{pod_name}", web::delete().to(delete))
         .route("/{pod_name}/logs", web::get().to(logs))
+        .route("/{pod_name}/eviction", web::post().to(evict))
         .route("", web::post().to(create));
 }
 
@@ -109,11 +113,16 @@ async fn get(state: State, q: web::Query<PodQueryParams>) -> impl Responder {
 /// - Pod Status
 ///     - 200: Status updated.
 ///     - 401: Pod is not assigned to node making call
-///     - 403: Node name not registered in system
-/// - Pod Spec
-///     - 501
+///     - 403: Node name not registered in system, or the caller is not that node
+/// - Pod Condition
+///     - 200: Condition recorded
+///     - 422: nominated node does not exist
+/// - Pod Spec (merge patch of the spec)
+///     - 200: Updated pod, generation bumped if the spec changed
+///     - 400: Invalid spec or immutable field changed
 async fn update(
     state: State,
+    user: Option<web::ReqData<UserInfo>>,
     path_string: web::Path<String>,
     body: web::Json<PodPatch>,
 ) -> impl Responder {
@@ -133,11 +142,37 @@ async fn update(
         PodField::Status => {
             let parsed: Result<PodStatusUpdate, _> = serde_json::from_value(patch.value);
             match parsed {
+                Ok(status_update)
+                    if user
+                        .as_ref()
+                        .is_some_and(|user| !user.may_act_as_node(&status_update.node_name)) =>
+                {
+                    HttpResponse::Forbidden().body("Caller is not the node of the status")
+                }
                 Ok(status_update) => update_status(state, pod_name, status_update).await,
                 Err(_) => HttpResponse::BadRequest().body("Invalid status format"),
             }
         }
-        PodField::Spec => HttpResponse::NotImplemented().finish(),
+        PodField::Condition => {
+            let parsed: Result<PodConditionUpdate, _> = serde_json::from_value(patch.value);
+            let Ok(update) = parsed else {
+                return HttpResponse::BadRequest().body("Invalid condition format");
+            };
+            match state.update_pod_condition(&pod_name, update).await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(err) => {
+                    tracing::warn!(error = %err, "Could not update pod condition");
+                    err.to_http_response()
+                }
+            }
+        }
+        PodField::Spec => match state.update_pod_spec(&pod_name, patch.value).await {
+            Ok(pod) => HttpResponse::Ok().json(pod),
+            Err(err) => {
+                tracing::warn!(error = %err, "Could not update pod spec");
+                err.to_http_response()
+            }
+        },
     }
 }
 
@@ -191,6 +226,7 @@ async fn update_status(
 /// - 201: Pod created.
 /// - 400: Invalid manifest format
 /// - 409: Repeat pod
+/// - 422: Unknown priority class
 async fn create(
     state: State,
     query: web::Query<CreatePodParams>,
@@ -216,6 +252,14 @@ async fn create(
     let pod_spec = PodSpec {
         node_name: "".to_string(),
         containers: manifest.spec.containers,
+        init_containers: manifest.spec.init_containers,
+        restart_policy: manifest.spec.restart_policy,
+        volumes: manifest.spec.volumes,
+        priority_class_name: manifest.spec.priority_class_name,
+        priority: 0,
+        tolerations: manifest.spec.tolerations,
+        anti_affinity: manifest.spec.pod_anti_affinity,
+        termination_grace_period_seconds: manifest.spec.termination_grace_period_seconds,
     };
 
     match state.add_pod(pod_spec, manifest.metadata.into()).await {
@@ -242,22 +286,42 @@ async fn create(
 
 /// Delete a pod by name.
 ///
+/// Pods bound to a node terminate gracefully, the node removes them once
+/// their containers are stopped.
+///
 /// # Arguments
 /// - `path_string`: Pod name from URL path.
+/// - `query`: Query parameters:
+///    - `gracePeriodSeconds` (u32, optional): overrides the pod's grace period, 0 deletes now
 ///
 /// # Returns
+/// - 202: Pod terminating
 /// - 204: Pod deleted
 /// - 404: Pod not found
-async fn delete(state: State, path_string: web::Path<String>) -> impl Responder {
+async fn delete(
+    state: State,
+    path_string: web::Path<String>,
+    query: web::Query<DeletePodParams>,
+) -> impl Responder {
     let pod_name = path_string.into_inner();
-    match state.delete_pod(&pod_name).await {
-        Ok(_) => {
+    match state
+        .terminate_pod(&pod_name, query.grace_period_seconds)
+        .await
+    {
+        Ok(true) => {
             tracing::info!(
                 name=%pod_name,
                 "Pod deleted"
             );
             HttpResponse::NoContent().finish()
         }
+        Ok(false) => {
+            tracing::info!(
+                name=%pod_name,
+                "Pod terminating"
+            );
+            HttpResponse::Accepted().finish()
+        }
         Err(err) => {
             tracing::warn!(
                 error=%err,
@@ -268,6 +332,26 @@ async fn delete(state: State, path_string: web::Path<String>) -> impl Responder
     }
 }
 
+/// Evict a pod if no disruption budget forbids it.
+///
+/// # Returns
+/// - 201: Pod evicted
+/// - 404: Pod not found
+/// - 429: Eviction would violate a disruption budget
+async fn evict(state: State, path_string: web::Path<String>) -> impl Responder {
+    let pod_name = path_string.into_inner();
+    match state.evict_pod(&pod_name).await {
+        Ok(_) => {
+            tracing::info!(name=%pod_name, "Pod evicted");
+            HttpResponse::Created().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not evict pod");
+            err.to_http_response()
+        }
+    }
+}
+
 /// Fetch pod logs by forwarding request to assigned node.
 ///
 /// # Arguments
@@ -312,7 +396,9 @@ async fn logs(
     };
 
     let port = socket_addr.port();
-    let host = format!("http://{}:{}", node_name, port);
+    // nodes serve certificates of the cluster CA when there is one
+    let scheme = if state.ca.is_some() { "https" } else { "http" };
+    let host = format!("{}://{}:{}", scheme, node_name, port);
     let mut url = format!("{}/pods/{}/logs", host, pod_info.id);
 
     let mut query_params = vec![];
@@ -327,7 +413,7 @@ async fn logs(
     }
 
     // Forward the request to the node
-    match reqwest::Client::new().get(&url).send().await {
+    match state.node_client.get(&url).send().await {
         Ok(resp) => {
             let status = resp.status();
 
@@ -369,23 +455,54 @@ mod tests {
     //!  - test_assign_pod_invalid_node_name
     //!  - test_assign_pod_not_found
     //!  - test_assign_pod_already_assigned
+    //!  - test_assign_pod_binds_claims
+    //!    volume provisioned on the node, other nodes refused
     //!
     //!  - test_update_pod_status
     //!  - test_update_pod_status_pod_name_not_found
     //!  - test_update_pod_status_node_not_found
     //!  - test_update_pod_status_not_assigned_to_caller
+    //!  - test_update_pod_status_other_node
+    //!    a node token cannot report for another node
+    //!
+    //!  - test_update_pod_condition
+    //!    condition and nomination survive a node status update
+    //!  - test_update_pod_condition_invalid_node
     //!
     //!  - test_update_pod_spec
+    //!    image change bumps the generation and emits an event
+    //!  - test_update_pod_spec_unchanged
+    //!  - test_update_pod_spec_immutable_field
     //!
     //!  CREATE
     //!  - test_create_pod
     //!  - test_create_pod_repeat_name
     //!  - test_create_pod_repeat_container_names
+    //!  - test_create_pod_invalid_container
+    //!  - test_create_pod_sidecar_outside_init
+    //!  - test_create_pod_volumes
+    //!  - test_create_pod_invalid_volume_name
+    //!    names that would leave the pod directory are rejected
+    //!  - test_create_pod_env_value_from
+    //!  - test_create_pod_priority_class
+    //!  - test_create_pod_unknown_priority_class
+    //!  - test_create_pod_unknown_pod_group
     //!
     //!  DELETE
     //!  - test_delete_pod
+    //!  - test_delete_assigned_pod_terminates
+    //!    marked with a deletion timestamp until confirmed with a 0 grace period
+    //!  - test_delete_terminating_pod_shorter_grace
+    //!    only a grace period ending sooner replaces the one in progress
     //!  - test_delete_not_found
+    //!
+    //!  EVICTION
+    //!  - test_evict_pod
+    //!  - test_evict_pod_not_found
+    //!  - test_evict_pod_budget_violated
+    //!    the last disruption allowed is spent, then 429
 
+    use crate::auth::Authenticator;
     use crate::endpoints::helpers::collect_stream_events;
     use crate::state::{ApiServerState, test_store::TestStore};
 
@@ -399,10 +516,18 @@ mod tests {
     };
     use serde_json::Value;
     use shared::models::metadata::ObjectMetadata;
-    use shared::models::pod::PodStatus;
+    use shared::models::pod::{PodCondition, PodConditionType, PodPhase, PodStatus};
+    use shared::models::poddisruptionbudget::PodDisruptionBudgetSpec;
+    use shared::models::podgroup::POD_GROUP_LABEL;
+    use shared::models::priorityclass::PriorityClassSpec;
     use shared::models::{
         node::Node,
-        pod::{ContainerSpec, Pod},
+        persistentvolume::{ReclaimPolicy, VolumePhase},
+        persistentvolumeclaim::{ClaimPhase, PersistentVolumeClaimSpec},
+        pod::{
+            ClaimVolume, ContainerSpec, EmptyDirVolume, EnvVar, Pod, RestartPolicy, Volume,
+            VolumeMount, VolumeSource,
+        },
     };
 
     async fn pod_service(
@@ -419,7 +544,8 @@ mod tests {
                 .route("/pods", web::post().to(create))
                 .route("/pods/{pod_name}", web::patch().to(update))
                 .route("/pods/{pod_name}", web::delete().to(delete))
-                .route("/pods/{pod_name}/logs", web::get().to(logs)),
+                .route("/pods/{pod_name}/logs", web::get().to(logs))
+                .route("/pods/{pod_name}/eviction", web::post().to(evict)),
         )
         .await
     }
@@ -440,6 +566,21 @@ mod tests {
         return metadata.name;
     }
 
+    async fn add_running_pod(state: &State, app: &str) -> String {
+        let mut metadata = ObjectMetadata::default();
+        metadata.labels.insert("app".to_string(), app.to_string());
+        let id = state
+            .add_pod(PodSpec::default(), metadata.clone().into())
+            .await
+            .unwrap();
+        let mut status = PodStatus {
+            phase: PodPhase::Running,
+            ..Default::default()
+        };
+        assert!(state.update_pod_status(&id, &mut status).await.is_ok());
+        metadata.name
+    }
+
     // --- Get tests ---
 
     #[actix_web::test]
@@ -593,6 +734,45 @@ mod tests {
         assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
     }
 
+    #[actix_web::test]
+    async fn test_update_pod_status_other_node() {
+        let auth = Authenticator::with_tokens(&[]);
+        let state = ApiServerState::new_with(Box::new(TestStore::new()), auth, None).await;
+        let (node_name, pod_name) = add_assigned_pod(&state).await;
+        let other = Node::default();
+        assert!(state.add_node(&other).await.is_ok());
+        let node_token = state.issue_node_token(&node_name).await.unwrap();
+        let other_token = state.issue_node_token(&other.name).await.unwrap();
+
+        let app = init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/pods/{pod_name}", web::patch().to(update))
+                .wrap(crate::endpoints::Authentication),
+        )
+        .await;
+
+        let payload = PodPatch {
+            pod_field: PodField::Status,
+            value: serde_json::to_value(PodStatusUpdate {
+                node_name,
+                status: PodStatus::default(),
+            })
+            .expect("could not serialize"),
+        };
+        for (token, status) in [
+            (other_token, StatusCode::FORBIDDEN),
+            (node_token, StatusCode::OK),
+        ] {
+            let req = TestRequest::patch()
+                .uri(&format!("/pods/{}", pod_name))
+                .insert_header(("Authorization", format!("Bearer {}", token)))
+                .set_json(&payload)
+                .to_request();
+            assert_eq!(call_service(&app, req).await.status(), status);
+        }
+    }
+
     // --- Patch Assign ---
 
     #[actix_web::test]
@@ -671,24 +851,221 @@ mod tests {
         assert_eq!(res.status(), StatusCode::CONFLICT);
     }
 
+    #[actix_web::test]
+    async fn test_assign_pod_binds_claims() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let nodes = [Node::default(), Node::default()];
+        for n in &nodes {
+            assert!(state.add_node(n).await.is_ok());
+        }
+        let spec = PodSpec {
+            volumes: vec![Volume {
+                name: "data".to_string(),
+                source: VolumeSource::PersistentVolumeClaim(ClaimVolume {
+                    claim_name: "db".to_string(),
+                    read_only: false,
+                }),
+            }],
+            ..Default::default()
+        };
+        let first = ObjectMetadata::default();
+        let second = ObjectMetadata::default();
+        for metadata in [&first, &second] {
+            let res = state.add_pod(spec.clone(), metadata.clone().into()).await;
+            assert!(res.is_ok());
+        }
+
+        // claim doesn't exist yet
+        let res = state.assign_pod(&first.name, nodes[0].name.clone()).await;
+        let status = res.unwrap_err().to_http_response().status();
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        let claim_spec = PersistentVolumeClaimSpec {
+            storage: "1Gi".to_string(),
+            volume_name: None,
+            reclaim_policy: ReclaimPolicy::Delete,
+        };
+        let metadata = ObjectMetadata {
+            name: "db".to_string(),
+            ..Default::default()
+        };
+        let mut rx = state.claim_tx.subscribe();
+        state
+            .add_persistentvolumeclaim(claim_spec, metadata.into())
+            .await
+            .unwrap();
+        assert!(
+            state
+                .assign_pod(&first.name, nodes[0].name.clone())
+                .await
+                .is_ok()
+        );
+
+        let claim = state.get_persistentvolumeclaim("db").await.unwrap();
+        assert_eq!(claim.status.phase, ClaimPhase::Bound);
+        assert_eq!(claim.status.node_name.as_ref(), Some(&nodes[0].name));
+        let pv = state
+            .get_persistentvolume(claim.status.volume_name.as_ref().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(pv.spec.node_name, nodes[0].name);
+        assert_eq!(pv.status.phase, VolumePhase::Bound);
+        assert!(pv.is_provisioned());
+        assert_eq!(rx.recv().await.unwrap().event_type, EventType::Added);
+        assert_eq!(rx.recv().await.unwrap().event_type, EventType::Modified);
+
+        // the data lives on the first node
+        let res = state.assign_pod(&second.name, nodes[1].name.clone()).await;
+        let status = res.unwrap_err().to_http_response().status();
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert!(
+            state
+                .assign_pod(&second.name, nodes[0].name.clone())
+                .await
+                .is_ok()
+        );
+    }
+
+    // --- Patch Condition ---
+
+    #[actix_web::test]
+    async fn test_update_pod_condition() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let n = Node::default();
+        assert!(state.add_node(&n).await.is_ok());
+        let pod_name = add_pod(&state).await;
+
+        let app = pod_service(&state).await;
+        let update = PodConditionUpdate {
+            condition: PodCondition::new(
+                PodConditionType::PodScheduled,
+                false,
+                "Preempting",
+                "Preempted 1 pod(s)",
+            ),
+            nominated_node_name: Some(n.name.clone()),
+        };
+        let payload = PodPatch {
+            pod_field: PodField::Condition,
+            value: serde_json::to_value(update).expect("could not serialize"),
+        };
+        let req = TestRequest::patch()
+            .uri(&format!("/pods/{}", pod_name))
+            .set_json(payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // node reported status must not drop the condition
+        let pod_id = state.cache.get_pod_id(&pod_name).unwrap();
+        state
+            .update_pod_status(&pod_id, &mut PodStatus::default())
+            .await
+            .unwrap();
+
+        let pods = state.get_pods(&None, &Default::default()).await;
+        let status = &pods[0].status;
+        assert_eq!(status.nominated_node_name, Some(n.name));
+        let condition = status
+            .get_condition(&PodConditionType::PodScheduled)
+            .expect("condition should be recorded");
+        assert_eq!(condition.reason, "Preempting");
+    }
+
+    #[actix_web::test]
+    async fn test_update_pod_condition_invalid_node() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let pod_name = add_pod(&state).await;
+
+        let app = pod_service(&state).await;
+        let update = PodConditionUpdate {
+            condition: PodCondition::new(PodConditionType::PodScheduled, false, "Preempting", ""),
+            nominated_node_name: Some("made-up".to_string()),
+        };
+        let payload = PodPatch {
+            pod_field: PodField::Condition,
+            value: serde_json::to_value(update).expect("could not serialize"),
+        };
+        let req = TestRequest::patch()
+            .uri(&format!("/pods/{}", pod_name))
+            .set_json(payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
     // --- Patch Pod Spec ---
 
     #[actix_web::test]
     async fn test_update_pod_spec() {
         let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
         let pod_name = add_pod(&state).await;
+        let mut rx = state.pod_tx.subscribe();
+
+        let app = pod_service(&state).await;
+        let mut containers = PodSpec::default().containers;
+        containers[0].image = "nginx:1.27".to_string();
+        let payload = PodPatch {
+            pod_field: PodField::Spec,
+            value: serde_json::json!({ "containers": containers }),
+        };
+        let req = TestRequest::patch()
+            .uri(&format!("/pods/{}", pod_name))
+            .set_json(payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let pod: Pod = read_body_json(res).await;
+        assert_eq!(pod.metadata.generation, 2);
+        assert_eq!(pod.spec.containers[0].image, "nginx:1.27");
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.event_type, EventType::Modified);
+        assert_eq!(event.pod.metadata.generation, 2);
+    }
+
+    #[actix_web::test]
+    async fn test_update_pod_spec_unchanged() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let pod_name = add_pod(&state).await;
 
         let app = pod_service(&state).await;
         let payload = PodPatch {
             pod_field: PodField::Spec,
-            value: Value::String("".to_string()),
+            value: serde_json::json!({ "containers": PodSpec::default().containers }),
         };
         let req = TestRequest::patch()
             .uri(&format!("/pods/{}", pod_name))
             .set_json(payload)
             .to_request();
         let res = call_service(&app, req).await;
-        assert_eq!(res.status(), StatusCode::NOT_IMPLEMENTED);
+        assert_eq!(res.status(), StatusCode::OK);
+        let pod: Pod = read_body_json(res).await;
+        assert_eq!(pod.metadata.generation, 1);
+    }
+
+    #[actix_web::test]
+    async fn test_update_pod_spec_immutable_field() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let (_, pod_name) = add_assigned_pod(&state).await;
+
+        let app = pod_service(&state).await;
+        for value in [
+            serde_json::json!({ "node_name": "other" }),
+            serde_json::json!({ "priority": 100 }),
+            serde_json::json!({ "containers": [] }),
+        ] {
+            let payload = PodPatch {
+                pod_field: PodField::Spec,
+                value,
+            };
+            let req = TestRequest::patch()
+                .uri(&format!("/pods/{}", pod_name))
+                .set_json(payload)
+                .to_request();
+            let res = call_service(&app, req).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        }
     }
 
     // --- Create pod ---
@@ -738,6 +1115,219 @@ mod tests {
         assert_eq!(res.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[actix_web::test]
+    async fn test_create_pod_invalid_container() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let mut payload = PodManifest::default();
+        payload.spec.containers = vec![ContainerSpec {
+            working_dir: Some("relative/dir".to_string()),
+            ..Default::default()
+        }];
+
+        let app = pod_service(&state).await;
+        let req = TestRequest::post()
+            .uri("/pods")
+            .set_json(payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_create_pod_sidecar_outside_init() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let sidecar = ContainerSpec {
+            name: "proxy".to_string(),
+            restart_policy: Some(RestartPolicy::Always),
+            ..Default::default()
+        };
+        let mut payload = PodManifest::default();
+        payload.spec.init_containers = vec![sidecar.clone()];
+        payload.spec.containers = vec![ContainerSpec::default()];
+
+        let app = pod_service(&state).await;
+        let req = TestRequest::post()
+            .uri("/pods")
+            .set_json(&payload)
+            .to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::CREATED);
+
+        payload.metadata.name = "other".to_string();
+        payload.spec.init_containers = Vec::new();
+        payload.spec.containers = vec![sidecar];
+        let req = TestRequest::post()
+            .uri("/pods")
+            .set_json(&payload)
+            .to_request();
+        assert_eq!(
+            call_service(&app, req).await.status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_create_pod_env_value_from() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let mut env: Vec<EnvVar> = serde_json::from_value(serde_json::json!([
+            {"name": "LOG_LEVEL", "valueFrom": {"configMapKeyRef": {"name": "app", "key": "log_level"}}},
+            {"name": "DB_PASSWORD", "valueFrom": {"secretKeyRef": {"name": "db", "key": "password"}}}
+        ]))
+        .unwrap();
+        let mut payload = PodManifest::default();
+        payload.spec.containers = vec![ContainerSpec {
+            env: Some(env.clone()),
+            ..Default::default()
+        }];
+
+        let app = pod_service(&state).await;
+        let req = TestRequest::post()
+            .uri("/pods")
+            .set_json(&payload)
+            .to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::CREATED);
+
+        // a literal value on top of valueFrom is ambiguous
+        env[0].value = "debug".to_string();
+        payload.metadata.name = "other".to_string();
+        payload.spec.containers[0].env = Some(env);
+        let req = TestRequest::post()
+            .uri("/pods")
+            .set_json(&payload)
+            .to_request();
+        assert_eq!(
+            call_service(&app, req).await.status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_create_pod_volumes() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let mut payload = PodManifest::default();
+        payload.spec.volumes = vec![Volume {
+            name: "cache".to_string(),
+            source: VolumeSource::EmptyDir(EmptyDirVolume::default()),
+        }];
+        payload.spec.containers = vec![ContainerSpec {
+            volume_mounts: vec![VolumeMount {
+                name: "cache".to_string(),
+                mount_path: "/cache".to_string(),
+                read_only: false,
+                sub_path: None,
+            }],
+            ..Default::default()
+        }];
+
+        let app = pod_service(&state).await;
+        let req = TestRequest::post()
+            .uri("/pods")
+            .set_json(&payload)
+            .to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::CREATED);
+
+        // mount of a volume the pod doesn't have
+        payload.metadata.name = "other".to_string();
+        payload.spec.volumes.clear();
+        let req = TestRequest::post()
+            .uri("/pods")
+            .set_json(&payload)
+            .to_request();
+        assert_eq!(
+            call_service(&app, req).await.status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_create_pod_invalid_volume_name() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = pod_service(&state).await;
+
+        for name in ["..", "../../x", "/etc/foo", "Cache"] {
+            let mut payload = PodManifest::default();
+            payload.spec.volumes = vec![Volume {
+                name: name.to_string(),
+                source: VolumeSource::EmptyDir(EmptyDirVolume::default()),
+            }];
+            payload.spec.containers = vec![ContainerSpec::default()];
+
+            let req = TestRequest::post()
+                .uri("/pods")
+                .set_json(&payload)
+                .to_request();
+            assert_eq!(
+                call_service(&app, req).await.status(),
+                StatusCode::BAD_REQUEST,
+                "{}",
+                name
+            );
+        }
+        assert!(state.get_pods(&None, &Default::default()).await.is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_create_pod_priority_class() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let spec = PriorityClassSpec {
+            value: 1000,
+            global_default: false,
+            description: "".to_string(),
+        };
+        let metadata = ObjectMetadata {
+            name: "critical".to_string(),
+            ..Default::default()
+        };
+        assert!(state.add_priorityclass(spec, metadata.into()).await.is_ok());
+
+        let mut payload = PodManifest::default();
+        payload.spec.containers = vec![ContainerSpec::default()];
+        payload.spec.priority_class_name = Some("critical".to_string());
+
+        let app = pod_service(&state).await;
+        let req = TestRequest::post()
+            .uri("/pods")
+            .set_json(payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+
+        let pods = state.get_pods(&None, &Default::default()).await;
+        assert_eq!(pods[0].spec.priority, 1000);
+    }
+
+    #[actix_web::test]
+    async fn test_create_pod_unknown_priority_class() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let mut payload = PodManifest::default();
+        payload.spec.priority_class_name = Some("made-up".to_string());
+
+        let app = pod_service(&state).await;
+        let req = TestRequest::post()
+            .uri("/pods")
+            .set_json(payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[actix_web::test]
+    async fn test_create_pod_unknown_pod_group() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let mut payload = PodManifest::default();
+        payload
+            .metadata
+            .labels
+            .insert(POD_GROUP_LABEL.to_string(), "made-up".to_string());
+
+        let app = pod_service(&state).await;
+        let req = TestRequest::post()
+            .uri("/pods")
+            .set_json(payload)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
     #[actix_web::test]
     async fn test_delete_pod() {
         let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
@@ -759,6 +1349,78 @@ mod tests {
         assert_eq!(pods.len(), 0, "There should be no pods");
     }
 
+    #[actix_web::test]
+    async fn test_delete_assigned_pod_terminates() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let (_, pod_name) = add_assigned_pod(&state).await;
+        let mut rx = state.pod_tx.subscribe();
+
+        let app = pod_service(&state).await;
+        let req = TestRequest::delete()
+            .uri(&format!("/pods/{}", pod_name))
+            .to_request();
+        let resp = call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.event_type, EventType::Modified);
+        assert!(event.pod.metadata.deletion_timestamp.is_some());
+        assert!(state.cache.get_pod_id(&pod_name).is_some());
+
+        // repeated requests keep the pod terminating
+        let req = TestRequest::delete()
+            .uri(&format!("/pods/{}", pod_name))
+            .to_request();
+        let resp = call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+        let req = TestRequest::delete()
+            .uri(&format!("/pods/{}?gracePeriodSeconds=0", pod_name))
+            .to_request();
+        let resp = call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert!(state.cache.get_pod_id(&pod_name).is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_delete_terminating_pod_shorter_grace() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let (_, pod_name) = add_assigned_pod(&state).await;
+        let mut rx = state.pod_tx.subscribe();
+        let app = pod_service(&state).await;
+
+        let req = TestRequest::delete()
+            .uri(&format!("/pods/{}?gracePeriodSeconds=60", pod_name))
+            .to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::ACCEPTED);
+        let first = rx.recv().await.unwrap();
+        assert_eq!(first.pod.spec.termination_grace_period_seconds, 60);
+
+        let req = TestRequest::delete()
+            .uri(&format!("/pods/{}?gracePeriodSeconds=5", pod_name))
+            .to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::ACCEPTED);
+        let shorter = rx.recv().await.unwrap();
+        assert_eq!(shorter.event_type, EventType::Modified);
+        assert_eq!(shorter.pod.spec.termination_grace_period_seconds, 5);
+
+        // a longer one does not extend it
+        let req = TestRequest::delete()
+            .uri(&format!("/pods/{}?gracePeriodSeconds=30", pod_name))
+            .to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::ACCEPTED);
+        assert!(rx.try_recv().is_err());
+
+        let req = TestRequest::delete()
+            .uri(&format!("/pods/{}?gracePeriodSeconds=0", pod_name))
+            .to_request();
+        assert_eq!(
+            call_service(&app, req).await.status(),
+            StatusCode::NO_CONTENT
+        );
+        assert!(state.cache.get_pod_id(&pod_name).is_none());
+    }
+
     #[actix_web::test]
     async fn test_delete_pod_not_found() {
         let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
@@ -768,4 +1430,67 @@ mod tests {
         let resp = call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
+
+    // --- Eviction tests ---
+
+    #[actix_web::test]
+    async fn test_evict_pod() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let pod_name = add_running_pod(&state, "web").await;
+        let app = pod_service(&state).await;
+
+        let req = TestRequest::post()
+            .uri(&format!("/pods/{}/eviction", pod_name))
+            .to_request();
+        let resp = call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        assert!(state.cache.get_pod_id(&pod_name).is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_evict_pod_not_found() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = pod_service(&state).await;
+
+        let req = TestRequest::post()
+            .uri("/pods/made-up/eviction")
+            .to_request();
+        let resp = call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn test_evict_pod_budget_violated() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let first = add_running_pod(&state, "web").await;
+        let second = add_running_pod(&state, "web").await;
+        let spec = PodDisruptionBudgetSpec {
+            min_available: Some(1),
+            max_unavailable: None,
+            selector: LabelSelector::try_from("app=web".to_string()).unwrap(),
+        };
+        let metadata = ObjectMetadata::default();
+        assert!(
+            state
+                .add_poddisruptionbudget(spec, metadata.into())
+                .await
+                .is_ok()
+        );
+        let app = pod_service(&state).await;
+
+        for (pod_name, expected) in [
+            (first, StatusCode::CREATED),
+            (second, StatusCode::TOO_MANY_REQUESTS),
+        ] {
+            let req = TestRequest::post()
+                .uri(&format!("/pods/{}/eviction", pod_name))
+                .to_request();
+            let resp = call_service(&app, req).await;
+            assert_eq!(resp.status(), expected);
+        }
+
+        let pdbs = state.get_poddisruptionbudgets().await;
+        assert_eq!(pdbs[0].status.current_healthy, 1);
+        assert_eq!(pdbs[0].status.disruptions_allowed, 0);
+    }
 }
@@ -0,0 +1,202 @@
+//! PriorityClass
+//!
+//! ## Routes
+//! - `GET    /priorityclasses`          — List priority classes
+//! - `POST   /priorityclasses`          — Create a new priority class
+//! - `DELETE /priorityclasses/{name}`   — Delete a priority class
+
+use crate::state::State;
+use actix_web::{HttpResponse, Responder, web};
+use shared::api::{CreateResponse, PriorityClassManifest};
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get))
+        .route("", web::post().to(create))
+        .route("/{name}", web::delete().to(delete));
+}
+
+/// List priority classes
+///
+/// # Returns
+/// - 200 list of priority classes
+async fn get(state: State) -> impl Responder {
+    HttpResponse::Ok().json(state.get_priorityclasses().await)
+}
+
+/// Create a new priority class.
+///
+/// # Arguments
+/// - `body`: PriorityClass manifest JSON.
+///
+/// # Returns
+/// - 201: Priority class created.
+/// - 400: Invalid manifest format
+/// - 409: Repeat name or second global default
+async fn create(state: State, payload: web::Json<PriorityClassManifest>) -> impl Responder {
+    let manifest = payload.into_inner();
+
+    if manifest.metadata.owner_reference.is_some() {
+        return HttpResponse::BadRequest().finish();
+    }
+
+    let name = manifest.metadata.name.clone();
+    match state
+        .add_priorityclass(manifest.spec, manifest.metadata.into())
+        .await
+    {
+        Ok(id) => {
+            tracing::info!(%name, "Priority class created");
+            let response = CreateResponse {
+                id,
+                status: "Accepted".into(),
+            };
+            HttpResponse::Created().json(response)
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not create priority class");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Delete a priority class by name.
+///
+/// # Returns
+/// - 204: Priority class deleted
+/// - 404: Priority class not found
+async fn delete(state: State, path_string: web::Path<String>) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.delete_priorityclass(&name).await {
+        Ok(_) => {
+            tracing::info!(%name, "Priority class deleted");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not delete priority class");
+            err.to_http_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //!  CREATE
+    //!  - test_create_priorityclass
+    //!  - test_create_priorityclass_repeat_name
+    //!  - test_create_priorityclass_second_default
+    //!
+    //!  DELETE
+    //!  - test_delete_priorityclass_not_found
+
+    use crate::state::{ApiServerState, test_store::TestStore};
+
+    use super::*;
+    use actix_web::body::BoxBody;
+    use actix_web::dev::Service;
+    use actix_web::{
+        App,
+        http::StatusCode,
+        test::{TestRequest, call_service, init_service, read_body_json},
+    };
+    use shared::models::{
+        metadata::ObjectMetadata,
+        priorityclass::{PriorityClass, PriorityClassSpec},
+    };
+
+    async fn priorityclass_service(
+        state: &State,
+    ) -> impl Service<
+        actix_http::Request,
+        Response = actix_web::dev::ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    > {
+        init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/priorityclasses", web::get().to(get))
+                .route("/priorityclasses", web::post().to(create))
+                .route("/priorityclasses/{name}", web::delete().to(delete)),
+        )
+        .await
+    }
+
+    fn manifest(name: &str, value: i32, global_default: bool) -> PriorityClassManifest {
+        PriorityClassManifest {
+            metadata: ObjectMetadata {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            spec: PriorityClassSpec {
+                value,
+                global_default,
+                description: "".to_string(),
+            },
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_priorityclass() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = priorityclass_service(&state).await;
+
+        let req = TestRequest::post()
+            .uri("/priorityclasses")
+            .set_json(manifest("high", 1000, false))
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+
+        let req = TestRequest::get().uri("/priorityclasses").to_request();
+        let res = call_service(&app, req).await;
+        let classes: Vec<PriorityClass> = read_body_json(res).await;
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0].spec.value, 1000);
+    }
+
+    #[actix_web::test]
+    async fn test_create_priorityclass_repeat_name() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = priorityclass_service(&state).await;
+
+        for expected in [StatusCode::CREATED, StatusCode::CONFLICT] {
+            let req = TestRequest::post()
+                .uri("/priorityclasses")
+                .set_json(manifest("high", 1000, false))
+                .to_request();
+            let res = call_service(&app, req).await;
+            assert_eq!(res.status(), expected);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_priorityclass_second_default() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = priorityclass_service(&state).await;
+
+        let req = TestRequest::post()
+            .uri("/priorityclasses")
+            .set_json(manifest("low", 0, true))
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+
+        let req = TestRequest::post()
+            .uri("/priorityclasses")
+            .set_json(manifest("batch", -10, true))
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_priorityclass_not_found() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = priorityclass_service(&state).await;
+
+        let req = TestRequest::delete()
+            .uri("/priorityclasses/made-up")
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,614 @@
+//! Roles, cluster roles and their bindings
+//!
+//! ## Routes
+//! - `GET    /roles`                        — List roles
+//! - `POST   /roles`                        — Create a role
+//! - `DELETE /roles/{name}`                 — Delete a role
+//! - `GET    /clusterroles`                 — List cluster roles
+//! - `POST   /clusterroles`                 — Create a cluster role
+//! - `DELETE /clusterroles/{name}`          — Delete a cluster role
+//! - `GET    /rolebindings`                 — List role bindings
+//! - `POST   /rolebindings`                 — Create a role binding
+//! - `DELETE /rolebindings/{name}`          — Delete a role binding
+//! - `GET    /clusterrolebindings`          — List cluster role bindings
+//! - `POST   /clusterrolebindings`          — Create a cluster role binding
+//! - `DELETE /clusterrolebindings/{name}`   — Delete a cluster role binding
+//! - `POST   /accessreviews`                — Check whether the caller may perform an action
+
+use crate::auth::{UserInfo, rbac::Attributes};
+use crate::state::State;
+use actix_web::{HttpResponse, Responder, web};
+use shared::api::{
+    AccessReviewRequest, AccessReviewResponse, ClusterRoleBindingManifest, ClusterRoleManifest,
+    CreateResponse, RoleBindingManifest, RoleManifest,
+};
+
+pub fn roles_config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get_roles))
+        .route("", web::post().to(create_role))
+        .route("/{name}", web::delete().to(delete_role));
+}
+
+pub fn clusterroles_config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get_clusterroles))
+        .route("", web::post().to(create_clusterrole))
+        .route("/{name}", web::delete().to(delete_clusterrole));
+}
+
+pub fn rolebindings_config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get_rolebindings))
+        .route("", web::post().to(create_rolebinding))
+        .route("/{name}", web::delete().to(delete_rolebinding));
+}
+
+pub fn clusterrolebindings_config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get_clusterrolebindings))
+        .route("", web::post().to(create_clusterrolebinding))
+        .route("/{name}", web::delete().to(delete_clusterrolebinding));
+}
+
+pub fn accessreviews_config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::post().to(review));
+}
+
+/// List roles
+async fn get_roles(state: State) -> impl Responder {
+    HttpResponse::Ok().json(state.get_roles().await)
+}
+
+/// Create a role.
+///
+/// # Returns
+/// - 201: Role created
+/// - 400: Rule without verbs or resources
+/// - 409: Repeat name
+async fn create_role(state: State, payload: web::Json<RoleManifest>) -> impl Responder {
+    let manifest = payload.into_inner();
+    let name = manifest.metadata.name.clone();
+    match state
+        .add_role(manifest.spec, manifest.metadata.into())
+        .await
+    {
+        Ok(id) => {
+            tracing::info!(%name, "Role created");
+            HttpResponse::Created().json(CreateResponse {
+                id,
+                status: "Accepted".into(),
+            })
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not create role");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Delete a role, its bindings stop granting anything.
+///
+/// # Returns
+/// - 204: Role deleted
+/// - 404: Role not found
+async fn delete_role(state: State, path_string: web::Path<String>) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.delete_role(&name).await {
+        Ok(_) => {
+            tracing::info!(%name, "Role deleted");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not delete role");
+            err.to_http_response()
+        }
+    }
+}
+
+/// List cluster roles
+async fn get_clusterroles(state: State) -> impl Responder {
+    HttpResponse::Ok().json(state.get_clusterroles().await)
+}
+
+/// Create a cluster role.
+///
+/// # Returns
+/// - 201: Cluster role created
+/// - 400: Rule without verbs or resources
+/// - 409: Repeat or built-in name
+async fn create_clusterrole(
+    state: State,
+    payload: web::Json<ClusterRoleManifest>,
+) -> impl Responder {
+    let manifest = payload.into_inner();
+    let name = manifest.metadata.name.clone();
+    match state
+        .add_clusterrole(manifest.spec, manifest.metadata.into())
+        .await
+    {
+        Ok(id) => {
+            tracing::info!(%name, "Cluster role created");
+            HttpResponse::Created().json(CreateResponse {
+                id,
+                status: "Accepted".into(),
+            })
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not create cluster role");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Delete a cluster role.
+///
+/// # Returns
+/// - 204: Cluster role deleted
+/// - 404: Cluster role not found
+async fn delete_clusterrole(state: State, path_string: web::Path<String>) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.delete_clusterrole(&name).await {
+        Ok(_) => {
+            tracing::info!(%name, "Cluster role deleted");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not delete cluster role");
+            err.to_http_response()
+        }
+    }
+}
+
+/// List role bindings
+async fn get_rolebindings(state: State) -> impl Responder {
+    HttpResponse::Ok().json(state.get_rolebindings().await)
+}
+
+/// Create a role binding, the role may be created later.
+///
+/// # Returns
+/// - 201: Role binding created
+/// - 400: No subjects
+/// - 409: Repeat name
+async fn create_rolebinding(
+    state: State,
+    payload: web::Json<RoleBindingManifest>,
+) -> impl Responder {
+    let manifest = payload.into_inner();
+    let name = manifest.metadata.name.clone();
+    match state
+        .add_rolebinding(manifest.spec, manifest.metadata.into())
+        .await
+    {
+        Ok(id) => {
+            tracing::info!(%name, "Role binding created");
+            HttpResponse::Created().json(CreateResponse {
+                id,
+                status: "Accepted".into(),
+            })
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not create role binding");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Delete a role binding.
+///
+/// # Returns
+/// - 204: Role binding deleted
+/// - 404: Role binding not found
+async fn delete_rolebinding(state: State, path_string: web::Path<String>) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.delete_rolebinding(&name).await {
+        Ok(_) => {
+            tracing::info!(%name, "Role binding deleted");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not delete role binding");
+            err.to_http_response()
+        }
+    }
+}
+
+/// List cluster role bindings, the built-in ones are not stored
+async fn get_clusterrolebindings(state: State) -> impl Responder {
+    HttpResponse::Ok().json(state.get_clusterrolebindings().await)
+}
+
+/// Create a cluster role binding.
+///
+/// # Returns
+/// - 201: Cluster role binding created
+/// - 400: No subjects, or a role instead of a cluster role
+/// - 409: Repeat or built-in name
+async fn create_clusterrolebinding(
+    state: State,
+    payload: web::Json<ClusterRoleBindingManifest>,
+) -> impl Responder {
+    let manifest = payload.into_inner();
+    let name = manifest.metadata.name.clone();
+    match state
+        .add_clusterrolebinding(manifest.spec, manifest.metadata.into())
+        .await
+    {
+        Ok(id) => {
+            tracing::info!(%name, "Cluster role binding created");
+            HttpResponse::Created().json(CreateResponse {
+                id,
+                status: "Accepted".into(),
+            })
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not create cluster role binding");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Delete a cluster role binding.
+///
+/// # Returns
+/// - 204: Cluster role binding deleted
+/// - 404: Cluster role binding not found
+async fn delete_clusterrolebinding(state: State, path_string: web::Path<String>) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.delete_clusterrolebinding(&name).await {
+        Ok(_) => {
+            tracing::info!(%name, "Cluster role binding deleted");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not delete cluster role binding");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Check whether the caller may perform an action, any authenticated caller can ask.
+///
+/// # Arguments
+/// - `body`: verb, resource and optional object name
+///
+/// # Returns
+/// - 200: Whether the action is allowed and why
+async fn review(
+    state: State,
+    user: Option<web::ReqData<UserInfo>>,
+    payload: web::Json<AccessReviewRequest>,
+) -> impl Responder {
+    let request = payload.into_inner();
+    let Some(user) = user else {
+        return HttpResponse::Ok().json(AccessReviewResponse {
+            allowed: true,
+            reason: "authentication is disabled".to_string(),
+        });
+    };
+    let attrs = Attributes::new(&request.verb, &request.resource, request.name.as_deref());
+    match state.authorize(&user, &attrs).await {
+        Ok(decision) => HttpResponse::Ok().json(AccessReviewResponse {
+            allowed: decision.is_ok(),
+            reason: decision.unwrap_or_else(|reason| reason),
+        }),
+        Err(err) => err.to_http_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //!  CREATE
+    //!  - test_create_role
+    //!    invalid rules refused, duplicate names conflict
+    //!  - test_create_clusterrolebinding
+    //!    only cluster roles, built-in names reserved
+    //!
+    //!  AUTHORIZATION
+    //!  - test_role_binding_grants_access
+    //!    a bound service account creates replica sets but cannot delete nodes
+    //!  - test_node_limited_to_own_objects
+    //!    a node cannot change another node or its pods, nor read secrets its pods don't use
+    //!  - test_access_review
+
+    use crate::auth::{Authenticator, MASTERS_GROUP};
+    use crate::endpoints::{Authentication, Authorization};
+    use crate::state::{ApiServerState, test_store::TestStore};
+
+    use super::*;
+    use actix_web::{
+        App,
+        http::StatusCode,
+        test::{TestRequest, call_service, init_service, read_body_json},
+    };
+    use shared::api::{NodePatch, NodeRegisterReq, NodeSpecPatch};
+    use shared::models::{
+        metadata::ObjectMetadata,
+        node::Node,
+        pod::{PodSpec, SecretVolume, Volume, VolumeSource},
+        rbac::{
+            ClusterRoleBindingSpec, DEFAULT_NAMESPACE, PolicyRule, RoleBindingSpec, RoleKind,
+            RoleRef, RoleSpec, Subject, SubjectKind,
+        },
+        secret::SecretSpec,
+    };
+
+    fn metadata(name: &str) -> ObjectMetadata {
+        ObjectMetadata {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn role(name: &str, verbs: &[&str], resources: &[&str]) -> RoleManifest {
+        RoleManifest {
+            metadata: metadata(name),
+            spec: RoleSpec {
+                namespace: DEFAULT_NAMESPACE.to_string(),
+                rules: vec![PolicyRule {
+                    verbs: verbs.iter().map(|v| v.to_string()).collect(),
+                    resources: resources.iter().map(|r| r.to_string()).collect(),
+                    resource_names: vec![],
+                }],
+            },
+        }
+    }
+
+    fn subjects() -> Vec<Subject> {
+        vec![Subject {
+            kind: SubjectKind::ServiceAccount,
+            name: "ci".to_string(),
+        }]
+    }
+
+    #[actix_web::test]
+    async fn test_create_role() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/roles", web::post().to(create_role)),
+        )
+        .await;
+
+        let req = TestRequest::post()
+            .uri("/roles")
+            .set_json(role("deployer", &["create"], &["replicasets"]))
+            .to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::CREATED);
+
+        let req = TestRequest::post()
+            .uri("/roles")
+            .set_json(role("deployer", &["get"], &["pods"]))
+            .to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::CONFLICT);
+
+        let req = TestRequest::post()
+            .uri("/roles")
+            .set_json(role("empty", &[], &["pods"]))
+            .to_request();
+        assert_eq!(
+            call_service(&app, req).await.status(),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(state.get_roles().await.len(), 1);
+    }
+
+    #[actix_web::test]
+    async fn test_create_clusterrolebinding() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = init_service(App::new().app_data(state.clone()).route(
+            "/clusterrolebindings",
+            web::post().to(create_clusterrolebinding),
+        ))
+        .await;
+        let binding = |name: &str, kind: RoleKind| ClusterRoleBindingManifest {
+            metadata: metadata(name),
+            spec: ClusterRoleBindingSpec {
+                role_ref: RoleRef {
+                    kind,
+                    name: "view".to_string(),
+                },
+                subjects: subjects(),
+            },
+        };
+
+        for (manifest, status) in [
+            (binding("ci", RoleKind::Role), StatusCode::BAD_REQUEST),
+            (
+                binding("system:node", RoleKind::ClusterRole),
+                StatusCode::CONFLICT,
+            ),
+            (binding("ci", RoleKind::ClusterRole), StatusCode::CREATED),
+        ] {
+            let req = TestRequest::post()
+                .uri("/clusterrolebindings")
+                .set_json(manifest)
+                .to_request();
+            assert_eq!(call_service(&app, req).await.status(), status);
+        }
+    }
+
+    async fn authorized_state() -> State {
+        let admin = UserInfo {
+            name: "admin".to_string(),
+            groups: vec![MASTERS_GROUP.to_string()],
+        };
+        let ci = UserInfo::service_account("ci", vec![]);
+        let auth = Authenticator::with_tokens(&[("admin-token", admin), ("ci-token", ci)]);
+        ApiServerState::new_with(Box::new(TestStore::new()), auth, None).await
+    }
+
+    #[actix_web::test]
+    async fn test_role_binding_grants_access() {
+        let state = authorized_state().await;
+        let app = init_service(
+            App::new()
+                .app_data(state.clone())
+                .configure(crate::endpoints::config)
+                .wrap(Authorization)
+                .wrap(Authentication),
+        )
+        .await;
+        let list_replicasets = |token: &str| {
+            TestRequest::get()
+                .uri("/replicasets")
+                .insert_header(("Authorization", format!("Bearer {}", token)))
+                .to_request()
+        };
+
+        let resp = call_service(&app, list_replicasets("ci-token")).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let requests = [
+            TestRequest::post().uri("/roles").set_json(role(
+                "deployer",
+                &["create", "list"],
+                &["replicasets"],
+            )),
+            TestRequest::post()
+                .uri("/rolebindings")
+                .set_json(RoleBindingManifest {
+                    metadata: metadata("ci-deployer"),
+                    spec: RoleBindingSpec {
+                        namespace: DEFAULT_NAMESPACE.to_string(),
+                        role_ref: RoleRef {
+                            kind: RoleKind::Role,
+                            name: "deployer".to_string(),
+                        },
+                        subjects: subjects(),
+                    },
+                }),
+        ];
+        for req in requests {
+            let req = req
+                .insert_header(("Authorization", "Bearer admin-token"))
+                .to_request();
+            assert_eq!(call_service(&app, req).await.status(), StatusCode::CREATED);
+        }
+
+        let resp = call_service(&app, list_replicasets("ci-token")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let req = TestRequest::delete()
+            .uri("/nodes/n1")
+            .insert_header(("Authorization", "Bearer ci-token"))
+            .to_request();
+        assert_eq!(
+            call_service(&app, req).await.status(),
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_node_limited_to_own_objects() {
+        let auth = Authenticator::with_tokens(&[("n1-token", UserInfo::node("n1"))]);
+        let state = ApiServerState::new_with(Box::new(TestStore::new()), auth, None).await;
+        for (i, name) in ["n1", "n2"].iter().enumerate() {
+            let node = Node {
+                name: name.to_string(),
+                addr: format!("10.0.0.{}:7621", i),
+                ..Default::default()
+            };
+            state.add_node(&node).await.unwrap();
+        }
+        for name in ["used", "unused"] {
+            state
+                .add_secret(SecretSpec::default(), metadata(name).into())
+                .await
+                .unwrap();
+        }
+        for (pod, node) in [("p1", "n1"), ("p2", "n2")] {
+            let spec = PodSpec {
+                volumes: vec![Volume {
+                    name: "creds".to_string(),
+                    source: VolumeSource::Secret(SecretVolume {
+                        secret_name: if node == "n1" { "used" } else { "unused" }.to_string(),
+                        optional: false,
+                    }),
+                }],
+                ..Default::default()
+            };
+            state.add_pod(spec, metadata(pod).into()).await.unwrap();
+            state.assign_pod(pod, node.to_string()).await.unwrap();
+        }
+        let app = init_service(
+            App::new()
+                .app_data(state.clone())
+                .configure(crate::endpoints::config)
+                .wrap(Authorization)
+                .wrap(Authentication),
+        )
+        .await;
+
+        let cordon = NodePatch {
+            spec: NodeSpecPatch {
+                unschedulable: Some(true),
+                ..Default::default()
+            },
+        };
+        let requests = [
+            (
+                TestRequest::patch().uri("/nodes/n2").set_json(&cordon),
+                StatusCode::FORBIDDEN,
+            ),
+            (
+                TestRequest::delete().uri("/nodes/n2"),
+                StatusCode::FORBIDDEN,
+            ),
+            (
+                TestRequest::post().uri("/nodes").set_json(NodeRegisterReq {
+                    port: 7621,
+                    name: "n2".to_string(),
+                    taints: Vec::new(),
+                    token: None,
+                    serving_key: None,
+                }),
+                StatusCode::FORBIDDEN,
+            ),
+            (TestRequest::delete().uri("/pods/p2"), StatusCode::FORBIDDEN),
+            (TestRequest::get().uri("/secrets"), StatusCode::FORBIDDEN),
+            (
+                TestRequest::get().uri("/secrets/unused"),
+                StatusCode::FORBIDDEN,
+            ),
+            (TestRequest::get().uri("/secrets/used"), StatusCode::OK),
+            (
+                TestRequest::patch().uri("/nodes/n1").set_json(&cordon),
+                StatusCode::OK,
+            ),
+        ];
+        for (req, status) in requests {
+            let req = req
+                .insert_header(("Authorization", "Bearer n1-token"))
+                .to_request();
+            let path = req.path().to_string();
+            assert_eq!(call_service(&app, req).await.status(), status, "{}", path);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_access_review() {
+        let state = authorized_state().await;
+        let app = init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/accessreviews", web::post().to(review))
+                .wrap(Authorization)
+                .wrap(Authentication),
+        )
+        .await;
+
+        for (token, allowed) in [("ci-token", false), ("admin-token", true)] {
+            let req = TestRequest::post()
+                .uri("/accessreviews")
+                .insert_header(("Authorization", format!("Bearer {}", token)))
+                .set_json(AccessReviewRequest {
+                    verb: "delete".to_string(),
+                    resource: "nodes".to_string(),
+                    name: None,
+                })
+                .to_request();
+            let resp = call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+            let review: AccessReviewResponse = read_body_json(resp).await;
+            assert_eq!(review.allowed, allowed, "{}", review.reason);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Scheduler
+//!
+//! ## Routes
+//! - `POST   /scheduler/simulate`   — Dry run placement of pods without binding
+
+use crate::controllers::scheduler::{self, SchedulerState};
+use crate::state::State;
+use actix_web::{HttpResponse, Responder, web};
+use shared::{
+    api::SimulationRequest,
+    models::pod::{Pod, PodSpec, PodStatus},
+};
+use std::collections::HashSet;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/simulate", web::post().to(simulate));
+}
+
+/// Run filter and score for each pod against a snapshot of the scheduler state.
+///
+/// # Arguments
+/// - `body`: list of pod manifests, placed in order
+///
+/// # Returns
+/// - 200: per pod chosen node and per node verdicts
+/// - 400: Empty list or repeated pod names
+/// - 422: Unknown priority class
+async fn simulate(
+    state: State,
+    scheduler: web::Data<SchedulerState>,
+    payload: web::Json<SimulationRequest>,
+) -> impl Responder {
+    let request = payload.into_inner();
+
+    let mut names = HashSet::new();
+    if request.pods.is_empty()
+        || !request
+            .pods
+            .iter()
+            .all(|p| names.insert(p.metadata.name.clone()))
+    {
+        return HttpResponse::BadRequest().body("Expected pods with unique names");
+    }
+
+    // priorities are resolved as on creation so preemption order is simulated too
+    let mut pods = Vec::new();
+    for manifest in request.pods {
+        let mut spec = PodSpec {
+            node_name: "".to_string(),
+            containers: manifest.spec.containers,
+            init_containers: manifest.spec.init_containers,
+            restart_policy: manifest.spec.restart_policy,
+            volumes: manifest.spec.volumes,
+            priority_class_name: manifest.spec.priority_class_name,
+            priority: 0,
+            tolerations: manifest.spec.tolerations,
+            anti_affinity: manifest.spec.pod_anti_affinity,
+            termination_grace_period_seconds: manifest.spec.termination_grace_period_seconds,
+        };
+        if let Err(err) = state.resolve_priority(&mut spec).await {
+            return err.to_http_response();
+        }
+        pods.push(Pod {
+            metadata: manifest.metadata.into(),
+            spec,
+            status: PodStatus::default(),
+        });
+    }
+
+    let scheduler = scheduler.into_inner();
+    let result = scheduler::simulate(&scheduler, pods).await;
+    HttpResponse::Ok().json(result)
+}
+
+#[cfg(test)]
+mod tests {
+    //!  SIMULATE
+    //!  - test_simulate
+    //!    placements accumulate on the snapshot, real state is untouched
+    //!  - test_simulate_empty
+    //!  - test_simulate_unknown_priority_class
+
+    use super::*;
+    use crate::controllers::scheduler::State;
+    use crate::state::{ApiServerState, test_store::TestStore};
+    use actix_web::body::BoxBody;
+    use actix_web::dev::Service;
+    use actix_web::{
+        App,
+        http::StatusCode,
+        test::{TestRequest, call_service, init_service, read_body_json},
+    };
+    use shared::{
+        api::{PodManifest, SimulationResult},
+        models::node::Node,
+    };
+
+    async fn scheduler_service(
+        state: &State,
+    ) -> impl Service<
+        actix_http::Request,
+        Response = actix_web::dev::ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    > {
+        let api = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        init_service(
+            App::new()
+                .app_data(api.clone())
+                .app_data(web::Data::from(state.clone()))
+                .route("/scheduler/simulate", web::post().to(simulate)),
+        )
+        .await
+    }
+
+    #[actix_web::test]
+    async fn test_simulate() {
+        let state = SchedulerState::new_with_extenders("http://localhost:0", Vec::new());
+        for name in ["n1", "n2"] {
+            state.add_node(&Node {
+                name: name.to_string(),
+                ..Default::default()
+            });
+        }
+        // n2 has no room for any pod, n1 fits all of them
+        if let Some(mut res) = state.node_resources.get_mut("n2") {
+            res.cpu = 0;
+        }
+        if let Some(mut res) = state.node_resources.get_mut("n1") {
+            res.cpu = 10_000;
+        }
+
+        let app = scheduler_service(&state).await;
+        let pods: Vec<PodManifest> = (0..3).map(|_| PodManifest::default()).collect();
+        let req = TestRequest::post()
+            .uri("/scheduler/simulate")
+            .set_json(SimulationRequest { pods })
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let result: SimulationResult = read_body_json(res).await;
+        assert_eq!(result.pods.len(), 3);
+        for pod in &result.pods {
+            assert_eq!(pod.node_name.as_deref(), Some("n1"));
+            assert!(pod.nodes[0].feasible);
+            assert!(!pod.nodes[1].feasible);
+            assert_eq!(pod.nodes[1].reason.as_deref(), Some("Insufficient cpu"));
+        }
+
+        // earlier placements are visible to later pods
+        let first = result.pods[0].nodes[0].score.unwrap();
+        let last = result.pods[2].nodes[0].score.unwrap();
+        assert!(first > last);
+
+        // nothing bound in the real state
+        assert!(state.pod_map.get("n1").is_none());
+        assert!(state.pods.is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_simulate_empty() {
+        let state = SchedulerState::new_with_extenders("http://localhost:0", Vec::new());
+        let app = scheduler_service(&state).await;
+
+        let req = TestRequest::post()
+            .uri("/scheduler/simulate")
+            .set_json(SimulationRequest::default())
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_simulate_unknown_priority_class() {
+        let state = SchedulerState::new_with_extenders("http://localhost:0", Vec::new());
+        let app = scheduler_service(&state).await;
+
+        let mut pod = PodManifest::default();
+        pod.spec.priority_class_name = Some("made-up".to_string());
+        let req = TestRequest::post()
+            .uri("/scheduler/simulate")
+            .set_json(SimulationRequest { pods: vec![pod] })
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
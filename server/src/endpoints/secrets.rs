@@ -0,0 +1,205 @@
+//! Secret
+//!
+//! ## Routes
+//! - `GET    /secrets`          — List secrets
+//! - `POST   /secrets`          — Create a new secret
+//! - `GET    /secrets/{name}`   — Get a secret
+//! - `PUT    /secrets/{name}`   — Replace the data of a secret
+//! - `DELETE /secrets/{name}`   — Delete a secret
+
+use crate::state::State;
+use actix_web::{HttpResponse, Responder, web};
+use shared::{
+    api::{CreateResponse, SecretManifest},
+    models::secret::SecretSpec,
+};
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get))
+        .route("", web::post().to(create))
+        .route("/{name}", web::get().to(get_one))
+        .route("/{name}", web::put().to(update))
+        .route("/{name}", web::delete().to(delete));
+}
+
+/// List secrets
+///
+/// # Returns
+/// - 200 list of secrets
+async fn get(state: State) -> impl Responder {
+    HttpResponse::Ok().json(state.get_secrets().await)
+}
+
+/// Get a secret by name.
+///
+/// # Returns
+/// - 200: The secret
+/// - 404: Secret not found
+async fn get_one(state: State, path_string: web::Path<String>) -> impl Responder {
+    match state.get_secret(&path_string.into_inner()).await {
+        Ok(obj) => HttpResponse::Ok().json(obj),
+        Err(err) => err.to_http_response(),
+    }
+}
+
+/// Create a new secret.
+///
+/// # Arguments
+/// - `body`: Secret manifest JSON.
+///
+/// # Returns
+/// - 201: Secret created.
+/// - 400: Invalid manifest format or keys
+/// - 409: Repeat name
+async fn create(state: State, payload: web::Json<SecretManifest>) -> impl Responder {
+    let manifest = payload.into_inner();
+
+    if manifest.metadata.owner_reference.is_some() {
+        return HttpResponse::BadRequest().finish();
+    }
+
+    let name = manifest.metadata.name.clone();
+    match state
+        .add_secret(manifest.spec, manifest.metadata.into())
+        .await
+    {
+        Ok(id) => {
+            tracing::info!(%name, "Secret created");
+            let response = CreateResponse {
+                id,
+                status: "Accepted".into(),
+            };
+            HttpResponse::Created().json(response)
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not create secret");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Replace the data of a secret, pods mounting it get the new files.
+///
+/// # Returns
+/// - 204: Secret updated
+/// - 400: Invalid keys
+/// - 404: Secret not found
+async fn update(
+    state: State,
+    path_string: web::Path<String>,
+    payload: web::Json<SecretSpec>,
+) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.update_secret(&name, payload.into_inner()).await {
+        Ok(()) => {
+            tracing::info!(%name, "Secret updated");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not update secret");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Delete a secret by name.
+///
+/// # Returns
+/// - 204: Secret deleted
+/// - 404: Secret not found
+async fn delete(state: State, path_string: web::Path<String>) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.delete_secret(&name).await {
+        Ok(_) => {
+            tracing::info!(%name, "Secret deleted");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not delete secret");
+            err.to_http_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //!  CREATE
+    //!  - test_create_secret
+    //!
+    //!  UPDATE
+    //!  - test_update_secret_not_found
+
+    use std::collections::HashMap;
+
+    use crate::state::{ApiServerState, test_store::TestStore};
+
+    use super::*;
+    use actix_web::body::BoxBody;
+    use actix_web::dev::Service;
+    use actix_web::{
+        App,
+        http::StatusCode,
+        test::{TestRequest, call_service, init_service, read_body_json},
+    };
+    use shared::models::{metadata::ObjectMetadata, secret::Secret};
+
+    async fn secret_service(
+        state: &State,
+    ) -> impl Service<
+        actix_http::Request,
+        Response = actix_web::dev::ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    > {
+        init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/secrets", web::post().to(create))
+                .route("/secrets/{name}", web::get().to(get_one))
+                .route("/secrets/{name}", web::put().to(update)),
+        )
+        .await
+    }
+
+    fn spec(password: &str) -> SecretSpec {
+        SecretSpec {
+            data: HashMap::from([("password".to_string(), password.to_string())]),
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_secret() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = secret_service(&state).await;
+
+        let req = TestRequest::post()
+            .uri("/secrets")
+            .set_json(SecretManifest {
+                metadata: ObjectMetadata {
+                    name: "db".to_string(),
+                    ..Default::default()
+                },
+                spec: spec("hunter2"),
+            })
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+
+        let req = TestRequest::get().uri("/secrets/db").to_request();
+        let res = call_service(&app, req).await;
+        let secret: Secret = read_body_json(res).await;
+        assert_eq!(secret.spec.data["password"], "hunter2");
+    }
+
+    #[actix_web::test]
+    async fn test_update_secret_not_found() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = secret_service(&state).await;
+
+        let req = TestRequest::put()
+            .uri("/secrets/made-up")
+            .set_json(spec("hunter2"))
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,228 @@
+//! Endpoints
+//!
+//! Ready backends of each service, written by the endpoints controller
+//!
+//! ## Routes
+//! - `GET    /endpoints`          — List or watch the endpoints of every service
+//! - `GET    /endpoints/{name}`   — Get the endpoints of a service
+//! - `PUT    /endpoints/{name}`   — Replace the endpoints of a service
+
+use crate::state::State;
+use actix_web::{
+    HttpResponse, Responder,
+    web::{self, Bytes},
+};
+use serde::Deserialize;
+use shared::{
+    api::{EndpointsEvent, EventType},
+    models::endpoints::EndpointPort,
+};
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get))
+        .route("/{name}", web::get().to(get_one))
+        .route("/{name}", web::put().to(update));
+}
+
+#[derive(Deserialize)]
+pub struct EndpointsQuery {
+    watch: Option<bool>,
+}
+
+/// List or watch endpoints
+///
+/// # Arguments
+/// - `query`: Query parameters:
+///    - `watch` (bool, optional): If true, opens a watch stream of endpoints events.
+///
+/// # Returns
+/// - 200 list of endpoints or stream of endpoints events
+async fn get(state: State, query: web::Query<EndpointsQuery>) -> impl Responder {
+    let all = state.get_all_endpoints().await;
+    if query.watch.unwrap_or(false) {
+        let mut rx = state.endpoints_tx.subscribe();
+        let stream = async_stream::stream! {
+            for endpoints in all {
+                let event = EndpointsEvent {
+                    endpoints,
+                    event_type: EventType::Added
+                };
+                let json = serde_json::to_string(&event).unwrap();
+                yield Ok::<_, actix_web::Error>(Bytes::from(json + "\n"));
+            }
+            while let Ok(event) = rx.recv().await {
+                let json = serde_json::to_string(&event).unwrap();
+                yield Ok::<_, actix_web::Error>(Bytes::from(json + "\n"));
+            }
+        };
+
+        HttpResponse::Ok()
+            .content_type("application/json")
+            .streaming(stream)
+    } else {
+        HttpResponse::Ok().json(&all)
+    }
+}
+
+/// Get the endpoints of a service by name.
+///
+/// # Returns
+/// - 200: The endpoints
+/// - 404: Endpoints not found
+async fn get_one(state: State, path_string: web::Path<String>) -> impl Responder {
+    match state.get_endpoints(&path_string.into_inner()).await {
+        Ok(obj) => HttpResponse::Ok().json(obj),
+        Err(err) => err.to_http_response(),
+    }
+}
+
+/// Replace the endpoints of a service.
+///
+/// # Arguments
+/// - `body`: Service ports with their ready addresses.
+///
+/// # Returns
+/// - 204: Endpoints stored, an event is sent if they changed
+/// - 404: Service not found
+async fn update(
+    state: State,
+    path_string: web::Path<String>,
+    payload: web::Json<Vec<EndpointPort>>,
+) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.update_endpoints(&name, payload.into_inner()).await {
+        Ok(_) => HttpResponse::NoContent().finish(),
+        Err(err) => {
+            tracing::warn!(error=%err, %name, "Could not update endpoints");
+            err.to_http_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //!  UPDATE
+    //!  - test_update_endpoints
+    //!    watchers see the first write and changes, not repeats
+    //!  - test_update_endpoints_unknown_service
+
+    use crate::endpoints::helpers::collect_stream_events;
+    use crate::state::{ApiServerState, test_store::TestStore};
+
+    use super::*;
+    use actix_web::body::BoxBody;
+    use actix_web::dev::Service;
+    use actix_web::{
+        App,
+        http::StatusCode,
+        test::{TestRequest, call_service, init_service},
+    };
+    use shared::models::{
+        endpoints::EndpointAddress,
+        metadata::ObjectMetadata,
+        pod::Protocol,
+        service::{ServicePort, ServiceSpec},
+    };
+
+    async fn endpoints_service(
+        state: &State,
+    ) -> impl Service<
+        actix_http::Request,
+        Response = actix_web::dev::ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    > {
+        init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/endpoints", web::get().to(get))
+                .route("/endpoints/{name}", web::get().to(get_one))
+                .route("/endpoints/{name}", web::put().to(update)),
+        )
+        .await
+    }
+
+    fn ports(ips: &[&str]) -> Vec<EndpointPort> {
+        vec![EndpointPort {
+            name: None,
+            port: 80,
+            protocol: Protocol::Tcp,
+            addresses: ips
+                .iter()
+                .map(|ip| EndpointAddress {
+                    ip: ip.to_string(),
+                    port: 8080,
+                    pod_name: format!("pod-{}", ip),
+                    node_name: "node-a".to_string(),
+                })
+                .collect(),
+        }]
+    }
+
+    async fn add_service(state: &State, name: &str) {
+        let spec = ServiceSpec {
+            selector: None,
+            ports: vec![ServicePort {
+                name: None,
+                port: 80,
+                target_port: None,
+                node_port: None,
+                protocol: Protocol::Tcp,
+            }],
+        };
+        let metadata = ObjectMetadata {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        state.add_service(spec, metadata.into()).await.unwrap();
+    }
+
+    #[actix_web::test]
+    async fn test_update_endpoints() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = endpoints_service(&state).await;
+        add_service(&state, "web").await;
+
+        let req = TestRequest::get().uri("/endpoints?watch=true").to_request();
+        let resp = call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        for ips in [&["10.0.0.2"][..], &["10.0.0.2"], &["10.0.0.2", "10.0.0.3"]] {
+            let req = TestRequest::put()
+                .uri("/endpoints/web")
+                .set_json(ports(ips))
+                .to_request();
+            assert_eq!(
+                call_service(&app, req).await.status(),
+                StatusCode::NO_CONTENT
+            );
+        }
+
+        let mut events: Vec<EndpointsEvent> = Vec::new();
+        collect_stream_events(resp, &mut events, 2).await;
+        assert_eq!(events[0].event_type, EventType::Added);
+        assert_eq!(events[1].event_type, EventType::Modified);
+        assert_eq!(events[1].endpoints.addresses(80, Protocol::Tcp).len(), 2);
+        assert_eq!(events[1].endpoints.metadata.generation, 2);
+    }
+
+    #[actix_web::test]
+    async fn test_update_endpoints_unknown_service() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = endpoints_service(&state).await;
+
+        let req = TestRequest::put()
+            .uri("/endpoints/web")
+            .set_json(ports(&["10.0.0.2"]))
+            .to_request();
+        assert_eq!(
+            call_service(&app, req).await.status(),
+            StatusCode::NOT_FOUND
+        );
+
+        let req = TestRequest::get().uri("/endpoints/web").to_request();
+        assert_eq!(
+            call_service(&app, req).await.status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! Service
+//!
+//! ## Routes
+//! - `GET    /services`          — List or watch services
+//! - `POST   /services`          — Create a new service
+//! - `GET    /services/{name}`   — Get a service
+//! - `DELETE /services/{name}`   — Delete a service and its endpoints
+
+use crate::state::State;
+use actix_web::{
+    HttpResponse, Responder,
+    web::{self, Bytes},
+};
+use serde::Deserialize;
+use shared::api::{CreateResponse, EventType, ServiceEvent, ServiceManifest};
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(get))
+        .route("", web::post().to(create))
+        .route("/{name}", web::get().to(get_one))
+        .route("/{name}", web::delete().to(delete));
+}
+
+#[derive(Deserialize)]
+pub struct ServiceQuery {
+    watch: Option<bool>,
+}
+
+/// List or watch services
+///
+/// # Arguments
+/// - `query`: Query parameters:
+///    - `watch` (bool, optional): If true, opens a watch stream of service events.
+///
+/// # Returns
+/// - 200 list of services or stream of service events
+async fn get(state: State, query: web::Query<ServiceQuery>) -> impl Responder {
+    let services = state.get_services().await;
+    if query.watch.unwrap_or(false) {
+        let mut rx = state.service_tx.subscribe();
+        let stream = async_stream::stream! {
+            for service in services {
+                let event = ServiceEvent {
+                    service,
+                    event_type: EventType::Added
+                };
+                let json = serde_json::to_string(&event).unwrap();
+                yield Ok::<_, actix_web::Error>(Bytes::from(json + "\n"));
+            }
+            while let Ok(event) = rx.recv().await {
+                let json = serde_json::to_string(&event).unwrap();
+                yield Ok::<_, actix_web::Error>(Bytes::from(json + "\n"));
+            }
+        };
+
+        HttpResponse::Ok()
+            .content_type("application/json")
+            .streaming(stream)
+    } else {
+        HttpResponse::Ok().json(&services)
+    }
+}
+
+/// Get a service by name.
+///
+/// # Returns
+/// - 200: The service
+/// - 404: Service not found
+async fn get_one(state: State, path_string: web::Path<String>) -> impl Responder {
+    match state.get_service(&path_string.into_inner()).await {
+        Ok(obj) => HttpResponse::Ok().json(obj),
+        Err(err) => err.to_http_response(),
+    }
+}
+
+/// Create a new service.
+///
+/// # Arguments
+/// - `body`: Service manifest JSON.
+///
+/// # Returns
+/// - 201: Service created, unset node ports allocated
+/// - 400: Invalid manifest format or ports
+/// - 409: Repeat name or node port taken
+async fn create(state: State, payload: web::Json<ServiceManifest>) -> impl Responder {
+    let manifest = payload.into_inner();
+
+    if manifest.metadata.owner_reference.is_some() {
+        return HttpResponse::BadRequest().finish();
+    }
+
+    let name = manifest.metadata.name.clone();
+    match state
+        .add_service(manifest.spec, manifest.metadata.into())
+        .await
+    {
+        Ok(id) => {
+            tracing::info!(%name, "Service created");
+            let response = CreateResponse {
+                id,
+                status: "Accepted".into(),
+            };
+            HttpResponse::Created().json(response)
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not create service");
+            err.to_http_response()
+        }
+    }
+}
+
+/// Delete a service by name, its endpoints go with it.
+///
+/// # Returns
+/// - 204: Service deleted
+/// - 404: Service not found
+async fn delete(state: State, path_string: web::Path<String>) -> impl Responder {
+    let name = path_string.into_inner();
+    match state.delete_service(&name).await {
+        Ok(_) => {
+            tracing::info!(%name, "Service deleted");
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => {
+            tracing::warn!(error=%err, "Could not delete service");
+            err.to_http_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //!  CREATE
+    //!  - test_create_service
+    //!    node ports allocated, taken ones rejected
+    //!  - test_create_service_invalid_ports
+    //!
+    //!  DELETE
+    //!  - test_delete_service_removes_endpoints
+
+    use crate::state::{ApiServerState, test_store::TestStore};
+
+    use super::*;
+    use actix_web::body::BoxBody;
+    use actix_web::{
+        App,
+        http::StatusCode,
+        test::{TestRequest, call_service, init_service, read_body_json},
+    };
+    use shared::models::{
+        endpoints::EndpointPort,
+        metadata::ObjectMetadata,
+        pod::Protocol,
+        service::{NODE_PORT_RANGE, Service, ServicePort, ServiceSpec, TargetPort},
+    };
+
+    async fn service_service(
+        state: &State,
+    ) -> impl actix_web::dev::Service<
+        actix_http::Request,
+        Response = actix_web::dev::ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    > {
+        init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/services", web::get().to(get))
+                .route("/services", web::post().to(create))
+                .route("/services/{name}", web::get().to(get_one))
+                .route("/services/{name}", web::delete().to(delete)),
+        )
+        .await
+    }
+
+    fn port(name: Option<&str>, port: u16, node_port: Option<u16>) -> ServicePort {
+        ServicePort {
+            name: name.map(str::to_string),
+            port,
+            target_port: None,
+            node_port,
+            protocol: Protocol::Tcp,
+        }
+    }
+
+    fn manifest(name: &str, ports: Vec<ServicePort>) -> ServiceManifest {
+        ServiceManifest {
+            metadata: ObjectMetadata {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            spec: ServiceSpec {
+                selector: None,
+                ports,
+            },
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_service() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = service_service(&state).await;
+
+        let first = *NODE_PORT_RANGE.start();
+        for (name, ports, expected) in [
+            ("web", vec![port(None, 80, None)], StatusCode::CREATED),
+            ("web", vec![port(None, 80, None)], StatusCode::CONFLICT),
+            (
+                "taken",
+                vec![port(None, 80, Some(first))],
+                StatusCode::CONFLICT,
+            ),
+            (
+                "api",
+                vec![port(None, 80, Some(first + 1))],
+                StatusCode::CREATED,
+            ),
+            ("db", vec![port(None, 5432, None)], StatusCode::CREATED),
+        ] {
+            let req = TestRequest::post()
+                .uri("/services")
+                .set_json(manifest(name, ports))
+                .to_request();
+            assert_eq!(call_service(&app, req).await.status(), expected, "{}", name);
+        }
+
+        let req = TestRequest::get().uri("/services/db").to_request();
+        let service: Service = read_body_json(call_service(&app, req).await).await;
+        assert_eq!(service.spec.ports[0].node_port, Some(first + 2));
+    }
+
+    #[actix_web::test]
+    async fn test_create_service_invalid_ports() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = service_service(&state).await;
+
+        let mut zero_target = port(None, 80, None);
+        zero_target.target_port = Some(TargetPort::Number(0));
+        for ports in [
+            vec![],
+            vec![port(None, 0, None)],
+            vec![zero_target],
+            vec![port(None, 80, Some(8080))],
+            vec![port(None, 80, None), port(Some("b"), 81, None)],
+            vec![port(Some("a"), 80, None), port(Some("b"), 80, None)],
+        ] {
+            let req = TestRequest::post()
+                .uri("/services")
+                .set_json(manifest("web", ports.clone()))
+                .to_request();
+            let res = call_service(&app, req).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{:?}", ports);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_delete_service_removes_endpoints() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = service_service(&state).await;
+        let ports = vec![EndpointPort {
+            name: None,
+            port: 80,
+            protocol: Protocol::Tcp,
+            addresses: vec![],
+        }];
+        let res = state.update_endpoints("web", ports.clone()).await;
+        assert_eq!(
+            res.unwrap_err().to_http_response().status(),
+            StatusCode::NOT_FOUND
+        );
+
+        let req = TestRequest::post()
+            .uri("/services")
+            .set_json(manifest("web", vec![port(None, 80, None)]))
+            .to_request();
+        call_service(&app, req).await;
+        state.update_endpoints("web", ports).await.unwrap();
+        assert!(state.get_endpoints("web").await.is_ok());
+
+        for expected in [StatusCode::NO_CONTENT, StatusCode::NOT_FOUND] {
+            let req = TestRequest::delete().uri("/services/web").to_request();
+            assert_eq!(call_service(&app, req).await.status(), expected);
+        }
+        assert!(state.get_endpoints("web").await.is_err());
+    }
+}
@@ -0,0 +1,142 @@
+//! Tokens
+//!
+//! ## Routes
+//! - `POST   /tokens`   — Issue a signed token for a service account
+
+use std::time::Duration;
+
+use crate::auth::{MASTERS_GROUP, UserInfo};
+use crate::state::State;
+use actix_web::{HttpResponse, Responder, web};
+use shared::api::{TokenRequest, TokenResponse};
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::post().to(create));
+}
+
+/// Issue a token for a service account.
+///
+/// # Arguments
+/// - `body`: service account name, extra groups and optional lifetime
+///
+/// # Returns
+/// - 201: Signed token
+/// - 400: Empty or invalid service account name
+/// - 403: Caller is not in `system:masters`
+async fn create(
+    state: State,
+    user: Option<web::ReqData<UserInfo>>,
+    payload: web::Json<TokenRequest>,
+) -> impl Responder {
+    if user.is_some_and(|user| !user.in_group(MASTERS_GROUP)) {
+        return HttpResponse::Forbidden().body("Only masters can issue tokens");
+    }
+    let request = payload.into_inner();
+    if request.service_account.is_empty() || request.service_account.contains(':') {
+        return HttpResponse::BadRequest().body("Invalid service account name");
+    }
+
+    let account = UserInfo::service_account(&request.service_account, request.groups);
+    let ttl = request.expiration_seconds.map(Duration::from_secs);
+    let token = state.auth.issue(&account, ttl);
+    tracing::info!(name=%account.name, "Token issued");
+    HttpResponse::Created().json(TokenResponse { token })
+}
+
+#[cfg(test)]
+mod tests {
+    //!  CREATE
+    //!  - test_create_token
+    //!    the token authenticates its service account
+    //!  - test_create_token_requires_credentials
+    //!    401 without a valid token, 403 for non masters
+
+    use crate::auth::{Authenticator, SERVICE_ACCOUNTS_GROUP};
+    use crate::endpoints::Authentication;
+    use crate::state::{ApiServerState, test_store::TestStore};
+
+    use super::*;
+    use actix_web::{
+        App,
+        http::StatusCode,
+        test::{TestRequest, call_service, init_service, read_body_json},
+    };
+
+    fn request(name: &str) -> TokenRequest {
+        TokenRequest {
+            service_account: name.to_string(),
+            groups: vec!["ci".to_string()],
+            expiration_seconds: Some(600),
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_token() {
+        let state = ApiServerState::new_with_store(Box::new(TestStore::new())).await;
+        let app = init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/tokens", web::post().to(create)),
+        )
+        .await;
+
+        let req = TestRequest::post()
+            .uri("/tokens")
+            .set_json(request("deployer"))
+            .to_request();
+        let resp = call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let body: TokenResponse = read_body_json(resp).await;
+
+        let user = state.authenticate_token(&body.token).await.unwrap();
+        assert_eq!(user.name, "system:serviceaccount:deployer");
+        assert!(user.in_group("ci") && user.in_group(SERVICE_ACCOUNTS_GROUP));
+
+        let req = TestRequest::post()
+            .uri("/tokens")
+            .set_json(request("system:node:n1"))
+            .to_request();
+        assert_eq!(
+            call_service(&app, req).await.status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_create_token_requires_credentials() {
+        let admin = UserInfo {
+            name: "admin".to_string(),
+            groups: vec![MASTERS_GROUP.to_string()],
+        };
+        let dev = UserInfo {
+            name: "dev".to_string(),
+            groups: vec![],
+        };
+        let auth = Authenticator::with_tokens(&[("admin-token", admin), ("dev-token", dev)]);
+        let state = ApiServerState::new_with(Box::new(TestStore::new()), auth, None).await;
+        let app = init_service(
+            App::new()
+                .app_data(state.clone())
+                .route("/tokens", web::post().to(create))
+                .wrap(Authentication),
+        )
+        .await;
+
+        for (header, status) in [
+            (None, StatusCode::UNAUTHORIZED),
+            (Some("Bearer made-up"), StatusCode::UNAUTHORIZED),
+            (Some("Basic admin-token"), StatusCode::UNAUTHORIZED),
+            (Some("Bearer dev-token"), StatusCode::FORBIDDEN),
+            (Some("Bearer admin-token"), StatusCode::CREATED),
+        ] {
+            let mut req = TestRequest::post()
+                .uri("/tokens")
+                .set_json(request("deployer"));
+            if let Some(header) = header {
+                req = req.insert_header(("Authorization", header));
+            }
+            let resp = call_service(&app, req.to_request()).await;
+            assert_eq!(resp.status(), status, "{:?}", header);
+        }
+    }
+}
@@ -1,14 +1,16 @@
 //! cr8s-server entrypoint.
 //! Starts the Actix-web server and launches the scheduler and drift controller
 
-use actix_web::{App, HttpServer};
+use actix_web::{App, HttpServer, web};
 use tracing_subscriber::{self, EnvFilter};
 
+mod auth;
 mod controllers;
 mod endpoints;
 mod state;
 
-use state::ApiServerState;
+use auth::{Authenticator, ca::ClusterCa};
+use state::{ApiServerState, encryption::SecretCipher};
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -16,21 +18,40 @@ async fn main() -> std::io::Result<()> {
         .unwrap_or_else(|_| EnvFilter::new("actix_server=warn,actix_web=warn"));
     tracing_subscriber::fmt().with_env_filter(env_filter).init();
 
-    let state = ApiServerState::new().await;
+    let mut auth = Authenticator::from_env().map_err(std::io::Error::other)?;
+    // controllers call the apiserver as masters
+    shared::utils::set_bearer_token(Some(auth.add_loopback_token()));
+    let ca = ClusterCa::from_env().map_err(std::io::Error::other)?;
+    if let Some(ca) = &ca {
+        shared::utils::set_ca_certificate(Some(ca.cert_pem().as_bytes().to_vec()));
+    }
+    let tls = auth::tls::server_config(ca.as_ref()).map_err(std::io::Error::other)?;
+
+    let cipher = SecretCipher::from_env().map_err(std::io::Error::other)?;
+
+    let state = ApiServerState::new(auth, ca, cipher).await;
     let port = std::env::var("CR8S_SERVER_PORT")
         .ok()
         .and_then(|s| s.parse().ok())
         .unwrap_or(7620);
 
-    controllers::run(format!("http://localhost:{}", port));
+    let scheme = if tls.is_some() { "https" } else { "http" };
+    let scheduler = web::Data::from(controllers::run(format!("{}://localhost:{}", scheme, port)));
 
     let server = HttpServer::new(move || {
         App::new()
             .app_data(state.clone())
+            .app_data(scheduler.clone())
             .configure(endpoints::config)
+            .wrap(endpoints::Authorization)
+            .wrap(endpoints::Authentication)
             .wrap(endpoints::Logging)
     })
-    .bind(("0.0.0.0", port))?;
+    .on_connect(auth::tls::on_connect);
+    let server = match tls {
+        Some(config) => server.bind_rustls_0_23(("0.0.0.0", port), config)?,
+        None => server.bind(("0.0.0.0", port))?,
+    };
 
     server.run().await
 }
@@ -43,7 +43,7 @@ impl CacheManager {
     // --- Node ops ---
     //
     // - Check name and addr duplicates
-    // - Add to cache
+    // - Add to and remove from cache
 
     pub fn node_name_exists(&self, name: &str) -> bool {
         self.node_names.contains(name)
@@ -59,6 +59,12 @@ impl CacheManager {
         self.node_names.insert(name.to_string());
     }
 
+    /// Removes a node name and address from the cache.
+    pub fn remove_node(&self, name: &str, addr: &str) {
+        self.node_addrs.remove(addr);
+        self.node_names.remove(name);
+    }
+
     // --- RS ops ---
     //
     // - Check name duplicates
@@ -0,0 +1,174 @@
+//! Encryption at rest for secrets.
+//!
+//! Secrets are sealed with AES-256-GCM before they reach etcd. The key is read
+//! from `CR8S_ENCRYPTION_KEY`, 32 bytes encoded as base64. The etcd key of the
+//! object is authenticated along with it, so a sealed value can't be moved to
+//! another secret. Values written before a key was configured are still read.
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use ring::{
+    aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey},
+    rand::{SecureRandom, SystemRandom},
+};
+
+use super::errors::StoreError;
+
+/// Marks sealed values, anything else is stored as plain JSON.
+const PREFIX: &str = "cr8s:enc:aesgcm:v1:";
+
+pub struct SecretCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl SecretCipher {
+    /// Builds a cipher from a 32 byte key.
+    pub fn new(key: &[u8]) -> Result<Self, String> {
+        let key = UnboundKey::new(&AES_256_GCM, key)
+            .map_err(|_| "Encryption key must be 32 bytes".to_string())?;
+        Ok(Self {
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Reads the base64 key from `CR8S_ENCRYPTION_KEY`, secrets stay in plain text without one.
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let Ok(encoded) = std::env::var("CR8S_ENCRYPTION_KEY") else {
+            tracing::warn!("CR8S_ENCRYPTION_KEY not set, secrets are stored unencrypted");
+            return Ok(None);
+        };
+        Self::from_base64(&encoded).map(Some)
+    }
+
+    fn from_base64(encoded: &str) -> Result<Self, String> {
+        let key = STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| format!("CR8S_ENCRYPTION_KEY is not valid base64: {}", e))?;
+        Self::new(&key).map_err(|e| format!("Invalid CR8S_ENCRYPTION_KEY: {}", e))
+    }
+
+    /// Seals a value stored under `key`.
+    pub fn encrypt(&self, key: &str, plaintext: &[u8]) -> Result<String, StoreError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| StoreError::UnexpectedError("Failed to generate nonce".to_string()))?;
+
+        let mut sealed = plaintext.to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(key.as_bytes()),
+                &mut sealed,
+            )
+            .map_err(|_| StoreError::UnexpectedError("Failed to encrypt".to_string()))?;
+
+        let mut out = nonce.to_vec();
+        out.extend(sealed);
+        Ok(format!("{}{}", PREFIX, STANDARD.encode(out)))
+    }
+
+    /// Opens a value sealed under `key`, plain values are returned as they are.
+    pub fn decrypt(&self, key: &str, value: &str) -> Result<Vec<u8>, StoreError> {
+        let Some(encoded) = value.strip_prefix(PREFIX) else {
+            return Ok(value.as_bytes().to_vec());
+        };
+        let failed = || StoreError::UnexpectedError(format!("Failed to decrypt {}", key));
+
+        let data = STANDARD.decode(encoded).map_err(|_| failed())?;
+        if data.len() < NONCE_LEN {
+            return Err(failed());
+        }
+        let (nonce, sealed) = data.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| failed())?;
+        let mut sealed = sealed.to_vec();
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::from(key.as_bytes()), &mut sealed)
+            .map_err(|_| failed())?;
+        Ok(plaintext.to_vec())
+    }
+
+    pub fn is_encrypted(value: &str) -> bool {
+        value.starts_with(PREFIX)
+    }
+}
+
+/// Opens a value read from etcd, sealed values need a cipher.
+pub fn open(cipher: Option<&SecretCipher>, key: &str, value: &str) -> Result<Vec<u8>, StoreError> {
+    match cipher {
+        Some(cipher) => cipher.decrypt(key, value),
+        None if SecretCipher::is_encrypted(value) => Err(StoreError::UnexpectedError(format!(
+            "{} is encrypted, set CR8S_ENCRYPTION_KEY",
+            key
+        ))),
+        None => Ok(value.as_bytes().to_vec()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    //! - test_encrypt_roundtrip
+    //!   sealed value hides the plaintext and opens back
+    //! - test_decrypt_wrong_key
+    //!   another key or another object key can't open it
+    //! - test_decrypt_plain_value
+    //!   values written without a key are read as they are
+    //! - test_open_without_key
+    //!   sealed values ask for the key instead of failing to parse
+    //! - test_from_base64
+    //!   a bad key is an error, not a panic
+
+    use super::*;
+
+    const KEY: &str = "/cr8s/secrets/db";
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let cipher = SecretCipher::new(&[7u8; 32]).unwrap();
+
+        let sealed = cipher.encrypt(KEY, b"hunter2").unwrap();
+        assert!(SecretCipher::is_encrypted(&sealed));
+        assert!(!sealed.contains("hunter2"));
+        assert_ne!(sealed, cipher.encrypt(KEY, b"hunter2").unwrap());
+
+        assert_eq!(cipher.decrypt(KEY, &sealed).unwrap(), b"hunter2");
+    }
+
+    #[test]
+    fn test_decrypt_wrong_key() {
+        let cipher = SecretCipher::new(&[7u8; 32]).unwrap();
+        let other = SecretCipher::new(&[8u8; 32]).unwrap();
+
+        let sealed = cipher.encrypt(KEY, b"hunter2").unwrap();
+        assert!(other.decrypt(KEY, &sealed).is_err());
+        assert!(cipher.decrypt("/cr8s/secrets/other", &sealed).is_err());
+        assert!(SecretCipher::new(&[7u8; 16]).is_err());
+    }
+
+    #[test]
+    fn test_decrypt_plain_value() {
+        let cipher = SecretCipher::new(&[7u8; 32]).unwrap();
+        assert_eq!(cipher.decrypt(KEY, "{}").unwrap(), b"{}");
+    }
+
+    #[test]
+    fn test_open_without_key() {
+        let cipher = SecretCipher::new(&[7u8; 32]).unwrap();
+        let sealed = cipher.encrypt(KEY, b"{}").unwrap();
+
+        assert_eq!(open(Some(&cipher), KEY, &sealed).unwrap(), b"{}");
+        assert_eq!(open(None, KEY, "{}").unwrap(), b"{}");
+        let err = open(None, KEY, &sealed).unwrap_err().to_string();
+        assert!(err.contains("set CR8S_ENCRYPTION_KEY"), "{}", err);
+    }
+
+    #[test]
+    fn test_from_base64() {
+        assert!(SecretCipher::from_base64(&STANDARD.encode([7u8; 32])).is_ok());
+        assert!(SecretCipher::from_base64("not base64!").is_err());
+        assert!(SecretCipher::from_base64(&STANDARD.encode([7u8; 16])).is_err());
+    }
+}
@@ -12,6 +12,8 @@ pub enum StoreError {
     NotFound(String),
     /// Referenced resource is invalid or missing
     InvalidReference(String),
+    /// Refused to protect availability, can be retried later
+    TooManyRequests(String),
     /// An unexpected error occurred in logic or state not covered by other cases.
     UnexpectedError(String),
     /// Error from an external storage backend
@@ -26,6 +28,7 @@ impl StoreError {
             StoreError::Conflict(msg) => Http::Conflict().body(msg.clone()),
             StoreError::NotFound(msg) => Http::NotFound().body(msg.clone()),
             StoreError::InvalidReference(msg) => Http::UnprocessableEntity().body(msg.clone()),
+            StoreError::TooManyRequests(msg) => Http::TooManyRequests().body(msg.clone()),
             StoreError::UnexpectedError(_) | StoreError::BackendError(_) => {
                 Http::InternalServerError().body("Unexpected error")
             }
@@ -40,6 +43,7 @@ impl fmt::Display for StoreError {
             StoreError::Conflict(msg) => write!(f, "Conflict: {}", msg),
             StoreError::NotFound(msg) => write!(f, "Not found error: {}", msg),
             StoreError::InvalidReference(msg) => write!(f, "Invalid reference error: {}", msg),
+            StoreError::TooManyRequests(msg) => write!(f, "Too many requests: {}", msg),
             StoreError::UnexpectedError(msg) => write!(f, "Unexpected error: {}", msg),
             StoreError::BackendError(msg) => write!(f, "Backend error: {}", msg),
         }
@@ -5,6 +5,7 @@
 //! Event broadcasting mechanism for notifications on watches
 
 mod cache;
+pub mod encryption;
 mod errors;
 mod store;
 #[cfg(test)]
@@ -14,20 +15,58 @@ use actix_web::web;
 use chrono::Utc;
 use futures::future::join_all;
 use std::collections::{HashMap, HashSet};
-use tokio::sync::broadcast;
+use std::sync::{
+    Arc, RwLock,
+    atomic::{AtomicU64, Ordering},
+};
+use tokio::sync::{Mutex, broadcast};
 use uuid::Uuid;
 
 use shared::{
-    api::{EventType, NodeEvent, PodEvent, ReplicaSetEvent},
+    api::{
+        EndpointsEvent, EventType, IngressEvent, NodeEvent, NodePatch, PersistentVolumeClaimEvent,
+        PodConditionUpdate, PodEvent, PodGroupEvent, ReplicaSetEvent, ServiceEvent,
+    },
     models::{
+        configmap::{ConfigMap, ConfigMapSpec, MAX_DATA_SIZE},
+        endpoints::{EndpointPort, Endpoints},
+        ingress::{Ingress, IngressSpec},
         metadata::Metadata,
-        node::Node,
-        pod::{ContainerSpec, Pod, PodSpec, PodStatus},
+        node::{Node, NodeStatus, Taint},
+        persistentvolume::{
+            PersistentVolume, PersistentVolumeSpec, PersistentVolumeStatus, VolumePhase,
+            parse_storage,
+        },
+        persistentvolumeclaim::{
+            ClaimPhase, PersistentVolumeClaim, PersistentVolumeClaimSpec,
+            PersistentVolumeClaimStatus, provisioned_volume_name,
+        },
+        pod::{
+            ContainerSpec, EnvFromRef, EnvVarSource, Pod, PodCondition, PodConditionType, PodPhase,
+            PodSpec, PodStatus, Volume, VolumeSource,
+        },
+        poddisruptionbudget::{
+            PodDisruptionBudget, PodDisruptionBudgetSpec, PodDisruptionBudgetStatus,
+        },
+        podgroup::{POD_GROUP_LABEL, PodGroup, PodGroupSpec},
+        priorityclass::{PriorityClass, PriorityClassSpec},
+        rbac::{
+            ClusterRole, ClusterRoleBinding, ClusterRoleBindingSpec, ClusterRoleSpec, PolicyRule,
+            Role, RoleBinding, RoleBindingSpec, RoleKind, RoleSpec, Subject,
+        },
         replicaset::{ReplicaSet, ReplicaSetSpec, ReplicaSetStatus},
+        secret::{Secret, SecretSpec},
+        service::{NODE_PORT_RANGE, Service, ServicePort, ServiceSpec, TargetPort},
     },
 };
 
+use crate::auth::{
+    Authenticator, MASTERS_GROUP, UserInfo,
+    ca::ClusterCa,
+    rbac::{self, Attributes, Policy},
+};
 use cache::CacheManager;
+use encryption::SecretCipher;
 use errors::StoreError;
 use store::{EtcdStore, Store};
 
@@ -40,42 +79,139 @@ pub struct ApiServerState {
     pub pod_tx: broadcast::Sender<PodEvent>,
     pub node_tx: broadcast::Sender<NodeEvent>,
     pub replicaset_tx: broadcast::Sender<ReplicaSetEvent>,
+    pub podgroup_tx: broadcast::Sender<PodGroupEvent>,
+    pub claim_tx: broadcast::Sender<PersistentVolumeClaimEvent>,
+    pub service_tx: broadcast::Sender<ServiceEvent>,
+    pub endpoints_tx: broadcast::Sender<EndpointsEvent>,
+    pub ingress_tx: broadcast::Sender<IngressEvent>,
     /// In-memory fast-access cache for node/pod metadata.
     pub cache: CacheManager,
+    /// Serializes evictions so concurrent requests cannot overspend a budget
+    eviction_lock: Mutex<()>,
+    /// Serializes service creation so two services cannot take the same node port
+    node_port_lock: Mutex<()>,
+    /// Identifies callers from their credentials
+    pub auth: Authenticator,
+    /// Roles and bindings requests are authorized against, reloaded after they change
+    policy: RwLock<Option<Arc<Policy>>>,
+    /// Bumped on every change so a policy loaded meanwhile is not cached
+    policy_version: AtomicU64,
+    /// Issues node serving certificates, nodes serve plain HTTP without it
+    pub ca: Option<ClusterCa>,
+    /// Client for requests to node agents, trusting the cluster CA
+    pub node_client: reqwest::Client,
 }
 
 impl ApiServerState {
     //! - add_pod(spec, metadata): Validate and add a new pod to the store and cache, then broadcast an event
     //! - delete_pod(name): Remove a pod the store and cache, then broadcast an event
+    //! - evict_pod(name): Delete a pod unless it would violate a disruption budget
     //! - assign_pod(name, node_name): Assign an unassigned pod to a  ode, update store and cache, broadcast event
+    //!   Claims the pod mounts are bound to volumes on the node first
     //! - update_pod_status(id, status, cont_status): Update the status and container statuses of a pod
+    //! - update_pod_condition(name, update): Record a control plane condition on a pod
     //! - get_pods(query): List pods optionally filtered by node name
     //!
     //! - add_replicaset(sepc, metadata)
     //! - get_replicasets()
     //!
+    //! - add_priorityclass(spec, metadata): Add a class, at most one can be the global default
+    //! - get_priorityclasses()
+    //! - delete_priorityclass(name)
+    //!
+    //! - add_poddisruptionbudget(spec, metadata)
+    //! - get_poddisruptionbudgets(): List budgets with a refreshed status
+    //! - delete_poddisruptionbudget(name)
+    //!
+    //! - add_configmap(spec, metadata)
+    //! - get_configmaps() / get_configmap(name)
+    //! - update_configmap(name, spec): Replace the data, nodes pick it up on their next sync
+    //! - delete_configmap(name)
+    //!
+    //! - add_secret(spec, metadata), get_secrets(), get_secret(name), update_secret(name, spec),
+    //!   delete_secret(name): Same as config maps, the store encrypts them at rest
+    //!
+    //! - add_persistentvolume(spec, metadata): Add a volume prepared on a node
+    //! - get_persistentvolumes(node_name) / get_persistentvolume(name)
+    //! - delete_persistentvolume(name): Remove a volume no claim is bound to
+    //!
+    //! - add_persistentvolumeclaim(spec, metadata): Add a claim, binding it when it names a volume
+    //! - get_persistentvolumeclaims() / get_persistentvolumeclaim(name)
+    //! - delete_persistentvolumeclaim(name): Remove an unused claim and release its volume
+    //!
+    //! - add_service(spec, metadata): Add a service, allocating its missing node ports
+    //! - get_services() / get_service(name)
+    //! - delete_service(name): Remove a service and its endpoints
+    //!
+    //! - get_all_endpoints() / get_endpoints(name)
+    //! - update_endpoints(name, ports): Replace the ready backends of a service
+    //!
+    //! - add_ingress(spec, metadata), get_ingresses(), get_ingress(name)
+    //! - update_ingress(name, spec): Replace the routes, gateways reload them
+    //! - delete_ingress(name)
+    //!
+    //! - add_role(spec, metadata), get_roles(), delete_role(name), and the same for
+    //!   cluster roles, role bindings and cluster role bindings
+    //! - authorize(user, attributes): Check an action against the roles bound to the user
+    //!
+    //! - add_podgroup(spec, metadata): Add a pod group, then broadcast an event
+    //! - get_podgroups()
+    //! - delete_podgroup(name): Remove a pod group, then broadcast an event
+    //!
     //! - add_node(node): Add a new node to the store and cache, then broadcast an event
     //! - get_nodes(): Retrieve all Nodes from the store
     //! - get_node(name): Get a specific Node by name from the store
     //! - update_node_heartbeat(node_name): Update the heartbeat timestamp of a node in the store
+    //! - update_node_spec(node_name, patch): Cordon, uncordon or retaint a node, then broadcast an event
 
     /// Construc ts a new instance with a custom store implementation.
 
-    pub async fn new() -> State {
-        Self::new_with_store(Box::new(EtcdStore::new().await)).await
+    pub async fn new(
+        auth: Authenticator,
+        ca: Option<ClusterCa>,
+        cipher: Option<SecretCipher>,
+    ) -> State {
+        Self::new_with(Box::new(EtcdStore::new(cipher).await), auth, ca).await
     }
 
+    #[cfg(test)]
     pub async fn new_with_store(store: Box<dyn Store + Send + Sync>) -> State {
+        Self::new_with(store, Authenticator::disabled(), None).await
+    }
+
+    pub async fn new_with(
+        store: Box<dyn Store + Send + Sync>,
+        auth: Authenticator,
+        ca: Option<ClusterCa>,
+    ) -> State {
+        let node_client = ca.as_ref().map(ClusterCa::client).unwrap_or_default();
         let (pod_tx, _) = broadcast::channel(10);
         let (node_tx, _) = broadcast::channel(10);
         let (replicaset_tx, _) = broadcast::channel(10);
+        let (podgroup_tx, _) = broadcast::channel(10);
+        let (claim_tx, _) = broadcast::channel(10);
+        let (service_tx, _) = broadcast::channel(10);
+        let (endpoints_tx, _) = broadcast::channel(10);
+        let (ingress_tx, _) = broadcast::channel(10);
         let cache = CacheManager::new();
         web::Data::new(Self {
             store,
             pod_tx,
             node_tx,
             replicaset_tx,
+            podgroup_tx,
+            claim_tx,
+            service_tx,
+            endpoints_tx,
+            ingress_tx,
             cache,
+            eviction_lock: Mutex::new(()),
+            node_port_lock: Mutex::new(()),
+            auth,
+            policy: RwLock::new(None),
+            policy_version: AtomicU64::new(0),
+            ca,
+            node_client,
         })
     }
 
@@ -84,7 +220,15 @@ impl ApiServerState {
         spec: ReplicaSetSpec,
         metadata: Metadata,
     ) -> Result<Uuid, StoreError> {
-        validate_container_list(&spec.template.spec.containers)?;
+        validate_container_list(
+            &spec.template.spec.containers,
+            &spec.template.spec.init_containers,
+        )?;
+        validate_volumes(
+            &spec.template.spec.volumes,
+            &spec.template.spec.containers,
+            &spec.template.spec.init_containers,
+        )?;
 
         // save object and metadata in store and cache
         let rs = ReplicaSet {
@@ -110,30 +254,1153 @@ impl ApiServerState {
         self.store.list_replicasets().await.unwrap_or_default()
     }
 
+    /// Adds a new priority class, rejecting a second global default.
+    pub async fn add_priorityclass(
+        &self,
+        spec: PriorityClassSpec,
+        metadata: Metadata,
+    ) -> Result<Uuid, StoreError> {
+        if self
+            .store
+            .get_priorityclass(&metadata.name)
+            .await?
+            .is_some()
+        {
+            return Err(StoreError::Conflict(format!(
+                "Duplicate priority class name: {}",
+                metadata.name
+            )));
+        }
+        if spec.global_default && self.get_default_priorityclass().await?.is_some() {
+            return Err(StoreError::Conflict(
+                "A global default priority class already exists".to_string(),
+            ));
+        }
+
+        let pc = PriorityClass { metadata, spec };
+        self.store.put_priorityclass(&pc.metadata.name, &pc).await?;
+        Ok(pc.metadata.id)
+    }
+
+    /// Retrieves all priority classes.
+    pub async fn get_priorityclasses(&self) -> Vec<PriorityClass> {
+        self.store.list_priorityclasses().await.unwrap_or_default()
+    }
+
+    /// Deletes a priority class, pods keep their already resolved priority.
+    pub async fn delete_priorityclass(&self, name: &str) -> Result<(), StoreError> {
+        self.store
+            .get_priorityclass(name)
+            .await?
+            .ok_or_else(|| StoreError::NotFound("Priority class not found".to_string()))?;
+        self.store.delete_priorityclass(name).await
+    }
+
+    /// Adds a new disruption budget.
+    pub async fn add_poddisruptionbudget(
+        &self,
+        spec: PodDisruptionBudgetSpec,
+        metadata: Metadata,
+    ) -> Result<Uuid, StoreError> {
+        if spec.min_available.is_some() == spec.max_unavailable.is_some() {
+            return Err(StoreError::WrongFormat(
+                "Exactly one of minAvailable and maxUnavailable must be set".to_string(),
+            ));
+        }
+        if self
+            .store
+            .get_poddisruptionbudget(&metadata.name)
+            .await?
+            .is_some()
+        {
+            return Err(StoreError::Conflict(format!(
+                "Duplicate disruption budget name: {}",
+                metadata.name
+            )));
+        }
+
+        let mut pdb = PodDisruptionBudget {
+            metadata,
+            spec,
+            status: Default::default(),
+        };
+        pdb.status = self.disruption_status(&pdb.spec).await;
+        self.store
+            .put_poddisruptionbudget(&pdb.metadata.name, &pdb)
+            .await?;
+        Ok(pdb.metadata.id)
+    }
+
+    /// Retrieves all disruption budgets, refreshing their status.
+    pub async fn get_poddisruptionbudgets(&self) -> Vec<PodDisruptionBudget> {
+        let mut pdbs = self
+            .store
+            .list_poddisruptionbudgets()
+            .await
+            .unwrap_or_default();
+        for pdb in pdbs.iter_mut() {
+            pdb.status = self.disruption_status(&pdb.spec).await;
+            if let Err(err) = self
+                .store
+                .put_poddisruptionbudget(&pdb.metadata.name, pdb)
+                .await
+            {
+                tracing::warn!(error=%err, "Could not persist disruption budget status");
+            }
+        }
+        pdbs
+    }
+
+    /// Deletes a disruption budget.
+    pub async fn delete_poddisruptionbudget(&self, name: &str) -> Result<(), StoreError> {
+        self.store
+            .get_poddisruptionbudget(name)
+            .await?
+            .ok_or_else(|| StoreError::NotFound("Disruption budget not found".to_string()))?;
+        self.store.delete_poddisruptionbudget(name).await
+    }
+
+    /// Counts matching and running pods for a budget.
+    async fn disruption_status(&self, spec: &PodDisruptionBudgetSpec) -> PodDisruptionBudgetStatus {
+        let pods = self.get_pods(&None, &spec.selector.match_labels).await;
+        let healthy = pods
+            .iter()
+            .filter(|p| {
+                matches!(p.status.phase, PodPhase::Running)
+                    && p.metadata.deletion_timestamp.is_none()
+            })
+            .count();
+        spec.status(pods.len() as u16, healthy as u16)
+    }
+
+    /// Adds a new pod group and emits a PodGroupEvent.
+    pub async fn add_podgroup(
+        &self,
+        spec: PodGroupSpec,
+        metadata: Metadata,
+    ) -> Result<Uuid, StoreError> {
+        if spec.min_member < 1 {
+            return Err(StoreError::WrongFormat(
+                "Pod group minMember must be at least 1".to_string(),
+            ));
+        }
+        if self.store.get_podgroup(&metadata.name).await?.is_some() {
+            return Err(StoreError::Conflict(format!(
+                "Duplicate pod group name: {}",
+                metadata.name
+            )));
+        }
+
+        let group = PodGroup { metadata, spec };
+        self.store
+            .put_podgroup(&group.metadata.name, &group)
+            .await?;
+
+        let event = PodGroupEvent {
+            event_type: EventType::Added,
+            podgroup: group.clone(),
+        };
+        let _ = self.podgroup_tx.send(event);
+        Ok(group.metadata.id)
+    }
+
+    /// Retrieves all pod groups.
+    pub async fn get_podgroups(&self) -> Vec<PodGroup> {
+        self.store.list_podgroups().await.unwrap_or_default()
+    }
+
+    /// Deletes a pod group, member pods are left untouched.
+    pub async fn delete_podgroup(&self, name: &str) -> Result<(), StoreError> {
+        let group = self
+            .store
+            .get_podgroup(name)
+            .await?
+            .ok_or_else(|| StoreError::NotFound("Pod group not found".to_string()))?;
+        self.store.delete_podgroup(name).await?;
+
+        let event = PodGroupEvent {
+            event_type: EventType::Deleted,
+            podgroup: group,
+        };
+        let _ = self.podgroup_tx.send(event);
+        Ok(())
+    }
+
+    /// Adds a new config map.
+    pub async fn add_configmap(
+        &self,
+        spec: ConfigMapSpec,
+        metadata: Metadata,
+    ) -> Result<Uuid, StoreError> {
+        validate_data(&spec.data)?;
+        if self.store.get_configmap(&metadata.name).await?.is_some() {
+            return Err(StoreError::Conflict(format!(
+                "Duplicate config map name: {}",
+                metadata.name
+            )));
+        }
+
+        let cm = ConfigMap { metadata, spec };
+        self.store.put_configmap(&cm.metadata.name, &cm).await?;
+        Ok(cm.metadata.id)
+    }
+
+    /// Retrieves all config maps.
+    pub async fn get_configmaps(&self) -> Vec<ConfigMap> {
+        self.store.list_configmaps().await.unwrap_or_default()
+    }
+
+    pub async fn get_configmap(&self, name: &str) -> Result<ConfigMap, StoreError> {
+        self.store
+            .get_configmap(name)
+            .await?
+            .ok_or_else(|| StoreError::NotFound("Config map not found".to_string()))
+    }
+
+    /// Replaces the data of a config map, running pods see it on the node's next sync.
+    pub async fn update_configmap(
+        &self,
+        name: &str,
+        spec: ConfigMapSpec,
+    ) -> Result<(), StoreError> {
+        validate_data(&spec.data)?;
+        let mut cm = self.get_configmap(name).await?;
+        cm.spec = spec;
+        cm.metadata.generation += 1;
+        cm.metadata.modified_at = Utc::now();
+        self.store.put_configmap(name, &cm).await
+    }
+
+    /// Deletes a config map, containers already started keep their environment.
+    pub async fn delete_configmap(&self, name: &str) -> Result<(), StoreError> {
+        self.get_configmap(name).await?;
+        self.store.delete_configmap(name).await
+    }
+
+    /// Adds a new secret.
+    pub async fn add_secret(
+        &self,
+        spec: SecretSpec,
+        metadata: Metadata,
+    ) -> Result<Uuid, StoreError> {
+        validate_data(&spec.data)?;
+        if self.store.get_secret(&metadata.name).await?.is_some() {
+            return Err(StoreError::Conflict(format!(
+                "Duplicate secret name: {}",
+                metadata.name
+            )));
+        }
+
+        let secret = Secret { metadata, spec };
+        self.store
+            .put_secret(&secret.metadata.name, &secret)
+            .await?;
+        Ok(secret.metadata.id)
+    }
+
+    /// Retrieves all secrets.
+    pub async fn get_secrets(&self) -> Vec<Secret> {
+        self.store.list_secrets().await.unwrap_or_default()
+    }
+
+    pub async fn get_secret(&self, name: &str) -> Result<Secret, StoreError> {
+        self.store
+            .get_secret(name)
+            .await?
+            .ok_or_else(|| StoreError::NotFound("Secret not found".to_string()))
+    }
+
+    /// Replaces the data of a secret, running pods see it on the node's next sync.
+    pub async fn update_secret(&self, name: &str, spec: SecretSpec) -> Result<(), StoreError> {
+        validate_data(&spec.data)?;
+        let mut secret = self.get_secret(name).await?;
+        secret.spec = spec;
+        secret.metadata.generation += 1;
+        secret.metadata.modified_at = Utc::now();
+        self.store.put_secret(name, &secret).await
+    }
+
+    /// Deletes a secret, containers already started keep their environment.
+    pub async fn delete_secret(&self, name: &str) -> Result<(), StoreError> {
+        self.get_secret(name).await?;
+        self.store.delete_secret(name).await
+    }
+
+    /// Adds a volume an admin prepared on a node, free for a claim naming it.
+    pub async fn add_persistentvolume(
+        &self,
+        mut spec: PersistentVolumeSpec,
+        metadata: Metadata,
+    ) -> Result<Uuid, StoreError> {
+        validate_name("persistent volume", &metadata.name)?;
+        validate_storage(&spec.capacity)?;
+        if spec.node_name.is_empty() {
+            return Err(StoreError::WrongFormat(
+                "Persistent volume needs a nodeName".to_string(),
+            ));
+        }
+        if spec.path.as_ref().is_some_and(|p| !p.starts_with('/')) {
+            return Err(StoreError::WrongFormat(
+                "Persistent volume path must be absolute".to_string(),
+            ));
+        }
+        if self
+            .store
+            .get_persistentvolume(&metadata.name)
+            .await?
+            .is_some()
+        {
+            return Err(StoreError::Conflict(format!(
+                "Duplicate persistent volume name: {}",
+                metadata.name
+            )));
+        }
+
+        // claims bind themselves
+        spec.claim_ref = None;
+        let pv = PersistentVolume {
+            metadata,
+            spec,
+            status: PersistentVolumeStatus::default(),
+        };
+        self.store
+            .put_persistentvolume(&pv.metadata.name, &pv)
+            .await?;
+        Ok(pv.metadata.id)
+    }
+
+    /// Retrieves all persistent volumes, optionally only the ones on a node.
+    pub async fn get_persistentvolumes(&self, node_name: &Option<String>) -> Vec<PersistentVolume> {
+        let volumes = self
+            .store
+            .list_persistentvolumes()
+            .await
+            .unwrap_or_default();
+        match node_name {
+            Some(node) => volumes
+                .into_iter()
+                .filter(|pv| &pv.spec.node_name == node)
+                .collect(),
+            None => volumes,
+        }
+    }
+
+    pub async fn get_persistentvolume(&self, name: &str) -> Result<PersistentVolume, StoreError> {
+        self.store
+            .get_persistentvolume(name)
+            .await?
+            .ok_or_else(|| StoreError::NotFound("Persistent volume not found".to_string()))
+    }
+
+    /// Deletes a persistent volume, its data is left on the node.
+    pub async fn delete_persistentvolume(&self, name: &str) -> Result<(), StoreError> {
+        let pv = self.get_persistentvolume(name).await?;
+        if pv.status.phase == VolumePhase::Bound {
+            return Err(StoreError::Conflict(format!(
+                "Persistent volume {} is bound to claim {}",
+                name,
+                pv.spec.claim_ref.unwrap_or_default()
+            )));
+        }
+        self.store.delete_persistentvolume(name).await
+    }
+
+    /// Adds a claim and emits an event.
+    ///
+    /// A claim naming a volume is bound to it right away, the others wait for
+    /// the first pod using them to be assigned a node.
+    pub async fn add_persistentvolumeclaim(
+        &self,
+        spec: PersistentVolumeClaimSpec,
+        metadata: Metadata,
+    ) -> Result<Uuid, StoreError> {
+        validate_name("persistent volume claim", &metadata.name)?;
+        let requested = validate_storage(&spec.storage)?;
+        if self
+            .store
+            .get_persistentvolumeclaim(&metadata.name)
+            .await?
+            .is_some()
+        {
+            return Err(StoreError::Conflict(format!(
+                "Duplicate persistent volume claim name: {}",
+                metadata.name
+            )));
+        }
+
+        let mut claim = PersistentVolumeClaim {
+            metadata,
+            spec,
+            status: PersistentVolumeClaimStatus::default(),
+        };
+        if let Some(volume) = claim.spec.volume_name.clone() {
+            let mut pv = self
+                .store
+                .get_persistentvolume(&volume)
+                .await?
+                .ok_or_else(|| {
+                    StoreError::InvalidReference(format!(
+                        "No persistent volume exists with name={}",
+                        volume
+                    ))
+                })?;
+            if pv.status.phase != VolumePhase::Available {
+                return Err(StoreError::Conflict(format!(
+                    "Persistent volume {} is not available",
+                    volume
+                )));
+            }
+            if parse_storage(&pv.spec.capacity).unwrap_or_default() < requested {
+                return Err(StoreError::WrongFormat(format!(
+                    "Persistent volume {} is smaller than the claim",
+                    volume
+                )));
+            }
+            self.bind_claim(&mut claim, &mut pv).await?;
+        }
+        self.store
+            .put_persistentvolumeclaim(&claim.metadata.name, &claim)
+            .await?;
+
+        let event = PersistentVolumeClaimEvent {
+            event_type: EventType::Added,
+            claim: claim.clone(),
+        };
+        let _ = self.claim_tx.send(event);
+        Ok(claim.metadata.id)
+    }
+
+    /// Retrieves all persistent volume claims.
+    pub async fn get_persistentvolumeclaims(&self) -> Vec<PersistentVolumeClaim> {
+        self.store
+            .list_persistentvolumeclaims()
+            .await
+            .unwrap_or_default()
+    }
+
+    pub async fn get_persistentvolumeclaim(
+        &self,
+        name: &str,
+    ) -> Result<PersistentVolumeClaim, StoreError> {
+        self.store
+            .get_persistentvolumeclaim(name)
+            .await?
+            .ok_or_else(|| StoreError::NotFound("Persistent volume claim not found".to_string()))
+    }
+
+    /// Deletes a claim no pod mounts and releases its volume.
+    ///
+    /// Released volumes with the `Delete` policy are removed by their node,
+    /// the others keep their data until an admin deletes them.
+    pub async fn delete_persistentvolumeclaim(&self, name: &str) -> Result<(), StoreError> {
+        let claim = self.get_persistentvolumeclaim(name).await?;
+        if let Some(pod) = self
+            .store
+            .list_pods()
+            .await?
+            .iter()
+            .find(|p| p.spec.claim_refs().contains(name))
+        {
+            return Err(StoreError::Conflict(format!(
+                "Persistent volume claim {} is used by pod {}",
+                name, pod.metadata.name
+            )));
+        }
+
+        if let Some(volume) = &claim.status.volume_name
+            && let Some(mut pv) = self.store.get_persistentvolume(volume).await?
+        {
+            pv.status.phase = VolumePhase::Released;
+            self.store.put_persistentvolume(volume, &pv).await?;
+        }
+        self.store.delete_persistentvolumeclaim(name).await?;
+
+        let event = PersistentVolumeClaimEvent {
+            event_type: EventType::Deleted,
+            claim,
+        };
+        let _ = self.claim_tx.send(event);
+        Ok(())
+    }
+
+    /// Binds the claims a pod mounts to volumes on the node, provisioning the missing ones.
+    ///
+    /// Fails without binding anything if a claim is missing or bound elsewhere.
+    async fn bind_claims(&self, pod: &Pod, node_name: &str) -> Result<(), StoreError> {
+        let mut unbound = Vec::new();
+        for name in pod.spec.claim_refs() {
+            let claim = self
+                .store
+                .get_persistentvolumeclaim(name)
+                .await?
+                .ok_or_else(|| {
+                    StoreError::InvalidReference(format!(
+                        "No persistent volume claim exists with name={}",
+                        name
+                    ))
+                })?;
+            match &claim.status.node_name {
+                Some(node) if node != node_name => {
+                    return Err(StoreError::Conflict(format!(
+                        "Persistent volume claim {} is bound to a volume on {}",
+                        name, node
+                    )));
+                }
+                Some(_) => {}
+                None => unbound.push(claim),
+            }
+        }
+
+        for mut claim in unbound {
+            let mut pv = PersistentVolume {
+                metadata: Metadata {
+                    name: provisioned_volume_name(&claim),
+                    ..Default::default()
+                },
+                spec: PersistentVolumeSpec {
+                    capacity: claim.spec.storage.clone(),
+                    node_name: node_name.to_string(),
+                    path: None,
+                    reclaim_policy: claim.spec.reclaim_policy,
+                    claim_ref: None,
+                },
+                status: PersistentVolumeStatus::default(),
+            };
+            self.bind_claim(&mut claim, &mut pv).await?;
+            self.store
+                .put_persistentvolumeclaim(&claim.metadata.name, &claim)
+                .await?;
+            tracing::info!(claim=%claim.metadata.name, volume=%pv.metadata.name, node=%node_name, "Provisioned volume");
+
+            let event = PersistentVolumeClaimEvent {
+                event_type: EventType::Modified,
+                claim,
+            };
+            let _ = self.claim_tx.send(event);
+        }
+        Ok(())
+    }
+
+    /// Marks a claim and a volume as bound to each other, storing the volume.
+    async fn bind_claim(
+        &self,
+        claim: &mut PersistentVolumeClaim,
+        pv: &mut PersistentVolume,
+    ) -> Result<(), StoreError> {
+        pv.spec.claim_ref = Some(claim.metadata.name.clone());
+        pv.status.phase = VolumePhase::Bound;
+        self.store
+            .put_persistentvolume(&pv.metadata.name, pv)
+            .await?;
+        claim.status = PersistentVolumeClaimStatus {
+            phase: ClaimPhase::Bound,
+            volume_name: Some(pv.metadata.name.clone()),
+            node_name: Some(pv.spec.node_name.clone()),
+        };
+        Ok(())
+    }
+
+    /// Adds a service and emits an event, node ports left unset are allocated.
+    pub async fn add_service(
+        &self,
+        mut spec: ServiceSpec,
+        metadata: Metadata,
+    ) -> Result<Uuid, StoreError> {
+        validate_service_ports(&spec.ports)?;
+
+        let _guard = self.node_port_lock.lock().await;
+        if self.store.get_service(&metadata.name).await?.is_some() {
+            return Err(StoreError::Conflict(format!(
+                "Duplicate service name: {}",
+                metadata.name
+            )));
+        }
+        let mut allocated: HashSet<u16> = self
+            .store
+            .list_services()
+            .await?
+            .iter()
+            .flat_map(|s| s.spec.ports.iter().filter_map(|p| p.node_port))
+            .collect();
+        for port in spec.ports.iter().filter_map(|p| p.node_port) {
+            if !allocated.insert(port) {
+                return Err(StoreError::Conflict(format!(
+                    "Node port {} is already allocated",
+                    port
+                )));
+            }
+        }
+        for port in spec.ports.iter_mut().filter(|p| p.node_port.is_none()) {
+            let free = NODE_PORT_RANGE
+                .clone()
+                .find(|p| !allocated.contains(p))
+                .ok_or_else(|| StoreError::Conflict("No node ports left".to_string()))?;
+            allocated.insert(free);
+            port.node_port = Some(free);
+        }
+
+        let service = Service { metadata, spec };
+        self.store
+            .put_service(&service.metadata.name, &service)
+            .await?;
+
+        let event = ServiceEvent {
+            event_type: EventType::Added,
+            service: service.clone(),
+        };
+        let _ = self.service_tx.send(event);
+        Ok(service.metadata.id)
+    }
+
+    /// Retrieves all services.
+    pub async fn get_services(&self) -> Vec<Service> {
+        self.store.list_services().await.unwrap_or_default()
+    }
+
+    pub async fn get_service(&self, name: &str) -> Result<Service, StoreError> {
+        self.store
+            .get_service(name)
+            .await?
+            .ok_or_else(|| StoreError::NotFound("Service not found".to_string()))
+    }
+
+    /// Deletes a service along with its endpoints, emitting an event for each.
+    pub async fn delete_service(&self, name: &str) -> Result<(), StoreError> {
+        let service = self.get_service(name).await?;
+        self.store.delete_service(name).await?;
+        if let Some(endpoints) = self.store.get_endpoints(name).await? {
+            self.store.delete_endpoints(name).await?;
+            let event = EndpointsEvent {
+                event_type: EventType::Deleted,
+                endpoints,
+            };
+            let _ = self.endpoints_tx.send(event);
+        }
+
+        let event = ServiceEvent {
+            event_type: EventType::Deleted,
+            service,
+        };
+        let _ = self.service_tx.send(event);
+        Ok(())
+    }
+
+    /// Retrieves the endpoints of every service.
+    pub async fn get_all_endpoints(&self) -> Vec<Endpoints> {
+        self.store.list_endpoints().await.unwrap_or_default()
+    }
+
+    pub async fn get_endpoints(&self, name: &str) -> Result<Endpoints, StoreError> {
+        self.store
+            .get_endpoints(name)
+            .await?
+            .ok_or_else(|| StoreError::NotFound("Endpoints not found".to_string()))
+    }
+
+    /// Replaces the backends of a service and emits an event if they changed.
+    pub async fn update_endpoints(
+        &self,
+        name: &str,
+        ports: Vec<EndpointPort>,
+    ) -> Result<(), StoreError> {
+        self.get_service(name).await?;
+
+        let (event_type, endpoints) = match self.store.get_endpoints(name).await? {
+            Some(existing) if existing.ports == ports => return Ok(()),
+            Some(mut existing) => {
+                existing.ports = ports;
+                existing.metadata.generation += 1;
+                existing.metadata.modified_at = Utc::now();
+                (EventType::Modified, existing)
+            }
+            None => (
+                EventType::Added,
+                Endpoints {
+                    metadata: Metadata {
+                        name: name.to_string(),
+                        ..Default::default()
+                    },
+                    ports,
+                },
+            ),
+        };
+        self.store.put_endpoints(name, &endpoints).await?;
+
+        let event = EndpointsEvent {
+            event_type,
+            endpoints,
+        };
+        let _ = self.endpoints_tx.send(event);
+        Ok(())
+    }
+
+    /// Adds an ingress and emits an event.
+    pub async fn add_ingress(
+        &self,
+        spec: IngressSpec,
+        metadata: Metadata,
+    ) -> Result<Uuid, StoreError> {
+        validate_ingress(&spec)?;
+        if self.store.get_ingress(&metadata.name).await?.is_some() {
+            return Err(StoreError::Conflict(format!(
+                "Duplicate ingress name: {}",
+                metadata.name
+            )));
+        }
+
+        let ingress = Ingress { metadata, spec };
+        self.store
+            .put_ingress(&ingress.metadata.name, &ingress)
+            .await?;
+
+        let event = IngressEvent {
+            event_type: EventType::Added,
+            ingress: ingress.clone(),
+        };
+        let _ = self.ingress_tx.send(event);
+        Ok(ingress.metadata.id)
+    }
+
+    /// Retrieves all ingresses.
+    pub async fn get_ingresses(&self) -> Vec<Ingress> {
+        self.store.list_ingresses().await.unwrap_or_default()
+    }
+
+    pub async fn get_ingress(&self, name: &str) -> Result<Ingress, StoreError> {
+        self.store
+            .get_ingress(name)
+            .await?
+            .ok_or_else(|| StoreError::NotFound("Ingress not found".to_string()))
+    }
+
+    /// Replaces the routes of an ingress and emits an event.
+    pub async fn update_ingress(&self, name: &str, spec: IngressSpec) -> Result<(), StoreError> {
+        validate_ingress(&spec)?;
+        let mut ingress = self.get_ingress(name).await?;
+        ingress.spec = spec;
+        ingress.metadata.generation += 1;
+        ingress.metadata.modified_at = Utc::now();
+        self.store.put_ingress(name, &ingress).await?;
+
+        let event = IngressEvent {
+            event_type: EventType::Modified,
+            ingress,
+        };
+        let _ = self.ingress_tx.send(event);
+        Ok(())
+    }
+
+    /// Deletes an ingress and emits an event.
+    pub async fn delete_ingress(&self, name: &str) -> Result<(), StoreError> {
+        let ingress = self.get_ingress(name).await?;
+        self.store.delete_ingress(name).await?;
+
+        let event = IngressEvent {
+            event_type: EventType::Deleted,
+            ingress,
+        };
+        let _ = self.ingress_tx.send(event);
+        Ok(())
+    }
+
+    pub async fn add_role(&self, spec: RoleSpec, metadata: Metadata) -> Result<Uuid, StoreError> {
+        validate_rules(&spec.rules)?;
+        if self.store.get_role(&metadata.name).await?.is_some() {
+            return Err(StoreError::Conflict(format!(
+                "Duplicate role name: {}",
+                metadata.name
+            )));
+        }
+        let role = Role { metadata, spec };
+        self.store.put_role(&role.metadata.name, &role).await?;
+        self.invalidate_policy();
+        Ok(role.metadata.id)
+    }
+
+    pub async fn get_roles(&self) -> Vec<Role> {
+        self.store.list_roles().await.unwrap_or_default()
+    }
+
+    pub async fn delete_role(&self, name: &str) -> Result<(), StoreError> {
+        if self.store.get_role(name).await?.is_none() {
+            return Err(StoreError::NotFound("Role not found".to_string()));
+        }
+        self.store.delete_role(name).await?;
+        self.invalidate_policy();
+        Ok(())
+    }
+
+    /// Adds a cluster role, the names of the built-in ones are reserved.
+    pub async fn add_clusterrole(
+        &self,
+        spec: ClusterRoleSpec,
+        metadata: Metadata,
+    ) -> Result<Uuid, StoreError> {
+        validate_rules(&spec.rules)?;
+        if rbac::is_builtin(&metadata.name)
+            || self.store.get_clusterrole(&metadata.name).await?.is_some()
+        {
+            return Err(StoreError::Conflict(format!(
+                "Duplicate cluster role name: {}",
+                metadata.name
+            )));
+        }
+        let role = ClusterRole { metadata, spec };
+        self.store
+            .put_clusterrole(&role.metadata.name, &role)
+            .await?;
+        self.invalidate_policy();
+        Ok(role.metadata.id)
+    }
+
+    pub async fn get_clusterroles(&self) -> Vec<ClusterRole> {
+        self.store.list_clusterroles().await.unwrap_or_default()
+    }
+
+    pub async fn delete_clusterrole(&self, name: &str) -> Result<(), StoreError> {
+        if self.store.get_clusterrole(name).await?.is_none() {
+            return Err(StoreError::NotFound("Cluster role not found".to_string()));
+        }
+        self.store.delete_clusterrole(name).await?;
+        self.invalidate_policy();
+        Ok(())
+    }
+
+    pub async fn add_rolebinding(
+        &self,
+        spec: RoleBindingSpec,
+        metadata: Metadata,
+    ) -> Result<Uuid, StoreError> {
+        validate_subjects(&spec.subjects)?;
+        if self.store.get_rolebinding(&metadata.name).await?.is_some() {
+            return Err(StoreError::Conflict(format!(
+                "Duplicate role binding name: {}",
+                metadata.name
+            )));
+        }
+        let binding = RoleBinding { metadata, spec };
+        self.store
+            .put_rolebinding(&binding.metadata.name, &binding)
+            .await?;
+        self.invalidate_policy();
+        Ok(binding.metadata.id)
+    }
+
+    pub async fn get_rolebindings(&self) -> Vec<RoleBinding> {
+        self.store.list_rolebindings().await.unwrap_or_default()
+    }
+
+    pub async fn delete_rolebinding(&self, name: &str) -> Result<(), StoreError> {
+        if self.store.get_rolebinding(name).await?.is_none() {
+            return Err(StoreError::NotFound("Role binding not found".to_string()));
+        }
+        self.store.delete_rolebinding(name).await?;
+        self.invalidate_policy();
+        Ok(())
+    }
+
+    /// Adds a cluster role binding, it can only grant a cluster role.
+    pub async fn add_clusterrolebinding(
+        &self,
+        spec: ClusterRoleBindingSpec,
+        metadata: Metadata,
+    ) -> Result<Uuid, StoreError> {
+        validate_subjects(&spec.subjects)?;
+        if spec.role_ref.kind != RoleKind::ClusterRole {
+            return Err(StoreError::WrongFormat(
+                "A cluster role binding must reference a cluster role".to_string(),
+            ));
+        }
+        if rbac::is_builtin(&metadata.name)
+            || self
+                .store
+                .get_clusterrolebinding(&metadata.name)
+                .await?
+                .is_some()
+        {
+            return Err(StoreError::Conflict(format!(
+                "Duplicate cluster role binding name: {}",
+                metadata.name
+            )));
+        }
+        let binding = ClusterRoleBinding { metadata, spec };
+        self.store
+            .put_clusterrolebinding(&binding.metadata.name, &binding)
+            .await?;
+        self.invalidate_policy();
+        Ok(binding.metadata.id)
+    }
+
+    pub async fn get_clusterrolebindings(&self) -> Vec<ClusterRoleBinding> {
+        self.store
+            .list_clusterrolebindings()
+            .await
+            .unwrap_or_default()
+    }
+
+    pub async fn delete_clusterrolebinding(&self, name: &str) -> Result<(), StoreError> {
+        if self.store.get_clusterrolebinding(name).await?.is_none() {
+            return Err(StoreError::NotFound(
+                "Cluster role binding not found".to_string(),
+            ));
+        }
+        self.store.delete_clusterrolebinding(name).await?;
+        self.invalidate_policy();
+        Ok(())
+    }
+
+    /// Checks the action against the roles bound to the user, nodes are further
+    /// limited to their own objects. Returns the binding allowing it, or why it is denied.
+    pub async fn authorize(
+        &self,
+        user: &UserInfo,
+        attrs: &Attributes,
+    ) -> Result<Result<String, String>, StoreError> {
+        let cached = self.policy.read().ok().and_then(|p| p.clone());
+        let policy = match cached {
+            Some(policy) => policy,
+            None => {
+                let version = self.policy_version.load(Ordering::SeqCst);
+                let policy = Arc::new(Policy::new(
+                    self.store.list_roles().await?,
+                    self.store.list_clusterroles().await?,
+                    self.store.list_rolebindings().await?,
+                    self.store.list_clusterrolebindings().await?,
+                ));
+                if let Ok(mut cache) = self.policy.write()
+                    && self.policy_version.load(Ordering::SeqCst) == version
+                {
+                    *cache = Some(policy.clone());
+                }
+                policy
+            }
+        };
+        let allowed = match policy.authorize(user, attrs) {
+            Ok(reason) => reason,
+            denied => return Ok(denied),
+        };
+        match user.node_name() {
+            Some(node) if !user.in_group(MASTERS_GROUP) => {
+                Ok(match self.node_restriction(node, attrs).await? {
+                    Some(reason) => Err(reason),
+                    None => Ok(allowed),
+                })
+            }
+            _ => Ok(Ok(allowed)),
+        }
+    }
+
+    /// Why the node may not touch the object: writes are limited to the node itself,
+    /// the pods bound to it and its volumes, reads of configmaps and secrets to those
+    /// its pods use. Secrets of ingress TLS are readable by every ingress gateway.
+    async fn node_restriction(
+        &self,
+        node: &str,
+        attrs: &Attributes,
+    ) -> Result<Option<String>, StoreError> {
+        // collections are checked by the role alone
+        let Some(name) = attrs.name.as_deref() else {
+            return Ok(None);
+        };
+        let collection = attrs.resource.split('/').next().unwrap_or_default();
+        let allowed = match (collection, attrs.verb.as_str()) {
+            ("nodes" | "pods" | "persistentvolumes", "get") => true,
+            ("nodes", _) => name == node,
+            // unknown pods are left for the handler to answer
+            ("pods", _) => self
+                .cache
+                .get_pod_info(name)
+                .is_none_or(|info| info.node == node),
+            ("persistentvolumes", _) => self
+                .store
+                .get_persistentvolume(name)
+                .await?
+                .is_none_or(|pv| pv.spec.node_name == node),
+            ("configmaps", _) => self
+                .get_pods(&Some(node.to_string()), &HashMap::new())
+                .await
+                .iter()
+                .any(|pod| pod.spec.configmap_refs().contains(name)),
+            ("secrets", _) => {
+                self.get_pods(&Some(node.to_string()), &HashMap::new())
+                    .await
+                    .iter()
+                    .any(|pod| pod.spec.secret_refs().contains(name))
+                    || self
+                        .store
+                        .list_ingresses()
+                        .await?
+                        .iter()
+                        .any(|ingress| ingress.spec.tls.iter().any(|t| t.secret_name == name))
+            }
+            _ => true,
+        };
+        Ok((!allowed).then(|| {
+            format!(
+                "node {} cannot {} {} {}, it does not belong to the node",
+                node, attrs.verb, attrs.resource, name
+            )
+        }))
+    }
+
+    fn invalidate_policy(&self) {
+        if let Ok(mut cache) = self.policy.write() {
+            self.policy_version.fetch_add(1, Ordering::SeqCst);
+            *cache = None;
+        }
+    }
+
+    async fn get_default_priorityclass(&self) -> Result<Option<PriorityClass>, StoreError> {
+        Ok(self
+            .store
+            .list_priorityclasses()
+            .await?
+            .into_iter()
+            .find(|pc| pc.spec.global_default))
+    }
+
+    /// Resolves the pod priority from its named class or the global default.
+    pub async fn resolve_priority(&self, spec: &mut PodSpec) -> Result<(), StoreError> {
+        let class = match &spec.priority_class_name {
+            Some(name) => Some(self.store.get_priorityclass(name).await?.ok_or_else(|| {
+                StoreError::InvalidReference(format!("No priority class exists with name={}", name))
+            })?),
+            None => self.get_default_priorityclass().await?,
+        };
+        if let Some(class) = class {
+            spec.priority_class_name = Some(class.metadata.name);
+            spec.priority = class.spec.value;
+        }
+        Ok(())
+    }
+
     /// Adds a new pod, assigns it a UUID, and emits a PodEvent.
-    pub async fn add_pod(&self, spec: PodSpec, metadata: Metadata) -> Result<Uuid, StoreError> {
+    pub async fn add_pod(&self, mut spec: PodSpec, metadata: Metadata) -> Result<Uuid, StoreError> {
         // validate spec and name
-        validate_container_list(&spec.containers)?;
+        validate_container_list(&spec.containers, &spec.init_containers)?;
+        validate_volumes(&spec.volumes, &spec.containers, &spec.init_containers)?;
+        self.resolve_priority(&mut spec).await?;
+        if let Some(group) = metadata.labels.get(POD_GROUP_LABEL)
+            && self.store.get_podgroup(group).await?.is_none()
+        {
+            return Err(StoreError::InvalidReference(format!(
+                "No pod group exists with name={}",
+                group
+            )));
+        }
+
+        let pod = Pod {
+            spec,
+            metadata,
+            status: PodStatus::default(),
+        };
+
+        // save object and metadata in store and cache
+        self.store.put_pod(&pod.metadata.id, &pod).await?;
+        self.cache.add_pod(&pod.metadata.name, &pod.metadata.id);
+        self.cache
+            .add_pod_labels(&pod.metadata.id, &pod.metadata.labels);
+
+        // send event
+        let event = PodEvent {
+            event_type: EventType::Added,
+            pod: pod.clone(),
+        };
+        let _ = self.pod_tx.send(event);
+        Ok(pod.metadata.id)
+    }
+
+    /// Deletes a pod only if every disruption budget covering it allows one more disruption.
+    /// Pods that are not running do not count against any budget.
+    pub async fn evict_pod(&self, name: &str) -> Result<(), StoreError> {
+        let _guard = self.eviction_lock.lock().await;
+
+        let id = self
+            .cache
+            .get_pod_id(name)
+            .ok_or_else(|| StoreError::NotFound("Pod not found".to_string()))?;
+        let pod = self
+            .store
+            .get_pod(id)
+            .await?
+            .ok_or_else(|| StoreError::NotFound("Pod not found".to_string()))?;
+
+        if matches!(pod.status.phase, PodPhase::Running)
+            && pod.metadata.deletion_timestamp.is_none()
+        {
+            for mut pdb in self.store.list_poddisruptionbudgets().await? {
+                if !pdb.spec.selector.matches(&pod.metadata.labels) {
+                    continue;
+                }
+                pdb.status = self.disruption_status(&pdb.spec).await;
+                if pdb.status.disruptions_allowed == 0 {
+                    return Err(StoreError::TooManyRequests(format!(
+                        "Cannot evict pod as it would violate the disruption budget {}",
+                        pdb.metadata.name
+                    )));
+                }
+                pdb.status.disruptions_allowed -= 1;
+                pdb.status.current_healthy -= 1;
+                self.store
+                    .put_poddisruptionbudget(&pdb.metadata.name, &pdb)
+                    .await?;
+            }
+        }
+
+        self.terminate_pod(name, None).await.map(|_| ())
+    }
+
+    /// Starts the graceful deletion of a pod.
+    ///
+    /// Pods bound to a node are marked with a deletion timestamp and stay
+    /// until the node confirms their containers are stopped. Unbound pods,
+    /// or a grace period of 0, remove the pod right away. A pod already
+    /// terminating takes a new grace period only if it ends sooner.
+    ///
+    /// Returns true if the pod was removed, false if it is terminating.
+    pub async fn terminate_pod(
+        &self,
+        name: &str,
+        grace_period_seconds: Option<u32>,
+    ) -> Result<bool, StoreError> {
+        let id = self
+            .cache
+            .get_pod_id(name)
+            .ok_or_else(|| StoreError::NotFound("Pod not found".to_string()))?;
+        let mut pod = self
+            .store
+            .get_pod(id)
+            .await?
+            .ok_or_else(|| StoreError::NotFound("Pod not found".to_string()))?;
 
-        let pod = Pod {
-            spec,
-            metadata,
-            status: PodStatus::default(),
-        };
+        let grace = grace_period_seconds.unwrap_or(pod.spec.termination_grace_period_seconds);
+        if grace == 0
+            || pod.spec.node_name.is_empty()
+            || !self.cache.node_name_exists(&pod.spec.node_name)
+        {
+            return self.delete_pod(name).await.map(|_| true);
+        }
+        // a terminating pod only takes a grace period ending before the current one
+        let now = Utc::now();
+        if let Some(since) = pod.metadata.deletion_timestamp {
+            let deadline =
+                since + chrono::Duration::seconds(pod.spec.termination_grace_period_seconds.into());
+            if now + chrono::Duration::seconds(grace.into()) >= deadline {
+                return Ok(false);
+            }
+        }
 
-        // save object and metadata in store and cache
-        self.store.put_pod(&pod.metadata.id, &pod).await?;
-        self.cache.add_pod(&pod.metadata.name, &pod.metadata.id);
-        self.cache
-            .add_pod_labels(&pod.metadata.id, &pod.metadata.labels);
+        pod.metadata.deletion_timestamp = Some(now);
+        pod.spec.termination_grace_period_seconds = grace;
+        self.store.put_pod(&id, &pod).await?;
 
-        // send event
+        // send event, the node takes it from here
         let event = PodEvent {
-            event_type: EventType::Added,
-            pod: pod.clone(),
+            event_type: EventType::Modified,
+            pod,
         };
         let _ = self.pod_tx.send(event);
-        Ok(pod.metadata.id)
+        Ok(false)
     }
 
     /// Deletes a pod by name and emits a deletion event.
@@ -194,10 +1461,18 @@ impl ApiServerState {
                 name
             )));
         }
+        self.bind_claims(&pod, &node_name).await?;
 
         // assign ad store node
         pod.spec.node_name = node_name.clone();
         pod.metadata.generation += 1;
+        pod.status.nominated_node_name = None;
+        pod.status.set_condition(PodCondition::new(
+            PodConditionType::PodScheduled,
+            true,
+            "Scheduled",
+            &format!("Assigned to {}", node_name),
+        ));
         self.store.put_pod(&pod.metadata.id, &pod).await?;
 
         // update cache, move from unassigned to node
@@ -224,8 +1499,14 @@ impl ApiServerState {
             .await?
             .ok_or(StoreError::NotFound("Pod not found in store".to_string()))?;
 
-        validate_container_statuses(&pod.spec, &mut status.container_status);
+        validate_container_statuses(&pod.spec.containers, &mut status.container_status);
+        validate_container_statuses(&pod.spec.init_containers, &mut status.init_container_status);
+        // conditions and nomination are owned by the control plane
+        let conditions = std::mem::take(&mut pod.status.conditions);
+        let nominated_node_name = pod.status.nominated_node_name.take();
         pod.status = status.clone();
+        pod.status.conditions = conditions;
+        pod.status.nominated_node_name = nominated_node_name;
         pod.status.last_update = Some(Utc::now());
         self.store.put_pod(&id, &pod).await?;
         // send event
@@ -237,6 +1518,89 @@ impl ApiServerState {
         Ok(())
     }
 
+    /// Records a condition on a pod and updates its nominated node.
+    pub async fn update_pod_condition(
+        &self,
+        name: &str,
+        update: PodConditionUpdate,
+    ) -> Result<(), StoreError> {
+        let id = self
+            .cache
+            .get_pod_id(name)
+            .ok_or_else(|| StoreError::NotFound(format!("No pod exists with name={}", name)))?;
+        let mut pod = self
+            .store
+            .get_pod(id)
+            .await?
+            .ok_or(StoreError::NotFound("Pod not found in store".to_string()))?;
+
+        if let Some(node_name) = &update.nominated_node_name
+            && !self.cache.node_name_exists(node_name)
+        {
+            return Err(StoreError::InvalidReference(format!(
+                "No node exists with name={}",
+                node_name
+            )));
+        }
+
+        pod.status.set_condition(update.condition);
+        pod.status.nominated_node_name = update.nominated_node_name;
+        self.store.put_pod(&id, &pod).await?;
+
+        // send event
+        let event = PodEvent {
+            event_type: EventType::Modified,
+            pod,
+        };
+        let _ = self.pod_tx.send(event);
+        Ok(())
+    }
+
+    /// Applies a merge patch to a pod spec and bumps its generation.
+    ///
+    /// Only container images, environment and ports may change, any other
+    /// field differing from the stored spec is rejected. A patch that leaves
+    /// the spec unchanged keeps the current generation.
+    pub async fn update_pod_spec(
+        &self,
+        name: &str,
+        patch: serde_json::Value,
+    ) -> Result<Pod, StoreError> {
+        let id = self
+            .cache
+            .get_pod_id(name)
+            .ok_or_else(|| StoreError::NotFound(format!("No pod exists with name={}", name)))?;
+        let mut pod = self
+            .store
+            .get_pod(id)
+            .await?
+            .ok_or(StoreError::NotFound("Pod not found in store".to_string()))?;
+
+        let mut merged = serde_json::to_value(&pod.spec)
+            .map_err(|e| StoreError::UnexpectedError(e.to_string()))?;
+        merge_patch(&mut merged, &patch);
+        let spec: PodSpec = serde_json::from_value(merged)
+            .map_err(|e| StoreError::WrongFormat(format!("Invalid pod spec: {}", e)))?;
+        validate_container_list(&spec.containers, &spec.init_containers)?;
+        validate_volumes(&spec.volumes, &spec.containers, &spec.init_containers)?;
+        validate_spec_update(&pod.spec, &spec)?;
+
+        if same_json(&spec, &pod.spec) {
+            return Ok(pod);
+        }
+        pod.spec = spec;
+        pod.metadata.generation += 1;
+        self.store.put_pod(&id, &pod).await?;
+
+        // send event
+        let event = PodEvent {
+            event_type: EventType::Modified,
+            pod: pod.clone(),
+        };
+        let _ = self.pod_tx.send(event);
+        Ok(pod)
+    }
+
     /// Retrieves all pods, or only those scheduled on a specific node.
     pub async fn get_pods(
         &self,
@@ -299,27 +1663,566 @@ impl ApiServerState {
         node.last_heartbeat = Utc::now();
         self.store.put_node(node_name, &node).await
     }
+
+    /// Issues a fresh token for a node, replacing any previous one.
+    /// It authenticates the node agent and lets it reclaim its name on registration.
+    pub async fn issue_node_token(&self, node_name: &str) -> Result<String, StoreError> {
+        let token = self.auth.issue(&UserInfo::node(node_name), None);
+        self.store.put_node_token(node_name, &token).await?;
+        Ok(token)
+    }
+
+    /// User of a bearer token, node tokens are only valid until the node registers again.
+    pub async fn authenticate_token(&self, token: &str) -> Option<UserInfo> {
+        if let Some(user) = self.auth.static_token(token) {
+            return Some(user);
+        }
+        let user = self.auth.signed_token(token)?;
+        match user.node_name() {
+            Some(node_name) if !self.node_token_matches(node_name, token).await => None,
+            _ => Some(user),
+        }
+    }
+
+    /// Checks a registration token against the one stored for the node.
+    pub async fn node_token_matches(&self, node_name: &str, token: &str) -> bool {
+        match self.store.get_node_token(node_name).await {
+            Ok(Some(stored)) => stored == token,
+            _ => false,
+        }
+    }
+
+    /// Re-registers a known node under a new address, keeping its id and cordon state.
+    pub async fn reregister_node(
+        &self,
+        node_name: &str,
+        addr: String,
+        taints: Vec<Taint>,
+    ) -> Result<Node, StoreError> {
+        let mut node = self
+            .store
+            .get_node(node_name)
+            .await?
+            .ok_or(StoreError::NotFound(format!(
+                "Node {} not found in store",
+                node_name
+            )))?;
+        if node.addr != addr && self.cache.node_addr_exists(&addr) {
+            return Err(StoreError::Conflict("Duplicate node address".to_string()));
+        }
+
+        self.cache.remove_node(&node.name, &node.addr);
+        node.addr = addr;
+        node.spec.taints = taints;
+        node.status = NodeStatus::Ready;
+        node.started_at = Utc::now();
+        node.last_heartbeat = Utc::now();
+        self.store.put_node(node_name, &node).await?;
+        self.cache.add_node(&node.name, &node.addr);
+
+        let event = NodeEvent {
+            event_type: EventType::Modified,
+            node: node.clone(),
+        };
+        let _ = self.node_tx.send(event);
+        Ok(node)
+    }
+
+    /// Removes a node from the cluster.
+    ///
+    /// Pods still bound to the node are deleted so their controllers can
+    /// recreate them elsewhere.
+    pub async fn delete_node(&self, node_name: &str) -> Result<(), StoreError> {
+        let node = self
+            .store
+            .get_node(node_name)
+            .await?
+            .ok_or(StoreError::NotFound(format!(
+                "Node {} not found in store",
+                node_name
+            )))?;
+
+        for pod in self
+            .get_pods(&Some(node_name.to_string()), &HashMap::new())
+            .await
+        {
+            if let Err(e) = self.delete_pod(&pod.metadata.name).await {
+                tracing::warn!(pod=%pod.metadata.name, error=%e, "Failed to delete pod of removed node");
+            }
+        }
+
+        self.store.delete_node(node_name).await?;
+        self.cache.remove_node(&node.name, &node.addr);
+
+        let event = NodeEvent {
+            event_type: EventType::Deleted,
+            node,
+        };
+        let _ = self.node_tx.send(event);
+        Ok(())
+    }
+
+    /// Applies a spec patch to a node and emits a modification event.
+    pub async fn update_node_spec(
+        &self,
+        node_name: &str,
+        patch: NodePatch,
+    ) -> Result<Node, StoreError> {
+        let mut node = self
+            .store
+            .get_node(node_name)
+            .await?
+            .ok_or(StoreError::NotFound(format!(
+                "Node {} not found in store",
+                node_name
+            )))?;
+        if let Some(unschedulable) = patch.spec.unschedulable {
+            node.spec.unschedulable = unschedulable;
+        }
+        if let Some(taints) = patch.spec.taints {
+            node.spec.taints = taints;
+        }
+        self.store.put_node(node_name, &node).await?;
+
+        let event = NodeEvent {
+            event_type: EventType::Modified,
+            node: node.clone(),
+        };
+        let _ = self.node_tx.send(event);
+        Ok(node)
+    }
 }
 
 /// Validates pod spec for duplicate container names.
-fn validate_container_list(list: &Vec<ContainerSpec>) -> Result<(), StoreError> {
+/// Checks container names are unique across init and main containers, and each container on its own.
+fn validate_container_list(
+    list: &[ContainerSpec],
+    init_list: &[ContainerSpec],
+) -> Result<(), StoreError> {
     let mut seen_names = HashSet::new();
 
-    for container in list {
+    for container in init_list.iter().chain(list) {
         if !seen_names.insert(&container.name) {
             return Err(StoreError::WrongFormat(format!(
                 "Duplicate container name found: '{}'",
                 container.name
             )));
         }
+        validate_container(container)?;
+    }
+
+    for container in list {
+        if container.restart_policy.is_some() {
+            return Err(StoreError::WrongFormat(format!(
+                "Container '{}': restartPolicy is only allowed on init containers",
+                container.name
+            )));
+        }
+    }
+    let mut port_names = HashSet::new();
+    let mut host_ports = HashSet::new();
+    for port in init_list
+        .iter()
+        .chain(list)
+        .flat_map(|c| c.ports.iter().flatten())
+    {
+        if port.container_port == 0 || port.host_port == Some(0) {
+            return Err(StoreError::WrongFormat("Port 0 is not allowed".to_string()));
+        }
+        if let Some(name) = &port.name
+            && !port_names.insert(name)
+        {
+            return Err(StoreError::WrongFormat(format!(
+                "Duplicate port name found: '{}'",
+                name
+            )));
+        }
+        if let Some(host_port) = port.host_port
+            && !host_ports.insert((host_port, port.protocol))
+        {
+            return Err(StoreError::WrongFormat(format!(
+                "Host port {} is bound twice",
+                host_port
+            )));
+        }
+    }
+
+    for container in init_list {
+        if container.restart_policy.is_some() && !container.is_sidecar() {
+            return Err(StoreError::WrongFormat(format!(
+                "Container '{}': init containers only accept restartPolicy Always",
+                container.name
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_container(container: &ContainerSpec) -> Result<(), StoreError> {
+    let invalid = |msg: &str| {
+        Err(StoreError::WrongFormat(format!(
+            "Container '{}': {}",
+            container.name, msg
+        )))
+    };
+
+    if container.image.trim().is_empty() {
+        return invalid("image is required");
+    }
+    if container.command.as_ref().is_some_and(|c| c.is_empty()) {
+        return invalid("command can't be empty");
+    }
+    if container
+        .working_dir
+        .as_ref()
+        .is_some_and(|dir| !dir.starts_with('/'))
+    {
+        return invalid("workingDir must be an absolute path");
+    }
+    if container.user.is_some() && container.run_as_user.is_some() {
+        return invalid("only one of user and runAsUser can be set");
+    }
+    if container.user.as_ref().is_some_and(|u| u.trim().is_empty()) {
+        return invalid("user can't be empty");
+    }
+    for env in container.env.iter().flatten() {
+        if env.name.is_empty() {
+            return invalid("env name can't be empty");
+        }
+        let selector = match &env.value_from {
+            Some(EnvVarSource::ConfigMapKeyRef(s) | EnvVarSource::SecretKeyRef(s)) => s,
+            None => continue,
+        };
+        if !env.value.is_empty() {
+            return invalid(&format!(
+                "env '{}': only one of value and valueFrom can be set",
+                env.name
+            ));
+        }
+        if selector.name.is_empty() || selector.key.is_empty() {
+            return invalid(&format!(
+                "env '{}': valueFrom needs a name and key",
+                env.name
+            ));
+        }
+    }
+    for env_from in &container.env_from {
+        let (EnvFromRef::ConfigMapRef(r) | EnvFromRef::SecretRef(r)) = &env_from.source;
+        if r.name.is_empty() {
+            return invalid("envFrom needs a name");
+        }
+    }
+    Ok(())
+}
+
+/// Checks config map and secret keys can be used as file and variable names.
+fn validate_data(data: &HashMap<String, String>) -> Result<(), StoreError> {
+    for key in data.keys() {
+        let valid = !key.is_empty()
+            && key != "."
+            && key != ".."
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(StoreError::WrongFormat(format!(
+                "Invalid key '{}', only alphanumerics, '-', '_' and '.' are allowed",
+                key
+            )));
+        }
+    }
+    let size: usize = data.iter().map(|(k, v)| k.len() + v.len()).sum();
+    if size > MAX_DATA_SIZE {
+        return Err(StoreError::WrongFormat(format!(
+            "Data is {} bytes, at most {} are allowed",
+            size, MAX_DATA_SIZE
+        )));
+    }
+    Ok(())
+}
+
+/// Parses a storage size, zero is not a size.
+fn validate_storage(size: &str) -> Result<u64, StoreError> {
+    match parse_storage(size) {
+        Some(bytes) if bytes > 0 => Ok(bytes),
+        _ => Err(StoreError::WrongFormat(format!(
+            "Invalid storage size '{}', expected a number with an optional Ki, Mi, Gi or Ti suffix",
+            size
+        ))),
+    }
+}
+
+/// Checks service ports are unique and within their ranges.
+fn validate_service_ports(ports: &[ServicePort]) -> Result<(), StoreError> {
+    let invalid = |msg: String| Err(StoreError::WrongFormat(msg));
+
+    if ports.is_empty() {
+        return invalid("Service needs at least one port".to_string());
+    }
+    let mut names = HashSet::new();
+    let mut numbers = HashSet::new();
+    for port in ports {
+        match &port.name {
+            Some(name) if !names.insert(name) => {
+                return invalid(format!("Duplicate service port name: {}", name));
+            }
+            None if ports.len() > 1 => {
+                return invalid("Every port needs a name when there are several".to_string());
+            }
+            _ => {}
+        }
+        if port.port == 0 || port.target_port == Some(TargetPort::Number(0)) {
+            return invalid("Service ports must be greater than 0".to_string());
+        }
+        if !numbers.insert((port.port, port.protocol)) {
+            return invalid(format!(
+                "Duplicate service port: {}/{}",
+                port.port,
+                port.protocol.as_str()
+            ));
+        }
+        if let Some(node_port) = port.node_port
+            && !NODE_PORT_RANGE.contains(&node_port)
+        {
+            return invalid(format!(
+                "Node port {} is outside {}-{}",
+                node_port,
+                NODE_PORT_RANGE.start(),
+                NODE_PORT_RANGE.end()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Checks every rule names at least one verb and resource.
+fn validate_rules(rules: &[PolicyRule]) -> Result<(), StoreError> {
+    for rule in rules {
+        if rule.verbs.is_empty() || rule.resources.is_empty() {
+            return Err(StoreError::WrongFormat(
+                "Rules need at least one verb and resource".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn validate_subjects(subjects: &[Subject]) -> Result<(), StoreError> {
+    if subjects.is_empty() || subjects.iter().any(|s| s.name.is_empty()) {
+        return Err(StoreError::WrongFormat(
+            "Bindings need subjects with a name".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks ingress rules route somewhere and the TLS hosts have a secret.
+fn validate_ingress(spec: &IngressSpec) -> Result<(), StoreError> {
+    let invalid = |msg: String| Err(StoreError::WrongFormat(msg));
+
+    if spec.rules.is_empty() {
+        return invalid("Ingress needs at least one rule".to_string());
+    }
+    let mut routes = HashSet::new();
+    for rule in &spec.rules {
+        if rule.host.as_ref().is_some_and(|h| !is_valid_host(h)) {
+            return invalid(format!("Invalid ingress host: {:?}", rule.host));
+        }
+        if rule.paths.is_empty() {
+            return invalid("Every ingress rule needs at least one path".to_string());
+        }
+        for path in &rule.paths {
+            if !path.path.starts_with('/') {
+                return invalid(format!("Ingress path must be absolute: {}", path.path));
+            }
+            if path.backend.service.is_empty() || path.backend.port == 0 {
+                return invalid("Ingress backend needs a service and a port".to_string());
+            }
+            if !routes.insert((&rule.host, path.path.trim_end_matches('/'))) {
+                return invalid(format!("Duplicate ingress path: {}", path.path));
+            }
+        }
+    }
+    for tls in &spec.tls {
+        if tls.secret_name.is_empty() || tls.hosts.is_empty() {
+            return invalid("Ingress TLS needs hosts and a secret name".to_string());
+        }
+        if let Some(host) = tls.hosts.iter().find(|h| !is_valid_host(h)) {
+            return invalid(format!("Invalid ingress TLS host: {}", host));
+        }
+    }
+    Ok(())
+}
+
+/// Lowercase DNS name, wildcards are not supported.
+fn is_valid_host(host: &str) -> bool {
+    !host.is_empty() && host.split('.').all(is_dns_label)
+}
+
+fn is_dns_label(label: &str) -> bool {
+    !label.is_empty()
+        && label.len() <= 63
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// Names of objects backed by a directory on the node must be DNS labels,
+/// so they can't point at the directory holding them or outside of it.
+fn validate_name(kind: &str, name: &str) -> Result<(), StoreError> {
+    if is_dns_label(name) {
+        return Ok(());
+    }
+    Err(StoreError::WrongFormat(format!(
+        "Invalid {} name {:?}, expected a lowercase DNS label",
+        kind, name
+    )))
+}
+
+/// Checks volumes are well formed and every mount refers to one of them.
+fn validate_volumes(
+    volumes: &[Volume],
+    list: &[ContainerSpec],
+    init_list: &[ContainerSpec],
+) -> Result<(), StoreError> {
+    let invalid = |msg: String| Err(StoreError::WrongFormat(msg));
+
+    let mut names = HashSet::new();
+    for volume in volumes {
+        if !names.insert(&volume.name) {
+            return invalid(format!("Duplicate volume name found: '{}'", volume.name));
+        }
+        // the node keeps each volume in a directory named after it
+        validate_name("volume", &volume.name)?;
+        match &volume.source {
+            VolumeSource::HostPath(host) if !host.path.starts_with('/') => {
+                return invalid(format!(
+                    "Volume '{}': hostPath must be an absolute path",
+                    volume.name
+                ));
+            }
+            VolumeSource::ConfigMap(r) if r.name.is_empty() => {
+                return invalid(format!("Volume '{}': configMap needs a name", volume.name));
+            }
+            VolumeSource::Secret(s) if s.secret_name.is_empty() => {
+                return invalid(format!("Volume '{}': secretName is required", volume.name));
+            }
+            VolumeSource::PersistentVolumeClaim(c) if c.claim_name.is_empty() => {
+                return invalid(format!("Volume '{}': claimName is required", volume.name));
+            }
+            _ => {}
+        }
+    }
+
+    for container in init_list.iter().chain(list) {
+        let mut paths = HashSet::new();
+        for mount in &container.volume_mounts {
+            let prefix = format!("Container '{}', mount '{}'", container.name, mount.name);
+            if !names.contains(&mount.name) {
+                return invalid(format!("{}: no volume with that name", prefix));
+            }
+            if !mount.mount_path.starts_with('/') {
+                return invalid(format!("{}: mountPath must be an absolute path", prefix));
+            }
+            if !paths.insert(&mount.mount_path) {
+                return invalid(format!(
+                    "{}: mountPath {} used twice",
+                    prefix, mount.mount_path
+                ));
+            }
+            if let Some(sub_path) = &mount.sub_path
+                && (sub_path.starts_with('/') || sub_path.split('/').any(|part| part == ".."))
+            {
+                return invalid(format!(
+                    "{}: subPath must be relative and stay inside the volume",
+                    prefix
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Applies a JSON merge patch (RFC 7386), objects merge and anything else replaces.
+fn merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    let serde_json::Value::Object(entries) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = serde_json::Value::Object(Default::default());
+    }
+    let serde_json::Value::Object(fields) = target else {
+        return;
+    };
+    for (key, value) in entries {
+        if value.is_null() {
+            fields.remove(key);
+        } else {
+            merge_patch(
+                fields.entry(key.clone()).or_insert(serde_json::Value::Null),
+                value,
+            );
+        }
     }
+}
 
+/// Rejects spec updates touching fields other than container images, env and ports.
+fn validate_spec_update(old: &PodSpec, new: &PodSpec) -> Result<(), StoreError> {
+    let immutable = |field: &str| {
+        Err(StoreError::WrongFormat(format!(
+            "Field spec.{} is immutable",
+            field
+        )))
+    };
+    if old.node_name != new.node_name {
+        return immutable("node_name");
+    }
+    if old.priority_class_name != new.priority_class_name || old.priority != new.priority {
+        return immutable("priority");
+    }
+    if !same_json(&old.tolerations, &new.tolerations) {
+        return immutable("tolerations");
+    }
+    if !same_json(&old.anti_affinity, &new.anti_affinity) {
+        return immutable("anti_affinity");
+    }
+    let names = |spec: &PodSpec| -> Vec<String> {
+        spec.containers.iter().map(|c| c.name.clone()).collect()
+    };
+    if names(old) != names(new) {
+        return immutable("containers[].name");
+    }
+    if !same_json(&old.volumes, &new.volumes) {
+        return immutable("volumes");
+    }
+    // bound by the pause container when the pod starts
+    if !old.host_ports().eq(new.host_ports()) {
+        return immutable("containers[].ports[].hostPort");
+    }
+    if !same_json(&old.init_containers, &new.init_containers) {
+        return immutable("init_containers");
+    }
+    if old.restart_policy != new.restart_policy {
+        return immutable("restart_policy");
+    }
     Ok(())
 }
 
+/// Compares values that lack `PartialEq` through their JSON form.
+fn same_json<T: serde::Serialize>(a: &T, b: &T) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
 /// Cleans up container status list to only include valid container names from spec.
-fn validate_container_statuses(spec: &PodSpec, container_statuses: &mut Vec<(String, String)>) {
-    let valid_names: HashSet<_> = spec.containers.iter().map(|c| c.name.clone()).collect();
+fn validate_container_statuses(
+    containers: &[ContainerSpec],
+    container_statuses: &mut Vec<(String, String)>,
+) {
+    let valid_names: HashSet<_> = containers.iter().map(|c| c.name.clone()).collect();
 
     // Filter out invalid entries
     container_statuses.retain(|(name, _)| valid_names.contains(name));
@@ -330,7 +2233,7 @@ fn validate_container_statuses(spec: &PodSpec, container_statuses: &mut Vec<(Str
         .collect();
 
     // Insert default status for containers not included
-    for container in &spec.containers {
+    for container in containers {
         if !existing_names.contains(&container.name) {
             container_statuses.push((container.name.clone(), "EMPTY".to_string()));
         }
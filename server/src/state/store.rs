@@ -2,18 +2,37 @@
 //!
 //! This module provides basic CRUD operations for Pods and Nodes using etcd
 //! as the backend. It serializes and deserializes objects using JSON and
-//! manages key construction using standard prefixes.
+//! manages key construction using standard prefixes. Secrets are encrypted
+//! before being written when a key is configured.
 
 use etcd_client::{Client, ConnectOptions, GetOptions};
 use serde::{Serialize, de::DeserializeOwned};
-use shared::models::{node::Node, pod::Pod, replicaset::ReplicaSet};
+use shared::models::{
+    configmap::ConfigMap,
+    endpoints::Endpoints,
+    ingress::Ingress,
+    node::Node,
+    persistentvolume::PersistentVolume,
+    persistentvolumeclaim::PersistentVolumeClaim,
+    pod::Pod,
+    poddisruptionbudget::PodDisruptionBudget,
+    podgroup::PodGroup,
+    priorityclass::PriorityClass,
+    rbac::{ClusterRole, ClusterRoleBinding, Role, RoleBinding},
+    replicaset::ReplicaSet,
+    secret::Secret,
+    service::Service,
+};
 use tokio::{
     sync::Mutex,
     time::{Duration, timeout},
 };
 use uuid::Uuid;
 
-use super::errors::StoreError;
+use super::{
+    encryption::{self, SecretCipher},
+    errors::StoreError,
+};
 
 use async_trait::async_trait;
 
@@ -33,21 +52,135 @@ pub trait Store: Send + Sync {
     async fn get_node(&self, name: &str) -> Result<Option<Node>, StoreError>;
     async fn put_node(&self, name: &str, node: &Node) -> Result<(), StoreError>;
     async fn list_nodes(&self) -> Result<Vec<Node>, StoreError>;
+    async fn delete_node(&self, name: &str) -> Result<(), StoreError>;
+    async fn get_node_token(&self, name: &str) -> Result<Option<String>, StoreError>;
+    async fn put_node_token(&self, name: &str, token: &str) -> Result<(), StoreError>;
+
+    async fn get_priorityclass(&self, name: &str) -> Result<Option<PriorityClass>, StoreError>;
+    async fn put_priorityclass(&self, name: &str, pc: &PriorityClass) -> Result<(), StoreError>;
+    async fn list_priorityclasses(&self) -> Result<Vec<PriorityClass>, StoreError>;
+    async fn delete_priorityclass(&self, name: &str) -> Result<(), StoreError>;
+
+    async fn get_podgroup(&self, name: &str) -> Result<Option<PodGroup>, StoreError>;
+    async fn put_podgroup(&self, name: &str, group: &PodGroup) -> Result<(), StoreError>;
+    async fn list_podgroups(&self) -> Result<Vec<PodGroup>, StoreError>;
+    async fn delete_podgroup(&self, name: &str) -> Result<(), StoreError>;
+
+    async fn get_poddisruptionbudget(
+        &self,
+        name: &str,
+    ) -> Result<Option<PodDisruptionBudget>, StoreError>;
+    async fn put_poddisruptionbudget(
+        &self,
+        name: &str,
+        pdb: &PodDisruptionBudget,
+    ) -> Result<(), StoreError>;
+    async fn list_poddisruptionbudgets(&self) -> Result<Vec<PodDisruptionBudget>, StoreError>;
+    async fn delete_poddisruptionbudget(&self, name: &str) -> Result<(), StoreError>;
+
+    async fn get_configmap(&self, name: &str) -> Result<Option<ConfigMap>, StoreError>;
+    async fn put_configmap(&self, name: &str, cm: &ConfigMap) -> Result<(), StoreError>;
+    async fn list_configmaps(&self) -> Result<Vec<ConfigMap>, StoreError>;
+    async fn delete_configmap(&self, name: &str) -> Result<(), StoreError>;
+
+    async fn get_secret(&self, name: &str) -> Result<Option<Secret>, StoreError>;
+    async fn put_secret(&self, name: &str, secret: &Secret) -> Result<(), StoreError>;
+    async fn list_secrets(&self) -> Result<Vec<Secret>, StoreError>;
+    async fn delete_secret(&self, name: &str) -> Result<(), StoreError>;
+
+    async fn get_persistentvolume(
+        &self,
+        name: &str,
+    ) -> Result<Option<PersistentVolume>, StoreError>;
+    async fn put_persistentvolume(
+        &self,
+        name: &str,
+        pv: &PersistentVolume,
+    ) -> Result<(), StoreError>;
+    async fn list_persistentvolumes(&self) -> Result<Vec<PersistentVolume>, StoreError>;
+    async fn delete_persistentvolume(&self, name: &str) -> Result<(), StoreError>;
+
+    async fn get_persistentvolumeclaim(
+        &self,
+        name: &str,
+    ) -> Result<Option<PersistentVolumeClaim>, StoreError>;
+    async fn put_persistentvolumeclaim(
+        &self,
+        name: &str,
+        pvc: &PersistentVolumeClaim,
+    ) -> Result<(), StoreError>;
+    async fn list_persistentvolumeclaims(&self) -> Result<Vec<PersistentVolumeClaim>, StoreError>;
+    async fn delete_persistentvolumeclaim(&self, name: &str) -> Result<(), StoreError>;
+
+    async fn get_service(&self, name: &str) -> Result<Option<Service>, StoreError>;
+    async fn put_service(&self, name: &str, svc: &Service) -> Result<(), StoreError>;
+    async fn list_services(&self) -> Result<Vec<Service>, StoreError>;
+    async fn delete_service(&self, name: &str) -> Result<(), StoreError>;
+
+    async fn get_endpoints(&self, name: &str) -> Result<Option<Endpoints>, StoreError>;
+    async fn put_endpoints(&self, name: &str, ep: &Endpoints) -> Result<(), StoreError>;
+    async fn list_endpoints(&self) -> Result<Vec<Endpoints>, StoreError>;
+    async fn delete_endpoints(&self, name: &str) -> Result<(), StoreError>;
+
+    async fn get_ingress(&self, name: &str) -> Result<Option<Ingress>, StoreError>;
+    async fn put_ingress(&self, name: &str, ing: &Ingress) -> Result<(), StoreError>;
+    async fn list_ingresses(&self) -> Result<Vec<Ingress>, StoreError>;
+    async fn delete_ingress(&self, name: &str) -> Result<(), StoreError>;
+    async fn get_role(&self, name: &str) -> Result<Option<Role>, StoreError>;
+    async fn put_role(&self, name: &str, obj: &Role) -> Result<(), StoreError>;
+    async fn list_roles(&self) -> Result<Vec<Role>, StoreError>;
+    async fn delete_role(&self, name: &str) -> Result<(), StoreError>;
+    async fn get_clusterrole(&self, name: &str) -> Result<Option<ClusterRole>, StoreError>;
+    async fn put_clusterrole(&self, name: &str, obj: &ClusterRole) -> Result<(), StoreError>;
+    async fn list_clusterroles(&self) -> Result<Vec<ClusterRole>, StoreError>;
+    async fn delete_clusterrole(&self, name: &str) -> Result<(), StoreError>;
+    async fn get_rolebinding(&self, name: &str) -> Result<Option<RoleBinding>, StoreError>;
+    async fn put_rolebinding(&self, name: &str, obj: &RoleBinding) -> Result<(), StoreError>;
+    async fn list_rolebindings(&self) -> Result<Vec<RoleBinding>, StoreError>;
+    async fn delete_rolebinding(&self, name: &str) -> Result<(), StoreError>;
+    async fn get_clusterrolebinding(
+        &self,
+        name: &str,
+    ) -> Result<Option<ClusterRoleBinding>, StoreError>;
+    async fn put_clusterrolebinding(
+        &self,
+        name: &str,
+        obj: &ClusterRoleBinding,
+    ) -> Result<(), StoreError>;
+    async fn list_clusterrolebindings(&self) -> Result<Vec<ClusterRoleBinding>, StoreError>;
+    async fn delete_clusterrolebinding(&self, name: &str) -> Result<(), StoreError>;
 }
 
 /// Etcd-backed store for persisting cluster state
 pub struct EtcdStore {
     etcd: Mutex<Client>,
     timeout: u64,
+    /// Seals secrets, they are stored in plain text without it
+    cipher: Option<SecretCipher>,
 }
 
 impl EtcdStore {
     const POD_PREFIX: &'static str = "/cr8s/pods/";
     const NODE_PREFIX: &'static str = "/cr8s/nodes/";
+    const NODE_TOKEN_PREFIX: &'static str = "/cr8s/nodetokens/";
     const REPLICASET_PREFIX: &'static str = "/cr8s/replicasets/";
+    const PRIORITYCLASS_PREFIX: &'static str = "/cr8s/priorityclasses/";
+    const PODGROUP_PREFIX: &'static str = "/cr8s/podgroups/";
+    const PDB_PREFIX: &'static str = "/cr8s/poddisruptionbudgets/";
+    const CONFIGMAP_PREFIX: &'static str = "/cr8s/configmaps/";
+    const SECRET_PREFIX: &'static str = "/cr8s/secrets/";
+    const PV_PREFIX: &'static str = "/cr8s/persistentvolumes/";
+    const PVC_PREFIX: &'static str = "/cr8s/persistentvolumeclaims/";
+    const SERVICE_PREFIX: &'static str = "/cr8s/services/";
+    const ENDPOINTS_PREFIX: &'static str = "/cr8s/endpoints/";
+    const INGRESS_PREFIX: &'static str = "/cr8s/ingresses/";
+    const ROLE_PREFIX: &'static str = "/cr8s/roles/";
+    const CLUSTERROLE_PREFIX: &'static str = "/cr8s/clusterroles/";
+    const ROLEBINDING_PREFIX: &'static str = "/cr8s/rolebindings/";
+    const CLUSTERROLEBINDING_PREFIX: &'static str = "/cr8s/clusterrolebindings/";
 
     /// Creates a new EtcdStore instance, connecting to the ETCD_ADDR environment variable.
-    pub async fn new() -> Self {
+    pub async fn new(cipher: Option<SecretCipher>) -> Self {
         let etcd_addr = std::env::var("ETCD_ADDR").unwrap_or_else(|_| "etcd:2379".to_string());
 
         let opts = ConnectOptions::default()
@@ -62,6 +195,7 @@ impl EtcdStore {
         Self {
             etcd: Mutex::new(etcd),
             timeout: 300,
+            cipher,
         }
     }
     fn pod_prefix() -> &'static str {
@@ -73,15 +207,102 @@ impl EtcdStore {
     fn replicaset_prefix() -> &'static str {
         Self::REPLICASET_PREFIX
     }
+    fn priorityclass_prefix() -> &'static str {
+        Self::PRIORITYCLASS_PREFIX
+    }
+    fn podgroup_prefix() -> &'static str {
+        Self::PODGROUP_PREFIX
+    }
+    fn pdb_prefix() -> &'static str {
+        Self::PDB_PREFIX
+    }
+    fn configmap_prefix() -> &'static str {
+        Self::CONFIGMAP_PREFIX
+    }
+    fn secret_prefix() -> &'static str {
+        Self::SECRET_PREFIX
+    }
+    fn pv_prefix() -> &'static str {
+        Self::PV_PREFIX
+    }
+    fn pvc_prefix() -> &'static str {
+        Self::PVC_PREFIX
+    }
+    fn service_prefix() -> &'static str {
+        Self::SERVICE_PREFIX
+    }
+    fn endpoints_prefix() -> &'static str {
+        Self::ENDPOINTS_PREFIX
+    }
+    fn ingress_prefix() -> &'static str {
+        Self::INGRESS_PREFIX
+    }
+    fn role_prefix() -> &'static str {
+        Self::ROLE_PREFIX
+    }
+    fn clusterrole_prefix() -> &'static str {
+        Self::CLUSTERROLE_PREFIX
+    }
+    fn rolebinding_prefix() -> &'static str {
+        Self::ROLEBINDING_PREFIX
+    }
+    fn clusterrolebinding_prefix() -> &'static str {
+        Self::CLUSTERROLEBINDING_PREFIX
+    }
     fn pod_key(id: &Uuid) -> String {
         format!("{}{}", Self::POD_PREFIX, id)
     }
     fn node_key(name: &str) -> String {
         format!("{}{}", Self::NODE_PREFIX, name)
     }
+    fn node_token_key(name: &str) -> String {
+        format!("{}{}", Self::NODE_TOKEN_PREFIX, name)
+    }
     fn replicaset_key(id: &Uuid) -> String {
         format!("{}{}", Self::REPLICASET_PREFIX, id)
     }
+    fn priorityclass_key(name: &str) -> String {
+        format!("{}{}", Self::PRIORITYCLASS_PREFIX, name)
+    }
+    fn podgroup_key(name: &str) -> String {
+        format!("{}{}", Self::PODGROUP_PREFIX, name)
+    }
+    fn pdb_key(name: &str) -> String {
+        format!("{}{}", Self::PDB_PREFIX, name)
+    }
+    fn configmap_key(name: &str) -> String {
+        format!("{}{}", Self::CONFIGMAP_PREFIX, name)
+    }
+    fn secret_key(name: &str) -> String {
+        format!("{}{}", Self::SECRET_PREFIX, name)
+    }
+    fn pv_key(name: &str) -> String {
+        format!("{}{}", Self::PV_PREFIX, name)
+    }
+    fn pvc_key(name: &str) -> String {
+        format!("{}{}", Self::PVC_PREFIX, name)
+    }
+    fn service_key(name: &str) -> String {
+        format!("{}{}", Self::SERVICE_PREFIX, name)
+    }
+    fn endpoints_key(name: &str) -> String {
+        format!("{}{}", Self::ENDPOINTS_PREFIX, name)
+    }
+    fn ingress_key(name: &str) -> String {
+        format!("{}{}", Self::INGRESS_PREFIX, name)
+    }
+    fn role_key(name: &str) -> String {
+        format!("{}{}", Self::ROLE_PREFIX, name)
+    }
+    fn clusterrole_key(name: &str) -> String {
+        format!("{}{}", Self::CLUSTERROLE_PREFIX, name)
+    }
+    fn rolebinding_key(name: &str) -> String {
+        format!("{}{}", Self::ROLEBINDING_PREFIX, name)
+    }
+    fn clusterrolebinding_key(name: &str) -> String {
+        format!("{}{}", Self::CLUSTERROLEBINDING_PREFIX, name)
+    }
 
     async fn with_timeout<T, F>(&self, fut: F) -> Result<T, StoreError>
     where
@@ -110,6 +331,17 @@ impl EtcdStore {
     where
         T: DeserializeOwned,
     {
+        self.get_raw(key)
+            .await?
+            .map(|val| {
+                serde_json::from_str::<T>(&val)
+                    .map_err(|e| StoreError::UnexpectedError(e.to_string()))
+            })
+            .transpose()
+    }
+
+    /// Retrieves the value stored at a key as it is.
+    async fn get_raw(&self, key: &str) -> Result<Option<String>, StoreError> {
         // pretty rust
         let mut client = self.etcd.lock().await;
         self.with_timeout(client.get(key, None))
@@ -121,11 +353,8 @@ impl EtcdStore {
             .kvs()
             .first()
             .map(|kv| {
-                let val = kv
-                    .value_str()
-                    .map_err(|e| StoreError::UnexpectedError(e.to_string()))?;
-
-                serde_json::from_str::<T>(val)
+                kv.value_str()
+                    .map(str::to_string)
                     .map_err(|e| StoreError::UnexpectedError(e.to_string()))
             })
             .transpose()
@@ -138,6 +367,11 @@ impl EtcdStore {
     {
         let json =
             serde_json::to_string(value).map_err(|e| StoreError::UnexpectedError(e.to_string()))?;
+        self.put_raw(key, json).await
+    }
+
+    /// Writes a value to etcd as it is.
+    async fn put_raw(&self, key: &str, json: String) -> Result<(), StoreError> {
         let mut client = self.etcd.lock().await;
         self.with_timeout(client.put(key, json, None))
             .await
@@ -150,6 +384,16 @@ impl EtcdStore {
     where
         T: DeserializeOwned,
     {
+        Ok(self
+            .list_raw(prefix)
+            .await?
+            .iter()
+            .filter_map(|(_, val)| serde_json::from_str::<T>(val).ok())
+            .collect())
+    }
+
+    /// Lists the keys and values stored under a prefix as they are.
+    async fn list_raw(&self, prefix: &str) -> Result<Vec<(String, String)>, StoreError> {
         // pretty rust
         let mut client = self.etcd.lock().await;
         Ok(self
@@ -161,9 +405,20 @@ impl EtcdStore {
             })?
             .kvs()
             .iter()
-            .filter_map(|kv| serde_json::from_str::<T>(kv.value_str().ok()?).ok())
+            .filter_map(|kv| {
+                Some((
+                    kv.key_str().ok()?.to_string(),
+                    kv.value_str().ok()?.to_string(),
+                ))
+            })
             .collect())
     }
+
+    /// Opens a secret read from etcd, plain ones are read as they are.
+    fn decode_secret(&self, key: &str, value: &str) -> Result<Secret, StoreError> {
+        let json = encryption::open(self.cipher.as_ref(), key, value)?;
+        serde_json::from_slice(&json).map_err(|e| StoreError::UnexpectedError(e.to_string()))
+    }
 }
 
 #[async_trait]
@@ -206,4 +461,276 @@ impl Store for EtcdStore {
     async fn list_nodes(&self) -> Result<Vec<Node>, StoreError> {
         self.list_objects::<Node>(Self::node_prefix()).await
     }
+    async fn delete_node(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::node_token_key(name)).await?;
+        self.delete_object(&Self::node_key(name)).await
+    }
+    async fn get_node_token(&self, name: &str) -> Result<Option<String>, StoreError> {
+        self.get_object::<String>(&Self::node_token_key(name)).await
+    }
+    async fn put_node_token(&self, name: &str, token: &str) -> Result<(), StoreError> {
+        self.put_object::<String>(&Self::node_token_key(name), &token.to_string())
+            .await
+    }
+
+    async fn get_priorityclass(&self, name: &str) -> Result<Option<PriorityClass>, StoreError> {
+        self.get_object::<PriorityClass>(&Self::priorityclass_key(name))
+            .await
+    }
+    async fn put_priorityclass(&self, name: &str, pc: &PriorityClass) -> Result<(), StoreError> {
+        self.put_object::<PriorityClass>(&Self::priorityclass_key(name), pc)
+            .await
+    }
+    async fn list_priorityclasses(&self) -> Result<Vec<PriorityClass>, StoreError> {
+        self.list_objects::<PriorityClass>(Self::priorityclass_prefix())
+            .await
+    }
+    async fn delete_priorityclass(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::priorityclass_key(name)).await
+    }
+
+    async fn get_podgroup(&self, name: &str) -> Result<Option<PodGroup>, StoreError> {
+        self.get_object::<PodGroup>(&Self::podgroup_key(name)).await
+    }
+    async fn put_podgroup(&self, name: &str, group: &PodGroup) -> Result<(), StoreError> {
+        self.put_object::<PodGroup>(&Self::podgroup_key(name), group)
+            .await
+    }
+    async fn list_podgroups(&self) -> Result<Vec<PodGroup>, StoreError> {
+        self.list_objects::<PodGroup>(Self::podgroup_prefix()).await
+    }
+    async fn delete_podgroup(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::podgroup_key(name)).await
+    }
+
+    async fn get_poddisruptionbudget(
+        &self,
+        name: &str,
+    ) -> Result<Option<PodDisruptionBudget>, StoreError> {
+        self.get_object::<PodDisruptionBudget>(&Self::pdb_key(name))
+            .await
+    }
+    async fn put_poddisruptionbudget(
+        &self,
+        name: &str,
+        pdb: &PodDisruptionBudget,
+    ) -> Result<(), StoreError> {
+        self.put_object::<PodDisruptionBudget>(&Self::pdb_key(name), pdb)
+            .await
+    }
+    async fn list_poddisruptionbudgets(&self) -> Result<Vec<PodDisruptionBudget>, StoreError> {
+        self.list_objects::<PodDisruptionBudget>(Self::pdb_prefix())
+            .await
+    }
+    async fn delete_poddisruptionbudget(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::pdb_key(name)).await
+    }
+
+    async fn get_configmap(&self, name: &str) -> Result<Option<ConfigMap>, StoreError> {
+        self.get_object::<ConfigMap>(&Self::configmap_key(name))
+            .await
+    }
+    async fn put_configmap(&self, name: &str, cm: &ConfigMap) -> Result<(), StoreError> {
+        self.put_object::<ConfigMap>(&Self::configmap_key(name), cm)
+            .await
+    }
+    async fn list_configmaps(&self) -> Result<Vec<ConfigMap>, StoreError> {
+        self.list_objects::<ConfigMap>(Self::configmap_prefix())
+            .await
+    }
+    async fn delete_configmap(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::configmap_key(name)).await
+    }
+
+    async fn get_secret(&self, name: &str) -> Result<Option<Secret>, StoreError> {
+        let key = Self::secret_key(name);
+        self.get_raw(&key)
+            .await?
+            .map(|val| self.decode_secret(&key, &val))
+            .transpose()
+    }
+    async fn put_secret(&self, name: &str, secret: &Secret) -> Result<(), StoreError> {
+        let key = Self::secret_key(name);
+        let json = serde_json::to_string(secret)
+            .map_err(|e| StoreError::UnexpectedError(e.to_string()))?;
+        let value = match &self.cipher {
+            Some(cipher) => cipher.encrypt(&key, json.as_bytes())?,
+            None => json,
+        };
+        self.put_raw(&key, value).await
+    }
+    async fn list_secrets(&self) -> Result<Vec<Secret>, StoreError> {
+        Ok(self
+            .list_raw(Self::secret_prefix())
+            .await?
+            .iter()
+            .filter_map(|(key, val)| {
+                self.decode_secret(key, val)
+                    .inspect_err(|e| tracing::error!(%key, error=%e, "Could not read secret"))
+                    .ok()
+            })
+            .collect())
+    }
+    async fn delete_secret(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::secret_key(name)).await
+    }
+
+    async fn get_persistentvolume(
+        &self,
+        name: &str,
+    ) -> Result<Option<PersistentVolume>, StoreError> {
+        self.get_object::<PersistentVolume>(&Self::pv_key(name))
+            .await
+    }
+    async fn put_persistentvolume(
+        &self,
+        name: &str,
+        pv: &PersistentVolume,
+    ) -> Result<(), StoreError> {
+        self.put_object::<PersistentVolume>(&Self::pv_key(name), pv)
+            .await
+    }
+    async fn list_persistentvolumes(&self) -> Result<Vec<PersistentVolume>, StoreError> {
+        self.list_objects::<PersistentVolume>(Self::pv_prefix())
+            .await
+    }
+    async fn delete_persistentvolume(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::pv_key(name)).await
+    }
+
+    async fn get_persistentvolumeclaim(
+        &self,
+        name: &str,
+    ) -> Result<Option<PersistentVolumeClaim>, StoreError> {
+        self.get_object::<PersistentVolumeClaim>(&Self::pvc_key(name))
+            .await
+    }
+    async fn put_persistentvolumeclaim(
+        &self,
+        name: &str,
+        pvc: &PersistentVolumeClaim,
+    ) -> Result<(), StoreError> {
+        self.put_object::<PersistentVolumeClaim>(&Self::pvc_key(name), pvc)
+            .await
+    }
+    async fn list_persistentvolumeclaims(&self) -> Result<Vec<PersistentVolumeClaim>, StoreError> {
+        self.list_objects::<PersistentVolumeClaim>(Self::pvc_prefix())
+            .await
+    }
+    async fn delete_persistentvolumeclaim(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::pvc_key(name)).await
+    }
+
+    async fn get_service(&self, name: &str) -> Result<Option<Service>, StoreError> {
+        self.get_object::<Service>(&Self::service_key(name)).await
+    }
+    async fn put_service(&self, name: &str, svc: &Service) -> Result<(), StoreError> {
+        self.put_object::<Service>(&Self::service_key(name), svc)
+            .await
+    }
+    async fn list_services(&self) -> Result<Vec<Service>, StoreError> {
+        self.list_objects::<Service>(Self::service_prefix()).await
+    }
+    async fn delete_service(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::service_key(name)).await
+    }
+
+    async fn get_endpoints(&self, name: &str) -> Result<Option<Endpoints>, StoreError> {
+        self.get_object::<Endpoints>(&Self::endpoints_key(name))
+            .await
+    }
+    async fn put_endpoints(&self, name: &str, ep: &Endpoints) -> Result<(), StoreError> {
+        self.put_object::<Endpoints>(&Self::endpoints_key(name), ep)
+            .await
+    }
+    async fn list_endpoints(&self) -> Result<Vec<Endpoints>, StoreError> {
+        self.list_objects::<Endpoints>(Self::endpoints_prefix())
+            .await
+    }
+    async fn delete_endpoints(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::endpoints_key(name)).await
+    }
+
+    async fn get_ingress(&self, name: &str) -> Result<Option<Ingress>, StoreError> {
+        self.get_object::<Ingress>(&Self::ingress_key(name)).await
+    }
+    async fn put_ingress(&self, name: &str, ing: &Ingress) -> Result<(), StoreError> {
+        self.put_object::<Ingress>(&Self::ingress_key(name), ing)
+            .await
+    }
+    async fn list_ingresses(&self) -> Result<Vec<Ingress>, StoreError> {
+        self.list_objects::<Ingress>(Self::ingress_prefix()).await
+    }
+    async fn delete_ingress(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::ingress_key(name)).await
+    }
+
+    async fn get_role(&self, name: &str) -> Result<Option<Role>, StoreError> {
+        self.get_object::<Role>(&Self::role_key(name)).await
+    }
+    async fn put_role(&self, name: &str, obj: &Role) -> Result<(), StoreError> {
+        self.put_object::<Role>(&Self::role_key(name), obj).await
+    }
+    async fn list_roles(&self) -> Result<Vec<Role>, StoreError> {
+        self.list_objects::<Role>(Self::role_prefix()).await
+    }
+    async fn delete_role(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::role_key(name)).await
+    }
+
+    async fn get_clusterrole(&self, name: &str) -> Result<Option<ClusterRole>, StoreError> {
+        self.get_object::<ClusterRole>(&Self::clusterrole_key(name))
+            .await
+    }
+    async fn put_clusterrole(&self, name: &str, obj: &ClusterRole) -> Result<(), StoreError> {
+        self.put_object::<ClusterRole>(&Self::clusterrole_key(name), obj)
+            .await
+    }
+    async fn list_clusterroles(&self) -> Result<Vec<ClusterRole>, StoreError> {
+        self.list_objects::<ClusterRole>(Self::clusterrole_prefix())
+            .await
+    }
+    async fn delete_clusterrole(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::clusterrole_key(name)).await
+    }
+
+    async fn get_rolebinding(&self, name: &str) -> Result<Option<RoleBinding>, StoreError> {
+        self.get_object::<RoleBinding>(&Self::rolebinding_key(name))
+            .await
+    }
+    async fn put_rolebinding(&self, name: &str, obj: &RoleBinding) -> Result<(), StoreError> {
+        self.put_object::<RoleBinding>(&Self::rolebinding_key(name), obj)
+            .await
+    }
+    async fn list_rolebindings(&self) -> Result<Vec<RoleBinding>, StoreError> {
+        self.list_objects::<RoleBinding>(Self::rolebinding_prefix())
+            .await
+    }
+    async fn delete_rolebinding(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::rolebinding_key(name)).await
+    }
+
+    async fn get_clusterrolebinding(
+        &self,
+        name: &str,
+    ) -> Result<Option<ClusterRoleBinding>, StoreError> {
+        self.get_object::<ClusterRoleBinding>(&Self::clusterrolebinding_key(name))
+            .await
+    }
+    async fn put_clusterrolebinding(
+        &self,
+        name: &str,
+        obj: &ClusterRoleBinding,
+    ) -> Result<(), StoreError> {
+        self.put_object::<ClusterRoleBinding>(&Self::clusterrolebinding_key(name), obj)
+            .await
+    }
+    async fn list_clusterrolebindings(&self) -> Result<Vec<ClusterRoleBinding>, StoreError> {
+        self.list_objects::<ClusterRoleBinding>(Self::clusterrolebinding_prefix())
+            .await
+    }
+    async fn delete_clusterrolebinding(&self, name: &str) -> Result<(), StoreError> {
+        self.delete_object(&Self::clusterrolebinding_key(name))
+            .await
+    }
 }
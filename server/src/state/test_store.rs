@@ -6,14 +6,44 @@ use super::errors::StoreError;
 use super::store::Store;
 use async_trait::async_trait;
 use dashmap::DashMap;
-use shared::models::{node::Node, pod::Pod, replicaset::ReplicaSet};
+use shared::models::{
+    configmap::ConfigMap,
+    endpoints::Endpoints,
+    ingress::Ingress,
+    node::Node,
+    persistentvolume::PersistentVolume,
+    persistentvolumeclaim::PersistentVolumeClaim,
+    pod::Pod,
+    poddisruptionbudget::PodDisruptionBudget,
+    podgroup::PodGroup,
+    priorityclass::PriorityClass,
+    rbac::{ClusterRole, ClusterRoleBinding, Role, RoleBinding},
+    replicaset::ReplicaSet,
+    secret::Secret,
+    service::Service,
+};
 use uuid::Uuid;
 
 /// An in-memory implementation of `Store`, used for testing purposes.
 pub struct TestStore {
     pub pods: DashMap<Uuid, Pod>,
     pub nodes: DashMap<String, Node>,
+    pub node_tokens: DashMap<String, String>,
     pub replicasets: DashMap<Uuid, ReplicaSet>,
+    pub priorityclasses: DashMap<String, PriorityClass>,
+    pub podgroups: DashMap<String, PodGroup>,
+    pub poddisruptionbudgets: DashMap<String, PodDisruptionBudget>,
+    pub configmaps: DashMap<String, ConfigMap>,
+    pub secrets: DashMap<String, Secret>,
+    pub persistentvolumes: DashMap<String, PersistentVolume>,
+    pub persistentvolumeclaims: DashMap<String, PersistentVolumeClaim>,
+    pub services: DashMap<String, Service>,
+    pub endpoints: DashMap<String, Endpoints>,
+    pub ingresses: DashMap<String, Ingress>,
+    pub roles: DashMap<String, Role>,
+    pub clusterroles: DashMap<String, ClusterRole>,
+    pub rolebindings: DashMap<String, RoleBinding>,
+    pub clusterrolebindings: DashMap<String, ClusterRoleBinding>,
 }
 
 impl TestStore {
@@ -21,7 +51,22 @@ impl TestStore {
         Self {
             pods: DashMap::new(),
             nodes: DashMap::new(),
+            node_tokens: DashMap::new(),
             replicasets: DashMap::new(),
+            priorityclasses: DashMap::new(),
+            podgroups: DashMap::new(),
+            poddisruptionbudgets: DashMap::new(),
+            configmaps: DashMap::new(),
+            secrets: DashMap::new(),
+            persistentvolumes: DashMap::new(),
+            persistentvolumeclaims: DashMap::new(),
+            services: DashMap::new(),
+            endpoints: DashMap::new(),
+            ingresses: DashMap::new(),
+            roles: DashMap::new(),
+            clusterroles: DashMap::new(),
+            rolebindings: DashMap::new(),
+            clusterrolebindings: DashMap::new(),
         }
     }
 }
@@ -67,6 +112,24 @@ impl Store for TestStore {
             .collect())
     }
 
+    async fn delete_node(&self, name: &str) -> Result<(), StoreError> {
+        self.node_tokens.remove(name);
+        self.nodes.remove(name);
+        Ok(())
+    }
+
+    async fn get_node_token(&self, name: &str) -> Result<Option<String>, StoreError> {
+        Ok(self
+            .node_tokens
+            .get(name)
+            .map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_node_token(&self, name: &str, token: &str) -> Result<(), StoreError> {
+        self.node_tokens.insert(name.to_string(), token.to_string());
+        Ok(())
+    }
+
     async fn get_replicaset(&self, id: Uuid) -> Result<Option<ReplicaSet>, StoreError> {
         Ok(self.replicasets.get(&id).map(|ref_entry| ref_entry.clone()))
     }
@@ -88,4 +151,364 @@ impl Store for TestStore {
         self.replicasets.remove(id);
         Ok(())
     }
+
+    async fn get_priorityclass(&self, name: &str) -> Result<Option<PriorityClass>, StoreError> {
+        Ok(self
+            .priorityclasses
+            .get(name)
+            .map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_priorityclass(&self, name: &str, pc: &PriorityClass) -> Result<(), StoreError> {
+        self.priorityclasses.insert(name.to_string(), pc.clone());
+        Ok(())
+    }
+
+    async fn list_priorityclasses(&self) -> Result<Vec<PriorityClass>, StoreError> {
+        Ok(self
+            .priorityclasses
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn delete_priorityclass(&self, name: &str) -> Result<(), StoreError> {
+        self.priorityclasses.remove(name);
+        Ok(())
+    }
+
+    async fn get_podgroup(&self, name: &str) -> Result<Option<PodGroup>, StoreError> {
+        Ok(self.podgroups.get(name).map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_podgroup(&self, name: &str, group: &PodGroup) -> Result<(), StoreError> {
+        self.podgroups.insert(name.to_string(), group.clone());
+        Ok(())
+    }
+
+    async fn list_podgroups(&self) -> Result<Vec<PodGroup>, StoreError> {
+        Ok(self
+            .podgroups
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn delete_podgroup(&self, name: &str) -> Result<(), StoreError> {
+        self.podgroups.remove(name);
+        Ok(())
+    }
+
+    async fn get_poddisruptionbudget(
+        &self,
+        name: &str,
+    ) -> Result<Option<PodDisruptionBudget>, StoreError> {
+        Ok(self
+            .poddisruptionbudgets
+            .get(name)
+            .map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_poddisruptionbudget(
+        &self,
+        name: &str,
+        pdb: &PodDisruptionBudget,
+    ) -> Result<(), StoreError> {
+        self.poddisruptionbudgets
+            .insert(name.to_string(), pdb.clone());
+        Ok(())
+    }
+
+    async fn list_poddisruptionbudgets(&self) -> Result<Vec<PodDisruptionBudget>, StoreError> {
+        Ok(self
+            .poddisruptionbudgets
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn delete_poddisruptionbudget(&self, name: &str) -> Result<(), StoreError> {
+        self.poddisruptionbudgets.remove(name);
+        Ok(())
+    }
+
+    async fn get_configmap(&self, name: &str) -> Result<Option<ConfigMap>, StoreError> {
+        Ok(self.configmaps.get(name).map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_configmap(&self, name: &str, cm: &ConfigMap) -> Result<(), StoreError> {
+        self.configmaps.insert(name.to_string(), cm.clone());
+        Ok(())
+    }
+
+    async fn list_configmaps(&self) -> Result<Vec<ConfigMap>, StoreError> {
+        Ok(self
+            .configmaps
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn delete_configmap(&self, name: &str) -> Result<(), StoreError> {
+        self.configmaps.remove(name);
+        Ok(())
+    }
+
+    async fn get_secret(&self, name: &str) -> Result<Option<Secret>, StoreError> {
+        Ok(self.secrets.get(name).map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_secret(&self, name: &str, secret: &Secret) -> Result<(), StoreError> {
+        self.secrets.insert(name.to_string(), secret.clone());
+        Ok(())
+    }
+
+    async fn list_secrets(&self) -> Result<Vec<Secret>, StoreError> {
+        Ok(self
+            .secrets
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn delete_secret(&self, name: &str) -> Result<(), StoreError> {
+        self.secrets.remove(name);
+        Ok(())
+    }
+
+    async fn get_persistentvolume(
+        &self,
+        name: &str,
+    ) -> Result<Option<PersistentVolume>, StoreError> {
+        Ok(self
+            .persistentvolumes
+            .get(name)
+            .map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_persistentvolume(
+        &self,
+        name: &str,
+        pv: &PersistentVolume,
+    ) -> Result<(), StoreError> {
+        self.persistentvolumes.insert(name.to_string(), pv.clone());
+        Ok(())
+    }
+
+    async fn list_persistentvolumes(&self) -> Result<Vec<PersistentVolume>, StoreError> {
+        Ok(self
+            .persistentvolumes
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn delete_persistentvolume(&self, name: &str) -> Result<(), StoreError> {
+        self.persistentvolumes.remove(name);
+        Ok(())
+    }
+
+    async fn get_persistentvolumeclaim(
+        &self,
+        name: &str,
+    ) -> Result<Option<PersistentVolumeClaim>, StoreError> {
+        Ok(self
+            .persistentvolumeclaims
+            .get(name)
+            .map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_persistentvolumeclaim(
+        &self,
+        name: &str,
+        pvc: &PersistentVolumeClaim,
+    ) -> Result<(), StoreError> {
+        self.persistentvolumeclaims
+            .insert(name.to_string(), pvc.clone());
+        Ok(())
+    }
+
+    async fn list_persistentvolumeclaims(&self) -> Result<Vec<PersistentVolumeClaim>, StoreError> {
+        Ok(self
+            .persistentvolumeclaims
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn delete_persistentvolumeclaim(&self, name: &str) -> Result<(), StoreError> {
+        self.persistentvolumeclaims.remove(name);
+        Ok(())
+    }
+
+    async fn get_service(&self, name: &str) -> Result<Option<Service>, StoreError> {
+        Ok(self.services.get(name).map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_service(&self, name: &str, svc: &Service) -> Result<(), StoreError> {
+        self.services.insert(name.to_string(), svc.clone());
+        Ok(())
+    }
+
+    async fn list_services(&self) -> Result<Vec<Service>, StoreError> {
+        Ok(self
+            .services
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn delete_service(&self, name: &str) -> Result<(), StoreError> {
+        self.services.remove(name);
+        Ok(())
+    }
+
+    async fn get_endpoints(&self, name: &str) -> Result<Option<Endpoints>, StoreError> {
+        Ok(self.endpoints.get(name).map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_endpoints(&self, name: &str, ep: &Endpoints) -> Result<(), StoreError> {
+        self.endpoints.insert(name.to_string(), ep.clone());
+        Ok(())
+    }
+
+    async fn list_endpoints(&self) -> Result<Vec<Endpoints>, StoreError> {
+        Ok(self
+            .endpoints
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn delete_endpoints(&self, name: &str) -> Result<(), StoreError> {
+        self.endpoints.remove(name);
+        Ok(())
+    }
+
+    async fn get_ingress(&self, name: &str) -> Result<Option<Ingress>, StoreError> {
+        Ok(self.ingresses.get(name).map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_ingress(&self, name: &str, ing: &Ingress) -> Result<(), StoreError> {
+        self.ingresses.insert(name.to_string(), ing.clone());
+        Ok(())
+    }
+
+    async fn list_ingresses(&self) -> Result<Vec<Ingress>, StoreError> {
+        Ok(self
+            .ingresses
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn delete_ingress(&self, name: &str) -> Result<(), StoreError> {
+        self.ingresses.remove(name);
+        Ok(())
+    }
+
+    async fn get_role(&self, name: &str) -> Result<Option<Role>, StoreError> {
+        Ok(self.roles.get(name).map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_role(&self, name: &str, obj: &Role) -> Result<(), StoreError> {
+        self.roles.insert(name.to_string(), obj.clone());
+        Ok(())
+    }
+
+    async fn list_roles(&self) -> Result<Vec<Role>, StoreError> {
+        Ok(self
+            .roles
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn delete_role(&self, name: &str) -> Result<(), StoreError> {
+        self.roles.remove(name);
+        Ok(())
+    }
+
+    async fn get_clusterrole(&self, name: &str) -> Result<Option<ClusterRole>, StoreError> {
+        Ok(self
+            .clusterroles
+            .get(name)
+            .map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_clusterrole(&self, name: &str, obj: &ClusterRole) -> Result<(), StoreError> {
+        self.clusterroles.insert(name.to_string(), obj.clone());
+        Ok(())
+    }
+
+    async fn list_clusterroles(&self) -> Result<Vec<ClusterRole>, StoreError> {
+        Ok(self
+            .clusterroles
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn delete_clusterrole(&self, name: &str) -> Result<(), StoreError> {
+        self.clusterroles.remove(name);
+        Ok(())
+    }
+
+    async fn get_rolebinding(&self, name: &str) -> Result<Option<RoleBinding>, StoreError> {
+        Ok(self
+            .rolebindings
+            .get(name)
+            .map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_rolebinding(&self, name: &str, obj: &RoleBinding) -> Result<(), StoreError> {
+        self.rolebindings.insert(name.to_string(), obj.clone());
+        Ok(())
+    }
+
+    async fn list_rolebindings(&self) -> Result<Vec<RoleBinding>, StoreError> {
+        Ok(self
+            .rolebindings
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn delete_rolebinding(&self, name: &str) -> Result<(), StoreError> {
+        self.rolebindings.remove(name);
+        Ok(())
+    }
+
+    async fn get_clusterrolebinding(
+        &self,
+        name: &str,
+    ) -> Result<Option<ClusterRoleBinding>, StoreError> {
+        Ok(self
+            .clusterrolebindings
+            .get(name)
+            .map(|ref_entry| ref_entry.clone()))
+    }
+
+    async fn put_clusterrolebinding(
+        &self,
+        name: &str,
+        obj: &ClusterRoleBinding,
+    ) -> Result<(), StoreError> {
+        self.clusterrolebindings
+            .insert(name.to_string(), obj.clone());
+        Ok(())
+    }
+
+    async fn list_clusterrolebindings(&self) -> Result<Vec<ClusterRoleBinding>, StoreError> {
+        Ok(self
+            .clusterrolebindings
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn delete_clusterrolebinding(&self, name: &str) -> Result<(), StoreError> {
+        self.clusterrolebindings.remove(name);
+        Ok(())
+    }
 }
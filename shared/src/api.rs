@@ -6,10 +6,24 @@ use serde_json::Value;
 use uuid::Uuid;
 
 use crate::models::{
-    metadata::ObjectMetadata,
-    node::Node,
-    pod::{ContainerSpec, Pod, PodStatus},
+    configmap::ConfigMapSpec,
+    endpoints::Endpoints,
+    ingress::{Ingress, IngressSpec},
+    metadata::{LabelSelector, ObjectMetadata},
+    node::{Node, Taint},
+    persistentvolume::PersistentVolumeSpec,
+    persistentvolumeclaim::{PersistentVolumeClaim, PersistentVolumeClaimSpec},
+    pod::{
+        ContainerSpec, Pod, PodCondition, PodStatus, RestartPolicy, Toleration, Volume,
+        default_grace_period,
+    },
+    poddisruptionbudget::PodDisruptionBudgetSpec,
+    podgroup::{PodGroup, PodGroupSpec},
+    priorityclass::PriorityClassSpec,
+    rbac::{ClusterRoleBindingSpec, ClusterRoleSpec, RoleBindingSpec, RoleSpec},
     replicaset::{ReplicaSet, ReplicaSetSpec},
+    secret::SecretSpec,
+    service::{Service, ServiceSpec},
 };
 
 // --- Query Params ---
@@ -24,6 +38,14 @@ pub struct PodQueryParams {
     pub label_selector: String,
 }
 
+/// Query parameters for deleting a pod.
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct DeletePodParams {
+    /// Overrides the pod's grace period, 0 removes the pod right away
+    #[serde(rename = "gracePeriodSeconds")]
+    pub grace_period_seconds: Option<u32>,
+}
+
 /// Fetching logs from a container.
 #[derive(Deserialize, Debug)]
 pub struct LogsQueryParams {
@@ -44,6 +66,70 @@ pub struct CreatePodParams {
 pub struct NodeRegisterReq {
     pub port: u16,
     pub name: String,
+    #[serde(default)]
+    pub taints: Vec<Taint>,
+    /// Token from a previous registration, lets a restarted node reclaim its name.
+    #[serde(default)]
+    pub token: Option<String>,
+    /// PEM public key the cluster CA issues the node's serving certificate for.
+    #[serde(default)]
+    pub serving_key: Option<String>,
+}
+
+/// Response returned to a node after a successful registration.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct NodeRegisterResp {
+    pub id: Uuid,
+    pub token: String,
+    /// PEM serving certificate, when a key was sent and the apiserver runs a cluster CA.
+    #[serde(default)]
+    pub serving_cert: Option<String>,
+}
+
+/// Partial update of a node's spec, unset fields are left unchanged.
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct NodePatch {
+    #[serde(default)]
+    pub spec: NodeSpecPatch,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct NodeSpecPatch {
+    pub unschedulable: Option<bool>,
+    pub taints: Option<Vec<Taint>>,
+}
+
+/// Request for a signed service account token.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TokenRequest {
+    pub service_account: String,
+    /// Groups besides `system:serviceaccounts`
+    #[serde(default)]
+    pub groups: Vec<String>,
+    /// Lifetime of the token, it never expires without one
+    #[serde(default)]
+    pub expiration_seconds: Option<u64>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TokenResponse {
+    pub token: String,
+}
+
+/// Asks whether the caller may perform an action.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AccessReviewRequest {
+    pub verb: String,
+    pub resource: String,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AccessReviewResponse {
+    pub allowed: bool,
+    /// Binding that allowed the action, or why it was denied
+    pub reason: String,
 }
 
 /// Response returned when a pod or resource is created.
@@ -68,9 +154,135 @@ pub struct ReplicaSetManifest {
     pub spec: ReplicaSetSpec,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PriorityClassManifest {
+    pub metadata: ObjectMetadata,
+    pub spec: PriorityClassSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ConfigMapManifest {
+    pub metadata: ObjectMetadata,
+    pub spec: ConfigMapSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SecretManifest {
+    pub metadata: ObjectMetadata,
+    pub spec: SecretSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PersistentVolumeManifest {
+    pub metadata: ObjectMetadata,
+    pub spec: PersistentVolumeSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PersistentVolumeClaimManifest {
+    pub metadata: ObjectMetadata,
+    pub spec: PersistentVolumeClaimSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ServiceManifest {
+    pub metadata: ObjectMetadata,
+    pub spec: ServiceSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IngressManifest {
+    pub metadata: ObjectMetadata,
+    pub spec: IngressSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RoleManifest {
+    pub metadata: ObjectMetadata,
+    pub spec: RoleSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClusterRoleManifest {
+    pub metadata: ObjectMetadata,
+    pub spec: ClusterRoleSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RoleBindingManifest {
+    pub metadata: ObjectMetadata,
+    pub spec: RoleBindingSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClusterRoleBindingManifest {
+    pub metadata: ObjectMetadata,
+    pub spec: ClusterRoleBindingSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PodDisruptionBudgetManifest {
+    pub metadata: ObjectMetadata,
+    pub spec: PodDisruptionBudgetSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PodGroupManifest {
+    pub metadata: ObjectMetadata,
+    pub spec: PodGroupSpec,
+}
+
 #[derive(Deserialize, Clone, Serialize, Debug, Default)]
 pub struct PodContainers {
     pub containers: Vec<ContainerSpec>,
+    #[serde(rename = "initContainers", default)]
+    pub init_containers: Vec<ContainerSpec>,
+    #[serde(rename = "restartPolicy", default)]
+    pub restart_policy: RestartPolicy,
+    #[serde(default)]
+    pub volumes: Vec<Volume>,
+    #[serde(rename = "priorityClassName", default)]
+    pub priority_class_name: Option<String>,
+    #[serde(default)]
+    pub tolerations: Vec<Toleration>,
+    #[serde(rename = "podAntiAffinity", default)]
+    pub pod_anti_affinity: Option<LabelSelector>,
+    #[serde(
+        rename = "terminationGracePeriodSeconds",
+        default = "default_grace_period"
+    )]
+    pub termination_grace_period_seconds: u32,
+}
+
+// --- Scheduler simulation ---
+
+/// Pods to place in a scheduler dry run, in order.
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct SimulationRequest {
+    pub pods: Vec<PodManifest>,
+}
+
+/// Outcome of a dry run, one entry per requested pod.
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct SimulationResult {
+    pub pods: Vec<PodSimulation>,
+}
+
+/// Where a pod would land and how every node was judged.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PodSimulation {
+    pub pod_name: String,
+    pub node_name: Option<String>,
+    pub nodes: Vec<NodeVerdict>,
+}
+
+/// Filter and score verdict of a single node for a pod.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct NodeVerdict {
+    pub node_name: String,
+    pub feasible: bool,
+    pub score: Option<f64>,
+    pub reason: Option<String>,
 }
 
 // --- Pod and Node Events ---
@@ -96,6 +308,41 @@ pub struct ReplicaSetEvent {
     pub replicaset: ReplicaSet,
 }
 
+/// Event structure representing changes to a pod group.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PodGroupEvent {
+    pub event_type: EventType,
+    pub podgroup: PodGroup,
+}
+
+/// Event structure representing changes to a persistent volume claim.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PersistentVolumeClaimEvent {
+    pub event_type: EventType,
+    pub claim: PersistentVolumeClaim,
+}
+
+/// Event structure representing changes to a service.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ServiceEvent {
+    pub event_type: EventType,
+    pub service: Service,
+}
+
+/// Event structure representing changes to the endpoints of a service.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EndpointsEvent {
+    pub event_type: EventType,
+    pub endpoints: Endpoints,
+}
+
+/// Event structure representing changes to an ingress.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IngressEvent {
+    pub event_type: EventType,
+    pub ingress: Ingress,
+}
+
 /// Enum representing the type of event that occurred.
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum EventType {
@@ -122,6 +369,8 @@ pub enum PodField {
     Spec,
     #[serde(rename = "status")]
     Status,
+    #[serde(rename = "condition")]
+    Condition,
 }
 
 /// Message used to update the status of a pod and its containers.
@@ -130,3 +379,11 @@ pub struct PodStatusUpdate {
     pub node_name: String,
     pub status: PodStatus,
 }
+
+/// Message used by controllers to record a pod condition,
+/// optionally nominating the node the pod is expected to land on.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct PodConditionUpdate {
+    pub condition: PodCondition,
+    pub nominated_node_name: Option<String>,
+}
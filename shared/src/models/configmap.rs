@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::metadata::Metadata;
+
+// --- Core ---
+
+/// Non confidential configuration pods consume as environment variables or files.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ConfigMap {
+    pub metadata: Metadata,
+    pub spec: ConfigMapSpec,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ConfigMapSpec {
+    /// Keys double as file names when mounted
+    #[serde(default)]
+    pub data: HashMap<String, String>,
+}
+
+/// Total size allowed for the data of a config map or secret.
+pub const MAX_DATA_SIZE: usize = 1024 * 1024;
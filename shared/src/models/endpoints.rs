@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{metadata::Metadata, pod::Protocol};
+
+// --- Core ---
+
+/// Ready backends of the service with the same name, kept by the endpoints controller.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Endpoints {
+    pub metadata: Metadata,
+    #[serde(default)]
+    pub ports: Vec<EndpointPort>,
+}
+
+/// Addresses serving one port of the service.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct EndpointPort {
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Service port
+    pub port: u16,
+    #[serde(default)]
+    pub protocol: Protocol,
+    #[serde(default)]
+    pub addresses: Vec<EndpointAddress>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct EndpointAddress {
+    pub ip: String,
+    /// Target port on the pod
+    pub port: u16,
+    #[serde(rename = "podName")]
+    pub pod_name: String,
+    #[serde(rename = "nodeName")]
+    pub node_name: String,
+}
+
+// --- Impl ---
+
+impl Endpoints {
+    /// Addresses of the service port with the given number and protocol.
+    pub fn addresses(&self, port: u16, protocol: Protocol) -> &[EndpointAddress] {
+        self.ports
+            .iter()
+            .find(|p| p.port == port && p.protocol == protocol)
+            .map(|p| p.addresses.as_slice())
+            .unwrap_or_default()
+    }
+}
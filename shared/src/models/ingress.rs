@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::metadata::Metadata;
+
+// --- Core ---
+
+/// HTTP routes from hosts and path prefixes to service ports.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Ingress {
+    pub metadata: Metadata,
+    pub spec: IngressSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IngressSpec {
+    pub rules: Vec<IngressRule>,
+    /// Hosts served over HTTPS and the secrets holding their certificates
+    #[serde(default)]
+    pub tls: Vec<IngressTls>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IngressRule {
+    /// Matches every host when unset
+    #[serde(default)]
+    pub host: Option<String>,
+    pub paths: Vec<IngressPath>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IngressPath {
+    /// Prefix matched on whole path segments, the longest one wins
+    #[serde(default = "root_path")]
+    pub path: String,
+    pub backend: IngressBackend,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct IngressBackend {
+    pub service: String,
+    /// Service port, not the target port of the pods
+    pub port: u16,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IngressTls {
+    pub hosts: Vec<String>,
+    /// Secret with the PEM encoded `tls.crt` and `tls.key`
+    #[serde(rename = "secretName")]
+    pub secret_name: String,
+}
+
+/// Secret key of the certificate chain.
+pub const TLS_CERT_KEY: &str = "tls.crt";
+/// Secret key of the private key.
+pub const TLS_PRIVATE_KEY: &str = "tls.key";
+
+fn root_path() -> String {
+    "/".to_string()
+}
+
+// --- Impl ---
+
+impl IngressPath {
+    /// Whether the request path is under this prefix, `/app` matches `/app/x` but not `/apps`.
+    pub fn matches(&self, path: &str) -> bool {
+        let prefix = self.path.trim_end_matches('/');
+        match path.strip_prefix(prefix) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    }
+}
@@ -17,6 +17,9 @@ pub struct Metadata {
     pub generation: u16,
     #[serde(default)]
     pub labels: HashMap<String, String>,
+    /// Set when deletion was requested, the object is terminating until removed
+    #[serde(rename = "deletionTimestamp", default)]
+    pub deletion_timestamp: Option<DateTime<Utc>>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -69,6 +72,7 @@ impl Default for Metadata {
             modified_at: now,
             generation: 1,
             labels: HashMap::new(),
+            deletion_timestamp: None,
         }
     }
 }
@@ -84,6 +88,15 @@ impl From<ObjectMetadata> for Metadata {
     }
 }
 
+impl LabelSelector {
+    /// True if every selector label is present with the same value.
+    pub fn matches(&self, labels: &HashMap<String, String>) -> bool {
+        self.match_labels
+            .iter()
+            .all(|(k, v)| labels.get(k) == Some(v))
+    }
+}
+
 impl TryFrom<String> for LabelSelector {
     type Error = ();
 
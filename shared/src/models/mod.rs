@@ -1,4 +1,15 @@
+pub mod configmap;
+pub mod endpoints;
+pub mod ingress;
 pub mod metadata;
 pub mod node;
+pub mod persistentvolume;
+pub mod persistentvolumeclaim;
 pub mod pod;
+pub mod poddisruptionbudget;
+pub mod podgroup;
+pub mod priorityclass;
+pub mod rbac;
 pub mod replicaset;
+pub mod secret;
+pub mod service;
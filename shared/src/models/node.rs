@@ -11,6 +11,19 @@ pub struct Node {
     pub addr: String,
     pub started_at: DateTime<Utc>,
     pub last_heartbeat: DateTime<Utc>,
+    #[serde(default)]
+    pub spec: NodeSpec,
+}
+
+/// Scheduling constraints set by the agent at registration or by operators.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct NodeSpec {
+    /// Cordoned nodes take no new pods
+    #[serde(default)]
+    pub unschedulable: bool,
+    /// Repel pods that do not tolerate them
+    #[serde(default)]
+    pub taints: Vec<Taint>,
 }
 
 /// Status of a node in the cluster.
@@ -21,6 +34,23 @@ pub enum NodeStatus {
     Stopped,
 }
 
+/// Node taint, only pods with a matching toleration are placed or kept on the node.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Taint {
+    pub key: String,
+    #[serde(default)]
+    pub value: String,
+    pub effect: TaintEffect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum TaintEffect {
+    /// New pods are not scheduled on the node
+    NoSchedule,
+    /// Running pods are also evicted from the node
+    NoExecute,
+}
+
 impl Default for Node {
     fn default() -> Self {
         Self {
@@ -30,6 +60,30 @@ impl Default for Node {
             addr: "0.0.0.0:1000".to_string(),
             started_at: Utc::now(),
             last_heartbeat: Utc::now(),
+            spec: NodeSpec::default(),
+        }
+    }
+}
+
+impl TryFrom<&str> for Taint {
+    type Error = ();
+
+    /// Parses `key=value:Effect` or `key:Effect`
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let (pair, effect) = input.trim().rsplit_once(':').ok_or(())?;
+        let effect = match effect {
+            "NoSchedule" => TaintEffect::NoSchedule,
+            "NoExecute" => TaintEffect::NoExecute,
+            _ => return Err(()),
+        };
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        if key.is_empty() {
+            return Err(());
         }
+        Ok(Taint {
+            key: key.to_string(),
+            value: value.to_string(),
+            effect,
+        })
     }
 }
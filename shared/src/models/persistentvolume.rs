@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::metadata::Metadata;
+
+// --- Core ---
+
+/// Piece of storage on a node that outlives the pods using it.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PersistentVolume {
+    pub metadata: Metadata,
+    pub spec: PersistentVolumeSpec,
+    #[serde(default)]
+    pub status: PersistentVolumeStatus,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PersistentVolumeSpec {
+    /// Size such as `512Mi` or `10Gi`
+    pub capacity: String,
+    /// Node holding the data, pods using the volume only run there
+    #[serde(rename = "nodeName")]
+    pub node_name: String,
+    /// Host directory, provisioned under the node's volume root when unset
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(rename = "persistentVolumeReclaimPolicy", default)]
+    pub reclaim_policy: ReclaimPolicy,
+    /// Claim bound to the volume
+    #[serde(rename = "claimRef", default)]
+    pub claim_ref: Option<String>,
+}
+
+/// What happens to a volume once its claim is deleted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum ReclaimPolicy {
+    /// Volume is released, the data stays until an admin removes it
+    Retain,
+    /// Volume removed, along with its data when the node provisioned it
+    #[default]
+    Delete,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PersistentVolumeStatus {
+    pub phase: VolumePhase,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum VolumePhase {
+    /// Free for a claim naming it
+    #[default]
+    Available,
+    Bound,
+    /// Claim deleted, waiting for the reclaim policy
+    Released,
+}
+
+impl PersistentVolume {
+    /// Whether the node provisions the directory, rather than an admin.
+    pub fn is_provisioned(&self) -> bool {
+        self.spec.path.is_none()
+    }
+}
+
+/// Parses a storage size in bytes, with an optional `Ki`, `Mi`, `Gi` or `Ti` suffix.
+pub fn parse_storage(size: &str) -> Option<u64> {
+    let units = [
+        ("Ki", 1 << 10),
+        ("Mi", 1 << 20),
+        ("Gi", 1 << 30),
+        ("Ti", 1 << 40),
+    ];
+    let (number, multiplier) = units
+        .iter()
+        .find_map(|(suffix, m)| size.strip_suffix(suffix).map(|n| (n, *m)))
+        .unwrap_or((size, 1));
+    number.parse::<u64>().ok()?.checked_mul(multiplier)
+}
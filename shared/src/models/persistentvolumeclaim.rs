@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{metadata::Metadata, persistentvolume::ReclaimPolicy};
+
+// --- Core ---
+
+/// Request for storage, bound to a volume on the node of the first pod using it.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PersistentVolumeClaim {
+    pub metadata: Metadata,
+    pub spec: PersistentVolumeClaimSpec,
+    #[serde(default)]
+    pub status: PersistentVolumeClaimStatus,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PersistentVolumeClaimSpec {
+    /// Size such as `512Mi` or `10Gi`
+    pub storage: String,
+    /// Existing volume to bind to, one is provisioned when unset
+    #[serde(rename = "volumeName", default)]
+    pub volume_name: Option<String>,
+    /// Reclaim policy of the provisioned volume
+    #[serde(rename = "reclaimPolicy", default)]
+    pub reclaim_policy: ReclaimPolicy,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PersistentVolumeClaimStatus {
+    pub phase: ClaimPhase,
+    /// Volume the claim is bound to
+    #[serde(rename = "volumeName", default)]
+    pub volume_name: Option<String>,
+    /// Node holding the volume, pods using the claim only run there
+    #[serde(rename = "nodeName", default)]
+    pub node_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum ClaimPhase {
+    /// Waiting for a pod to be scheduled
+    #[default]
+    Pending,
+    Bound,
+}
+
+/// Name of the volume provisioned for a claim.
+pub fn provisioned_volume_name(claim: &PersistentVolumeClaim) -> String {
+    format!("pvc-{}", claim.metadata.id)
+}
@@ -1,7 +1,12 @@
+use std::collections::BTreeSet;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::models::metadata::Metadata;
+use crate::models::{
+    metadata::{LabelSelector, Metadata},
+    node::{Taint, TaintEffect},
+};
 
 // --- Core ---
 
@@ -17,6 +22,53 @@ pub struct Pod {
 pub struct PodSpec {
     pub node_name: String,
     pub containers: Vec<ContainerSpec>,
+    /// Run in order before the containers, sidecars among them keep running
+    #[serde(default)]
+    pub init_containers: Vec<ContainerSpec>,
+    #[serde(default)]
+    pub restart_policy: RestartPolicy,
+    /// Storage the containers can mount
+    #[serde(default)]
+    pub volumes: Vec<Volume>,
+    #[serde(default)]
+    pub priority_class_name: Option<String>,
+    /// Resolved from the priority class when the pod is created
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub tolerations: Vec<Toleration>,
+    /// Pods matching the selector must not share a node with this one
+    #[serde(default)]
+    pub anti_affinity: Option<LabelSelector>,
+    /// Time containers get between SIGTERM and SIGKILL, preStop hooks included
+    #[serde(default = "default_grace_period")]
+    pub termination_grace_period_seconds: u32,
+}
+
+pub const DEFAULT_GRACE_PERIOD_SECONDS: u32 = 30;
+
+/// What the node does when a container exits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum RestartPolicy {
+    #[default]
+    Always,
+    OnFailure,
+    Never,
+}
+
+pub fn default_grace_period() -> u32 {
+    DEFAULT_GRACE_PERIOD_SECONDS
+}
+
+/// Allows a pod onto nodes with a matching taint.
+/// An empty value matches any value, a missing effect matches every effect.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Toleration {
+    pub key: String,
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub effect: Option<TaintEffect>,
 }
 
 /// Actual state
@@ -26,6 +78,17 @@ pub struct PodStatus {
     pub container_status: Vec<(String, String)>,
     pub last_update: Option<DateTime<Utc>>,
     pub observed_generation: u16,
+    /// Init and sidecar containers, kept apart from the main ones
+    #[serde(default)]
+    pub init_container_status: Vec<(String, String)>,
+    /// Address shared by every container of the pod, reported by its node
+    #[serde(rename = "podIP", default)]
+    pub pod_ip: Option<String>,
+    /// Set by the control plane, preserved across node status updates
+    #[serde(default)]
+    pub conditions: Vec<PodCondition>,
+    #[serde(default)]
+    pub nominated_node_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -37,6 +100,23 @@ pub enum PodPhase {
     Succeeded,
 }
 
+/// Observation about the pod lifecycle recorded by the control plane.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PodCondition {
+    #[serde(rename = "type")]
+    pub condition_type: PodConditionType,
+    pub status: bool,
+    pub reason: String,
+    pub message: String,
+    #[serde(rename = "lastTransitionTime")]
+    pub last_transition_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum PodConditionType {
+    PodScheduled,
+}
+
 // --- Containers ---
 
 /// Definition of a container within a Pod.
@@ -46,13 +126,235 @@ pub struct ContainerSpec {
     pub image: String,
     pub ports: Option<Vec<Port>>,
     pub env: Option<Vec<EnvVar>>,
+    /// Every key of a config map or secret becomes a variable, `env` takes precedence
+    #[serde(rename = "envFrom", default)]
+    pub env_from: Vec<EnvFromSource>,
+    #[serde(default)]
+    pub lifecycle: Option<Lifecycle>,
+    /// Replaces the image entrypoint
+    #[serde(default)]
+    pub command: Option<Vec<String>>,
+    /// Replaces the image default arguments
+    #[serde(default)]
+    pub args: Option<Vec<String>>,
+    #[serde(rename = "workingDir", default)]
+    pub working_dir: Option<String>,
+    /// User name or `uid[:gid]` the process runs as
+    #[serde(default)]
+    pub user: Option<String>,
+    #[serde(rename = "runAsUser", default)]
+    pub run_as_user: Option<u32>,
+    #[serde(default)]
+    pub tty: bool,
+    /// Keep stdin open
+    #[serde(default)]
+    pub stdin: bool,
+    #[serde(rename = "imagePullPolicy", default)]
+    pub image_pull_policy: ImagePullPolicy,
+    /// Only `Always` on an init container, making it a sidecar
+    #[serde(rename = "restartPolicy", default)]
+    pub restart_policy: Option<RestartPolicy>,
+    #[serde(rename = "volumeMounts", default)]
+    pub volume_mounts: Vec<VolumeMount>,
+}
+
+impl ContainerSpec {
+    /// Init container started before the main ones and kept running alongside them.
+    pub fn is_sidecar(&self) -> bool {
+        self.restart_policy == Some(RestartPolicy::Always)
+    }
+
+    /// User the process runs as, `runAsUser` only applies without an explicit user.
+    pub fn effective_user(&self) -> Option<String> {
+        self.user
+            .clone()
+            .or_else(|| self.run_as_user.map(|uid| uid.to_string()))
+    }
+}
+
+/// When the node pulls the container image.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub enum ImagePullPolicy {
+    /// Pull on every container start
+    Always,
+    /// Pull only when the image is missing on the node
+    #[default]
+    IfNotPresent,
+    /// Never pull, fail when the image is missing
+    Never,
+}
+
+/// Hooks run by the node around a container's life.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Lifecycle {
+    /// Run before the container is sent SIGTERM
+    #[serde(rename = "preStop", default)]
+    pub pre_stop: Option<LifecycleHandler>,
+}
+
+/// Action taken by a lifecycle hook.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum LifecycleHandler {
+    /// Command executed inside the container
+    #[serde(rename = "exec")]
+    Exec { command: Vec<String> },
+    /// GET request sent to the container
+    #[serde(rename = "httpGet")]
+    HttpGet { path: String, port: u16 },
+}
+
+// --- Volumes ---
+
+/// Named storage of a pod, shared by the containers mounting it.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Volume {
+    pub name: String,
+    #[serde(flatten)]
+    pub source: VolumeSource,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum VolumeSource {
+    /// Empty directory created with the pod and removed with it
+    #[serde(rename = "emptyDir")]
+    EmptyDir(EmptyDirVolume),
+    /// File or directory of the node
+    #[serde(rename = "hostPath")]
+    HostPath(HostPathVolume),
+    /// Keys of a config map as files, kept up to date by the node
+    #[serde(rename = "configMap")]
+    ConfigMap(ObjectRef),
+    /// Keys of a secret as files, kept in memory on the node
+    #[serde(rename = "secret")]
+    Secret(SecretVolume),
+    /// Volume bound to a claim, kept when the pod goes away
+    #[serde(rename = "persistentVolumeClaim")]
+    PersistentVolumeClaim(ClaimVolume),
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct EmptyDirVolume {
+    #[serde(default)]
+    pub medium: StorageMedium,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum StorageMedium {
+    /// Node disk
+    #[default]
+    #[serde(rename = "")]
+    Default,
+    /// RAM backed, gone with the node
+    Memory,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SecretVolume {
+    #[serde(rename = "secretName")]
+    pub secret_name: String,
+    #[serde(default)]
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClaimVolume {
+    #[serde(rename = "claimName")]
+    pub claim_name: String,
+    /// Mounts the volume read only in every container
+    #[serde(rename = "readOnly", default)]
+    pub read_only: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HostPathVolume {
+    pub path: String,
+    #[serde(rename = "type", default)]
+    pub path_type: HostPathType,
+    /// Mounts the path read only in every container
+    #[serde(rename = "readOnly", default)]
+    pub read_only: bool,
+}
+
+/// What must exist at a host path before the pod starts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum HostPathType {
+    /// No checks
+    #[default]
+    #[serde(rename = "")]
+    Unset,
+    DirectoryOrCreate,
+    Directory,
+    FileOrCreate,
+    File,
+    Socket,
+}
+
+/// Where a container sees a pod volume.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VolumeMount {
+    pub name: String,
+    #[serde(rename = "mountPath")]
+    pub mount_path: String,
+    #[serde(rename = "readOnly", default)]
+    pub read_only: bool,
+    /// Path inside the volume to mount instead of its root
+    #[serde(rename = "subPath", default)]
+    pub sub_path: Option<String>,
 }
 
 /// Environment variable for a container.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct EnvVar {
     pub name: String,
+    #[serde(default)]
     pub value: String,
+    /// Takes the value from a config map or secret key instead
+    #[serde(rename = "valueFrom", default)]
+    pub value_from: Option<EnvVarSource>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum EnvVarSource {
+    #[serde(rename = "configMapKeyRef")]
+    ConfigMapKeyRef(KeySelector),
+    #[serde(rename = "secretKeyRef")]
+    SecretKeyRef(KeySelector),
+}
+
+/// A single key of a config map or secret.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KeySelector {
+    pub name: String,
+    pub key: String,
+    /// Don't fail the container when the object or key is missing
+    #[serde(default)]
+    pub optional: bool,
+}
+
+/// Config map or secret whose keys all become environment variables.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EnvFromSource {
+    /// Prepended to every key
+    #[serde(default)]
+    pub prefix: String,
+    #[serde(flatten)]
+    pub source: EnvFromRef,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum EnvFromRef {
+    #[serde(rename = "configMapRef")]
+    ConfigMapRef(ObjectRef),
+    #[serde(rename = "secretRef")]
+    SecretRef(ObjectRef),
+}
+
+/// Config map or secret referenced by name.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ObjectRef {
+    pub name: String,
+    #[serde(default)]
+    pub optional: bool,
 }
 
 /// Port mapping for a container.
@@ -60,6 +362,32 @@ pub struct EnvVar {
 pub struct Port {
     #[serde(rename = "containerPort")]
     pub container_port: u16,
+    /// Published on the node, at most one pod per node can bind it
+    #[serde(rename = "hostPort", default)]
+    pub host_port: Option<u16>,
+    #[serde(default)]
+    pub protocol: Protocol,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Protocol {
+    #[default]
+    #[serde(rename = "TCP")]
+    Tcp,
+    #[serde(rename = "UDP")]
+    Udp,
+}
+
+impl Protocol {
+    /// Lowercase name used by docker port specs.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Protocol::Tcp => "tcp",
+            Protocol::Udp => "udp",
+        }
+    }
 }
 
 // --- Impl ---
@@ -81,6 +409,170 @@ impl Default for PodStatus {
             container_status: Vec::new(),
             last_update: None,
             observed_generation: 0,
+            init_container_status: Vec::new(),
+            pod_ip: None,
+            conditions: Vec::new(),
+            nominated_node_name: None,
+        }
+    }
+}
+
+impl PodStatus {
+    /// Replaces the condition of the same type, or appends it.
+    pub fn set_condition(&mut self, condition: PodCondition) {
+        match self
+            .conditions
+            .iter_mut()
+            .find(|c| c.condition_type == condition.condition_type)
+        {
+            Some(existing) => *existing = condition,
+            None => self.conditions.push(condition),
+        }
+    }
+
+    pub fn get_condition(&self, condition_type: &PodConditionType) -> Option<&PodCondition> {
+        self.conditions
+            .iter()
+            .find(|c| &c.condition_type == condition_type)
+    }
+}
+
+impl Pod {
+    /// True if the pod runs with an address and every main container is up.
+    pub fn is_ready(&self) -> bool {
+        matches!(self.status.phase, PodPhase::Running)
+            && self.metadata.deletion_timestamp.is_none()
+            && self.status.pod_ip.is_some()
+            && !self.status.container_status.is_empty()
+            && self
+                .status
+                .container_status
+                .iter()
+                .all(|(_, status)| status == "running")
+    }
+
+    /// True if this pod's anti-affinity selects the other pod.
+    pub fn repels(&self, other: &Pod) -> bool {
+        self.spec
+            .anti_affinity
+            .as_ref()
+            .is_some_and(|selector| selector.matches(&other.metadata.labels))
+    }
+}
+
+impl PodSpec {
+    /// True if every taint is matched by one of the tolerations.
+    pub fn tolerates(&self, taints: &[Taint]) -> bool {
+        taints
+            .iter()
+            .all(|taint| self.tolerations.iter().any(|t| t.matches(taint)))
+    }
+
+    pub fn sidecars(&self) -> impl Iterator<Item = &ContainerSpec> {
+        self.init_containers.iter().filter(|c| c.is_sidecar())
+    }
+
+    /// Containers expected to be running once the pod is up, sidecars first.
+    pub fn running_containers(&self) -> impl Iterator<Item = &ContainerSpec> {
+        self.sidecars().chain(self.containers.iter())
+    }
+
+    /// Ports of every container, init containers included.
+    pub fn ports(&self) -> impl Iterator<Item = &Port> {
+        self.init_containers
+            .iter()
+            .chain(&self.containers)
+            .flat_map(|c| c.ports.iter().flatten())
+    }
+
+    /// Node ports the pod binds, with their protocol.
+    pub fn host_ports(&self) -> impl Iterator<Item = (u16, Protocol)> + '_ {
+        self.ports()
+            .filter_map(|p| p.host_port.map(|port| (port, p.protocol)))
+    }
+
+    /// Config maps the pod reads from, through env or volumes.
+    pub fn configmap_refs(&self) -> BTreeSet<&str> {
+        let env = self.env_refs().filter_map(|r| match r {
+            EnvRef::ConfigMap(name) => Some(name),
+            EnvRef::Secret(_) => None,
+        });
+        let volumes = self.volumes.iter().filter_map(|v| match &v.source {
+            VolumeSource::ConfigMap(r) => Some(r.name.as_str()),
+            _ => None,
+        });
+        env.chain(volumes).collect()
+    }
+
+    /// Secrets the pod reads from, through env or volumes.
+    pub fn secret_refs(&self) -> BTreeSet<&str> {
+        let env = self.env_refs().filter_map(|r| match r {
+            EnvRef::Secret(name) => Some(name),
+            EnvRef::ConfigMap(_) => None,
+        });
+        let volumes = self.volumes.iter().filter_map(|v| match &v.source {
+            VolumeSource::Secret(s) => Some(s.secret_name.as_str()),
+            _ => None,
+        });
+        env.chain(volumes).collect()
+    }
+
+    /// Persistent volume claims the pod mounts.
+    pub fn claim_refs(&self) -> BTreeSet<&str> {
+        self.volumes
+            .iter()
+            .filter_map(|v| match &v.source {
+                VolumeSource::PersistentVolumeClaim(c) => Some(c.claim_name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn env_refs(&self) -> impl Iterator<Item = EnvRef<'_>> {
+        self.init_containers
+            .iter()
+            .chain(&self.containers)
+            .flat_map(|c| {
+                let from = c.env_from.iter().map(|e| match &e.source {
+                    EnvFromRef::ConfigMapRef(r) => EnvRef::ConfigMap(&r.name),
+                    EnvFromRef::SecretRef(r) => EnvRef::Secret(&r.name),
+                });
+                let vars = c.env.iter().flatten().filter_map(|e| match &e.value_from {
+                    Some(EnvVarSource::ConfigMapKeyRef(k)) => Some(EnvRef::ConfigMap(&k.name)),
+                    Some(EnvVarSource::SecretKeyRef(k)) => Some(EnvRef::Secret(&k.name)),
+                    None => None,
+                });
+                from.chain(vars)
+            })
+    }
+}
+
+enum EnvRef<'a> {
+    ConfigMap(&'a str),
+    Secret(&'a str),
+}
+
+impl Toleration {
+    pub fn matches(&self, taint: &Taint) -> bool {
+        self.key == taint.key
+            && self.value.as_ref().is_none_or(|v| *v == taint.value)
+            && self.effect.is_none_or(|e| e == taint.effect)
+    }
+}
+
+impl PodCondition {
+    pub fn new(
+        condition_type: PodConditionType,
+        status: bool,
+        reason: &str,
+        message: &str,
+    ) -> Self {
+        PodCondition {
+            condition_type,
+            status,
+            reason: reason.to_string(),
+            message: message.to_string(),
+            last_transition_time: Utc::now(),
         }
     }
 }
@@ -92,6 +584,18 @@ impl Default for ContainerSpec {
             image: "busybox:latest".to_string(),
             ports: None,
             env: None,
+            env_from: Vec::new(),
+            lifecycle: None,
+            command: None,
+            args: None,
+            working_dir: None,
+            user: None,
+            run_as_user: None,
+            tty: false,
+            stdin: false,
+            image_pull_policy: ImagePullPolicy::default(),
+            restart_policy: None,
+            volume_mounts: Vec::new(),
         }
     }
 }
@@ -101,6 +605,14 @@ impl Default for PodSpec {
         PodSpec {
             node_name: "".to_string(),
             containers: vec![ContainerSpec::default()],
+            init_containers: Vec::new(),
+            restart_policy: RestartPolicy::default(),
+            volumes: Vec::new(),
+            priority_class_name: None,
+            priority: 0,
+            tolerations: Vec::new(),
+            anti_affinity: None,
+            termination_grace_period_seconds: DEFAULT_GRACE_PERIOD_SECONDS,
         }
     }
 }
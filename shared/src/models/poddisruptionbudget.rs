@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::metadata::{LabelSelector, Metadata};
+
+// --- Core ---
+
+/// Limits how many pods matching a selector can be voluntarily evicted at once.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PodDisruptionBudget {
+    pub metadata: Metadata,
+    pub spec: PodDisruptionBudgetSpec,
+    #[serde(default)]
+    pub status: PodDisruptionBudgetStatus,
+}
+
+/// Exactly one of `minAvailable` and `maxUnavailable` must be set.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PodDisruptionBudgetSpec {
+    #[serde(rename = "minAvailable", default)]
+    pub min_available: Option<u16>,
+    #[serde(rename = "maxUnavailable", default)]
+    pub max_unavailable: Option<u16>,
+    pub selector: LabelSelector,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PodDisruptionBudgetStatus {
+    /// Matching pods that are running
+    #[serde(rename = "currentHealthy")]
+    pub current_healthy: u16,
+    /// Running pods the budget requires
+    #[serde(rename = "desiredHealthy")]
+    pub desired_healthy: u16,
+    /// All matching pods
+    #[serde(rename = "expectedPods")]
+    pub expected_pods: u16,
+    /// Evictions allowed right now
+    #[serde(rename = "disruptionsAllowed")]
+    pub disruptions_allowed: u16,
+}
+
+impl PodDisruptionBudgetSpec {
+    /// Derive the status from the number of matching and running pods.
+    pub fn status(&self, expected_pods: u16, current_healthy: u16) -> PodDisruptionBudgetStatus {
+        let desired_healthy = match (self.min_available, self.max_unavailable) {
+            (Some(min), _) => min,
+            (None, Some(max)) => expected_pods.saturating_sub(max),
+            (None, None) => 0,
+        };
+        PodDisruptionBudgetStatus {
+            current_healthy,
+            desired_healthy,
+            expected_pods,
+            disruptions_allowed: current_healthy.saturating_sub(desired_healthy),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::metadata::Metadata;
+
+/// Label on pod metadata naming the pod group it belongs to.
+pub const POD_GROUP_LABEL: &str = "cr8s.io/pod-group";
+
+// --- Core ---
+
+/// Set of pods that must be scheduled together or not at all.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PodGroup {
+    pub metadata: Metadata,
+    pub spec: PodGroupSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PodGroupSpec {
+    /// Pending members required before any of them is placed
+    #[serde(rename = "minMember")]
+    pub min_member: u16,
+    /// Time to wait for enough members before backing off
+    #[serde(rename = "scheduleTimeoutSeconds", default = "default_timeout")]
+    pub schedule_timeout_seconds: u64,
+}
+
+fn default_timeout() -> u64 {
+    60
+}
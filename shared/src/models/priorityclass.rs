@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::metadata::Metadata;
+
+// --- Core ---
+
+/// Cluster wide mapping from a class name to a pod scheduling priority.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PriorityClass {
+    pub metadata: Metadata,
+    pub spec: PriorityClassSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PriorityClassSpec {
+    /// Higher values are scheduled first and may preempt lower ones
+    pub value: i32,
+    /// Applied to pods that do not name a priority class
+    #[serde(rename = "globalDefault", default)]
+    pub global_default: bool,
+    #[serde(default)]
+    pub description: String,
+}
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::metadata::Metadata;
+
+/// Namespace of every namespaced object until objects carry their own.
+pub const DEFAULT_NAMESPACE: &str = "default";
+
+/// Matches every verb, resource or name.
+pub const WILDCARD: &str = "*";
+
+// --- Roles ---
+
+/// Permissions on namespaced resources inside one namespace.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Role {
+    pub metadata: Metadata,
+    pub spec: RoleSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RoleSpec {
+    #[serde(default = "default_namespace")]
+    pub namespace: String,
+    pub rules: Vec<PolicyRule>,
+}
+
+/// Permissions on any resource, granted cluster-wide or in the namespace of a role binding.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClusterRole {
+    pub metadata: Metadata,
+    pub spec: ClusterRoleSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClusterRoleSpec {
+    pub rules: Vec<PolicyRule>,
+}
+
+/// Allows the verbs on the resources, `*` matches any.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PolicyRule {
+    pub verbs: Vec<String>,
+    /// Collections such as `pods`, or subresources such as `pods/logs`
+    pub resources: Vec<String>,
+    /// Limits the rule to these objects, every object when empty
+    #[serde(rename = "resourceNames", default)]
+    pub resource_names: Vec<String>,
+}
+
+impl PolicyRule {
+    pub fn allows(&self, verb: &str, resource: &str, name: Option<&str>) -> bool {
+        let matches =
+            |values: &[String], value: &str| values.iter().any(|v| v == WILDCARD || v == value);
+        matches(&self.verbs, verb)
+            && matches(&self.resources, resource)
+            && (self.resource_names.is_empty()
+                || name.is_some_and(|name| self.resource_names.iter().any(|n| n == name)))
+    }
+}
+
+// --- Bindings ---
+
+/// Grants a role, or a cluster role limited to the namespace, to subjects.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RoleBinding {
+    pub metadata: Metadata,
+    pub spec: RoleBindingSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RoleBindingSpec {
+    #[serde(default = "default_namespace")]
+    pub namespace: String,
+    #[serde(rename = "roleRef")]
+    pub role_ref: RoleRef,
+    pub subjects: Vec<Subject>,
+}
+
+/// Grants a cluster role to subjects in every namespace.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClusterRoleBinding {
+    pub metadata: Metadata,
+    pub spec: ClusterRoleBindingSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClusterRoleBindingSpec {
+    #[serde(rename = "roleRef")]
+    pub role_ref: RoleRef,
+    pub subjects: Vec<Subject>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RoleRef {
+    pub kind: RoleKind,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum RoleKind {
+    Role,
+    ClusterRole,
+}
+
+/// User, group or service account a binding applies to.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Subject {
+    pub kind: SubjectKind,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum SubjectKind {
+    User,
+    Group,
+    ServiceAccount,
+}
+
+fn default_namespace() -> String {
+    DEFAULT_NAMESPACE.to_string()
+}
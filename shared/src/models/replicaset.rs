@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
-    api::{PodContainers, PodManifest},
+    api::PodManifest,
     models::metadata::{LabelSelector, Metadata, ObjectMetadata, OwnerKind, OwnerReference},
 };
 
@@ -54,9 +54,7 @@ impl From<ReplicaSet> for PodManifest {
                 }),
                 labels: rs.spec.template.metadata.labels,
             },
-            spec: PodContainers {
-                containers: rs.spec.template.spec.containers,
-            },
+            spec: rs.spec.template.spec,
         }
     }
 }
@@ -0,0 +1,21 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::metadata::Metadata;
+
+// --- Core ---
+
+/// Confidential data such as passwords or tokens, encrypted at rest by the apiserver.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Secret {
+    pub metadata: Metadata,
+    pub spec: SecretSpec,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SecretSpec {
+    /// Plain text values, keys double as file names when mounted
+    #[serde(default)]
+    pub data: HashMap<String, String>,
+}
@@ -0,0 +1,68 @@
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+    metadata::{LabelSelector, Metadata},
+    pod::{Pod, Protocol},
+};
+
+// --- Core ---
+
+/// Stable entry point to the ready pods matching a selector.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Service {
+    pub metadata: Metadata,
+    pub spec: ServiceSpec,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ServiceSpec {
+    /// Pods backing the service, endpoints are left alone without one
+    #[serde(default)]
+    pub selector: Option<LabelSelector>,
+    pub ports: Vec<ServicePort>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ServicePort {
+    /// Required when the service exposes more than one port
+    #[serde(default)]
+    pub name: Option<String>,
+    pub port: u16,
+    /// Port or port name on the pods, defaults to `port`
+    #[serde(rename = "targetPort", default)]
+    pub target_port: Option<TargetPort>,
+    /// Opened on every node, allocated when unset
+    #[serde(rename = "nodePort", default)]
+    pub node_port: Option<u16>,
+    #[serde(default)]
+    pub protocol: Protocol,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TargetPort {
+    Number(u16),
+    Name(String),
+}
+
+/// Node ports services can be exposed on.
+pub const NODE_PORT_RANGE: RangeInclusive<u16> = 30000..=32767;
+
+// --- Impl ---
+
+impl ServicePort {
+    /// Port the pod serves this service port on, if it declares the named one.
+    pub fn target_for(&self, pod: &Pod) -> Option<u16> {
+        match &self.target_port {
+            None => Some(self.port),
+            Some(TargetPort::Number(port)) => Some(*port),
+            Some(TargetPort::Name(name)) => pod
+                .spec
+                .ports()
+                .find(|p| p.name.as_ref() == Some(name) && p.protocol == self.protocol)
+                .map(|p| p.container_port),
+        }
+    }
+}
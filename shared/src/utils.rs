@@ -1,16 +1,101 @@
+use std::sync::RwLock;
+
 use futures_util::TryStreamExt;
-use reqwest::Client;
+use reqwest::{
+    Certificate, Client, Identity,
+    header::{AUTHORIZATION, HeaderMap, HeaderValue},
+};
 use serde::de::DeserializeOwned;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio_util::io::StreamReader;
 
+/// Credentials of the process and the client built from them.
+struct Credentials {
+    token: Option<String>,
+    /// Cluster CA in PEM, `CR8S_CA_CERT` is read without it
+    ca: Option<Vec<u8>>,
+    client: Option<Client>,
+}
+
+static CREDENTIALS: RwLock<Credentials> = RwLock::new(Credentials {
+    token: None,
+    ca: None,
+    client: None,
+});
+
+/// Sets the bearer token every client of this process presents to the apiserver.
+pub fn set_bearer_token(token: Option<String>) {
+    let mut credentials = CREDENTIALS.write().unwrap();
+    credentials.token = token;
+    credentials.client = None;
+}
+
+/// Sets the CA the apiserver certificate is verified against, instead of `CR8S_CA_CERT`.
+pub fn set_ca_certificate(pem: Option<Vec<u8>>) {
+    let mut credentials = CREDENTIALS.write().unwrap();
+    credentials.ca = pem;
+    credentials.client = None;
+}
+
+/// HTTP client for the apiserver, presenting the process credentials.
+///
+/// The cluster CA is read from `CR8S_CA_CERT` and a client certificate from
+/// `CR8S_CLIENT_CERT` and `CR8S_CLIENT_KEY`, all PEM files.
+pub fn client() -> Client {
+    if let Some(client) = &CREDENTIALS.read().unwrap().client {
+        return client.clone();
+    }
+    let mut credentials = CREDENTIALS.write().unwrap();
+    let client = build_client(credentials.token.as_deref(), credentials.ca.as_deref());
+    credentials.client = Some(client.clone());
+    client
+}
+
+fn build_client(token: Option<&str>, ca: Option<&[u8]>) -> Client {
+    let mut builder = Client::builder();
+    if let Some(token) = token
+        && let Ok(mut value) = HeaderValue::from_str(&format!("Bearer {}", token))
+    {
+        value.set_sensitive(true);
+        builder = builder.default_headers(HeaderMap::from_iter([(AUTHORIZATION, value)]));
+    }
+    if let Some(pem) = ca {
+        match Certificate::from_pem(pem) {
+            Ok(ca) => builder = builder.add_root_certificate(ca),
+            Err(err) => tracing::warn!(error=%err, "Ignoring invalid cluster CA"),
+        }
+    } else if let Ok(path) = std::env::var("CR8S_CA_CERT") {
+        match std::fs::read(&path).map(|pem| Certificate::from_pem(&pem)) {
+            Ok(Ok(ca)) => builder = builder.add_root_certificate(ca),
+            _ => tracing::warn!(%path, "Ignoring invalid CR8S_CA_CERT"),
+        }
+    }
+    if let (Ok(cert), Ok(key)) = (
+        std::env::var("CR8S_CLIENT_CERT"),
+        std::env::var("CR8S_CLIENT_KEY"),
+    ) {
+        let pem = std::fs::read(&cert).and_then(|mut pem| {
+            pem.extend(std::fs::read(&key)?);
+            Ok(pem)
+        });
+        match pem.map(|pem| Identity::from_pem(&pem)) {
+            Ok(Ok(identity)) => builder = builder.identity(identity),
+            _ => tracing::warn!(%cert, "Ignoring invalid client certificate"),
+        }
+    }
+    builder.build().unwrap_or_else(|err| {
+        tracing::warn!(error=%err, "Falling back to a default HTTP client");
+        Client::new()
+    })
+}
+
 /// Generic watcher for streaming API responses.
 pub async fn watch_stream<T, F>(url: &str, mut handle_event: F)
 where
     T: DeserializeOwned,
     F: FnMut(T) + Send + 'static,
 {
-    let client = Client::new();
+    let client = client();
     match client.get(url).send().await {
         Ok(resp) if resp.status().is_success() => {
             let byte_stream = resp
@@ -41,7 +126,7 @@ where
     F: FnMut(T) -> Fut + Send + 'static,
     Fut: Future<Output = ()> + Send,
 {
-    let client = Client::new();
+    let client = client();
     match client.get(url).send().await {
         Ok(resp) if resp.status().is_success() => {
             let byte_stream = resp
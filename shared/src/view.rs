@@ -6,10 +6,22 @@ use std::borrow::Cow;
 use chrono::Utc;
 use tabled::Tabled;
 
+use crate::api::NodeVerdict;
 use crate::models::{
+    configmap::ConfigMap,
+    endpoints::Endpoints,
+    ingress::Ingress,
     node::{Node, NodeStatus},
+    persistentvolume::{PersistentVolume, VolumePhase},
+    persistentvolumeclaim::{ClaimPhase, PersistentVolumeClaim},
     pod::{Pod, PodPhase},
+    poddisruptionbudget::PodDisruptionBudget,
+    podgroup::PodGroup,
+    priorityclass::PriorityClass,
+    rbac::{ClusterRole, ClusterRoleBinding, PolicyRule, Role, RoleBinding, RoleRef, Subject},
     replicaset::ReplicaSet,
+    secret::Secret,
+    service::Service,
 };
 
 // --- Display impls for status enums ---
@@ -46,7 +58,11 @@ impl Tabled for Node {
     fn fields(&self) -> Vec<Cow<'_, str>> {
         vec![
             Cow::Owned(self.name.clone()),
-            Cow::Owned(self.status.to_string()),
+            Cow::Owned(if self.spec.unschedulable {
+                format!("{},SchedulingDisabled", self.status)
+            } else {
+                self.status.to_string()
+            }),
             Cow::Owned(self.addr.clone()),
             Cow::Owned(human_duration(
                 Utc::now()
@@ -100,7 +116,11 @@ impl Tabled for Pod {
         vec![
             Cow::Owned(self.metadata.name.clone()),
             Cow::Owned(format!("{}/{}", ready_count, total_containers)),
-            Cow::Owned(self.status.phase.to_string()),
+            Cow::Owned(if self.metadata.deletion_timestamp.is_some() {
+                "Terminating".to_string()
+            } else {
+                self.status.phase.to_string()
+            }),
             Cow::Borrowed("0"),
             Cow::Owned(human_duration(
                 Utc::now()
@@ -153,6 +173,380 @@ impl Tabled for ReplicaSet {
     }
 }
 
+// --- PriorityClass ---
+
+impl Tabled for PriorityClass {
+    const LENGTH: usize = 4;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![
+            Cow::Owned(self.metadata.name.clone()),
+            Cow::Owned(self.spec.value.to_string()),
+            Cow::Owned(self.spec.global_default.to_string()),
+            Cow::Owned(human_duration(
+                Utc::now()
+                    .signed_duration_since(self.metadata.created_at)
+                    .to_std()
+                    .unwrap_or_default(),
+            )),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NAME"),
+            Cow::Borrowed("VALUE"),
+            Cow::Borrowed("GLOBAL-DEFAULT"),
+            Cow::Borrowed("AGE"),
+        ]
+    }
+}
+
+// --- ConfigMap ---
+
+impl Tabled for ConfigMap {
+    const LENGTH: usize = 3;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![
+            Cow::Owned(self.metadata.name.clone()),
+            Cow::Owned(self.spec.data.len().to_string()),
+            Cow::Owned(human_duration(
+                Utc::now()
+                    .signed_duration_since(self.metadata.created_at)
+                    .to_std()
+                    .unwrap_or_default(),
+            )),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NAME"),
+            Cow::Borrowed("DATA"),
+            Cow::Borrowed("AGE"),
+        ]
+    }
+}
+
+// --- Secret ---
+
+/// Only the number of keys is shown, never the values.
+impl Tabled for Secret {
+    const LENGTH: usize = 3;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![
+            Cow::Owned(self.metadata.name.clone()),
+            Cow::Owned(self.spec.data.len().to_string()),
+            Cow::Owned(human_duration(
+                Utc::now()
+                    .signed_duration_since(self.metadata.created_at)
+                    .to_std()
+                    .unwrap_or_default(),
+            )),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NAME"),
+            Cow::Borrowed("DATA"),
+            Cow::Borrowed("AGE"),
+        ]
+    }
+}
+
+// --- PersistentVolume ---
+
+impl Tabled for PersistentVolume {
+    const LENGTH: usize = 7;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        let phase = match self.status.phase {
+            VolumePhase::Available => "Available",
+            VolumePhase::Bound => "Bound",
+            VolumePhase::Released => "Released",
+        };
+        vec![
+            Cow::Owned(self.metadata.name.clone()),
+            Cow::Owned(self.spec.capacity.clone()),
+            Cow::Owned(self.spec.node_name.clone()),
+            Cow::Owned(format!("{:?}", self.spec.reclaim_policy)),
+            Cow::Borrowed(phase),
+            Cow::Owned(self.spec.claim_ref.clone().unwrap_or_default()),
+            Cow::Owned(human_duration(
+                Utc::now()
+                    .signed_duration_since(self.metadata.created_at)
+                    .to_std()
+                    .unwrap_or_default(),
+            )),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NAME"),
+            Cow::Borrowed("CAPACITY"),
+            Cow::Borrowed("NODE"),
+            Cow::Borrowed("RECLAIM-POLICY"),
+            Cow::Borrowed("STATUS"),
+            Cow::Borrowed("CLAIM"),
+            Cow::Borrowed("AGE"),
+        ]
+    }
+}
+
+// --- PersistentVolumeClaim ---
+
+impl Tabled for PersistentVolumeClaim {
+    const LENGTH: usize = 6;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        let phase = match self.status.phase {
+            ClaimPhase::Pending => "Pending",
+            ClaimPhase::Bound => "Bound",
+        };
+        vec![
+            Cow::Owned(self.metadata.name.clone()),
+            Cow::Borrowed(phase),
+            Cow::Owned(self.status.volume_name.clone().unwrap_or_default()),
+            Cow::Owned(self.spec.storage.clone()),
+            Cow::Owned(self.status.node_name.clone().unwrap_or_default()),
+            Cow::Owned(human_duration(
+                Utc::now()
+                    .signed_duration_since(self.metadata.created_at)
+                    .to_std()
+                    .unwrap_or_default(),
+            )),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NAME"),
+            Cow::Borrowed("STATUS"),
+            Cow::Borrowed("VOLUME"),
+            Cow::Borrowed("CAPACITY"),
+            Cow::Borrowed("NODE"),
+            Cow::Borrowed("AGE"),
+        ]
+    }
+}
+
+// --- Service ---
+
+impl Tabled for Service {
+    const LENGTH: usize = 4;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        let selector = self.spec.selector.clone().map(String::from);
+        let ports = self
+            .spec
+            .ports
+            .iter()
+            .map(|p| match p.node_port {
+                Some(node_port) => format!("{}:{}/{}", p.port, node_port, p.protocol.as_str()),
+                None => format!("{}/{}", p.port, p.protocol.as_str()),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        vec![
+            Cow::Owned(self.metadata.name.clone()),
+            Cow::Owned(selector.unwrap_or_else(|| "<none>".to_string())),
+            Cow::Owned(ports),
+            Cow::Owned(human_duration(
+                Utc::now()
+                    .signed_duration_since(self.metadata.created_at)
+                    .to_std()
+                    .unwrap_or_default(),
+            )),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NAME"),
+            Cow::Borrowed("SELECTOR"),
+            Cow::Borrowed("PORTS"),
+            Cow::Borrowed("AGE"),
+        ]
+    }
+}
+
+// --- Endpoints ---
+
+impl Tabled for Endpoints {
+    const LENGTH: usize = 3;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        let addresses: Vec<String> = self
+            .ports
+            .iter()
+            .flat_map(|p| p.addresses.iter())
+            .map(|a| format!("{}:{}", a.ip, a.port))
+            .collect();
+        let shown = match addresses.len() {
+            0 => "<none>".to_string(),
+            n if n > 3 => format!("{} + {} more...", addresses[..3].join(","), n - 3),
+            _ => addresses.join(","),
+        };
+        vec![
+            Cow::Owned(self.metadata.name.clone()),
+            Cow::Owned(shown),
+            Cow::Owned(human_duration(
+                Utc::now()
+                    .signed_duration_since(self.metadata.created_at)
+                    .to_std()
+                    .unwrap_or_default(),
+            )),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NAME"),
+            Cow::Borrowed("ENDPOINTS"),
+            Cow::Borrowed("AGE"),
+        ]
+    }
+}
+
+// --- Ingress ---
+
+impl Tabled for Ingress {
+    const LENGTH: usize = 4;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        let mut hosts: Vec<&str> = self
+            .spec
+            .rules
+            .iter()
+            .map(|r| r.host.as_deref().unwrap_or("*"))
+            .collect();
+        hosts.dedup();
+        let tls: Vec<&str> = self
+            .spec
+            .tls
+            .iter()
+            .map(|t| t.secret_name.as_str())
+            .collect();
+        vec![
+            Cow::Owned(self.metadata.name.clone()),
+            Cow::Owned(hosts.join(",")),
+            Cow::Owned(if tls.is_empty() {
+                "<none>".to_string()
+            } else {
+                tls.join(",")
+            }),
+            Cow::Owned(human_duration(
+                Utc::now()
+                    .signed_duration_since(self.metadata.created_at)
+                    .to_std()
+                    .unwrap_or_default(),
+            )),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NAME"),
+            Cow::Borrowed("HOSTS"),
+            Cow::Borrowed("TLS"),
+            Cow::Borrowed("AGE"),
+        ]
+    }
+}
+
+// --- PodDisruptionBudget ---
+
+impl Tabled for PodDisruptionBudget {
+    const LENGTH: usize = 5;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        let or_na = |v: Option<u16>| v.map_or("N/A".to_string(), |v| v.to_string());
+        vec![
+            Cow::Owned(self.metadata.name.clone()),
+            Cow::Owned(or_na(self.spec.min_available)),
+            Cow::Owned(or_na(self.spec.max_unavailable)),
+            Cow::Owned(self.status.disruptions_allowed.to_string()),
+            Cow::Owned(human_duration(
+                Utc::now()
+                    .signed_duration_since(self.metadata.created_at)
+                    .to_std()
+                    .unwrap_or_default(),
+            )),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NAME"),
+            Cow::Borrowed("MIN-AVAILABLE"),
+            Cow::Borrowed("MAX-UNAVAILABLE"),
+            Cow::Borrowed("ALLOWED-DISRUPTIONS"),
+            Cow::Borrowed("AGE"),
+        ]
+    }
+}
+
+// --- PodGroup ---
+
+impl Tabled for PodGroup {
+    const LENGTH: usize = 4;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![
+            Cow::Owned(self.metadata.name.clone()),
+            Cow::Owned(self.spec.min_member.to_string()),
+            Cow::Owned(format!("{}s", self.spec.schedule_timeout_seconds)),
+            Cow::Owned(human_duration(
+                Utc::now()
+                    .signed_duration_since(self.metadata.created_at)
+                    .to_std()
+                    .unwrap_or_default(),
+            )),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NAME"),
+            Cow::Borrowed("MIN-MEMBER"),
+            Cow::Borrowed("TIMEOUT"),
+            Cow::Borrowed("AGE"),
+        ]
+    }
+}
+
+// --- Scheduler simulation ---
+
+impl Tabled for NodeVerdict {
+    const LENGTH: usize = 4;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![
+            Cow::Owned(self.node_name.clone()),
+            Cow::Borrowed(if self.feasible { "Fits" } else { "Filtered" }),
+            Cow::Owned(
+                self.score
+                    .map(|s| format!("{:.3}", s))
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            Cow::Owned(self.reason.clone().unwrap_or_default()),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NODE"),
+            Cow::Borrowed("VERDICT"),
+            Cow::Borrowed("SCORE"),
+            Cow::Borrowed("REASON"),
+        ]
+    }
+}
+
 // --- Utility functions ---
 
 /// Converts a `Duration` into a human-readable age string like `5m ago`, `2h ago`, etc.
@@ -165,3 +559,129 @@ fn human_duration(dur: std::time::Duration) -> String {
         _ => format!("{}d ago", secs / 86400),
     }
 }
+
+// --- RBAC ---
+
+fn rule_count(rules: &[PolicyRule]) -> String {
+    rules.len().to_string()
+}
+
+fn role_ref(role_ref: &RoleRef) -> String {
+    format!("{:?}/{}", role_ref.kind, role_ref.name)
+}
+
+fn subjects(subjects: &[Subject]) -> String {
+    let subjects: Vec<String> = subjects
+        .iter()
+        .map(|s| format!("{:?}/{}", s.kind, s.name))
+        .collect();
+    subjects.join(",")
+}
+
+impl Tabled for Role {
+    const LENGTH: usize = 4;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![
+            Cow::Owned(self.metadata.name.clone()),
+            Cow::Owned(self.spec.namespace.clone()),
+            Cow::Owned(rule_count(&self.spec.rules)),
+            Cow::Owned(human_duration(
+                Utc::now()
+                    .signed_duration_since(self.metadata.created_at)
+                    .to_std()
+                    .unwrap_or_default(),
+            )),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NAME"),
+            Cow::Borrowed("NAMESPACE"),
+            Cow::Borrowed("RULES"),
+            Cow::Borrowed("AGE"),
+        ]
+    }
+}
+
+impl Tabled for ClusterRole {
+    const LENGTH: usize = 3;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![
+            Cow::Owned(self.metadata.name.clone()),
+            Cow::Owned(rule_count(&self.spec.rules)),
+            Cow::Owned(human_duration(
+                Utc::now()
+                    .signed_duration_since(self.metadata.created_at)
+                    .to_std()
+                    .unwrap_or_default(),
+            )),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NAME"),
+            Cow::Borrowed("RULES"),
+            Cow::Borrowed("AGE"),
+        ]
+    }
+}
+
+impl Tabled for RoleBinding {
+    const LENGTH: usize = 5;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![
+            Cow::Owned(self.metadata.name.clone()),
+            Cow::Owned(self.spec.namespace.clone()),
+            Cow::Owned(role_ref(&self.spec.role_ref)),
+            Cow::Owned(subjects(&self.spec.subjects)),
+            Cow::Owned(human_duration(
+                Utc::now()
+                    .signed_duration_since(self.metadata.created_at)
+                    .to_std()
+                    .unwrap_or_default(),
+            )),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NAME"),
+            Cow::Borrowed("NAMESPACE"),
+            Cow::Borrowed("ROLE"),
+            Cow::Borrowed("SUBJECTS"),
+            Cow::Borrowed("AGE"),
+        ]
+    }
+}
+
+impl Tabled for ClusterRoleBinding {
+    const LENGTH: usize = 4;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![
+            Cow::Owned(self.metadata.name.clone()),
+            Cow::Owned(role_ref(&self.spec.role_ref)),
+            Cow::Owned(subjects(&self.spec.subjects)),
+            Cow::Owned(human_duration(
+                Utc::now()
+                    .signed_duration_since(self.metadata.created_at)
+                    .to_std()
+                    .unwrap_or_default(),
+            )),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("NAME"),
+            Cow::Borrowed("ROLE"),
+            Cow::Borrowed("SUBJECTS"),
+            Cow::Borrowed("AGE"),
+        ]
+    }
+}